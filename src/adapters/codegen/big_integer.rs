@@ -0,0 +1,721 @@
+/// Ọ̀nụ Big-Integer Runtime: Codegen Helpers
+///
+/// A `big-integer` value is an `i64*` into the arena.  Word 0 holds the
+/// signed limb count (its sign is the number's sign, zero has no limbs) and
+/// words 1..=|len| hold the magnitude as little-endian 64-bit limbs with no
+/// high zero limb.  Values are immutable: every helper bumps
+/// `@onu_arena_ptr` for its result and never writes to its operands.
+///
+/// The helpers are emitted as internal fastcc functions, like
+/// `__onu_wide_div_<bits>`, whenever the MIR calls any of them:
+///
+/// | Helper                    | Signature                       |
+/// |---------------------------|---------------------------------|
+/// | `__onu_big_from_integer`  | `(i64) -> big`                  |
+/// | `__onu_big_add` / `_sub`  | `(big, big) -> big`             |
+/// | `__onu_big_mul` / `_div`  | `(big, big) -> big`             |
+/// | `__onu_big_compare`       | `(big, big) -> i64` (-1, 0, 1)  |
+/// | `__onu_big_as_text`       | `(big) -> text`                 |
+///
+/// Division truncates toward zero and, like the fixed-width helper, returns
+/// zero for a zero divisor.
+use crate::adapters::codegen::compat::{onu_i8ptr, onu_ptr_to};
+use crate::domain::entities::mir::{MirInstruction, MirProgram};
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::basic_block::BasicBlock;
+use inkwell::builder::Builder;
+use inkwell::context::Context;
+use inkwell::module::{Linkage, Module};
+use inkwell::types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum, IntType, PointerType, StructType};
+use inkwell::values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, IntValue, PointerValue, ValueKind};
+use inkwell::IntPredicate;
+
+/// Prefix shared by every helper name; MIR calls carrying it are runtime calls.
+pub const HELPER_PREFIX: &str = "__onu_big_";
+
+/// Decimal digits per chunk when printing: 10^9 < 2^32, so one chunk and a
+/// 32-bit half limb fit together in a u64 during short division.
+const CHUNK_DIGITS: u64 = 9;
+const CHUNK_BASE: u64 = 1_000_000_000;
+
+pub struct BigIntegerRuntime<'a, 'ctx> {
+    context: &'ctx Context,
+    module: &'a Module<'ctx>,
+    builder: &'a Builder<'ctx>,
+}
+
+impl<'a, 'ctx> BigIntegerRuntime<'a, 'ctx> {
+    pub fn new(context: &'ctx Context, module: &'a Module<'ctx>, builder: &'a Builder<'ctx>) -> Self {
+        Self { context, module, builder }
+    }
+
+    /// Emits the helper set once if any MIR call refers to it.  Helpers the
+    /// program does not reach are removed again by global DCE.
+    pub fn emit_if_referenced(&self, program: &MirProgram) {
+        let referenced = program
+            .functions
+            .iter()
+            .flat_map(|f| &f.blocks)
+            .flat_map(|b| &b.instructions)
+            .any(|inst| matches!(inst, MirInstruction::Call { name, .. } if name.starts_with(HELPER_PREFIX)));
+        if !referenced || self.module.get_function("__onu_big_add").is_some() {
+            return;
+        }
+        // Callees first: `call` looks helpers up by name.
+        self.emit_normalize();
+        self.emit_from_integer();
+        self.emit_mag_compare();
+        self.emit_sub_in_place();
+        self.emit_mag_add();
+        self.emit_mag_sub();
+        self.emit_signed_add();
+        self.emit_add_sub();
+        self.emit_mul();
+        self.emit_div();
+        self.emit_compare();
+        self.emit_as_text();
+    }
+
+    // ── types and constants ──────────────────────────────────────────────
+
+    fn i64t(&self) -> IntType<'ctx> {
+        self.context.i64_type()
+    }
+
+    fn i128t(&self) -> IntType<'ctx> {
+        self.context.i128_type()
+    }
+
+    fn words(&self) -> PointerType<'ctx> {
+        onu_ptr_to(self.context.i64_type())
+    }
+
+    fn text(&self) -> StructType<'ctx> {
+        // Same layout as `LlvmTypeMapper` gives `Strings`.
+        self.context.struct_type(
+            &[self.i64t().into(), onu_i8ptr(self.context).into(), self.context.bool_type().into()],
+            false,
+        )
+    }
+
+    fn int(&self, v: i64) -> IntValue<'ctx> {
+        self.i64t().const_int(v as u64, true)
+    }
+
+    // ── function scaffolding ─────────────────────────────────────────────
+
+    fn begin(&self, name: &str, ret: Option<BasicTypeEnum<'ctx>>, params: &[BasicMetadataTypeEnum<'ctx>]) -> FunctionValue<'ctx> {
+        let fn_type = match ret {
+            Some(t) => t.fn_type(params, false),
+            None => self.context.void_type().fn_type(params, false),
+        };
+        let f = self.module.add_function(name, fn_type, Some(Linkage::Internal));
+        f.set_call_conventions(8); // fastcc
+        let kind_id = Attribute::get_named_enum_kind_id("nounwind");
+        f.add_attribute(AttributeLoc::Function, self.context.create_enum_attribute(kind_id, 0));
+        let entry = self.context.append_basic_block(f, "entry");
+        self.builder.position_at_end(entry);
+        f
+    }
+
+    fn int_param(&self, f: FunctionValue<'ctx>, i: u32) -> IntValue<'ctx> {
+        f.get_nth_param(i).unwrap().into_int_value()
+    }
+
+    fn ptr_param(&self, f: FunctionValue<'ctx>, i: u32) -> PointerValue<'ctx> {
+        f.get_nth_param(i).unwrap().into_pointer_value()
+    }
+
+    fn call(&self, name: &str, args: &[BasicMetadataValueEnum<'ctx>]) -> Option<BasicValueEnum<'ctx>> {
+        let f = self.module.get_function(name).expect("big-integer helper emitted out of order");
+        let call = self.builder.build_call(f, args, "call").unwrap();
+        call.set_call_convention(8);
+        match call.try_as_basic_value() {
+            ValueKind::Basic(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn call_int(&self, name: &str, args: &[BasicMetadataValueEnum<'ctx>]) -> IntValue<'ctx> {
+        self.call(name, args).unwrap().into_int_value()
+    }
+
+    fn call_ptr(&self, name: &str, args: &[BasicMetadataValueEnum<'ctx>]) -> PointerValue<'ctx> {
+        self.call(name, args).unwrap().into_pointer_value()
+    }
+
+    fn current_function(&self) -> FunctionValue<'ctx> {
+        self.builder.get_insert_block().unwrap().get_parent().unwrap()
+    }
+
+    fn block(&self, name: &str) -> BasicBlock<'ctx> {
+        self.context.append_basic_block(self.current_function(), name)
+    }
+
+    /// A mutable local: an entry-block alloca that mem2reg later promotes.
+    fn local(&self, name: &str, init: IntValue<'ctx>) -> PointerValue<'ctx> {
+        let entry = self.current_function().get_first_basic_block().unwrap();
+        let at_entry = self.context.create_builder();
+        match entry.get_first_instruction() {
+            Some(first) => at_entry.position_before(&first),
+            None => at_entry.position_at_end(entry),
+        }
+        let slot = at_entry.build_alloca(init.get_type(), name).unwrap();
+        self.builder.build_store(slot, init).unwrap();
+        slot
+    }
+
+    fn get(&self, slot: PointerValue<'ctx>) -> IntValue<'ctx> {
+        self.builder.build_load(slot, "v").unwrap().into_int_value()
+    }
+
+    fn set(&self, slot: PointerValue<'ctx>, v: IntValue<'ctx>) {
+        self.builder.build_store(slot, v).unwrap();
+    }
+
+    // ── integer shorthands ───────────────────────────────────────────────
+
+    fn add(&self, a: IntValue<'ctx>, b: IntValue<'ctx>) -> IntValue<'ctx> {
+        self.builder.build_int_add(a, b, "add").unwrap()
+    }
+
+    fn sub(&self, a: IntValue<'ctx>, b: IntValue<'ctx>) -> IntValue<'ctx> {
+        self.builder.build_int_sub(a, b, "sub").unwrap()
+    }
+
+    fn cmp(&self, pred: IntPredicate, a: IntValue<'ctx>, b: IntValue<'ctx>) -> IntValue<'ctx> {
+        self.builder.build_int_compare(pred, a, b, "cmp").unwrap()
+    }
+
+    fn select(&self, cond: IntValue<'ctx>, a: IntValue<'ctx>, b: IntValue<'ctx>) -> IntValue<'ctx> {
+        self.builder.build_select(cond, a, b, "sel").unwrap().into_int_value()
+    }
+
+    fn wide(&self, v: IntValue<'ctx>) -> IntValue<'ctx> {
+        self.builder.build_int_z_extend(v, self.i128t(), "wide").unwrap()
+    }
+
+    fn low(&self, v: IntValue<'ctx>) -> IntValue<'ctx> {
+        self.builder.build_int_truncate(v, self.i64t(), "low").unwrap()
+    }
+
+    fn high(&self, v: IntValue<'ctx>) -> IntValue<'ctx> {
+        let shifted = self
+            .builder
+            .build_right_shift(v, self.i128t().const_int(64, false), false, "hi")
+            .unwrap();
+        self.low(shifted)
+    }
+
+    /// -1 for a negative header, 1 otherwise (zero counts as positive).
+    fn sign_of(&self, header: IntValue<'ctx>) -> IntValue<'ctx> {
+        let negative = self.cmp(IntPredicate::SLT, header, self.int(0));
+        self.select(negative, self.int(-1), self.int(1))
+    }
+
+    fn abs(&self, header: IntValue<'ctx>) -> IntValue<'ctx> {
+        let negative = self.cmp(IntPredicate::SLT, header, self.int(0));
+        let negated = self.sub(self.int(0), header);
+        self.select(negative, negated, header)
+    }
+
+    fn max(&self, a: IntValue<'ctx>, b: IntValue<'ctx>) -> IntValue<'ctx> {
+        let greater = self.cmp(IntPredicate::SGT, a, b);
+        self.select(greater, a, b)
+    }
+
+    // ── memory ───────────────────────────────────────────────────────────
+
+    /// Bumps `@onu_arena_ptr` by `count` 8-byte words, aligned to 8 bytes.
+    fn alloc_words(&self, count: IntValue<'ctx>) -> PointerValue<'ctx> {
+        let arena_ptr = self.module.get_global("onu_arena_ptr").unwrap().as_pointer_value();
+        let current = self.builder.build_load(arena_ptr, "arena").unwrap().into_pointer_value();
+        let addr = self.builder.build_ptr_to_int(current, self.i64t(), "addr").unwrap();
+        let aligned = self.builder.build_and(self.add(addr, self.int(7)), self.int(-8), "aligned").unwrap();
+        let bytes = self.builder.build_int_mul(count, self.int(8), "bytes").unwrap();
+        let next = self
+            .builder
+            .build_int_to_ptr(self.add(aligned, bytes), onu_i8ptr(self.context), "next")
+            .unwrap();
+        self.builder.build_store(arena_ptr, next).unwrap();
+        self.builder.build_int_to_ptr(aligned, self.words(), "block").unwrap()
+    }
+
+    /// A block with room for a header and `limbs` limbs.
+    fn alloc_block(&self, limbs: IntValue<'ctx>) -> PointerValue<'ctx> {
+        self.alloc_words(self.add(limbs, self.int(1)))
+    }
+
+    fn header(&self, p: PointerValue<'ctx>) -> IntValue<'ctx> {
+        self.builder.build_load(p, "header").unwrap().into_int_value()
+    }
+
+    fn limb_ptr(&self, p: PointerValue<'ctx>, i: IntValue<'ctx>) -> PointerValue<'ctx> {
+        let idx = self.add(i, self.int(1));
+        unsafe { self.builder.build_in_bounds_gep(p, &[idx], "limb_ptr").unwrap() }
+    }
+
+    fn limb(&self, p: PointerValue<'ctx>, i: IntValue<'ctx>) -> IntValue<'ctx> {
+        self.builder.build_load(self.limb_ptr(p, i), "limb").unwrap().into_int_value()
+    }
+
+    fn set_limb(&self, p: PointerValue<'ctx>, i: IntValue<'ctx>, v: IntValue<'ctx>) {
+        self.builder.build_store(self.limb_ptr(p, i), v).unwrap();
+    }
+
+    /// Limb `i` of a block with `n` limbs, or 0 past its top.
+    fn limb_or_zero(&self, p: PointerValue<'ctx>, n: IntValue<'ctx>, i: IntValue<'ctx>) -> IntValue<'ctx> {
+        let v = self.local("limb_or_zero", self.int(0));
+        let in_range = self.cmp(IntPredicate::SLT, i, n);
+        self.if_then(in_range, || self.set(v, self.limb(p, i)));
+        self.get(v)
+    }
+
+    fn zero_limbs(&self, p: PointerValue<'ctx>, n: IntValue<'ctx>) {
+        self.for_up(self.int(0), n, |i| self.set_limb(p, i, self.int(0)));
+    }
+
+    fn copy_limbs(&self, dst: PointerValue<'ctx>, src: PointerValue<'ctx>, n: IntValue<'ctx>) {
+        self.for_up(self.int(0), n, |i| self.set_limb(dst, i, self.limb(src, i)));
+    }
+
+    // ── control flow ─────────────────────────────────────────────────────
+
+    fn if_then(&self, cond: IntValue<'ctx>, then: impl FnOnce()) {
+        let then_bb = self.block("then");
+        let done_bb = self.block("done");
+        self.builder.build_conditional_branch(cond, then_bb, done_bb).unwrap();
+        self.builder.position_at_end(then_bb);
+        then();
+        self.builder.build_unconditional_branch(done_bb).unwrap();
+        self.builder.position_at_end(done_bb);
+    }
+
+    /// Branches on `cond` and returns the (true, false) blocks, unpositioned.
+    fn split(&self, cond: IntValue<'ctx>) -> (BasicBlock<'ctx>, BasicBlock<'ctx>) {
+        let yes = self.block("yes");
+        let no = self.block("no");
+        self.builder.build_conditional_branch(cond, yes, no).unwrap();
+        (yes, no)
+    }
+
+    fn while_loop(&self, cond: impl Fn() -> IntValue<'ctx>, body: impl FnOnce()) {
+        let head_bb = self.block("while_head");
+        let body_bb = self.block("while_body");
+        let done_bb = self.block("while_done");
+        self.builder.build_unconditional_branch(head_bb).unwrap();
+        self.builder.position_at_end(head_bb);
+        let c = cond();
+        self.builder.build_conditional_branch(c, body_bb, done_bb).unwrap();
+        self.builder.position_at_end(body_bb);
+        body();
+        self.builder.build_unconditional_branch(head_bb).unwrap();
+        self.builder.position_at_end(done_bb);
+    }
+
+    /// Runs `body(i)` for `i` in `from..to`.
+    fn for_up(&self, from: IntValue<'ctx>, to: IntValue<'ctx>, body: impl FnOnce(IntValue<'ctx>)) {
+        let i = self.local("i", from);
+        let head_bb = self.block("up_head");
+        let body_bb = self.block("up_body");
+        let done_bb = self.block("up_done");
+        self.builder.build_unconditional_branch(head_bb).unwrap();
+        self.builder.position_at_end(head_bb);
+        let iv = self.get(i);
+        let more = self.cmp(IntPredicate::SLT, iv, to);
+        self.builder.build_conditional_branch(more, body_bb, done_bb).unwrap();
+        self.builder.position_at_end(body_bb);
+        body(iv);
+        self.set(i, self.add(iv, self.int(1)));
+        self.builder.build_unconditional_branch(head_bb).unwrap();
+        self.builder.position_at_end(done_bb);
+    }
+
+    /// Runs `body(i)` for `i` from `count - 1` down to 0.
+    fn for_down(&self, count: IntValue<'ctx>, body: impl FnOnce(IntValue<'ctx>)) {
+        let i = self.local("i", self.sub(count, self.int(1)));
+        let head_bb = self.block("down_head");
+        let body_bb = self.block("down_body");
+        let done_bb = self.block("down_done");
+        self.builder.build_unconditional_branch(head_bb).unwrap();
+        self.builder.position_at_end(head_bb);
+        let iv = self.get(i);
+        let more = self.cmp(IntPredicate::SGE, iv, self.int(0));
+        self.builder.build_conditional_branch(more, body_bb, done_bb).unwrap();
+        self.builder.position_at_end(body_bb);
+        body(iv);
+        self.set(i, self.sub(iv, self.int(1)));
+        self.builder.build_unconditional_branch(head_bb).unwrap();
+        self.builder.position_at_end(done_bb);
+    }
+
+    // ── helpers ──────────────────────────────────────────────────────────
+
+    /// `normalize(p, n, sign)`: drops high zero limbs from a block filled
+    /// with `n` limbs and writes its signed header.
+    fn emit_normalize(&self) {
+        let f = self.begin(
+            "__onu_big_normalize",
+            Some(self.words().into()),
+            &[self.words().into(), self.i64t().into(), self.i64t().into()],
+        );
+        let (p, n, sign) = (self.ptr_param(f, 0), self.int_param(f, 1), self.int_param(f, 2));
+        let len = self.local("len", n);
+        self.while_loop(
+            || {
+                let l = self.get(len);
+                let nonempty = self.cmp(IntPredicate::SGT, l, self.int(0));
+                let top = self.limb_or_zero(p, l, self.sub(l, self.int(1)));
+                let top_is_zero = self.cmp(IntPredicate::EQ, top, self.int(0));
+                self.builder.build_and(nonempty, top_is_zero, "trim").unwrap()
+            },
+            || self.set(len, self.sub(self.get(len), self.int(1))),
+        );
+        let l = self.get(len);
+        let negative = self.cmp(IntPredicate::SLT, sign, self.int(0));
+        let header = self.select(negative, self.sub(self.int(0), l), l);
+        self.builder.build_store(p, header).unwrap();
+        self.builder.build_return(Some(&p)).unwrap();
+    }
+
+    fn emit_from_integer(&self) {
+        let f = self.begin("__onu_big_from_integer", Some(self.words().into()), &[self.i64t().into()]);
+        let v = self.int_param(f, 0);
+        let p = self.alloc_block(self.int(1));
+        // |i64::MIN| wraps to 2^63, which is still the right unsigned limb.
+        self.set_limb(p, self.int(0), self.abs(v));
+        let is_zero = self.cmp(IntPredicate::EQ, v, self.int(0));
+        let header = self.select(is_zero, self.int(0), self.sign_of(v));
+        self.builder.build_store(p, header).unwrap();
+        self.builder.build_return(Some(&p)).unwrap();
+    }
+
+    /// `mag_compare(a, an, b, bn)`: -1, 0 or 1 comparing the magnitudes of
+    /// two limb runs, either of which may carry high zero limbs.
+    fn emit_mag_compare(&self) {
+        let f = self.begin(
+            "__onu_big_mag_compare",
+            Some(self.i64t().into()),
+            &[self.words().into(), self.i64t().into(), self.words().into(), self.i64t().into()],
+        );
+        let (a, an, b, bn) = (self.ptr_param(f, 0), self.int_param(f, 1), self.ptr_param(f, 2), self.int_param(f, 3));
+        let result = self.local("result", self.int(0));
+        let i = self.local("i", self.sub(self.max(an, bn), self.int(1)));
+        self.while_loop(
+            || {
+                let undecided = self.cmp(IntPredicate::EQ, self.get(result), self.int(0));
+                let more = self.cmp(IntPredicate::SGE, self.get(i), self.int(0));
+                self.builder.build_and(undecided, more, "scan").unwrap()
+            },
+            || {
+                let iv = self.get(i);
+                let x = self.limb_or_zero(a, an, iv);
+                let y = self.limb_or_zero(b, bn, iv);
+                let less = self.select(self.cmp(IntPredicate::ULT, x, y), self.int(-1), self.int(0));
+                self.set(result, self.select(self.cmp(IntPredicate::UGT, x, y), self.int(1), less));
+                self.set(i, self.sub(iv, self.int(1)));
+            },
+        );
+        self.builder.build_return(Some(&self.get(result))).unwrap();
+    }
+
+    /// `sub_in_place(r, rn, b, bn)`: r -= b over r's `rn` limbs; |r| >= |b|.
+    fn emit_sub_in_place(&self) {
+        let f = self.begin(
+            "__onu_big_sub_in_place",
+            None,
+            &[self.words().into(), self.i64t().into(), self.words().into(), self.i64t().into()],
+        );
+        let (r, rn, b, bn) = (self.ptr_param(f, 0), self.int_param(f, 1), self.ptr_param(f, 2), self.int_param(f, 3));
+        let borrow = self.local("borrow", self.int(0));
+        self.for_up(self.int(0), rn, |i| {
+            let x = self.wide(self.limb(r, i));
+            let y = self.wide(self.limb_or_zero(b, bn, i));
+            let d = self.builder.build_int_sub(self.builder.build_int_sub(x, y, "d").unwrap(), self.wide(self.get(borrow)), "d").unwrap();
+            self.set_limb(r, i, self.low(d));
+            // A negative 128-bit difference has its top bit set.
+            let top = self.builder.build_right_shift(d, self.i128t().const_int(127, false), false, "top").unwrap();
+            self.set(borrow, self.low(top));
+        });
+        self.builder.build_return(None).unwrap();
+    }
+
+    /// `mag_add(a, an, b, bn, sign)`: |a| + |b| with the given sign.
+    fn emit_mag_add(&self) {
+        let f = self.begin(
+            "__onu_big_mag_add",
+            Some(self.words().into()),
+            &[self.words().into(), self.i64t().into(), self.words().into(), self.i64t().into(), self.i64t().into()],
+        );
+        let (a, an, b, bn, sign) =
+            (self.ptr_param(f, 0), self.int_param(f, 1), self.ptr_param(f, 2), self.int_param(f, 3), self.int_param(f, 4));
+        let n = self.add(self.max(an, bn), self.int(1));
+        let r = self.alloc_block(n);
+        let carry = self.local("carry", self.int(0));
+        self.for_up(self.int(0), n, |i| {
+            let x = self.wide(self.limb_or_zero(a, an, i));
+            let y = self.wide(self.limb_or_zero(b, bn, i));
+            let s = self.builder.build_int_add(self.builder.build_int_add(x, y, "s").unwrap(), self.wide(self.get(carry)), "s").unwrap();
+            self.set_limb(r, i, self.low(s));
+            self.set(carry, self.high(s));
+        });
+        let res = self.call_ptr("__onu_big_normalize", &[r.into(), n.into(), sign.into()]);
+        self.builder.build_return(Some(&res)).unwrap();
+    }
+
+    /// `mag_sub(a, an, b, bn, sign)`: |a| - |b| with the given sign; |a| >= |b|.
+    fn emit_mag_sub(&self) {
+        let f = self.begin(
+            "__onu_big_mag_sub",
+            Some(self.words().into()),
+            &[self.words().into(), self.i64t().into(), self.words().into(), self.i64t().into(), self.i64t().into()],
+        );
+        let (a, an, b, bn, sign) =
+            (self.ptr_param(f, 0), self.int_param(f, 1), self.ptr_param(f, 2), self.int_param(f, 3), self.int_param(f, 4));
+        let r = self.alloc_block(an);
+        self.copy_limbs(r, a, an);
+        self.call("__onu_big_sub_in_place", &[r.into(), an.into(), b.into(), bn.into()]);
+        let res = self.call_ptr("__onu_big_normalize", &[r.into(), an.into(), sign.into()]);
+        self.builder.build_return(Some(&res)).unwrap();
+    }
+
+    /// `signed_add(a, b, flip)`: a + flip·b for flip in {1, -1}.
+    fn emit_signed_add(&self) {
+        let f = self.begin(
+            "__onu_big_signed_add",
+            Some(self.words().into()),
+            &[self.words().into(), self.words().into(), self.i64t().into()],
+        );
+        let (a, b, flip) = (self.ptr_param(f, 0), self.ptr_param(f, 1), self.int_param(f, 2));
+        let ah = self.header(a);
+        let bh = self.builder.build_int_mul(self.header(b), flip, "bh").unwrap();
+        let (a_sign, b_sign) = (self.sign_of(ah), self.sign_of(bh));
+        let (an, bn) = (self.abs(ah), self.abs(bh));
+
+        let (same_bb, differ_bb) = self.split(self.cmp(IntPredicate::EQ, a_sign, b_sign));
+        self.builder.position_at_end(same_bb);
+        let sum = self.call_ptr("__onu_big_mag_add", &[a.into(), an.into(), b.into(), bn.into(), a_sign.into()]);
+        self.builder.build_return(Some(&sum)).unwrap();
+
+        // Opposite signs: subtract the smaller magnitude from the larger,
+        // which keeps its sign.
+        self.builder.position_at_end(differ_bb);
+        let order = self.call_int("__onu_big_mag_compare", &[a.into(), an.into(), b.into(), bn.into()]);
+        let (a_larger_bb, b_larger_bb) = self.split(self.cmp(IntPredicate::SGE, order, self.int(0)));
+        self.builder.position_at_end(a_larger_bb);
+        let diff = self.call_ptr("__onu_big_mag_sub", &[a.into(), an.into(), b.into(), bn.into(), a_sign.into()]);
+        self.builder.build_return(Some(&diff)).unwrap();
+        self.builder.position_at_end(b_larger_bb);
+        let diff = self.call_ptr("__onu_big_mag_sub", &[b.into(), bn.into(), a.into(), an.into(), b_sign.into()]);
+        self.builder.build_return(Some(&diff)).unwrap();
+    }
+
+    fn emit_add_sub(&self) {
+        for (name, flip) in [("__onu_big_add", 1), ("__onu_big_sub", -1)] {
+            let f = self.begin(name, Some(self.words().into()), &[self.words().into(), self.words().into()]);
+            let (a, b) = (self.ptr_param(f, 0), self.ptr_param(f, 1));
+            let res = self.call_ptr("__onu_big_signed_add", &[a.into(), b.into(), self.int(flip).into()]);
+            self.builder.build_return(Some(&res)).unwrap();
+        }
+    }
+
+    /// Sign of a product or quotient of headers `ah` and `bh`.
+    fn product_sign(&self, ah: IntValue<'ctx>, bh: IntValue<'ctx>) -> IntValue<'ctx> {
+        let a_neg = self.cmp(IntPredicate::SLT, ah, self.int(0));
+        let b_neg = self.cmp(IntPredicate::SLT, bh, self.int(0));
+        let negative = self.builder.build_xor(a_neg, b_neg, "neg").unwrap();
+        self.select(negative, self.int(-1), self.int(1))
+    }
+
+    /// Schoolbook multiplication with 128-bit partial products.
+    fn emit_mul(&self) {
+        let f = self.begin("__onu_big_mul", Some(self.words().into()), &[self.words().into(), self.words().into()]);
+        let (a, b) = (self.ptr_param(f, 0), self.ptr_param(f, 1));
+        let (ah, bh) = (self.header(a), self.header(b));
+        let (an, bn) = (self.abs(ah), self.abs(bh));
+        let sign = self.product_sign(ah, bh);
+        let n = self.add(an, bn);
+        let r = self.alloc_block(n);
+        self.zero_limbs(r, n);
+        self.for_up(self.int(0), an, |i| {
+            let x = self.wide(self.limb(a, i));
+            let carry = self.local("carry", self.int(0));
+            self.for_up(self.int(0), bn, |j| {
+                let k = self.add(i, j);
+                let product = self.builder.build_int_mul(x, self.wide(self.limb(b, j)), "p").unwrap();
+                let t = self.builder.build_int_add(product, self.wide(self.limb(r, k)), "t").unwrap();
+                let t = self.builder.build_int_add(t, self.wide(self.get(carry)), "t").unwrap();
+                self.set_limb(r, k, self.low(t));
+                self.set(carry, self.high(t));
+            });
+            self.set_limb(r, self.add(i, bn), self.get(carry));
+        });
+        let res = self.call_ptr("__onu_big_normalize", &[r.into(), n.into(), sign.into()]);
+        self.builder.build_return(Some(&res)).unwrap();
+    }
+
+    /// Restoring binary long division — the `__onu_wide_div_<bits>` loop run
+    /// over limb arrays:
+    ///
+    ///   for i = bits(a)-1 downto 0:
+    ///     remainder = (remainder << 1) | bit i of |a|
+    ///     if remainder >= |b|: remainder -= |b|; set bit i of the quotient
+    fn emit_div(&self) {
+        let f = self.begin("__onu_big_div", Some(self.words().into()), &[self.words().into(), self.words().into()]);
+        let (a, b) = (self.ptr_param(f, 0), self.ptr_param(f, 1));
+        let (ah, bh) = (self.header(a), self.header(b));
+        let (an, bn) = (self.abs(ah), self.abs(bh));
+
+        let (zero_bb, divide_bb) = self.split(self.cmp(IntPredicate::EQ, bn, self.int(0)));
+        self.builder.position_at_end(zero_bb);
+        let zero = self.call_ptr("__onu_big_from_integer", &[self.int(0).into()]);
+        self.builder.build_return(Some(&zero)).unwrap();
+
+        self.builder.position_at_end(divide_bb);
+        let q = self.alloc_block(an);
+        self.zero_limbs(q, an);
+        // One spare limb holds the bit shifted out before the subtraction.
+        let rn = self.add(bn, self.int(1));
+        let rem = self.alloc_block(rn);
+        self.zero_limbs(rem, rn);
+        let bits = self.builder.build_int_mul(an, self.int(64), "bits").unwrap();
+        self.for_down(bits, |i| {
+            let word = self.builder.build_right_shift(i, self.int(6), false, "word").unwrap();
+            let shift = self.builder.build_and(i, self.int(63), "shift").unwrap();
+            let shifted = self.builder.build_right_shift(self.limb(a, word), shift, false, "shifted").unwrap();
+            let carry = self.local("carry", self.builder.build_and(shifted, self.int(1), "bit").unwrap());
+            self.for_up(self.int(0), rn, |j| {
+                let w = self.limb(rem, j);
+                let moved = self.builder.build_left_shift(w, self.int(1), "moved").unwrap();
+                self.set_limb(rem, j, self.builder.build_or(moved, self.get(carry), "rem").unwrap());
+                self.set(carry, self.builder.build_right_shift(w, self.int(63), false, "out").unwrap());
+            });
+            let order = self.call_int("__onu_big_mag_compare", &[rem.into(), rn.into(), b.into(), bn.into()]);
+            self.if_then(self.cmp(IntPredicate::SGE, order, self.int(0)), || {
+                self.call("__onu_big_sub_in_place", &[rem.into(), rn.into(), b.into(), bn.into()]);
+                let mask = self.builder.build_left_shift(self.int(1), shift, "mask").unwrap();
+                self.set_limb(q, word, self.builder.build_or(self.limb(q, word), mask, "q").unwrap());
+            });
+        });
+        let res = self.call_ptr("__onu_big_normalize", &[q.into(), an.into(), self.product_sign(ah, bh).into()]);
+        self.builder.build_return(Some(&res)).unwrap();
+    }
+
+    fn emit_compare(&self) {
+        let f = self.begin("__onu_big_compare", Some(self.i64t().into()), &[self.words().into(), self.words().into()]);
+        let (a, b) = (self.ptr_param(f, 0), self.ptr_param(f, 1));
+        let (ah, bh) = (self.header(a), self.header(b));
+        let (a_sign, b_sign) = (self.sign_of(ah), self.sign_of(bh));
+        let (differ_bb, same_bb) = self.split(self.cmp(IntPredicate::NE, a_sign, b_sign));
+        self.builder.position_at_end(differ_bb);
+        self.builder.build_return(Some(&a_sign)).unwrap();
+        self.builder.position_at_end(same_bb);
+        let order = self.call_int("__onu_big_mag_compare", &[a.into(), self.abs(ah).into(), b.into(), self.abs(bh).into()]);
+        let res = self.builder.build_int_mul(order, a_sign, "order").unwrap();
+        self.builder.build_return(Some(&res)).unwrap();
+    }
+
+    /// Decimal text by repeated short division of a scratch copy by 10^9.
+    /// Each limb is divided as two 32-bit halves so every step stays in u64.
+    fn emit_as_text(&self) {
+        let f = self.begin("__onu_big_as_text", Some(self.text().into()), &[self.words().into()]);
+        let a = self.ptr_param(f, 0);
+        let ah = self.header(a);
+        let n = self.abs(ah);
+        let scratch = self.alloc_block(n);
+        self.copy_limbs(scratch, a, n);
+        // A limb yields at most 64·log10(2)/9 < 3 chunks.
+        let chunks = self.alloc_block(self.add(self.builder.build_int_mul(n, self.int(3), "c").unwrap(), self.int(1)));
+        let count = self.local("count", self.int(0));
+        let top = self.local("top", n);
+        let base = self.int(CHUNK_BASE as i64);
+        let half = self.int(32);
+
+        // do { chunks[count++] = scratch % 10^9; scratch /= 10^9 } while scratch != 0
+        let chunk_bb = self.block("chunk");
+        self.builder.build_unconditional_branch(chunk_bb).unwrap();
+        self.builder.position_at_end(chunk_bb);
+        let rem = self.local("rem", self.int(0));
+        self.for_down(self.get(top), |j| {
+            let w = self.limb(scratch, j);
+            let mut quotient = self.int(0);
+            for part in [
+                self.builder.build_right_shift(w, half, false, "hi").unwrap(),
+                self.builder.build_and(w, self.int(0xffff_ffff), "lo").unwrap(),
+            ] {
+                let cur = self.builder.build_or(self.builder.build_left_shift(self.get(rem), half, "r").unwrap(), part, "cur").unwrap();
+                let q = self.builder.build_int_unsigned_div(cur, base, "q").unwrap();
+                self.set(rem, self.builder.build_int_unsigned_rem(cur, base, "rem").unwrap());
+                quotient = self.builder.build_or(self.builder.build_left_shift(quotient, half, "qs").unwrap(), q, "quot").unwrap();
+            }
+            self.set_limb(scratch, j, quotient);
+        });
+        self.set_limb(chunks, self.get(count), self.get(rem));
+        self.set(count, self.add(self.get(count), self.int(1)));
+        self.while_loop(
+            || {
+                let t = self.get(top);
+                let nonempty = self.cmp(IntPredicate::SGT, t, self.int(0));
+                let high = self.limb_or_zero(scratch, t, self.sub(t, self.int(1)));
+                let high_is_zero = self.cmp(IntPredicate::EQ, high, self.int(0));
+                self.builder.build_and(nonempty, high_is_zero, "trim").unwrap()
+            },
+            || self.set(top, self.sub(self.get(top), self.int(1))),
+        );
+        let done_bb = self.block("chunks_done");
+        let more = self.cmp(IntPredicate::SGT, self.get(top), self.int(0));
+        self.builder.build_conditional_branch(more, chunk_bb, done_bb).unwrap();
+        self.builder.position_at_end(done_bb);
+
+        // Write every chunk as nine digits from the end, with one leading
+        // byte spare for a sign, then skip the leading zeros.
+        let k = self.get(count);
+        let len = self.add(self.builder.build_int_mul(k, self.int(CHUNK_DIGITS as i64), "digits").unwrap(), self.int(1));
+        let buf_words = self.builder.build_right_shift(self.add(len, self.int(7)), self.int(3), false, "w").unwrap();
+        let buf = self
+            .builder
+            .build_pointer_cast(self.alloc_words(buf_words), onu_i8ptr(self.context), "buf")
+            .unwrap();
+        let byte_at = |i: IntValue<'ctx>| unsafe { self.builder.build_in_bounds_gep(buf, &[i], "byte").unwrap() };
+        let pos = self.local("pos", len);
+        let i8t = self.context.i8_type();
+        self.for_up(self.int(0), k, |c| {
+            let v = self.local("v", self.limb(chunks, c));
+            self.for_up(self.int(0), self.int(CHUNK_DIGITS as i64), |_| {
+                let p = self.sub(self.get(pos), self.int(1));
+                self.set(pos, p);
+                let digit = self.builder.build_int_unsigned_rem(self.get(v), self.int(10), "digit").unwrap();
+                let ascii = self.builder.build_int_truncate(self.add(digit, self.int(48)), i8t, "ascii").unwrap();
+                self.builder.build_store(byte_at(p), ascii).unwrap();
+                self.set(v, self.builder.build_int_unsigned_div(self.get(v), self.int(10), "v").unwrap());
+            });
+        });
+        let start = self.local("start", self.int(1));
+        let last = self.sub(len, self.int(1));
+        self.while_loop(
+            || {
+                let s = self.get(start);
+                let before_last = self.cmp(IntPredicate::SLT, s, last);
+                let byte = self.builder.build_load(byte_at(s), "b").unwrap().into_int_value();
+                let is_zero_digit = self.cmp(IntPredicate::EQ, byte, i8t.const_int(b'0' as u64, false));
+                self.builder.build_and(before_last, is_zero_digit, "skip").unwrap()
+            },
+            || self.set(start, self.add(self.get(start), self.int(1))),
+        );
+        self.if_then(self.cmp(IntPredicate::SLT, ah, self.int(0)), || {
+            let s = self.sub(self.get(start), self.int(1));
+            self.set(start, s);
+            self.builder.build_store(byte_at(s), i8t.const_int(b'-' as u64, false)).unwrap();
+        });
+
+        let s = self.get(start);
+        let text = self.text().get_undef();
+        let text = self.builder.build_insert_value(text, self.sub(len, s), 0, "len").unwrap();
+        let text = self.builder.build_insert_value(text, byte_at(s), 1, "data").unwrap();
+        let text = self
+            .builder
+            .build_insert_value(text, self.context.bool_type().const_int(1, false), 2, "dyn")
+            .unwrap();
+        self.builder.build_return(Some(&text.into_struct_value())).unwrap();
+    }
+}
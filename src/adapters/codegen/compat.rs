@@ -17,8 +17,8 @@ use inkwell::{
     AddressSpace,
     builder::Builder,
     context::Context,
-    types::{BasicType, BasicTypeEnum, PointerType},
-    values::{BasicValueEnum, IntValue, PointerValue},
+    types::{BasicType, BasicTypeEnum, FunctionType, PointerType},
+    values::{BasicMetadataValueEnum, BasicValueEnum, CallSiteValue, IntValue, PointerValue},
 };
 
 // ---------------------------------------------------------------------------
@@ -233,3 +233,38 @@ pub fn arena_ptr_initializer<'ctx>(
 ) -> PointerValue<'ctx> {
     arena_ptr_val // Already the right opaque type.
 }
+
+// ---------------------------------------------------------------------------
+// build_inline_asm_call — call a value from `Context::create_inline_asm`
+// ---------------------------------------------------------------------------
+
+/// Call an inline-asm value of type `fn_type`.
+///
+/// | LLVM version | Inkwell call                                        |
+/// |-------------|-----------------------------------------------------|
+/// | 14 / 15     | `build_call(CallableValue::try_from(asm), ..)`      |
+/// | 16+         | `build_indirect_call(fn_type, asm, ..)`             |
+#[cfg(feature = "typed-pointers")]
+#[inline(always)]
+pub fn build_inline_asm_call<'ctx>(
+    builder: &Builder<'ctx>,
+    _fn_type: FunctionType<'ctx>,
+    asm: PointerValue<'ctx>,
+    args: &[BasicMetadataValueEnum<'ctx>],
+    name: &str,
+) -> CallSiteValue<'ctx> {
+    let callable = inkwell::values::CallableValue::try_from(asm).expect("inline asm is callable");
+    builder.build_call(callable, args, name).unwrap()
+}
+
+#[cfg(not(feature = "typed-pointers"))]
+#[inline(always)]
+pub fn build_inline_asm_call<'ctx>(
+    builder: &Builder<'ctx>,
+    fn_type: FunctionType<'ctx>,
+    asm: PointerValue<'ctx>,
+    args: &[BasicMetadataValueEnum<'ctx>],
+    name: &str,
+) -> CallSiteValue<'ctx> {
+    builder.build_indirect_call(fn_type, asm, args, name).unwrap()
+}
@@ -0,0 +1,451 @@
+/// Ọ̀nụ Hardware Intrinsics: Dispatch for `KnownBehavior` Tags
+///
+/// A behavior tagged `with known behavior:` keeps its Ọ̀nụ body as the
+/// software implementation, emitted as `<name>.software`.  When the
+/// `HardwareIntrinsicPort` strategy supports the behavior and a kernel
+/// exists for it, `<name>` itself becomes a dispatcher that runs the
+/// kernel when the CPU reports the extension at run time and falls back
+/// to the software body otherwise.  Without a kernel the behavior is
+/// compiled as if it were untagged.
+///
+/// ## `sha256-compress`
+///
+/// The tagged behavior must take `(rounds, round, schedule, state)` —
+/// two integers, a shape of sixteen integers holding `W[round..round+16]`
+/// and a shape of eight integers holding `a..h` — and deliver the state
+/// after running `rounds` rounds from `round`, without the final add-back.
+/// Every word is a 32-bit value.  The kernel covers the whole-block call
+/// (`rounds` = 64, `round` = 0); any other call takes the software body.
+///
+/// | Target  | Kernel                                        | Detection                       |
+/// |---------|-----------------------------------------------|---------------------------------|
+/// | x86_64  | `llvm.x86.sha256rnds2` / `msg1` / `msg2`      | `cpuid` leaf 7, EBX bit 29      |
+/// | AArch64 | `llvm.aarch64.crypto.sha256h` / `h2` / `su0` / `su1` | `ID_AA64ISAR0_EL1.SHA2`  |
+///
+/// The detection asm has no side effects, so a dispatcher inlined into a
+/// loop has its check hoisted out of it.
+use crate::adapters::codegen::compat::build_inline_asm_call;
+use crate::application::ports::hardware_intrinsic_port::{HardwareIntrinsicPort, KnownBehavior};
+use crate::domain::entities::error::OnuError;
+use crate::domain::entities::mir::{MirFunction, MirProgram};
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::builder::Builder;
+use inkwell::context::Context;
+use inkwell::module::{Linkage, Module};
+use inkwell::types::{BasicType, BasicTypeEnum, StructType, VectorType};
+use inkwell::values::{
+    BasicMetadataValueEnum, BasicValue, BasicValueEnum, CallSiteValue, FunctionValue, IntValue, StructValue, ValueKind, VectorValue,
+};
+use inkwell::IntPredicate;
+
+/// Suffix of the software body behind a dispatcher.
+pub const SOFTWARE_SUFFIX: &str = ".software";
+
+/// SHA-256 round constants K[0..64] (FIPS 180-4 §4.2.2).
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// The name codegen gives a tagged behavior's body when it sits behind a
+/// dispatcher.
+pub fn software_body_name(name: &str) -> String {
+    format!("{}{}", name, SOFTWARE_SUFFIX)
+}
+
+fn value_of(call: CallSiteValue<'_>) -> BasicValueEnum<'_> {
+    match call.try_as_basic_value() {
+        ValueKind::Basic(v) => v,
+        _ => unreachable!("hardware intrinsic calls return a value"),
+    }
+}
+
+/// Instruction-set family of a SHA-256 kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sha256Kernel {
+    ShaNi,
+    Armv8,
+}
+
+impl Sha256Kernel {
+    fn for_strategy(strategy: &dyn HardwareIntrinsicPort) -> Option<Self> {
+        if !strategy.supports(&KnownBehavior::Sha256Compress) {
+            return None;
+        }
+        let triple = strategy.target_triple();
+        if triple.starts_with("x86_64") {
+            Some(Sha256Kernel::ShaNi)
+        } else if triple.starts_with("aarch64") {
+            Some(Sha256Kernel::Armv8)
+        } else {
+            None
+        }
+    }
+
+    fn target_features(self) -> &'static str {
+        match self {
+            Sha256Kernel::ShaNi => "+sha,+sse2,+ssse3,+sse4.1",
+            Sha256Kernel::Armv8 => "+neon,+sha2",
+        }
+    }
+}
+
+pub struct HardwareIntrinsicEmitter<'a, 'ctx> {
+    context: &'ctx Context,
+    module: &'a Module<'ctx>,
+    builder: &'a Builder<'ctx>,
+    strategy: &'a dyn HardwareIntrinsicPort,
+}
+
+impl<'a, 'ctx> HardwareIntrinsicEmitter<'a, 'ctx> {
+    pub fn new(
+        context: &'ctx Context,
+        module: &'a Module<'ctx>,
+        builder: &'a Builder<'ctx>,
+        strategy: &'a dyn HardwareIntrinsicPort,
+    ) -> Self {
+        Self { context, module, builder, strategy }
+    }
+
+    /// Puts a dispatcher in front of every tagged behavior this strategy
+    /// has a kernel for.  Must run after the functions are declared and
+    /// before their bodies are generated: each body moves to
+    /// `software_body_name(name)`.
+    pub fn emit_dispatchers(&self, program: &MirProgram) -> Result<(), OnuError> {
+        for func in &program.functions {
+            if func.known_behavior != Some(KnownBehavior::Sha256Compress) {
+                continue;
+            }
+            let Some(kernel) = Sha256Kernel::for_strategy(self.strategy) else {
+                continue;
+            };
+            let declared = self
+                .module
+                .get_function(&func.name)
+                .expect("tagged behavior declared before dispatch");
+            let (schedule, state) = self.sha256_signature(func, declared)?;
+            let software = self.software_body(declared);
+            let hardware = self.emit_sha256_kernel(&func.name, kernel, schedule, state);
+            self.emit_sha256_dispatcher(declared, software, hardware, kernel);
+        }
+        Ok(())
+    }
+
+    // ── contract ─────────────────────────────────────────────────────────
+
+    /// Checks the `(rounds, round, schedule, state) -> state` contract and
+    /// returns the schedule and state struct types.
+    fn sha256_signature(
+        &self,
+        func: &MirFunction,
+        declared: FunctionValue<'ctx>,
+    ) -> Result<(StructType<'ctx>, StructType<'ctx>), OnuError> {
+        let i64_type = self.context.i64_type();
+        let words = |t: Option<BasicTypeEnum<'ctx>>, n: u32| match t {
+            Some(BasicTypeEnum::StructType(s))
+                if s.count_fields() == n
+                    && s.get_field_types().iter().all(|f| *f == i64_type.as_basic_type_enum()) =>
+            {
+                Some(s)
+            }
+            _ => None,
+        };
+        let fn_type = declared.get_type();
+        let params = fn_type.get_param_types();
+        let fits = params.len() == 4
+            && params[0] == i64_type.into()
+            && params[1] == i64_type.into();
+        let schedule = params.get(2).and_then(|p| words(BasicTypeEnum::try_from(*p).ok(), 16));
+        let state = params.get(3).and_then(|p| words(BasicTypeEnum::try_from(*p).ok(), 8));
+        let ret = words(fn_type.get_return_type(), 8);
+        match (fits, schedule, state, ret) {
+            (true, Some(schedule), Some(state), Some(ret)) if ret == state => Ok((schedule, state)),
+            _ => Err(OnuError::CodeGenError {
+                message: format!(
+                    "`with known behavior: sha256-compress` on {} expects (an integer, an integer, a shape of 16 integers, a shape of 8 integers) delivering the 8-integer shape",
+                    func.name
+                ),
+            }),
+        }
+    }
+
+    // ── function scaffolding ─────────────────────────────────────────────
+
+    /// Declares `<name>.software` with the declared function's type,
+    /// calling convention and attributes.
+    fn software_body(&self, declared: FunctionValue<'ctx>) -> FunctionValue<'ctx> {
+        let name = declared.get_name().to_str().unwrap().to_string();
+        let software = self
+            .module
+            .add_function(&software_body_name(&name), declared.get_type(), Some(Linkage::Internal));
+        software.set_call_conventions(declared.get_call_conventions());
+        for attr in declared.attributes(AttributeLoc::Function) {
+            software.add_attribute(AttributeLoc::Function, attr);
+        }
+        software
+    }
+
+    fn add_enum_attribute(&self, f: FunctionValue<'ctx>, name: &str) {
+        let kind_id = Attribute::get_named_enum_kind_id(name);
+        f.add_attribute(AttributeLoc::Function, self.context.create_enum_attribute(kind_id, 0));
+    }
+
+    fn call(&self, f: FunctionValue<'ctx>, args: &[BasicMetadataValueEnum<'ctx>]) -> BasicValueEnum<'ctx> {
+        let call = self.builder.build_call(f, args, "call").unwrap();
+        call.set_call_convention(f.get_call_conventions());
+        value_of(call)
+    }
+
+    fn intrinsic(&self, name: &str, arity: usize) -> FunctionValue<'ctx> {
+        if let Some(f) = self.module.get_function(name) {
+            return f;
+        }
+        let v4 = self.v4();
+        let params = vec![v4.into(); arity];
+        self.module.add_function(name, v4.fn_type(&params, false), None)
+    }
+
+    fn call_intrinsic(&self, name: &str, args: &[VectorValue<'ctx>]) -> VectorValue<'ctx> {
+        let f = self.intrinsic(name, args.len());
+        let args: Vec<BasicMetadataValueEnum<'ctx>> = args.iter().map(|a| (*a).into()).collect();
+        value_of(self.builder.build_call(f, &args, "sha").unwrap()).into_vector_value()
+    }
+
+    // ── vectors ──────────────────────────────────────────────────────────
+
+    fn v4(&self) -> VectorType<'ctx> {
+        self.context.i32_type().vec_type(4)
+    }
+
+    fn lane(&self, i: u64) -> IntValue<'ctx> {
+        self.context.i32_type().const_int(i, false)
+    }
+
+    /// Packs four 32-bit words held in `i64` fields into `<4 x i32>`,
+    /// `fields[0]` in lane 0.
+    fn pack(&self, agg: StructValue<'ctx>, fields: [u32; 4]) -> VectorValue<'ctx> {
+        let b = self.builder;
+        let mut v = self.v4().get_undef();
+        for (lane, field) in fields.into_iter().enumerate() {
+            let word = b.build_extract_value(agg, field, "word").unwrap().into_int_value();
+            let word = b.build_int_truncate(word, self.context.i32_type(), "word32").unwrap();
+            v = b.build_insert_element(v, word, self.lane(lane as u64), "pack").unwrap();
+        }
+        v
+    }
+
+    /// Writes lane `lane` of `v`, zero-extended, into field `field` of `agg`.
+    fn unpack_into(&self, agg: StructValue<'ctx>, v: VectorValue<'ctx>, lane: u64, field: u32) -> StructValue<'ctx> {
+        let b = self.builder;
+        let word = b.build_extract_element(v, self.lane(lane), "lane").unwrap().into_int_value();
+        let word = b.build_int_z_extend(word, self.context.i64_type(), "word64").unwrap();
+        b.build_insert_value(agg, word, field, "state").unwrap().into_struct_value()
+    }
+
+    fn shuffle(&self, left: VectorValue<'ctx>, right: VectorValue<'ctx>, mask: [u64; 4]) -> VectorValue<'ctx> {
+        let mask: Vec<IntValue<'ctx>> = mask.iter().map(|m| self.lane(*m)).collect();
+        self.builder
+            .build_shuffle_vector(left, right, VectorType::const_vector(&mask), "shuffle")
+            .unwrap()
+    }
+
+    fn round_constants(&self, group: usize) -> VectorValue<'ctx> {
+        let k: Vec<IntValue<'ctx>> = SHA256_K[group * 4..group * 4 + 4]
+            .iter()
+            .map(|k| self.context.i32_type().const_int(*k as u64, false))
+            .collect();
+        VectorType::const_vector(&k)
+    }
+
+    // ── SHA-256 ──────────────────────────────────────────────────────────
+
+    /// `<name>.<kernel>(schedule, state) -> state`: all 64 rounds of one
+    /// block, fully unrolled, in 16 groups of four rounds.
+    fn emit_sha256_kernel(
+        &self,
+        name: &str,
+        kernel: Sha256Kernel,
+        schedule: StructType<'ctx>,
+        state: StructType<'ctx>,
+    ) -> FunctionValue<'ctx> {
+        let suffix = match kernel {
+            Sha256Kernel::ShaNi => "sha-ni",
+            Sha256Kernel::Armv8 => "armv8-crypto",
+        };
+        let fn_type = state.fn_type(&[schedule.into(), state.into()], false);
+        let f = self.module.add_function(&format!("{}.{}", name, suffix), fn_type, Some(Linkage::Internal));
+        f.set_call_conventions(8); // fastcc
+        for attr in ["nounwind", "readnone", "nofree", "nosync"] {
+            self.add_enum_attribute(f, attr);
+        }
+        f.add_attribute(
+            AttributeLoc::Function,
+            self.context.create_string_attribute("target-features", kernel.target_features()),
+        );
+        let entry = self.context.append_basic_block(f, "entry");
+        self.builder.position_at_end(entry);
+
+        let w = f.get_nth_param(0).unwrap().into_struct_value();
+        let h = f.get_nth_param(1).unwrap().into_struct_value();
+        let b = self.builder;
+
+        let mut msgs: Vec<VectorValue<'ctx>> = (0..4u32).map(|g| self.pack(w, [4 * g, 4 * g + 1, 4 * g + 2, 4 * g + 3])).collect();
+        let out = match kernel {
+            Sha256Kernel::ShaNi => {
+                // sha256rnds2 keeps the state as ABEF / CDGH with A and C in
+                // the top lane, and returns the new ABEF; the old ABEF
+                // becomes the new CDGH.
+                let mut abef = self.pack(h, [5, 4, 1, 0]);
+                let mut cdgh = self.pack(h, [7, 6, 3, 2]);
+                for g in 0..16 {
+                    if g >= 4 {
+                        // W[t..t+4] = msg2(msg1(W[t-16..], W[t-12..]) + W[t-7..], W[t-4..])
+                        let s0 = self.call_intrinsic("llvm.x86.sha256msg1", &[msgs[g - 4], msgs[g - 3]]);
+                        let w7 = self.shuffle(msgs[g - 2], msgs[g - 1], [1, 2, 3, 4]);
+                        let sum = b.build_int_add(s0, w7, "w7").unwrap();
+                        msgs.push(self.call_intrinsic("llvm.x86.sha256msg2", &[sum, msgs[g - 1]]));
+                    }
+                    let wk = b.build_int_add(msgs[g], self.round_constants(g), "wk").unwrap();
+                    let next = self.call_intrinsic("llvm.x86.sha256rnds2", &[cdgh, abef, wk]);
+                    cdgh = abef;
+                    abef = next;
+                    let wk_hi = self.shuffle(wk, wk, [2, 3, 2, 3]);
+                    let next = self.call_intrinsic("llvm.x86.sha256rnds2", &[cdgh, abef, wk_hi]);
+                    cdgh = abef;
+                    abef = next;
+                }
+                let mut out = state.get_undef();
+                for (lane, field) in [(3, 0), (2, 1), (1, 4), (0, 5)] {
+                    out = self.unpack_into(out, abef, lane, field);
+                }
+                for (lane, field) in [(3, 2), (2, 3), (1, 6), (0, 7)] {
+                    out = self.unpack_into(out, cdgh, lane, field);
+                }
+                out
+            }
+            Sha256Kernel::Armv8 => {
+                let mut abcd = self.pack(h, [0, 1, 2, 3]);
+                let mut efgh = self.pack(h, [4, 5, 6, 7]);
+                for g in 0..16 {
+                    if g >= 4 {
+                        let s0 = self.call_intrinsic("llvm.aarch64.crypto.sha256su0", &[msgs[g - 4], msgs[g - 3]]);
+                        msgs.push(self.call_intrinsic("llvm.aarch64.crypto.sha256su1", &[s0, msgs[g - 2], msgs[g - 1]]));
+                    }
+                    let wk = b.build_int_add(msgs[g], self.round_constants(g), "wk").unwrap();
+                    let next_abcd = self.call_intrinsic("llvm.aarch64.crypto.sha256h", &[abcd, efgh, wk]);
+                    efgh = self.call_intrinsic("llvm.aarch64.crypto.sha256h2", &[efgh, abcd, wk]);
+                    abcd = next_abcd;
+                }
+                let mut out = state.get_undef();
+                for i in 0..4 {
+                    out = self.unpack_into(out, abcd, i as u64, i);
+                    out = self.unpack_into(out, efgh, i as u64, i + 4);
+                }
+                out
+            }
+        };
+        b.build_return(Some(&out.as_basic_value_enum())).unwrap();
+        f
+    }
+
+    /// Body of `<name>`: whole-block calls on a CPU with the extension go to
+    /// the kernel, everything else to the software body.
+    fn emit_sha256_dispatcher(
+        &self,
+        dispatcher: FunctionValue<'ctx>,
+        software: FunctionValue<'ctx>,
+        hardware: FunctionValue<'ctx>,
+        kernel: Sha256Kernel,
+    ) {
+        let b = self.builder;
+        let i64_type = self.context.i64_type();
+        let entry = self.context.append_basic_block(dispatcher, "entry");
+        let detect_bb = self.context.append_basic_block(dispatcher, "detect");
+        let hardware_bb = self.context.append_basic_block(dispatcher, "hardware");
+        let software_bb = self.context.append_basic_block(dispatcher, "software");
+
+        let params: Vec<BasicValueEnum<'ctx>> = dispatcher.get_param_iter().collect();
+        b.position_at_end(entry);
+        let rounds = b
+            .build_int_compare(IntPredicate::EQ, params[0].into_int_value(), i64_type.const_int(64, false), "full")
+            .unwrap();
+        let first = b
+            .build_int_compare(IntPredicate::EQ, params[1].into_int_value(), i64_type.const_zero(), "first")
+            .unwrap();
+        let whole_block = b.build_and(rounds, first, "whole_block").unwrap();
+        b.build_conditional_branch(whole_block, detect_bb, software_bb).unwrap();
+
+        b.position_at_end(detect_bb);
+        let available = self.build_cpu_check(kernel);
+        b.build_conditional_branch(available, hardware_bb, software_bb).unwrap();
+
+        b.position_at_end(hardware_bb);
+        let result = self.call(hardware, &[params[2].into(), params[3].into()]);
+        b.build_return(Some(&result)).unwrap();
+
+        b.position_at_end(software_bb);
+        let args: Vec<BasicMetadataValueEnum<'ctx>> = params.iter().map(|p| (*p).into()).collect();
+        let result = self.call(software, &args);
+        b.build_return(Some(&result)).unwrap();
+    }
+
+    /// `i1` that is true when the running CPU implements the kernel's
+    /// extension.
+    fn build_cpu_check(&self, kernel: Sha256Kernel) -> IntValue<'ctx> {
+        let b = self.builder;
+        let i32_type = self.context.i32_type();
+        match kernel {
+            Sha256Kernel::ShaNi => {
+                // Leaf 0 gives the highest supported leaf; leaf 7 sub-leaf 0
+                // reports SHA in EBX bit 29.
+                let regs = self.context.struct_type(&[i32_type.into(); 4], false);
+                let cpuid_type = regs.fn_type(&[i32_type.into(), i32_type.into()], false);
+                let cpuid = self.context.create_inline_asm(
+                    cpuid_type,
+                    "cpuid".to_string(),
+                    "={ax},={bx},={cx},={dx},{ax},{cx}".to_string(),
+                    false,
+                    false,
+                    None,
+                    false,
+                );
+                let leaf = |n: u64| -> StructValue<'ctx> {
+                    let args = [i32_type.const_int(n, false).into(), i32_type.const_zero().into()];
+                    value_of(build_inline_asm_call(b, cpuid_type, cpuid, &args, "cpuid")).into_struct_value()
+                };
+                let max_leaf = b.build_extract_value(leaf(0), 0, "max_leaf").unwrap().into_int_value();
+                let has_leaf7 = b
+                    .build_int_compare(IntPredicate::UGE, max_leaf, i32_type.const_int(7, false), "has_leaf7")
+                    .unwrap();
+                let ebx = b.build_extract_value(leaf(7), 1, "ebx").unwrap().into_int_value();
+                let sha = b.build_and(ebx, i32_type.const_int(1 << 29, false), "sha_bit").unwrap();
+                let has_sha = b.build_int_compare(IntPredicate::NE, sha, i32_type.const_zero(), "has_sha").unwrap();
+                b.build_and(has_leaf7, has_sha, "sha_ni").unwrap()
+            }
+            Sha256Kernel::Armv8 => {
+                // Linux emulates EL0 reads of the ID registers; SHA2 is
+                // bits [15:12] of ID_AA64ISAR0_EL1.
+                let i64_type = self.context.i64_type();
+                let mrs_type = i64_type.fn_type(&[], false);
+                let mrs = self.context.create_inline_asm(
+                    mrs_type,
+                    "mrs $0, ID_AA64ISAR0_EL1".to_string(),
+                    "=r".to_string(),
+                    false,
+                    false,
+                    None,
+                    false,
+                );
+                let isar0 = value_of(build_inline_asm_call(b, mrs_type, mrs, &[], "isar0")).into_int_value();
+                let field = b.build_and(isar0, i64_type.const_int(0xf << 12, false), "sha2_field").unwrap();
+                b.build_int_compare(IntPredicate::NE, field, i64_type.const_zero(), "sha2").unwrap()
+            }
+        }
+    }
+}
@@ -2,7 +2,9 @@
 ///
 /// This implements the CodegenPort using the Inkwell library
 /// to translate MIR into LLVM Bitcode.
+pub mod big_integer;
 pub mod compat;
+pub mod hardware_intrinsics;
 pub mod platform;
 pub mod strategies;
 pub mod typemapper;
@@ -11,6 +13,7 @@ use crate::adapters::codegen::strategies::*;
 use crate::adapters::codegen::compat::{arena_ptr_initializer, onu_i8ptr};
 use crate::adapters::codegen::typemapper::LlvmTypeMapper;
 use crate::application::ports::compiler_ports::CodegenPort;
+use crate::application::ports::hardware_intrinsic_port::IntrinsicFactory;
 use crate::application::use_cases::registry_service::RegistryService;
 use crate::domain::entities::error::OnuError;
 use crate::domain::entities::mir::*;
@@ -63,6 +66,7 @@ impl CodegenPort for OnuCodegen {
                 .expect("Registry not provided to codegen"),
             ssa_storage: HashMap::new(),
             blocks: HashMap::new(),
+            memo_report: Vec::new(),
         };
 
         generator.generate(program)?;
@@ -113,6 +117,9 @@ struct LlvmGenerator<'ctx, 'a> {
     registry: &'a RegistryService,
     ssa_storage: HashMap<usize, PointerValue<'ctx>>,
     blocks: HashMap<usize, inkwell::basic_block::BasicBlock<'ctx>>,
+    /// Functions carrying `MemoCount` probes, in program order; `main`
+    /// prints their counters before returning.
+    memo_report: Vec<String>,
 }
 
 impl<'ctx, 'a> LlvmGenerator<'ctx, 'a> {
@@ -123,16 +130,36 @@ impl<'ctx, 'a> LlvmGenerator<'ctx, 'a> {
         // add/sub) that LLVM can always lower, bypassing the missing compiler-rt
         // entries for sdiv on types wider than i128.
         self.emit_wide_div_helpers(program);
+        big_integer::BigIntegerRuntime::new(self.context, &self.module, &self.builder).emit_if_referenced(program);
+        self.memo_report = Self::instrumented_functions(program);
 
         for func in &program.functions {
             self.declare_function(func);
         }
+        // Behaviors tagged `with known behavior:` get a hardware dispatcher
+        // under their own name; their bodies below become the fallback.
+        let strategy = IntrinsicFactory::create_for_host();
+        hardware_intrinsics::HardwareIntrinsicEmitter::new(self.context, &self.module, &self.builder, strategy.as_ref())
+            .emit_dispatchers(program)?;
         for func in &program.functions {
             self.generate_function(func)?;
         }
         Ok(())
     }
 
+    /// Names of the behaviors whose memo wrappers carry `MemoCount` probes.
+    fn instrumented_functions(program: &MirProgram) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for inst in program.functions.iter().flat_map(|f| f.blocks.iter()).flat_map(|b| b.instructions.iter()) {
+            if let MirInstruction::MemoCount { function, .. } = inst
+                && !names.contains(function)
+            {
+                names.push(function.clone());
+            }
+        }
+        names
+    }
+
     /// Scan the program for calls to `__onu_wide_div_N` helpers and emit their
     /// LLVM IR implementations if they have not yet been emitted.
     ///
@@ -436,7 +463,11 @@ impl<'ctx, 'a> LlvmGenerator<'ctx, 'a> {
         } else {
             func.name.clone()
         };
-        let function = self.module.get_function(&llvm_name).unwrap();
+        let function = self
+            .module
+            .get_function(&hardware_intrinsics::software_body_name(&llvm_name))
+            .or_else(|| self.module.get_function(&llvm_name))
+            .unwrap();
         self.ssa_storage.clear();
         self.blocks.clear();
 
@@ -543,6 +574,22 @@ impl<'ctx, 'a> LlvmGenerator<'ctx, 'a> {
                 &mut self.ssa_storage,
                 inst,
             ),
+            MirInstruction::CheckedArithmetic { .. } => CheckedArithmeticStrategy.generate(
+                self.context,
+                &self.module,
+                &self.builder,
+                self.registry,
+                &mut self.ssa_storage,
+                inst,
+            ),
+            MirInstruction::RuntimeCheck { .. } => RuntimeCheckStrategy.generate(
+                self.context,
+                &self.module,
+                &self.builder,
+                self.registry,
+                &mut self.ssa_storage,
+                inst,
+            ),
             MirInstruction::Call { .. } => CallStrategy.generate(
                 self.context,
                 &self.module,
@@ -711,6 +758,14 @@ impl<'ctx, 'a> LlvmGenerator<'ctx, 'a> {
                 &mut self.ssa_storage,
                 inst,
             ),
+            MirInstruction::Intrinsic { .. } => IntrinsicStrategy.generate(
+                self.context,
+                &self.module,
+                &self.builder,
+                self.registry,
+                &mut self.ssa_storage,
+                inst,
+            ),
             MirInstruction::BufferedWrite { .. } => BufferedWriteStrategy.generate(
                 self.context,
                 &self.module,
@@ -719,6 +774,16 @@ impl<'ctx, 'a> LlvmGenerator<'ctx, 'a> {
                 &mut self.ssa_storage,
                 inst,
             ),
+            // MemoPass lowers every `ClearMemo` to a MemSet over the cache.
+            MirInstruction::ClearMemo { .. } => Ok(()),
+            MirInstruction::MemoCount { .. } => MemoCountStrategy.generate(
+                self.context,
+                &self.module,
+                &self.builder,
+                self.registry,
+                &mut self.ssa_storage,
+                inst,
+            ),
             MirInstruction::FlushStdout => FlushStdoutStrategy.generate(
                 self.context,
                 &self.module,
@@ -741,6 +806,9 @@ impl<'ctx, 'a> LlvmGenerator<'ctx, 'a> {
                 // Flush the stdout buffer before returning from the entry point.
                 if is_main {
                     strategies::emit_flush_stdout(self.context, &self.module, &self.builder);
+                    if !self.memo_report.is_empty() {
+                        strategies::emit_memo_report(self.context, &self.module, &self.builder, &self.memo_report);
+                    }
                 }
 
                 let val = strategies::operand_to_llvm(
@@ -768,6 +836,21 @@ impl<'ctx, 'a> LlvmGenerator<'ctx, 'a> {
                 } else if is_void {
                     self.builder.build_return(None).unwrap();
                 } else {
+                    // A function widened by IntegerUpgradePass may return an
+                    // i64 operand (e.g. an argument) from a WideInt signature.
+                    let ret_type = function.get_type().get_return_type().unwrap();
+                    let val = if val.is_int_value()
+                        && ret_type.is_int_type()
+                        && val.into_int_value().get_type().get_bit_width()
+                            < ret_type.into_int_type().get_bit_width()
+                    {
+                        self.builder
+                            .build_int_z_extend(val.into_int_value(), ret_type.into_int_type(), "ret_zext")
+                            .unwrap()
+                            .into()
+                    } else {
+                        val
+                    };
                     self.builder.build_return(Some(&val)).unwrap();
                 }
             }
@@ -43,6 +43,15 @@ pub trait PlatformSyscalls {
         buf: PointerValue<'ctx>,
         max_len: IntValue<'ctx>,
     ) -> IntValue<'ctx>;
+
+    /// Emit an *exit* syscall terminating the whole process with `code`.
+    /// Control never returns; callers follow it with `unreachable`.
+    fn emit_exit<'ctx>(
+        &self,
+        context: &'ctx Context,
+        builder: &Builder<'ctx>,
+        code: IntValue<'ctx>,
+    );
 }
 
 /// Factory: returns the syscall provider for the current compilation target.
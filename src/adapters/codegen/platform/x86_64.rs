@@ -1,7 +1,7 @@
 /// x86_64 Syscall Implementation
 ///
 /// Implements `PlatformSyscalls` using the Linux x86_64 syscall ABI:
-///   - `%rax` = syscall number  (0 = read, 1 = write)
+///   - `%rax` = syscall number  (0 = read, 1 = write, 231 = exit_group)
 ///   - `%rdi` = arg 1           (file descriptor)
 ///   - `%rsi` = arg 2           (buffer pointer)
 ///   - `%rdx` = arg 3           (byte count)
@@ -99,4 +99,16 @@ impl PlatformSyscalls for X86_64Syscalls {
         // sys_read = 0
         Self::build_syscall_asm(context, builder, 0, fd, buf, max_len, "syscall_read")
     }
+
+    fn emit_exit<'ctx>(
+        &self,
+        context: &'ctx Context,
+        builder: &Builder<'ctx>,
+        code: IntValue<'ctx>,
+    ) {
+        // sys_exit_group = 231; the buffer and count registers are unused.
+        let null = onu_i8ptr(context).const_null();
+        let zero = context.i64_type().const_zero();
+        Self::build_syscall_asm(context, builder, 231, code, null, zero, "syscall_exit");
+    }
 }
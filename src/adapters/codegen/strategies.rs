@@ -8,7 +8,7 @@ use crate::domain::entities::error::OnuError;
 /// This module implements the Strategy Pattern for MIR Instruction generation.
 /// Each strategy is responsible for translating a specific MIR instruction
 /// into the corresponding LLVM IR.
-use crate::domain::entities::mir::{MirBinOp, MirInstruction, MirLiteral, MirOperand};
+use crate::domain::entities::mir::{MirBinOp, MirInstruction, MirIntrinsic, MirLiteral, MirOperand};
 use crate::domain::entities::types::OnuType;
 
 use inkwell::builder::Builder;
@@ -118,6 +118,28 @@ impl<'ctx> InstructionStrategy<'ctx> for BinaryOpStrategy {
                     .build_int_nsw_mul(l_val.into_int_value(), r_val.into_int_value(), "multmp")
                     .unwrap()
                     .into(),
+                MirBinOp::WrappingAdd => builder
+                    .build_int_add(l_val.into_int_value(), r_val.into_int_value(), "wraddtmp")
+                    .unwrap()
+                    .into(),
+                MirBinOp::SaturatingAdd => {
+                    let l_int = l_val.into_int_value();
+                    let width = l_int.get_type().get_bit_width();
+                    let sign = if is_unsigned(dest_type) { "u" } else { "s" };
+                    let name = format!("llvm.{}add.sat.i{}", sign, width);
+                    let intrinsic_fn = module.get_function(&name).unwrap_or_else(|| {
+                        let int_type = l_int.get_type();
+                        let fn_type = int_type.fn_type(&[int_type.into(), int_type.into()], false);
+                        module.add_function(&name, fn_type, None)
+                    });
+                    let call = builder
+                        .build_call(intrinsic_fn, &[l_int.into(), r_val.into_int_value().into()], "sataddtmp")
+                        .unwrap();
+                    match call.try_as_basic_value() {
+                        inkwell::values::ValueKind::Basic(v) => v,
+                        _ => l_int.get_type().const_zero().into(),
+                    }
+                }
                 MirBinOp::Div => builder
                     .build_int_signed_div(l_val.into_int_value(), r_val.into_int_value(), "divtmp")
                     .unwrap()
@@ -763,7 +785,12 @@ fn generate_receives_argument<'ctx>(
     } else {
         i64_type.const_int(0, false)
     };
-    let arg_ptr_ptr = unsafe { build_byte_gep(context, builder, argv_ptr, idx, "arg_ptr_ptr") };
+    // argv is an array of pointers: step 8 bytes per entry, then load the
+    // entry as a pointer rather than through the byte-typed GEP result.
+    let argv_bytes = cast_to_typed_ptr(context, builder, argv_ptr, i8_type, "argv_bytes");
+    let offset = builder.build_int_mul(idx, i64_type.const_int(8, false), "argv_offset").unwrap();
+    let arg_slot = unsafe { build_byte_gep(context, builder, argv_bytes, offset, "arg_slot") };
+    let arg_ptr_ptr = cast_to_typed_ptr(context, builder, arg_slot, i8_ptr_type, "arg_ptr_ptr");
     let arg_ptr = build_typed_load(context, builder, i8_ptr_type, arg_ptr_ptr, "arg_ptr").into_pointer_value();
 
     // Compute strlen by scanning for '\0' (pure LLVM loop — no libc)
@@ -1887,6 +1914,77 @@ impl<'ctx> InstructionStrategy<'ctx> for FunnelShiftRightStrategy {
     }
 }
 
+/// Strategy for `MirInstruction::Intrinsic`.
+///
+/// Truncates the operands to `width` bits, calls `@llvm.<name>.iN` and
+/// zero-extends the result back to `i64`.  `ctlz`, `cttz` and `abs` get
+/// `false` for their poison flag, so zero and the minimum value are defined.
+pub struct IntrinsicStrategy;
+impl<'ctx> InstructionStrategy<'ctx> for IntrinsicStrategy {
+    fn generate(
+        &self,
+        context: &'ctx Context,
+        module: &Module<'ctx>,
+        builder: &Builder<'ctx>,
+        _registry: &RegistryService,
+        ssa_storage: &mut HashMap<usize, PointerValue<'ctx>>,
+        inst: &MirInstruction,
+    ) -> Result<(), OnuError> {
+        if let MirInstruction::Intrinsic { dest, intrinsic, args, width } = inst {
+            let int_type = context.custom_width_int_type(*width);
+            let (name, poison_flag) = match intrinsic {
+                MirIntrinsic::PopCount => ("ctpop", false),
+                MirIntrinsic::LeadingZeros => ("ctlz", true),
+                MirIntrinsic::TrailingZeros => ("cttz", true),
+                MirIntrinsic::ByteSwap => ("bswap", false),
+                MirIntrinsic::FunnelShiftLeft => ("fshl", false),
+                MirIntrinsic::SignedMin => ("smin", false),
+                MirIntrinsic::SignedMax => ("smax", false),
+                MirIntrinsic::Abs => ("abs", true),
+            };
+            let fn_name = format!("llvm.{}.i{}", name, width);
+
+            let mut values: Vec<inkwell::values::BasicMetadataValueEnum> = args
+                .iter()
+                .map(|a| {
+                    let v = operand_to_llvm(context, builder, ssa_storage, a).into_int_value();
+                    if v.get_type().get_bit_width() != *width {
+                        builder.build_int_truncate(v, int_type, "intrinsic_trunc").unwrap().into()
+                    } else {
+                        v.into()
+                    }
+                })
+                .collect();
+            if poison_flag {
+                values.push(context.bool_type().const_zero().into());
+            }
+
+            let intrinsic_fn = module.get_function(&fn_name).unwrap_or_else(|| {
+                let mut params: Vec<inkwell::types::BasicMetadataTypeEnum> = vec![int_type.into(); args.len()];
+                if poison_flag {
+                    params.push(context.bool_type().into());
+                }
+                module.add_function(&fn_name, int_type.fn_type(&params, false), None)
+            });
+
+            let call_val = builder.build_call(intrinsic_fn, &values, "intrinsic_result").unwrap();
+            let result = match call_val.try_as_basic_value() {
+                inkwell::values::ValueKind::Basic(v) => v.into_int_value(),
+                _ => int_type.const_zero(),
+            };
+            let final_val = if *width < 64 {
+                builder.build_int_z_extend(result, context.i64_type(), "intrinsic_zext").unwrap()
+            } else {
+                result
+            };
+
+            let ptr = get_or_create_ssa(context, builder, ssa_storage, *dest, final_val.get_type().into());
+            builder.build_store(ptr, final_val).unwrap();
+        }
+        Ok(())
+    }
+}
+
 // ── Phase 4: Buffered I/O Strategies ────────────────────────────────────
 
 /// Strategy for `MirInstruction::BufferedWrite`.
@@ -1938,3 +2036,366 @@ impl<'ctx> InstructionStrategy<'ctx> for FlushStdoutStrategy {
         Ok(())
     }
 }
+
+// ── Phase 5: Runtime Check Strategies ───────────────────────────────────
+
+const STDERR_FD: u64 = 2;
+const TRAP_EXIT_CODE: u64 = 1;
+
+fn is_unsigned(typ: &OnuType) -> bool {
+    matches!(
+        typ,
+        OnuType::U8 | OnuType::U16 | OnuType::U32 | OnuType::U64 | OnuType::U128
+    )
+}
+
+/// Get or emit `__onu_trap(i8* msg, i64 len)`: flushes buffered stdout so
+/// earlier output is not lost, writes `msg` to stderr and exits the process
+/// with a non-zero status.  Marked `noreturn` + `cold` so LLVM moves every
+/// call site out of the hot path.
+fn get_or_emit_trap<'ctx>(
+    context: &'ctx Context,
+    module: &Module<'ctx>,
+) -> inkwell::values::FunctionValue<'ctx> {
+    use inkwell::attributes::{Attribute, AttributeLoc};
+
+    if let Some(f) = module.get_function("__onu_trap") {
+        return f;
+    }
+
+    let i64_type = context.i64_type();
+    let fn_type = context
+        .void_type()
+        .fn_type(&[onu_i8ptr(context).into(), i64_type.into()], false);
+    let trap_fn = module.add_function("__onu_trap", fn_type, Some(inkwell::module::Linkage::Internal));
+    for attr_name in &["noreturn", "cold", "noinline", "nounwind"] {
+        let kind_id = Attribute::get_named_enum_kind_id(attr_name);
+        trap_fn.add_attribute(AttributeLoc::Function, context.create_enum_attribute(kind_id, 0));
+    }
+
+    // A private builder keeps the caller's insertion point untouched.
+    let builder = context.create_builder();
+    let entry = context.append_basic_block(trap_fn, "entry");
+    builder.position_at_end(entry);
+
+    let msg = trap_fn.get_nth_param(0).unwrap().into_pointer_value();
+    let len = trap_fn.get_nth_param(1).unwrap().into_int_value();
+    emit_flush_stdout(context, module, &builder);
+
+    let syscalls = crate::adapters::codegen::platform::create_syscalls();
+    syscalls.emit_write(context, &builder, i64_type.const_int(STDERR_FD, false), msg, len);
+    syscalls.emit_exit(context, &builder, i64_type.const_int(TRAP_EXIT_CODE, false));
+    builder.build_unreachable().unwrap();
+
+    trap_fn
+}
+
+/// Emit a call to `__onu_trap` reporting `message` (a trailing newline is
+/// appended), then terminate the current block with `unreachable`.
+pub(crate) fn emit_trap_call<'ctx>(
+    context: &'ctx Context,
+    module: &Module<'ctx>,
+    builder: &Builder<'ctx>,
+    message: &str,
+) {
+    let trap_fn = get_or_emit_trap(context, module);
+    let text = format!("onu: {}\n", message);
+    let msg = builder.build_global_string_ptr(&text, "trap_msg").unwrap();
+    let len = context.i64_type().const_int(text.len() as u64, false);
+    builder
+        .build_call(trap_fn, &[msg.as_pointer_value().into(), len.into()], "")
+        .unwrap();
+    builder.build_unreachable().unwrap();
+}
+
+/// Strategy for `MirInstruction::CheckedArithmetic`.
+///
+/// Lowers to `llvm.{s,u}{add,sub,mul}.with.overflow.iN` and branches to
+/// `__onu_trap` when the overflow bit is set.  Signedness follows the
+/// destination type; the width follows the operands.
+pub struct CheckedArithmeticStrategy;
+impl<'ctx> InstructionStrategy<'ctx> for CheckedArithmeticStrategy {
+    fn generate(
+        &self,
+        context: &'ctx Context,
+        module: &Module<'ctx>,
+        builder: &Builder<'ctx>,
+        _registry: &RegistryService,
+        ssa_storage: &mut HashMap<usize, PointerValue<'ctx>>,
+        inst: &MirInstruction,
+    ) -> Result<(), OnuError> {
+        if let MirInstruction::CheckedArithmetic { dest, op, lhs, rhs, dest_type, site } = inst {
+            let mut l_val = operand_to_llvm(context, builder, ssa_storage, lhs).into_int_value();
+            let mut r_val = operand_to_llvm(context, builder, ssa_storage, rhs).into_int_value();
+            let l_width = l_val.get_type().get_bit_width();
+            let r_width = r_val.get_type().get_bit_width();
+            if l_width < r_width {
+                l_val = builder.build_int_z_extend(l_val, r_val.get_type(), "implicit_zext_l").unwrap();
+            } else if r_width < l_width {
+                r_val = builder.build_int_z_extend(r_val, l_val.get_type(), "implicit_zext_r").unwrap();
+            }
+
+            let (verb, operator) = match op {
+                MirBinOp::Add => ("add", "added-to"),
+                MirBinOp::Sub => ("sub", "decreased-by"),
+                MirBinOp::Mul => ("mul", "scales-by"),
+                other => {
+                    return Err(OnuError::CodeGenError {
+                        message: format!("CheckedArithmetic does not support {:?}", other),
+                    })
+                }
+            };
+            let int_type = l_val.get_type();
+            let sign = if is_unsigned(dest_type) { "u" } else { "s" };
+            let name = format!("llvm.{}{}.with.overflow.i{}", sign, verb, int_type.get_bit_width());
+            let intrinsic_fn = module.get_function(&name).unwrap_or_else(|| {
+                let result_type = context.struct_type(&[int_type.into(), context.bool_type().into()], false);
+                let fn_type = result_type.fn_type(&[int_type.into(), int_type.into()], false);
+                module.add_function(&name, fn_type, None)
+            });
+
+            let call = builder
+                .build_call(intrinsic_fn, &[l_val.into(), r_val.into()], "checked")
+                .unwrap();
+            let pair = match call.try_as_basic_value() {
+                inkwell::values::ValueKind::Basic(v) => v.into_struct_value(),
+                _ => {
+                    return Err(OnuError::CodeGenError {
+                        message: format!("{} returned no value", name),
+                    })
+                }
+            };
+            let result = builder.build_extract_value(pair, 0, "checked_val").unwrap();
+            let overflowed = builder
+                .build_extract_value(pair, 1, "overflowed")
+                .unwrap()
+                .into_int_value();
+
+            let parent_fn = builder.get_insert_block().unwrap().get_parent().unwrap();
+            let trap_bb = context.append_basic_block(parent_fn, "overflow_trap");
+            let cont_bb = context.append_basic_block(parent_fn, "overflow_ok");
+            builder.build_conditional_branch(overflowed, trap_bb, cont_bb).unwrap();
+
+            builder.position_at_end(trap_bb);
+            emit_trap_call(
+                context,
+                module,
+                builder,
+                &format!("integer overflow in `{}` within {}", operator, site),
+            );
+
+            builder.position_at_end(cont_bb);
+            let ptr = get_or_create_ssa(context, builder, ssa_storage, *dest, result.get_type());
+            builder.build_store(ptr, result).unwrap();
+        }
+        Ok(())
+    }
+}
+
+/// Strategy for `MirInstruction::RuntimeCheck`.
+///
+/// Branches to `__onu_trap` with the check's message when the condition is
+/// zero; execution continues in a fresh block otherwise.
+pub struct RuntimeCheckStrategy;
+impl<'ctx> InstructionStrategy<'ctx> for RuntimeCheckStrategy {
+    fn generate(
+        &self,
+        context: &'ctx Context,
+        module: &Module<'ctx>,
+        builder: &Builder<'ctx>,
+        _registry: &RegistryService,
+        ssa_storage: &mut HashMap<usize, PointerValue<'ctx>>,
+        inst: &MirInstruction,
+    ) -> Result<(), OnuError> {
+        if let MirInstruction::RuntimeCheck { condition, message } = inst {
+            let cond_val = operand_to_llvm(context, builder, ssa_storage, condition).into_int_value();
+            let passed = builder
+                .build_int_compare(
+                    inkwell::IntPredicate::NE,
+                    cond_val,
+                    cond_val.get_type().const_zero(),
+                    "check_passed",
+                )
+                .unwrap();
+
+            let parent_fn = builder.get_insert_block().unwrap().get_parent().unwrap();
+            let ok_bb = context.append_basic_block(parent_fn, "check_ok");
+            let trap_bb = context.append_basic_block(parent_fn, "check_failed");
+            builder.build_conditional_branch(passed, ok_bb, trap_bb).unwrap();
+
+            builder.position_at_end(trap_bb);
+            emit_trap_call(context, module, builder, message);
+
+            builder.position_at_end(ok_bb);
+        }
+        Ok(())
+    }
+}
+
+// ── Phase 6: Instrumentation Strategies ─────────────────────────────────
+
+const MEMO_STATS_PREFIX: &str = "__onu_memo_stats.";
+const DECIMAL_DIGITS_U64: u32 = 20;
+
+/// Get or add `__onu_memo_stats.{function}`: one zeroed i64 per
+/// `MemoCounter`, indexed by `MemoCounter::index`.
+fn memo_stats_global<'ctx>(
+    context: &'ctx Context,
+    module: &Module<'ctx>,
+    function: &str,
+) -> inkwell::values::GlobalValue<'ctx> {
+    use crate::domain::entities::mir::MemoCounter;
+
+    let name = format!("{}{}", MEMO_STATS_PREFIX, function);
+    module.get_global(&name).unwrap_or_else(|| {
+        let array_type = context.i64_type().array_type(MemoCounter::ALL.len() as u32);
+        let g = module.add_global(array_type, None, &name);
+        g.set_initializer(&array_type.const_zero());
+        g.set_linkage(inkwell::module::Linkage::Internal);
+        g
+    })
+}
+
+/// Get or emit `__onu_write_decimal(i64 value)`: writes `value` (treated as
+/// unsigned) to stderr in base 10, filling a stack buffer from the right.
+fn get_or_emit_write_decimal<'ctx>(
+    context: &'ctx Context,
+    module: &Module<'ctx>,
+) -> inkwell::values::FunctionValue<'ctx> {
+    if let Some(f) = module.get_function("__onu_write_decimal") {
+        return f;
+    }
+
+    let i8_type = context.i8_type();
+    let i64_type = context.i64_type();
+    let fn_type = context.void_type().fn_type(&[i64_type.into()], false);
+    let write_fn = module.add_function("__onu_write_decimal", fn_type, Some(inkwell::module::Linkage::Internal));
+
+    let builder = context.create_builder();
+    let entry = context.append_basic_block(write_fn, "entry");
+    let digit_bb = context.append_basic_block(write_fn, "digit");
+    let done_bb = context.append_basic_block(write_fn, "done");
+
+    builder.position_at_end(entry);
+    let buf = builder
+        .build_alloca(i8_type.array_type(DECIMAL_DIGITS_U64), "digits")
+        .unwrap();
+    builder.build_unconditional_branch(digit_bb).unwrap();
+
+    builder.position_at_end(digit_bb);
+    let value = builder.build_phi(i64_type, "value").unwrap();
+    let end = builder.build_phi(i64_type, "end").unwrap();
+    let ten = i64_type.const_int(10, false);
+    let pos = builder
+        .build_int_sub(end.as_basic_value().into_int_value(), i64_type.const_int(1, false), "pos")
+        .unwrap();
+    let current = value.as_basic_value().into_int_value();
+    let digit = builder.build_int_unsigned_rem(current, ten, "digit").unwrap();
+    let rest = builder.build_int_unsigned_div(current, ten, "rest").unwrap();
+    let ascii = builder
+        .build_int_add(
+            builder.build_int_truncate(digit, i8_type, "digit8").unwrap(),
+            i8_type.const_int(b'0' as u64, false),
+            "ascii",
+        )
+        .unwrap();
+    let slot = unsafe {
+        builder
+            .build_in_bounds_gep(buf, &[i64_type.const_zero(), pos], "slot")
+            .unwrap()
+    };
+    builder.build_store(slot, ascii).unwrap();
+    let more = builder
+        .build_int_compare(inkwell::IntPredicate::NE, rest, i64_type.const_zero(), "more")
+        .unwrap();
+    builder.build_conditional_branch(more, digit_bb, done_bb).unwrap();
+    value.add_incoming(&[(&write_fn.get_nth_param(0).unwrap(), entry), (&rest, digit_bb)]);
+    end.add_incoming(&[(&i64_type.const_int(DECIMAL_DIGITS_U64 as u64, false), entry), (&pos, digit_bb)]);
+
+    builder.position_at_end(done_bb);
+    let len = builder
+        .build_int_sub(i64_type.const_int(DECIMAL_DIGITS_U64 as u64, false), pos, "len")
+        .unwrap();
+    let syscalls = crate::adapters::codegen::platform::create_syscalls();
+    syscalls.emit_write(context, &builder, i64_type.const_int(STDERR_FD, false), slot, len);
+    builder.build_return(None).unwrap();
+
+    write_fn
+}
+
+/// Emit the `--memo-report` summary: one stderr line per instrumented
+/// function, e.g. "onu memo: `fib`: 88 hits, 91 misses, 0 collisions".
+/// Called just before `run` returns.
+pub(crate) fn emit_memo_report<'ctx>(
+    context: &'ctx Context,
+    module: &Module<'ctx>,
+    builder: &Builder<'ctx>,
+    functions: &[String],
+) {
+    use crate::domain::entities::mir::MemoCounter;
+
+    let i64_type = context.i64_type();
+    let stderr = i64_type.const_int(STDERR_FD, false);
+    let syscalls = crate::adapters::codegen::platform::create_syscalls();
+    let write_decimal = get_or_emit_write_decimal(context, module);
+    let write_text = |text: &str| {
+        let ptr = builder.build_global_string_ptr(text, "memo_report_text").unwrap();
+        let len = i64_type.const_int(text.len() as u64, false);
+        syscalls.emit_write(context, builder, stderr, ptr.as_pointer_value(), len);
+    };
+
+    for function in functions {
+        let stats = memo_stats_global(context, module, function);
+        write_text(&format!("onu memo: `{}`: ", function));
+        for (i, counter) in MemoCounter::ALL.iter().enumerate() {
+            let field = unsafe {
+                builder
+                    .build_in_bounds_gep(
+                        stats.as_pointer_value(),
+                        &[i64_type.const_zero(), i64_type.const_int(counter.index() as u64, false)],
+                        "memo_stat_ptr",
+                    )
+                    .unwrap()
+            };
+            let count = build_typed_load(context, builder, i64_type, field, "memo_stat");
+            builder.build_call(write_decimal, &[count.into()], "").unwrap();
+            let separator = if i + 1 < MemoCounter::ALL.len() { ", " } else { "\n" };
+            write_text(&format!(" {}{}", counter.label(), separator));
+        }
+    }
+}
+
+/// Strategy for `MirInstruction::MemoCount`: increments one counter in the
+/// function's `__onu_memo_stats.*` global.
+pub struct MemoCountStrategy;
+impl<'ctx> InstructionStrategy<'ctx> for MemoCountStrategy {
+    fn generate(
+        &self,
+        context: &'ctx Context,
+        module: &Module<'ctx>,
+        builder: &Builder<'ctx>,
+        _registry: &RegistryService,
+        _ssa_storage: &mut HashMap<usize, PointerValue<'ctx>>,
+        inst: &MirInstruction,
+    ) -> Result<(), OnuError> {
+        if let MirInstruction::MemoCount { function, counter } = inst {
+            let i64_type = context.i64_type();
+            let stats = memo_stats_global(context, module, function);
+            let field = unsafe {
+                builder
+                    .build_in_bounds_gep(
+                        stats.as_pointer_value(),
+                        &[i64_type.const_zero(), i64_type.const_int(counter.index() as u64, false)],
+                        "memo_stat_ptr",
+                    )
+                    .unwrap()
+            };
+            let count = build_typed_load(context, builder, i64_type, field, "memo_stat").into_int_value();
+            let bumped = builder
+                .build_int_add(count, i64_type.const_int(1, false), "memo_stat_next")
+                .unwrap();
+            builder.build_store(field, bumped).unwrap();
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::adapters::codegen::compat::onu_i8ptr;
+use crate::adapters::codegen::compat::{onu_i8ptr, onu_ptr_to};
 use crate::application::use_cases::registry_service::RegistryService;
 use crate::domain::entities::types::OnuType;
 use inkwell::context::Context;
@@ -66,6 +66,8 @@ impl LlvmTypeMapper {
             }
             // Raw byte-pointer (internal compiler type, used only in MemoPass-generated code).
             OnuType::Ptr => Some(onu_i8ptr(context).as_basic_type_enum()),
+            // Limb block in the arena: { i64 signed_len, i64 limbs... }.
+            OnuType::BigInt => Some(onu_ptr_to(context.i64_type()).as_basic_type_enum()),
             OnuType::Nothing => None,
 
             _ => Some(context.i64_type().as_basic_type_enum()),
@@ -0,0 +1,595 @@
+//! Ọ̀nụ Documentation Adapter: Module References from Intent Prose
+//!
+//! `onu doc` turns the prose a program already carries into a reference:
+//! one page per module, headed by its `with concern:` text, listing every
+//! shape with its fields and every behavior with its signature, whether it
+//! is pure or an effect, its termination, memoization and inlining
+//! annotations, and its `with intent:` text.  Behaviors link to the ones
+//! they call and back to the ones that call them, across every module
+//! documented together.
+//!
+//! The parser keeps only the words of intent and concern prose, so the text
+//! is copied verbatim from the source the way `onu fmt` does.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::adapters::formatter::{HeaderProse, type_name, with_article};
+use crate::adapters::lexer::OnuLexer;
+use crate::adapters::parser::OnuParser;
+use crate::adapters::parser::helpers::error_recovery::is_discourse_starter;
+use crate::application::options::LogLevel;
+use crate::application::ports::compiler_ports::Token;
+use crate::application::use_cases::registry_service::RegistryService;
+use crate::domain::entities::ast::{Argument, BehaviorHeader, Discourse, Expression};
+use crate::domain::entities::error::OnuError;
+use crate::domain::entities::types::{InlineHint, OnuType};
+
+/// The output language of a reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+}
+
+impl DocFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "markdown" | "md" => Some(DocFormat::Markdown),
+            "html" => Some(DocFormat::Html),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+        }
+    }
+}
+
+/// Everything documented about one module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleDoc {
+    pub name: String,
+    pub concern: String,
+    pub shapes: Vec<ShapeDoc>,
+    pub behaviors: Vec<BehaviorDoc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeDoc {
+    pub name: String,
+    pub intent: String,
+    pub fields: Vec<Argument>,
+}
+
+/// A behavior's header, with its intent as written, and the behaviors its
+/// body calls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BehaviorDoc {
+    pub header: BehaviorHeader,
+    pub calls: BTreeSet<String>,
+}
+
+pub struct OnuDocGenerator {
+    pub log_level: LogLevel,
+}
+
+impl OnuDocGenerator {
+    pub fn new(log_level: LogLevel) -> Self {
+        Self { log_level }
+    }
+
+    /// The modules declared in `source`.  Definitions before the first
+    /// `the module called` belong to a module named `default_module`.
+    pub fn extract(&self, source: &str, default_module: &str) -> Result<Vec<ModuleDoc>, OnuError> {
+        let lexed = OnuLexer::new(self.log_level).lex_with_layout(source)?;
+        let tokens: Vec<Token> = lexed.tokens.iter().map(|(t, _)| t.clone()).collect();
+        let parser = OnuParser::new(self.log_level);
+        let mut registry = RegistryService::new();
+        parser.scan_headers(&tokens, &mut registry)?;
+        let discourses = parser.parse_with_registry(tokens.clone(), &mut registry)?;
+
+        let starts: Vec<usize> = (0..tokens.len()).filter(|&i| is_discourse_starter(&tokens[i])).collect();
+        let lines: Vec<&str> = source.lines().collect();
+        let prose_of = |index: usize| match starts.get(index) {
+            // Skipped text between definitions would misalign the prose, so
+            // fall back to the parsed words.
+            Some(&start) if starts.len() == discourses.len() => {
+                let end = starts.get(index + 1).copied().unwrap_or(tokens.len());
+                HeaderProse::find(&lexed, start..end, &lines)
+            }
+            _ => HeaderProse::default(),
+        };
+
+        let mut modules: Vec<ModuleDoc> = Vec::new();
+        for (index, discourse) in discourses.iter().enumerate() {
+            // `with intent: nothing` states that there is no prose.
+            let mut prose = prose_of(index);
+            prose.intent = prose.intent.filter(|p| p != "nothing");
+            prose.concern = prose.concern.filter(|p| p != "nothing");
+            if let Discourse::Module { name, concern } = discourse {
+                let concern = prose.concern.unwrap_or_else(|| concern.clone());
+                modules.push(ModuleDoc { name: name.clone(), concern, shapes: Vec::new(), behaviors: Vec::new() });
+                continue;
+            }
+            if modules.is_empty() {
+                modules.push(ModuleDoc {
+                    name: default_module.to_string(),
+                    concern: String::new(),
+                    shapes: Vec::new(),
+                    behaviors: Vec::new(),
+                });
+            }
+            let module = modules.last_mut().expect("a module was just pushed");
+            match discourse {
+                Discourse::Shape { name, fields, .. } => module.shapes.push(ShapeDoc {
+                    name: name.clone(),
+                    intent: prose.intent.unwrap_or_default(),
+                    fields: fields.clone(),
+                }),
+                Discourse::Behavior { header, body } => {
+                    let mut header = header.clone();
+                    if let Some(intent) = prose.intent {
+                        header.intent = intent;
+                    }
+                    let mut calls = BTreeSet::new();
+                    calls_in(body, &mut calls);
+                    module.behaviors.push(BehaviorDoc { header, calls });
+                }
+                Discourse::Module { .. } | Discourse::Test { .. } => {}
+            }
+        }
+        Ok(modules)
+    }
+}
+
+/// Every behavior named by a call in `expr`, including shape constructors,
+/// field accessors and the standard library; `Reference` keeps only the
+/// documented behaviors.
+fn calls_in(expr: &Expression, calls: &mut BTreeSet<String>) {
+    match expr {
+        Expression::BehaviorCall { name, args } => {
+            calls.insert(name.clone());
+            args.iter().for_each(|a| calls_in(a, calls));
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            calls_in(left, calls);
+            calls_in(right, calls);
+        }
+        Expression::Tuple(items) | Expression::Array(items) | Expression::Block(items) => {
+            items.iter().for_each(|e| calls_in(e, calls));
+        }
+        Expression::Matrix { data, .. } => data.iter().for_each(|e| calls_in(e, calls)),
+        Expression::Derivation { value, body, .. } => {
+            calls_in(value, calls);
+            calls_in(body, calls);
+        }
+        Expression::If { condition, then_branch, else_branch } => {
+            calls_in(condition, calls);
+            calls_in(then_branch, calls);
+            calls_in(else_branch, calls);
+        }
+        Expression::ActsAs { subject, .. } => calls_in(subject, calls),
+        Expression::Emit(e) | Expression::Broadcasts(e) | Expression::Drop(e) => calls_in(e, calls),
+        _ => {}
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+/// The pages for a set of modules documented together, which may link to
+/// each other.
+pub struct Reference<'a> {
+    modules: &'a [ModuleDoc],
+    format: DocFormat,
+    /// The module defining each behavior and shape.
+    owners: BTreeMap<&'a str, &'a str>,
+    behaviors: BTreeSet<&'a str>,
+    callers: BTreeMap<&'a str, BTreeSet<&'a str>>,
+}
+
+impl<'a> Reference<'a> {
+    pub fn new(modules: &'a [ModuleDoc], format: DocFormat) -> Self {
+        let mut owners = BTreeMap::new();
+        for module in modules {
+            for shape in &module.shapes {
+                owners.insert(shape.name.as_str(), module.name.as_str());
+            }
+            for behavior in &module.behaviors {
+                owners.insert(behavior.header.name.as_str(), module.name.as_str());
+            }
+        }
+        let behaviors: BTreeSet<&str> = modules.iter().flat_map(|m| &m.behaviors).map(|b| b.header.name.as_str()).collect();
+        let mut callers: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for behavior in modules.iter().flat_map(|m| &m.behaviors) {
+            let caller = behavior.header.name.as_str();
+            for callee in behavior.calls.iter().filter(|c| c.as_str() != caller && behaviors.contains(c.as_str())) {
+                callers.entry(callee.as_str()).or_default().insert(caller);
+            }
+        }
+        Self { modules, format, owners, behaviors, callers }
+    }
+
+    /// The file a module's page is written to.
+    pub fn file_name(&self, module: &ModuleDoc) -> String {
+        format!("{}.{}", module.name, self.format.extension())
+    }
+
+    /// A page listing every module with its concern.
+    pub fn index(&self) -> String {
+        let mut page = Page::new(self.format, "Module Reference");
+        page.heading(1, "Module Reference", None);
+        let entries = self
+            .modules
+            .iter()
+            .map(|m| {
+                let link = page.link(&m.name, &self.file_name(m));
+                if m.concern.is_empty() { link } else { format!("{}: {}", link, page.text(&m.concern)) }
+            })
+            .collect();
+        page.list(entries);
+        page.finish()
+    }
+
+    pub fn page(&self, module: &ModuleDoc) -> String {
+        let mut page = Page::new(self.format, &module.name);
+        page.heading(1, &module.name, None);
+        if !module.concern.is_empty() {
+            let concern = page.emphasis(&module.concern);
+            page.paragraph(concern);
+        }
+
+        if !module.shapes.is_empty() {
+            page.heading(2, "Shapes", None);
+        }
+        for shape in &module.shapes {
+            page.heading(3, &shape.name, Some(&anchor(&shape.name)));
+            self.intent(&mut page, &shape.intent);
+            let fields = shape.fields.iter().map(|f| self.argument(&page, module, f)).collect();
+            page.list(fields);
+        }
+
+        if !module.behaviors.is_empty() {
+            page.heading(2, "Behaviors", None);
+        }
+        for behavior in &module.behaviors {
+            self.behavior(&mut page, module, behavior);
+        }
+        page.finish()
+    }
+
+    fn behavior(&self, page: &mut Page, module: &ModuleDoc, behavior: &BehaviorDoc) {
+        let header = &behavior.header;
+        page.heading(3, &header.name, Some(&anchor(&header.name)));
+        let mut kind = if header.is_effect { "effect behavior" } else { "pure behavior" }.to_string();
+        if behavior.calls.contains(&header.name) {
+            kind.push_str(", recursive");
+        }
+        let kind = page.emphasis(&kind);
+        page.paragraph(kind);
+        self.intent(page, &header.intent);
+
+        let mut items = Vec::new();
+        let takes = if header.takes.is_empty() {
+            page.text("nothing")
+        } else {
+            header.takes.iter().map(|a| self.argument(page, module, a)).collect::<Vec<_>>().join(", ")
+        };
+        items.push(page.field("Takes", &takes));
+        let delivers = match &header.delivers.0 {
+            OnuType::Nothing => page.text("nothing"),
+            other => self.typ(page, module, other, true),
+        };
+        items.push(page.field("Delivers", &delivers));
+
+        let diminishing = header.diminishing.iter().map(|d| page.code(d)).collect::<Vec<_>>().join(", ");
+        if header.skip_termination_check {
+            let text = format!("not guaranteed ({})", page.code("with no guaranteed termination"));
+            items.push(page.field("Termination", &text));
+        } else if !diminishing.is_empty() {
+            let text = format!("{} diminishes on every recursive call", diminishing);
+            items.push(page.field("Termination", &text));
+        }
+        // `with diminishing:` is what asks for a cache; whether the compiler
+        // builds one is reported by `--memo-report`.
+        if !header.is_effect && !diminishing.is_empty() {
+            let text = format!("keyed on {}", diminishing);
+            items.push(page.field("Memoization", &text));
+        }
+        match header.inlining {
+            InlineHint::Auto => {}
+            InlineHint::Always => items.push(page.field("Inlining", &page.text("always"))),
+            InlineHint::Never => items.push(page.field("Inlining", &page.text("never"))),
+        }
+        if let Some(known) = header.known_behavior {
+            items.push(page.field("Known behavior", &page.code(known.name())));
+        }
+
+        let calls: Vec<&str> = behavior
+            .calls
+            .iter()
+            .map(String::as_str)
+            .filter(|c| *c != header.name && self.behaviors.contains(c))
+            .collect();
+        if !calls.is_empty() {
+            let links = self.links(page, module, calls);
+            items.push(page.field("Calls", &links));
+        }
+        if let Some(callers) = self.callers.get(header.name.as_str()) {
+            let links = self.links(page, module, callers.iter().copied().collect());
+            items.push(page.field("Called by", &links));
+        }
+        page.list(items);
+    }
+
+    fn intent(&self, page: &mut Page, intent: &str) {
+        if intent.is_empty() {
+            let missing = page.emphasis("No intent given.");
+            page.paragraph(missing);
+        } else {
+            page.quote(intent);
+        }
+    }
+
+    fn links(&self, page: &Page, module: &ModuleDoc, names: Vec<&str>) -> String {
+        names.into_iter().map(|n| self.reference(page, module, n)).collect::<Vec<_>>().join(", ")
+    }
+
+    /// `name` linked to its definition, if it is documented.
+    fn reference(&self, page: &Page, module: &ModuleDoc, name: &str) -> String {
+        match self.owners.get(name) {
+            Some(owner) if *owner == module.name => page.link(name, &format!("#{}", anchor(name))),
+            Some(owner) => page.link(name, &format!("{}.{}#{}", owner, self.format.extension(), anchor(name))),
+            None => page.text(name),
+        }
+    }
+
+    fn argument(&self, page: &Page, module: &ModuleDoc, argument: &Argument) -> String {
+        let typ = self.typ(page, module, &argument.type_info.onu_type, true);
+        let via = if argument.type_info.is_observation { " via observation" } else { "" };
+        format!("{} called {}{}", typ, page.code(&argument.name), page.text(via))
+    }
+
+    /// A type as it is spelled in `takes:`, with shapes linked.
+    fn typ(&self, page: &Page, module: &ModuleDoc, typ: &OnuType, article: bool) -> String {
+        let spelled = type_name(typ).unwrap_or_else(|_| format!("{:?}", typ).to_lowercase());
+        let article = if article { with_article(&spelled).split_once(' ').map(|(a, _)| format!("{} ", a)) } else { None };
+        let body = match typ {
+            OnuType::Shape(name) => self.reference(page, module, name),
+            OnuType::Tuple(items) => {
+                let items: Vec<String> = items.iter().map(|t| self.typ(page, module, t, true)).collect();
+                format!("{} ({})", page.text("tuple of"), items.join(" "))
+            }
+            _ => page.text(&spelled),
+        };
+        format!("{}{}", article.unwrap_or_default(), body)
+    }
+}
+
+/// The link target of a shape or behavior heading: the slug Markdown
+/// renderers derive from it, and the `id` of the HTML heading.
+fn anchor(name: &str) -> String {
+    name.to_lowercase()
+}
+
+/// One page under construction, in either output format.
+struct Page {
+    format: DocFormat,
+    out: String,
+}
+
+impl Page {
+    fn new(format: DocFormat, title: &str) -> Self {
+        let mut page = Page { format, out: String::new() };
+        if format == DocFormat::Html {
+            let _ = writeln!(
+                page.out,
+                "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>",
+                escape(title)
+            );
+        }
+        page
+    }
+
+    fn finish(mut self) -> String {
+        if self.format == DocFormat::Html {
+            self.out.push_str("</body>\n</html>\n");
+        }
+        self.out
+    }
+
+    fn heading(&mut self, level: usize, title: &str, id: Option<&str>) {
+        let _ = match self.format {
+            DocFormat::Markdown => writeln!(self.out, "{} {}\n", "#".repeat(level), title),
+            DocFormat::Html => match id {
+                Some(id) => writeln!(self.out, "<h{0} id=\"{1}\">{2}</h{0}>", level, escape(id), escape(title)),
+                None => writeln!(self.out, "<h{0}>{1}</h{0}>", level, escape(title)),
+            },
+        };
+    }
+
+    /// A paragraph of already-rendered inline text.
+    fn paragraph(&mut self, inline: String) {
+        let _ = match self.format {
+            DocFormat::Markdown => writeln!(self.out, "{}\n", inline),
+            DocFormat::Html => writeln!(self.out, "<p>{}</p>", inline),
+        };
+    }
+
+    fn quote(&mut self, text: &str) {
+        let _ = match self.format {
+            DocFormat::Markdown => writeln!(self.out, "> {}\n", text),
+            DocFormat::Html => writeln!(self.out, "<blockquote>{}</blockquote>", escape(text)),
+        };
+    }
+
+    /// A bulleted list of already-rendered inline items.
+    fn list(&mut self, items: Vec<String>) {
+        if items.is_empty() {
+            return;
+        }
+        match self.format {
+            DocFormat::Markdown => {
+                for item in items {
+                    let _ = writeln!(self.out, "- {}", item);
+                }
+                self.out.push('\n');
+            }
+            DocFormat::Html => {
+                self.out.push_str("<ul>\n");
+                for item in items {
+                    let _ = writeln!(self.out, "<li>{}</li>", item);
+                }
+                self.out.push_str("</ul>\n");
+            }
+        }
+    }
+
+    fn field(&self, label: &str, value: &str) -> String {
+        match self.format {
+            DocFormat::Markdown => format!("**{}:** {}", label, value),
+            DocFormat::Html => format!("<strong>{}:</strong> {}", escape(label), value),
+        }
+    }
+
+    fn text(&self, text: &str) -> String {
+        match self.format {
+            DocFormat::Markdown => text.to_string(),
+            DocFormat::Html => escape(text),
+        }
+    }
+
+    fn code(&self, text: &str) -> String {
+        match self.format {
+            DocFormat::Markdown => format!("`{}`", text),
+            DocFormat::Html => format!("<code>{}</code>", escape(text)),
+        }
+    }
+
+    fn emphasis(&self, text: &str) -> String {
+        match self.format {
+            DocFormat::Markdown => format!("*{}*", text),
+            DocFormat::Html => format!("<em>{}</em>", escape(text)),
+        }
+    }
+
+    fn link(&self, text: &str, href: &str) -> String {
+        match self.format {
+            DocFormat::Markdown => format!("[{}]({})", text, href),
+            DocFormat::Html => format!("<a href=\"{}\">{}</a>", escape(href), escape(text)),
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "the module called Numbers with concern: counting things, carefully
+
+the shape called Pair
+    with intent: two numbers (kept together)
+    takes:
+        an integer called left
+        an integer called right
+
+the behavior called fib
+    with intent: the n-th Fibonacci number, from 0
+    takes:
+        an integer called n
+    delivers: an integer
+    with diminishing: n
+    as:
+        if n exceeds 1
+        then ((n decreased-by 1) utilizes fib) added-to ((n decreased-by 2) utilizes fib)
+        else n
+
+the effect behavior called run
+    takes: nothing
+    delivers: nothing
+    as:
+        derivation: x derives-from an integer 10 utilizes fib
+        broadcasts (x utilizes as-text)
+";
+
+    fn extract(source: &str) -> Vec<ModuleDoc> {
+        OnuDocGenerator::new(LogLevel::Error).extract(source, "Main").expect("source should parse")
+    }
+
+    #[test]
+    fn test_prose_is_kept_verbatim() {
+        let modules = extract(SOURCE);
+        assert_eq!(modules.len(), 1);
+        assert_eq!(modules[0].concern, "counting things, carefully");
+        assert_eq!(modules[0].shapes[0].intent, "two numbers (kept together)");
+        assert_eq!(modules[0].behaviors[0].header.intent, "the n-th Fibonacci number, from 0");
+        assert_eq!(modules[0].behaviors[1].header.intent, "");
+    }
+
+    #[test]
+    fn test_markdown_page_cross_links_callers_and_callees() {
+        let modules = extract(SOURCE);
+        let page = Reference::new(&modules, DocFormat::Markdown).page(&modules[0]);
+        assert!(page.starts_with("# Numbers\n\n*counting things, carefully*\n"));
+        assert!(page.contains("### fib\n\n*pure behavior, recursive*\n\n> the n-th Fibonacci number, from 0\n"));
+        assert!(page.contains("- **Takes:** an integer called `n`\n"));
+        assert!(page.contains("- **Termination:** `n` diminishes on every recursive call\n"));
+        assert!(page.contains("- **Memoization:** keyed on `n`\n"));
+        assert!(page.contains("- **Called by:** [run](#run)\n"));
+        assert!(page.contains("*effect behavior*\n\n*No intent given.*\n"));
+        assert!(page.contains("- **Calls:** [fib](#fib)\n"));
+        assert!(!page.contains("as-text"));
+    }
+
+    #[test]
+    fn test_links_between_modules_name_the_other_page() {
+        let modules = extract(
+            "the module called A with concern: first
+the behavior called one
+    with intent: one
+    takes: nothing
+    delivers: an integer
+    as:
+        1
+the module called B with concern: second
+the shape called Wrapper
+    with intent: wraps
+    takes:
+        a Wrapper called inner
+the behavior called two
+    with intent: two
+    takes:
+        a Wrapper called w
+    delivers: an integer
+    as:
+        nothing utilizes one
+",
+        );
+        assert_eq!(modules.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), ["A", "B"]);
+        let reference = Reference::new(&modules, DocFormat::Html);
+        let page = reference.page(&modules[1]);
+        assert!(page.contains("<strong>Calls:</strong> <a href=\"A.html#one\">one</a>"));
+        assert!(page.contains("a <a href=\"#wrapper\">Wrapper</a> called <code>w</code>"));
+        assert!(page.contains("<h3 id=\"wrapper\">Wrapper</h3>"));
+        assert!(reference.page(&modules[0]).contains("<a href=\"B.html#two\">two</a>"));
+        assert!(reference.index().contains("<li><a href=\"A.html\">A</a>: first</li>"));
+    }
+
+    #[test]
+    fn test_html_escapes_prose() {
+        let modules = extract("the behavior called lt\n    with intent: is a < b & \"c\"?\n    takes: nothing\n    delivers: nothing\n    as:\n        nothing\n");
+        assert_eq!(modules[0].name, "Main");
+        let page = Reference::new(&modules, DocFormat::Html).page(&modules[0]);
+        assert!(page.contains("<blockquote>is a &lt; b &amp; &quot;c&quot;?</blockquote>"), "{}", page);
+        assert!(page.ends_with("</body>\n</html>\n"));
+    }
+}
@@ -0,0 +1,736 @@
+//! Ọ̀nụ Formatter Adapter: Canonical Source Layout
+//!
+//! `onu fmt` reprints a program from its AST in one canonical layout:
+//! spaced keywords (`the module called`), one header clause per line under
+//! the header, bodies laid out by indentation rather than braces, and
+//! derivation chains written one statement per line.
+//!
+//! The AST does not hold everything a reader cares about, so three things
+//! are recovered from the source instead:
+//! - `with intent:` / `with concern:` prose is copied verbatim;
+//! - `--` comments, and blank lines inside bodies, are carried across by
+//!   aligning the original token stream with the reprinted one;
+//! - operators the parser folds together (`opposes`, `unites-with`, …) and
+//!   float literals are taken from the original tokens in order.
+//!
+//! Every result is re-parsed and compared with the original AST; a layout
+//! that would change what the program means is reported as an error rather
+//! than returned.
+
+use std::collections::VecDeque;
+
+use crate::adapters::lexer::{LexedSource, OnuLexer};
+use crate::adapters::parser::OnuParser;
+use crate::adapters::parser::helpers::error_recovery::is_discourse_starter;
+use crate::application::options::LogLevel;
+use crate::application::ports::compiler_ports::{Literal, Token};
+use crate::application::use_cases::registry_service::RegistryService;
+use crate::domain::entities::ast::{Argument, BehaviorHeader, Discourse, Expression, TypeInfo};
+use crate::domain::entities::error::OnuError;
+use crate::domain::entities::types::{InlineHint, OnuType};
+
+const INDENT: &str = "    ";
+
+/// Precedence of an expression that never needs parentheses.
+const ATOM: u8 = 6;
+/// Precedence of `if`, `derivation` and `broadcasts`, which read to the end
+/// of their scope and so need parentheses anywhere else.
+const OPEN: u8 = 0;
+
+pub struct OnuFormatter {
+    pub log_level: LogLevel,
+}
+
+impl OnuFormatter {
+    pub fn new(log_level: LogLevel) -> Self {
+        Self { log_level }
+    }
+
+    /// The canonical layout of `source`, which must parse.
+    pub fn format(&self, source: &str) -> Result<String, OnuError> {
+        let lexed = OnuLexer::new(self.log_level).lex_with_layout(source)?;
+        let tokens: Vec<Token> = lexed.tokens.iter().map(|(t, _)| t.clone()).collect();
+        let (discourses, registry) = self.parse(tokens.clone())?;
+
+        let starts: Vec<usize> = (0..tokens.len()).filter(|&i| is_discourse_starter(&tokens[i])).collect();
+        if starts.len() != discourses.len() {
+            return Err(format_error("the source has text the parser skips between definitions"));
+        }
+        let source_lines: Vec<&str> = source.lines().collect();
+
+        let mut printer = Printer::new(&registry, &tokens);
+        for (index, discourse) in discourses.iter().enumerate() {
+            let end = starts.get(index + 1).copied().unwrap_or(tokens.len());
+            let prose = HeaderProse::find(&lexed, starts[index]..end, &source_lines);
+            if index > 0 {
+                printer.blank();
+            }
+            printer.discourse(discourse, &prose)?;
+        }
+
+        let formatted = carry_layout(&lexed, source, &printer.lines, self.log_level)?;
+
+        let (reparsed, _) = self.parse(OnuLexer::new(self.log_level).lex_with_layout(&formatted)?.tokens.into_iter().map(|(t, _)| t).collect())?;
+        if without_lines(reparsed) != without_lines(discourses) {
+            return Err(format_error("the canonical layout would change the program's meaning"));
+        }
+        Ok(formatted)
+    }
+
+    fn parse(&self, tokens: Vec<Token>) -> Result<(Vec<Discourse>, RegistryService), OnuError> {
+        let parser = OnuParser::new(self.log_level);
+        let mut registry = RegistryService::new();
+        parser.scan_headers(&tokens, &mut registry)?;
+        let discourses = parser.parse_with_registry(tokens, &mut registry)?;
+        Ok((discourses, registry))
+    }
+}
+
+fn format_error(message: &str) -> OnuError {
+    OnuError::GrammarViolation { message: format!("fmt: {}", message), span: Default::default() }
+}
+
+/// Test declarations record their line, which formatting is free to move.
+fn without_lines(discourses: Vec<Discourse>) -> Vec<Discourse> {
+    discourses
+        .into_iter()
+        .map(|d| match d {
+            Discourse::Test { name, expects, body, .. } => Discourse::Test { name, expects, body, line: 0 },
+            other => other,
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Header prose
+// ---------------------------------------------------------------------------
+
+/// The verbatim `with intent:` and `with concern:` text of one discourse.
+#[derive(Default)]
+pub(crate) struct HeaderProse {
+    pub(crate) intent: Option<String>,
+    pub(crate) concern: Option<String>,
+}
+
+impl HeaderProse {
+    pub(crate) fn find(lexed: &LexedSource, range: std::ops::Range<usize>, lines: &[&str]) -> Self {
+        let mut prose = HeaderProse::default();
+        for (token, line) in &lexed.tokens[range] {
+            let text = lines.get(line - 1).copied().unwrap_or_default();
+            match token {
+                Token::WithIntent if prose.intent.is_none() => prose.intent = prose_after(text, "intent"),
+                Token::WithConcern if prose.concern.is_none() => prose.concern = prose_after(text, "concern"),
+                Token::As => break,
+                _ => {}
+            }
+        }
+        prose
+    }
+}
+
+/// The text following `with <keyword>:` on `line`, up to a comment or the
+/// next header clause, as the parser reads it.
+fn prose_after(line: &str, keyword: &str) -> Option<String> {
+    let at = line.match_indices(keyword).map(|(i, _)| i).find(|&i| {
+        let before = line[..i].trim_end_matches([' ', '\t', '-']);
+        before.ends_with("with") && line[i + keyword.len()..].trim_start().starts_with(':')
+    })?;
+    let rest = line[at + keyword.len()..].trim_start();
+    let rest = rest.strip_prefix(':')?.trim();
+
+    if let Some(quoted) = rest.strip_prefix('"') {
+        let close = quoted.find('"')?;
+        return Some(format!("\"{}\"", &quoted[..close]));
+    }
+    const STOPS: &[&str] = &[
+        "takes", "delivers", "as", "with diminishing", "with-diminishing", "with inlining", "with-inlining",
+        "with known behavior", "with-known-behavior", "no guaranteed termination", "no-guaranteed-termination",
+    ];
+    let mut end = rest.len();
+    for (i, _) in rest.char_indices() {
+        let at_word = i == 0 || rest[..i].ends_with(char::is_whitespace);
+        if !at_word {
+            continue;
+        }
+        let tail = &rest[i..];
+        let stops_clause = STOPS.iter().any(|stop| {
+            tail.strip_prefix(stop).is_some_and(|after| after.trim_start().starts_with(':'))
+        });
+        if tail.starts_with("--") || stops_clause {
+            end = i;
+            break;
+        }
+    }
+    let prose = rest[..end].trim_end();
+    (!prose.is_empty()).then(|| prose.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Printing
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineKind {
+    Blank,
+    /// A `the … called` line.
+    Header,
+    /// A header clause (`takes:`, `delivers:`, `as:` …) or argument.
+    Clause,
+    /// A line of a body.
+    Body,
+}
+
+/// A printed expression, with what it takes to use it as an operand.
+struct Printed {
+    text: String,
+    precedence: u8,
+    /// Whether it ends in an expression that would read on into whatever
+    /// follows it, as a `utilizes` call's last argument does.
+    open: bool,
+}
+
+impl Printed {
+    fn atom(text: String) -> Self {
+        Printed { text, precedence: ATOM, open: false }
+    }
+
+    fn wrapped(self, needs_parens: bool) -> String {
+        if needs_parens { format!("({})", self.text) } else { self.text }
+    }
+}
+
+struct Printer<'a> {
+    registry: &'a RegistryService,
+    lines: Vec<(String, LineKind)>,
+    /// The spelling of each operator the parser folds into a placeholder.
+    folded_operators: VecDeque<&'static str>,
+    /// Float literals, whose AST form keeps only part of their bits.
+    floats: VecDeque<u64>,
+}
+
+impl<'a> Printer<'a> {
+    fn new(registry: &'a RegistryService, tokens: &[Token]) -> Self {
+        let mut folded_operators = VecDeque::new();
+        let mut floats = VecDeque::new();
+        for (i, token) in tokens.iter().enumerate() {
+            let called = i > 0 && matches!(tokens[i - 1], Token::Utilizes);
+            match token {
+                Token::Opposes if !called => folded_operators.push_back("opposes"),
+                Token::UnitesWith if !called => folded_operators.push_back("unites-with"),
+                Token::JoinsWith if !called => folded_operators.push_back("joins-with"),
+                Token::Literal(Literal::FloatBits(bits)) => floats.push_back(*bits),
+                _ => {}
+            }
+        }
+        Printer { registry, lines: Vec::new(), folded_operators, floats }
+    }
+
+    fn line(&mut self, depth: usize, text: String, kind: LineKind) {
+        self.lines.push((format!("{}{}", INDENT.repeat(depth), text), kind));
+    }
+
+    fn blank(&mut self) {
+        self.lines.push((String::new(), LineKind::Blank));
+    }
+
+    fn discourse(&mut self, discourse: &Discourse, prose: &HeaderProse) -> Result<(), OnuError> {
+        match discourse {
+            Discourse::Module { name, concern } => {
+                self.line(0, format!("the module called {}", name), LineKind::Header);
+                let concern = prose.concern.clone().or_else(|| (!concern.is_empty()).then(|| concern.clone()));
+                if let Some(concern) = concern {
+                    self.line(1, format!("with concern: {}", concern), LineKind::Clause);
+                }
+            }
+            Discourse::Shape { name, fields, .. } => {
+                self.line(0, format!("the shape called {}", name), LineKind::Header);
+                self.intent(prose, None);
+                self.takes(fields)?;
+            }
+            Discourse::Behavior { header, body } => {
+                self.header(header, prose)?;
+                self.line(1, "as:".to_string(), LineKind::Clause);
+                self.statements(body, 2)?;
+            }
+            Discourse::Test { name, expects, body, .. } => {
+                self.line(0, format!("the test called {}", name), LineKind::Header);
+                self.intent(prose, None);
+                let expects = self.expression(expects)?.text;
+                self.line(1, format!("expects: {}", expects), LineKind::Clause);
+                self.line(1, "as:".to_string(), LineKind::Clause);
+                self.statements(body, 2)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn intent(&mut self, prose: &HeaderProse, parsed: Option<&str>) {
+        let intent = prose.intent.clone().or_else(|| parsed.filter(|i| !i.is_empty()).map(str::to_string));
+        if let Some(intent) = intent {
+            self.line(1, format!("with intent: {}", intent), LineKind::Clause);
+        }
+    }
+
+    /// Clauses in the one order the parser reads them all back from:
+    /// `takes:` runs to `delivers:`, and `with diminishing:` to `as:`.
+    fn header(&mut self, header: &BehaviorHeader, prose: &HeaderProse) -> Result<(), OnuError> {
+        let keyword = if header.is_effect { "the effect behavior called" } else { "the behavior called" };
+        self.line(0, format!("{} {}", keyword, header.name), LineKind::Header);
+        self.intent(prose, Some(&header.intent));
+        self.takes(&header.takes)?;
+        let delivers = match &header.delivers.0 {
+            OnuType::Nothing => "nothing".to_string(),
+            other => with_article(&type_name(other)?),
+        };
+        self.line(1, format!("delivers: {}", delivers), LineKind::Clause);
+        if header.skip_termination_check {
+            self.line(1, "with no guaranteed termination".to_string(), LineKind::Clause);
+        }
+        match header.inlining {
+            InlineHint::Auto => {}
+            InlineHint::Always => self.line(1, "with inlining: always".to_string(), LineKind::Clause),
+            InlineHint::Never => self.line(1, "with inlining: never".to_string(), LineKind::Clause),
+        }
+        if let Some(known) = header.known_behavior {
+            self.line(1, format!("with known behavior: {}", known.name()), LineKind::Clause);
+        }
+        if !header.diminishing.is_empty() {
+            self.line(1, format!("with diminishing: {}", header.diminishing.join(", ")), LineKind::Clause);
+        }
+        Ok(())
+    }
+
+    fn takes(&mut self, arguments: &[Argument]) -> Result<(), OnuError> {
+        if arguments.is_empty() {
+            self.line(1, "takes: nothing".to_string(), LineKind::Clause);
+            return Ok(());
+        }
+        self.line(1, "takes:".to_string(), LineKind::Clause);
+        for argument in arguments {
+            let via = if argument.type_info.is_observation { " via observation" } else { "" };
+            let typ = with_article(&type_name(&argument.type_info.onu_type)?);
+            self.line(2, format!("{} called {}{}", typ, argument.name, via), LineKind::Clause);
+        }
+        Ok(())
+    }
+
+    /// A body: one statement per line, derivation chains flattened.
+    fn statements(&mut self, expr: &Expression, depth: usize) -> Result<(), OnuError> {
+        match expr {
+            Expression::Block(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i + 1 < items.len() && ends_in_derivation(item) {
+                        // Only parentheses stop a derivation taking the rest of the block.
+                        let text = self.expression(item)?.wrapped(true);
+                        self.line(depth, text, LineKind::Body);
+                    } else {
+                        self.statement(item, depth, "")?;
+                    }
+                }
+                Ok(())
+            }
+            other => self.statement(other, depth, ""),
+        }
+    }
+
+    fn statement(&mut self, expr: &Expression, depth: usize, prefix: &str) -> Result<(), OnuError> {
+        let continued = if prefix.is_empty() { depth } else { depth + 1 };
+        match expr {
+            Expression::Derivation { name, type_info, value, body } => {
+                let head = self.derivation_head(name, type_info, value)?;
+                self.line(depth, format!("{}{}", prefix, head), LineKind::Body);
+                if **body != Expression::Nothing {
+                    self.statements(body, continued)?;
+                }
+                Ok(())
+            }
+            Expression::If { condition, then_branch, else_branch } => {
+                let inline = self.expression(expr)?.text;
+                if !contains_derivation(expr) && prefix.len() + inline.len() <= 80 {
+                    self.line(depth, format!("{}{}", prefix, inline), LineKind::Body);
+                    return Ok(());
+                }
+                let condition = self.expression(condition)?.text;
+                self.line(depth, format!("{}if {}", prefix, condition), LineKind::Body);
+                // An `else if` chain stays at one depth rather than stepping in.
+                let branches = if prefix == "else " { depth } else { depth + 1 };
+                self.statement(then_branch, branches, "then ")?;
+                self.statement(else_branch, branches, "else ")
+            }
+            other => {
+                let text = self.expression(other)?.text;
+                self.line(depth, format!("{}{}", prefix, text), LineKind::Body);
+                Ok(())
+            }
+        }
+    }
+
+    fn derivation_head(&mut self, name: &str, type_info: &Option<TypeInfo>, value: &Expression) -> Result<String, OnuError> {
+        let typ = match type_info {
+            Some(info) => format!("{} ", with_article(&type_name(&info.onu_type)?)),
+            None => String::new(),
+        };
+        let value_ends_scope = ends_in_derivation(value);
+        let printed = self.expression(value)?;
+        // Without a type, `a x …` would be read as the type `x`.
+        let reads_as_type = type_info.is_none()
+            && ["a ", "an ", "the "].iter().any(|article| printed.text.starts_with(article));
+        Ok(format!("derivation: {} derives-from {}{}", name, typ, printed.wrapped(value_ends_scope || reads_as_type)))
+    }
+
+    /// `expr` on one line, parenthesized only where the parser needs it.
+    fn expression(&mut self, expr: &Expression) -> Result<Printed, OnuError> {
+        Ok(match expr {
+            Expression::I64(n) if *n >= 0 => Printed::atom(n.to_string()),
+            Expression::F32(bits) => {
+                let Some(original) = self.floats.pop_front().filter(|b| *b as u32 == *bits) else {
+                    return Err(format_error("a float literal could not be matched to the source"));
+                };
+                Printed::atom(format!("{:?}", f64::from_bits(original)))
+            }
+            Expression::Boolean(b) => Printed::atom(b.to_string()),
+            Expression::Text(s) if !s.contains('"') => Printed::atom(format!("\"{}\"", s)),
+            Expression::Identifier(name) => Printed::atom(name.clone()),
+            Expression::Nothing => Printed::atom("nothing".to_string()),
+            Expression::BinaryOp { left, right, .. } => {
+                let op = self.folded_operators.pop_front().unwrap_or("opposes");
+                self.infix(op, 4, left, right)?
+            }
+            Expression::BehaviorCall { name, args } => self.call(name, args)?,
+            Expression::Emit(inner) => {
+                let inner = self.expression(inner)?;
+                Printed { text: format!("broadcasts {}", inner.text), precedence: OPEN, open: true }
+            }
+            Expression::If { condition, then_branch, else_branch } => {
+                let condition = self.expression(condition)?.text;
+                let then_branch = self.expression(then_branch)?.text;
+                let else_branch = self.expression(else_branch)?.text;
+                Printed {
+                    text: format!("if {} then {} else {}", condition, then_branch, else_branch),
+                    precedence: OPEN,
+                    open: true,
+                }
+            }
+            Expression::Derivation { name, type_info, value, body } => {
+                let mut text = self.derivation_head(name, type_info, value)?;
+                let body: &[Expression] = match body.as_ref() {
+                    Expression::Nothing => &[],
+                    Expression::Block(items) => items,
+                    single => std::slice::from_ref(single),
+                };
+                for (i, item) in body.iter().enumerate() {
+                    let needs_parens = i + 1 < body.len() && ends_in_derivation(item);
+                    text.push(' ');
+                    text.push_str(&self.expression(item)?.wrapped(needs_parens));
+                }
+                Printed { text, precedence: OPEN, open: true }
+            }
+            other => {
+                return Err(format_error(&format!("{} cannot be written back as source", variant_name(other))));
+            }
+        })
+    }
+
+    fn call(&mut self, name: &str, args: &[Expression]) -> Result<Printed, OnuError> {
+        if let (Some(precedence), [left, right]) = (infix_precedence(name), args) {
+            return self.infix(name, precedence, left, right);
+        }
+        if let ("duplicated-as" | "init-of" | "tail-of", [subject]) = (name, args) {
+            let subject = self.expression(subject)?;
+            let needs_parens = subject.precedence < 4 || subject.open;
+            return Ok(Printed { text: format!("{} {}", subject.wrapped(needs_parens), name), precedence: 4, open: false });
+        }
+
+        let signature = self.registry.get_signature(name);
+        let Some((subject, rest)) = args.split_first() else {
+            if signature.is_some_and(|s| s.input_types.is_empty()) {
+                return Ok(Printed::atom(name.to_string()));
+            }
+            return Err(format_error(&format!("'{}' is called with no arguments but takes some", name)));
+        };
+        let subject = self.expression(subject)?;
+        let needs_parens = subject.precedence < 5 || subject.open;
+        let mut text = format!("{} utilizes {}", subject.wrapped(needs_parens), name);
+        for arg in rest {
+            let arg = self.expression(arg)?;
+            let needs_parens = arg.precedence < ATOM;
+            text.push(' ');
+            text.push_str(&arg.wrapped(needs_parens));
+        }
+        if signature.is_none() && rest.is_empty() {
+            // The parser would take whatever follows as an argument.
+            return Ok(Printed::atom(format!("({})", text)));
+        }
+        Ok(Printed { text, precedence: 5, open: !rest.is_empty() })
+    }
+
+    fn infix(&mut self, op: &str, precedence: u8, left: &Expression, right: &Expression) -> Result<Printed, OnuError> {
+        let left = self.expression(left)?;
+        let right = self.expression(right)?;
+        let left_parens = left.precedence < precedence || left.open;
+        let right_parens = right.precedence <= precedence;
+        let open = right.open && !right_parens;
+        Ok(Printed {
+            text: format!("{} {} {}", left.wrapped(left_parens), op, right.wrapped(right_parens)),
+            precedence,
+            open,
+        })
+    }
+}
+
+/// The infix operators the parser turns into two-argument calls.
+fn infix_precedence(name: &str) -> Option<u8> {
+    match name {
+        "matches" | "exceeds" | "falls-short-of" => Some(2),
+        "added-to" | "decreased-by" | "wrapping-added-to" | "saturating-added-to" => Some(3),
+        "scales-by" | "partitions-by" | "joined-with" | "char-at" => Some(4),
+        "bit-and-with" | "bit-or-with" | "bit-xor-with" | "shifted-right-by" | "shifted-left-by" => Some(4),
+        _ => None,
+    }
+}
+
+/// Whether `expr` ends in a derivation, which would read on to the end of
+/// the enclosing scope.
+fn ends_in_derivation(expr: &Expression) -> bool {
+    match expr {
+        Expression::Derivation { .. } => true,
+        Expression::If { else_branch, .. } => ends_in_derivation(else_branch),
+        Expression::Emit(inner) => ends_in_derivation(inner),
+        _ => false,
+    }
+}
+
+fn contains_derivation(expr: &Expression) -> bool {
+    match expr {
+        Expression::Derivation { .. } => true,
+        Expression::If { condition, then_branch, else_branch } => {
+            contains_derivation(condition) || contains_derivation(then_branch) || contains_derivation(else_branch)
+        }
+        Expression::Emit(inner) => contains_derivation(inner),
+        Expression::BehaviorCall { args, .. } => args.iter().any(contains_derivation),
+        Expression::BinaryOp { left, right, .. } => contains_derivation(left) || contains_derivation(right),
+        _ => false,
+    }
+}
+
+pub(crate) fn type_name(typ: &OnuType) -> Result<String, OnuError> {
+    Ok(match typ {
+        OnuType::I64 => "integer".to_string(),
+        OnuType::BigInt => "big-integer".to_string(),
+        OnuType::F64 => "float".to_string(),
+        OnuType::Boolean => "boolean".to_string(),
+        OnuType::Strings => "string".to_string(),
+        OnuType::Nothing => "nothing".to_string(),
+        OnuType::Shape(name) => name.clone(),
+        OnuType::Tuple(elements) => {
+            let elements: Result<Vec<String>, OnuError> =
+                elements.iter().map(|e| type_name(e).map(|n| with_article(&n))).collect();
+            format!("tuple of ({})", elements?.join(" "))
+        }
+        other => return Err(format_error(&format!("the type {:?} has no source spelling", other))),
+    })
+}
+
+pub(crate) fn with_article(type_name: &str) -> String {
+    let article = if type_name.starts_with(['a', 'e', 'i', 'o', 'u', 'A', 'E', 'I', 'O', 'U']) { "an" } else { "a" };
+    format!("{} {}", article, type_name)
+}
+
+fn variant_name(expr: &Expression) -> String {
+    let debug = format!("{:?}", expr);
+    debug.split(['(', ' ', '{']).next().unwrap_or_default().to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Comments and blank lines
+// ---------------------------------------------------------------------------
+
+/// What goes on the lines above a printed line.
+enum Leading {
+    Blank,
+    Comment(String),
+}
+
+/// Carry the comments and body blank lines of `source` over to `printed`,
+/// by matching up the tokens of the two.
+fn carry_layout(
+    original: &LexedSource,
+    source: &str,
+    printed: &[(String, LineKind)],
+    log_level: LogLevel,
+) -> Result<String, OnuError> {
+    let text: Vec<&str> = printed.iter().map(|(line, _)| line.as_str()).collect();
+    let mut kinds = Vec::new();
+    for (line, kind) in printed {
+        kinds.extend(std::iter::repeat_n(*kind, line.split('\n').count()));
+    }
+    let joined = text.join("\n");
+    let mut lines: Vec<String> = joined.split('\n').map(str::to_string).collect();
+
+    let reprinted = OnuLexer::new(log_level).lex_with_layout(&joined)?;
+    let from: Vec<&(Token, usize)> = original.tokens.iter().filter(|(t, _)| *t != Token::NewLine).collect();
+    let to: Vec<&(Token, usize)> = reprinted.tokens.iter().filter(|(t, _)| *t != Token::NewLine).collect();
+    let matched = align(&from, &to);
+
+    // The printed line (0-based) of the first matched token at or after
+    // `start`, or at or before it when searching backwards.
+    let forward = |start: usize| (start..from.len()).find_map(|i| matched[i]).map(|j| to[j].1 - 1);
+    let backward = |end: usize| (0..end).rev().find_map(|i| matched[i]).map(|j| to[j].1 - 1);
+
+    let mut leading: Vec<Vec<Leading>> = (0..lines.len()).map(|_| Vec::new()).collect();
+    let mut trailing: Vec<Vec<String>> = (0..lines.len()).map(|_| Vec::new()).collect();
+    let mut after_last = Vec::new();
+
+    let mut events: Vec<(usize, Option<String>)> = original.comments.iter().map(|c| (c.line, Some(c.text.clone()))).collect();
+    let commented: Vec<usize> = original.comments.iter().map(|c| c.line).collect();
+    for (index, line) in source.lines().enumerate() {
+        if line.chars().all(|c| c.is_whitespace() || c == '{' || c == '}') && !commented.contains(&(index + 1)) {
+            events.push((index + 1, None));
+        }
+    }
+    events.sort_by_key(|(line, _)| *line);
+
+    for (line, comment) in events {
+        let first_after = from.partition_point(|(_, l)| *l <= line);
+        let on_line = from.iter().rposition(|(_, l)| *l == line);
+        match (comment, on_line) {
+            (Some(text), Some(last)) => {
+                let target = backward(last + 1).or_else(|| forward(last + 1)).unwrap_or(0);
+                trailing[target].push(text);
+            }
+            (Some(text), None) => match forward(first_after) {
+                Some(target) => leading[target].push(Leading::Comment(text)),
+                None => after_last.push(text),
+            },
+            (None, _) => {
+                if let Some(target) = forward(first_after) {
+                    leading[target].push(Leading::Blank);
+                }
+            }
+        }
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    for (index, line) in lines.iter_mut().enumerate() {
+        let indent: String = line.chars().take_while(|c| *c == ' ').collect();
+        for item in &leading[index] {
+            match item {
+                Leading::Blank => {
+                    let allowed = matches!(kinds[index], LineKind::Header | LineKind::Body)
+                        && out.last().is_some_and(|l| !l.is_empty() && l.trim() != "as:");
+                    if allowed {
+                        out.push(String::new());
+                    }
+                }
+                Leading::Comment(text) => out.push(format!("{}--{}", indent, text.trim_end())),
+            }
+        }
+        if kinds[index] == LineKind::Blank && out.last().is_some_and(String::is_empty) {
+            continue;
+        }
+        for text in &trailing[index] {
+            line.push_str(&format!(" --{}", text.trim_end()));
+        }
+        out.push(line.clone());
+    }
+    for text in after_last {
+        out.push(format!("--{}", text.trim_end()));
+    }
+    while out.first().is_some_and(String::is_empty) {
+        out.remove(0);
+    }
+    Ok(format!("{}\n", out.join("\n").trim_end()))
+}
+
+/// For each token of `from`, the index of the same token in `to`.  The two
+/// streams differ only locally (parentheses, optional `:`), so a greedy walk
+/// that resynchronizes on the nearest pair of agreeing tokens suffices.
+fn align(from: &[&(Token, usize)], to: &[&(Token, usize)]) -> Vec<Option<usize>> {
+    const WINDOW: usize = 12;
+    let same = |i: usize, j: usize| match (from.get(i), to.get(j)) {
+        (Some(a), Some(b)) => a.0 == b.0,
+        (None, None) => true,
+        _ => false,
+    };
+    let mut matched = vec![None; from.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < from.len() && j < to.len() {
+        if same(i, j) {
+            matched[i] = Some(j);
+            i += 1;
+            j += 1;
+            continue;
+        }
+        let resync = (1..2 * WINDOW)
+            .flat_map(|distance| (0..=distance).map(move |skip| (skip, distance - skip)))
+            .find(|&(a, b)| a < WINDOW && b < WINDOW && same(i + a, j + b) && same(i + a + 1, j + b + 1));
+        match resync {
+            Some((a, b)) => {
+                i += a;
+                j += b;
+            }
+            None => {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str) -> String {
+        OnuFormatter::new(LogLevel::Error).format(source).expect("formats")
+    }
+
+    #[test]
+    fn test_prose_is_copied_up_to_the_next_clause_or_comment() {
+        assert_eq!(prose_after("    with intent: add two (1=yes) -- note", "intent").as_deref(), Some("add two (1=yes)"));
+        assert_eq!(prose_after("with-intent: \"quoted\" takes: nothing", "intent").as_deref(), Some("\"quoted\""));
+        assert_eq!(prose_after("the behavior called f with intent: f as: 1", "intent").as_deref(), Some("f"));
+        assert_eq!(prose_after("with concern: language", "concern").as_deref(), Some("language"));
+    }
+
+    #[test]
+    fn test_braces_and_hyphenated_keywords_become_canonical() {
+        let source = "the-module-called M with-concern: tests\n\
+                      the-behavior-called run with-intent: go takes: nothing delivers: an integer as: {\n\
+                      derivation: x derives-from 1 x added-to 2 }\n";
+        assert_eq!(
+            format(source),
+            "the module called M\n    with concern: tests\n\n\
+             the behavior called run\n    with intent: go\n    takes: nothing\n    delivers: an integer\n    as:\n\
+             \x20       derivation: x derives-from 1\n        x added-to 2\n"
+        );
+    }
+
+    #[test]
+    fn test_comments_and_body_blank_lines_are_kept() {
+        let source = "-- leading\nthe behavior called run\n    with intent: go\n    takes: nothing\n    delivers: an integer\n    as:\n\
+                      \x20       derivation: x derives-from 1 -- one\n\n        -- then two\n        x added-to 2\n-- the end\n";
+        assert_eq!(
+            format(source),
+            "-- leading\nthe behavior called run\n    with intent: go\n    takes: nothing\n    delivers: an integer\n    as:\n\
+             \x20       derivation: x derives-from 1 -- one\n\n        -- then two\n        x added-to 2\n-- the end\n"
+        );
+    }
+
+    #[test]
+    fn test_parentheses_follow_precedence() {
+        let source = "the behavior called run with intent: go takes: nothing delivers: an integer as:\n\
+                      ((1 added-to 2) scales-by (3 decreased-by (4 decreased-by 5))) added-to (6 scales-by 7)\n";
+        assert!(format(source).contains("        (1 added-to 2) scales-by (3 decreased-by (4 decreased-by 5)) added-to 6 scales-by 7\n"));
+    }
+
+    #[test]
+    fn test_formatting_is_idempotent() {
+        let source = "the behavior called run with intent: go takes: nothing delivers: an integer as: {\n\
+                      if 1 matches 1 then derivation: y derives-from 2 y else 3 }\n";
+        let once = format(source);
+        assert!(once.contains("        if 1 matches 1\n            then derivation: y derives-from 2\n                y\n            else 3\n"), "{}", once);
+        assert_eq!(format(&once), once);
+    }
+}
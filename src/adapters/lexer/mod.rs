@@ -29,6 +29,31 @@ impl OnuLexer {
 
 impl LexerPort for OnuLexer {
     fn lex(&self, source: &str) -> Result<Vec<Token>, OnuError> {
+        let layout = self.lex_with_layout(source)?;
+        Ok(layout.tokens.into_iter().map(|(token, _)| token).collect())
+    }
+}
+
+/// A `--` comment, which `lex` discards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// The 1-based line the comment is on.
+    pub line: usize,
+    /// The text after `--`, without the trailing newline.
+    pub text: String,
+}
+
+/// Tokens with the 1-based line each starts on, and the comments between
+/// them: what tools that print source back out need from the lexer.
+#[derive(Debug, Clone, Default)]
+pub struct LexedSource {
+    pub tokens: Vec<(Token, usize)>,
+    pub comments: Vec<Comment>,
+}
+
+impl OnuLexer {
+    /// Lex `source`, keeping where every token and comment came from.
+    pub fn lex_with_layout(&self, source: &str) -> Result<LexedSource, OnuError> {
         self.log(LogLevel::Info, "Starting lexing process");
         let mut lexer = LexerInternal::new(source, self.log_level);
         let mut tokens = Vec::new();
@@ -36,17 +61,21 @@ impl LexerPort for OnuLexer {
         while let Some(token_result) = lexer.next_token() {
             let token = token_result?;
             self.log(LogLevel::Trace, &format!("Lexed token: {:?}", token));
-            tokens.push(token);
+            tokens.push((token, lexer.token_line));
         }
 
         self.log(LogLevel::Info, &format!("Lexing successful: {} tokens", tokens.len()));
-        Ok(tokens)
+        Ok(LexedSource { tokens, comments: lexer.comments })
     }
 }
 
 struct LexerInternal<'a> {
     input: Peekable<Chars<'a>>,
     log_level: LogLevel,
+    line: usize,
+    /// The line the most recent token started on.
+    token_line: usize,
+    comments: Vec<Comment>,
 }
 
 impl<'a> LexerInternal<'a> {
@@ -54,6 +83,9 @@ impl<'a> LexerInternal<'a> {
         Self {
             input: input.chars().peekable(),
             log_level,
+            line: 1,
+            token_line: 1,
+            comments: Vec::new(),
         }
     }
 
@@ -95,19 +127,24 @@ impl<'a> LexerInternal<'a> {
         self.log(LogLevel::Trace, "Skipping comment");
         self.input.next(); 
         self.input.next(); // skip both '-'
+        let mut text = String::new();
         while let Some(c) = self.input.next() {
             if c == '\n' { 
                 break; 
             }
+            text.push(c);
         }
+        self.comments.push(Comment { line: self.line, text });
+        self.line += 1;
     }
 
     fn next_token(&mut self) -> Option<Result<Token, OnuError>> {
         self.skip_whitespace_and_comments();
         let first_char = self.peek_char()?;
+        self.token_line = self.line;
 
         let token = match first_char {
-            '\n' => { self.input.next(); Token::NewLine }
+            '\n' => { self.input.next(); self.line += 1; Token::NewLine }
             '(' => { self.input.next(); Token::Delimiter('(') }
             ')' => { self.input.next(); Token::Delimiter(')') }
             '[' => { self.input.next(); Token::Delimiter('[') }
@@ -132,6 +169,7 @@ impl<'a> LexerInternal<'a> {
         let mut s = String::new();
         while let Some(c) = self.input.next() {
             if c == '"' { break; }
+            if c == '\n' { self.line += 1; }
             s.push(c);
         }
         Token::Literal(Literal::String(s))
@@ -163,9 +201,12 @@ impl<'a> LexerInternal<'a> {
             ("the-shape-called", Token::TheShapeCalled),
             ("the-behavior-called", Token::TheBehaviorCalled),
             ("the-effect-behavior-called", Token::TheEffectBehaviorCalled),
+            ("the-test-called", Token::TheTestCalled),
             ("with-intent", Token::WithIntent),
             ("with-concern", Token::WithConcern),
             ("with-diminishing", Token::WithDiminishing),
+            ("with-inlining", Token::WithInlining),
+            ("with-known-behavior", Token::WithKnownBehavior),
             ("no-guaranteed-termination", Token::NoGuaranteedTermination),
             ("derives-from", Token::DerivesFrom),
             ("decreased-by", Token::DecreasedBy),
@@ -229,6 +270,7 @@ impl<'a> LexerInternal<'a> {
             "else" => Token::Else,
             "takes" => Token::Takes,
             "delivers" => Token::Delivers,
+            "expects" => Token::Expects,
             "called" => Token::Called,
             "as" => Token::As,
             "nothing" => Token::Nothing,
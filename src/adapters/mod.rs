@@ -1 +1 @@
-pub mod lexer; pub mod parser; pub mod codegen;
+pub mod lexer; pub mod parser; pub mod codegen; pub mod formatter; pub mod docgen;
@@ -20,6 +20,7 @@ const DISCOURSE_STARTERS: &[fn(&Token) -> bool] = &[
     |t| matches!(t, Token::TheShapeCalled),
     |t| matches!(t, Token::TheBehaviorCalled),
     |t| matches!(t, Token::TheEffectBehaviorCalled),
+    |t| matches!(t, Token::TheTestCalled),
 ];
 
 /// Advance `pos` past tokens until we reach a discourse-starter or EOF.
@@ -19,7 +19,7 @@ use crate::application::ports::compiler_ports::{ParserPort, Token, Literal};
 use crate::application::options::LogLevel;
 use crate::domain::entities::error::{Diagnostic, OnuError, Span};
 use crate::domain::entities::ast::{Discourse, Expression, BehaviorHeader, ReturnType, Argument, TypeInfo, BinOp};
-use crate::domain::entities::types::OnuType;
+use crate::domain::entities::types::{InlineHint, KnownBehavior, OnuType};
 use crate::domain::entities::registry::BehaviorSignature;
 use crate::application::use_cases::registry_service::RegistryService;
 use chrono::Local;
@@ -47,7 +47,13 @@ impl OnuParser {
         while !parser.is_at_end() {
             let token = parser.peek();
             if matches!(token, Some(Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled)) {
+                // Step over blank lines so the recorded line is the header's own.
+                while matches!(parser.peek_raw(), Some(Token::NewLine)) {
+                    parser.advance_raw();
+                }
+                let line = parser.current_span().line;
                 let header = parser.parse_behavior_header(registry)?;
+                registry.record_behavior_line(&header.name, line);
                 let sig = BehaviorSignature {
                     input_types: header.takes.iter().map(|a| a.type_info.onu_type.clone()).collect(),
                     return_type: header.delivers.0.clone(),
@@ -58,7 +64,7 @@ impl OnuParser {
                 // Skip to the next discourse start by skipping the body block
                 while !parser.is_at_end() {
                     let next = parser.peek();
-                    if matches!(next, Some(Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled)) {
+                    if matches!(next, Some(Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled | Token::TheTestCalled)) {
                         break;
                     }
                     parser.advance();
@@ -310,7 +316,7 @@ impl ParserInternal {
     }
 
     fn is_expression_terminator(&self, token: &Token) -> bool {
-        matches!(token, Token::Then | Token::Else | Token::Takes | Token::Delivers | Token::As | Token::WithConcern | Token::WithIntent | Token::TheModuleCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled)
+        matches!(token, Token::Then | Token::Else | Token::Takes | Token::Delivers | Token::As | Token::WithConcern | Token::WithIntent | Token::Expects | Token::TheModuleCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled | Token::TheTestCalled)
             || matches!(token, Token::Operator(s) if s == ":")
             || matches!(token, Token::Delimiter(')'))
     }
@@ -322,6 +328,7 @@ impl ParserInternal {
                 Token::TheModuleCalled => return Ok(Some(self.parse_module()?)),
                 Token::TheShapeCalled => return Ok(Some(self.parse_shape(registry)?)),
                 Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled => return Ok(Some(self.parse_behavior(registry)?)),
+                Token::TheTestCalled => return Ok(Some(self.parse_test(registry)?)),
                 _ => { 
                     self.advance(); 
                 }
@@ -341,7 +348,7 @@ impl ParserInternal {
         let behaviors = Vec::new();
 
         while let Some(t) = self.peek() {
-            if matches!(t, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled) { break; }
+            if matches!(t, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled | Token::TheTestCalled) { break; }
             match t {
                 Token::Takes => {
                     self.advance();
@@ -363,7 +370,7 @@ impl ParserInternal {
         
         let mut concern = String::new();
         while let Some(t) = self.peek() {
-            if matches!(t, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled) { break; }
+            if matches!(t, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled | Token::TheTestCalled) { break; }
             if matches!(t, Token::WithConcern) {
                 self.advance();
                 self.match_token(Token::Operator(":".to_string()));
@@ -388,6 +395,44 @@ impl ParserInternal {
         Ok(Discourse::Behavior { header, body })
     }
 
+    fn parse_test(&mut self, registry: &mut RegistryService) -> Result<Discourse, OnuError> {
+        self.log(LogLevel::Debug, "Parsing test");
+        // Step over blank lines so the recorded line is the header's own.
+        while matches!(self.peek_raw(), Some(Token::NewLine)) {
+            self.advance_raw();
+        }
+        let line = self.current_span().line;
+        self.consume(Token::TheTestCalled)?;
+        let name = if let Some(Token::Identifier(n)) = self.advance() { n.clone() } else {
+            return Err(OnuError::GrammarViolation { message: "Expected test name".into(), span: self.current_span() });
+        };
+
+        let mut expects = None;
+        while let Some(t) = self.peek() {
+            match t {
+                Token::Expects => {
+                    self.advance();
+                    self.match_token(Token::Operator(":".to_string()));
+                    expects = Some(self.parse_expression(registry)?);
+                }
+                Token::As => break,
+                _ => { self.advance(); }
+            }
+        }
+        let Some(expects) = expects else {
+            return Err(OnuError::GrammarViolation {
+                message: format!("Test '{}' has no 'expects:' clause", name),
+                span: self.current_span(),
+            });
+        };
+
+        self.consume(Token::As)?;
+        self.match_token(Token::Operator(":".to_string()));
+        let body = self.parse_block(registry)?;
+
+        Ok(Discourse::Test { name, expects, body, line })
+    }
+
     fn parse_behavior_header(&mut self, registry: &mut RegistryService) -> Result<BehaviorHeader, OnuError> {
         let is_effect = matches!(self.peek(), Some(Token::TheEffectBehaviorCalled));
         let behavior_keyword = if is_effect { Token::TheEffectBehaviorCalled } else { Token::TheBehaviorCalled };
@@ -401,6 +446,8 @@ impl ParserInternal {
         let mut takes = Vec::new();
         let mut delivers = ReturnType(OnuType::Nothing);
         let mut diminishing = Vec::new();
+        let mut inlining = InlineHint::Auto;
+        let mut known_behavior = None;
         let mut skip_termination_check = false;
 
         while let Some(token) = self.peek() {
@@ -428,6 +475,8 @@ impl ParserInternal {
                                     Some(Token::NewLine) => { self.advance_raw(); break; }
                                     Some(Token::Takes) | Some(Token::Delivers)
                                     | Some(Token::As) | Some(Token::WithDiminishing)
+                                    | Some(Token::WithInlining)
+                                    | Some(Token::WithKnownBehavior)
                                     | Some(Token::NoGuaranteedTermination) => {
                                         // Two-token lookahead: only stop if followed by ':'
                                         let next = self.tokens.get(self.pos + 1);
@@ -440,6 +489,8 @@ impl ParserInternal {
                                             Some(Token::Delivers) => "delivers",
                                             Some(Token::As) => "as",
                                             Some(Token::WithDiminishing) => "with-diminishing",
+                                            Some(Token::WithInlining) => "with-inlining",
+                                            Some(Token::WithKnownBehavior) => "with-known-behavior",
                                             Some(Token::NoGuaranteedTermination) => "no-guaranteed-termination",
                                             _ => "",
                                         };
@@ -478,7 +529,7 @@ impl ParserInternal {
 
                     // Parse optional subsequent identifiers (comma-separated or just space-separated)
                     while let Some(t) = self.peek() {
-                        if matches!(t, Token::Takes | Token::Delivers | Token::WithIntent | Token::NoGuaranteedTermination | Token::As) {
+                        if matches!(t, Token::Takes | Token::Delivers | Token::WithIntent | Token::WithInlining | Token::WithKnownBehavior | Token::NoGuaranteedTermination | Token::As) {
                             break;
                         }
                         
@@ -494,6 +545,33 @@ impl ParserInternal {
                         }
                     }
                 }
+                Token::WithInlining => {
+                    self.advance();
+                    self.match_token(Token::Operator(":".to_string()));
+                    inlining = match self.advance() {
+                        Some(Token::Identifier(s)) if s == "always" => InlineHint::Always,
+                        Some(Token::Identifier(s)) if s == "never" => InlineHint::Never,
+                        _ => {
+                            return Err(OnuError::GrammarViolation {
+                                message: "Expected 'always' or 'never' after 'with inlining:'".into(),
+                                span: self.current_span(),
+                            });
+                        }
+                    };
+                }
+                Token::WithKnownBehavior => {
+                    self.advance();
+                    self.match_token(Token::Operator(":".to_string()));
+                    known_behavior = match self.advance() {
+                        Some(Token::Identifier(s)) if KnownBehavior::from_name(s).is_some() => KnownBehavior::from_name(s),
+                        _ => {
+                            return Err(OnuError::GrammarViolation {
+                                message: "Expected a known behavior such as 'sha256-compress' after 'with known behavior:'".into(),
+                                span: self.current_span(),
+                            });
+                        }
+                    };
+                }
                 Token::NoGuaranteedTermination => {
                     self.advance();
                     skip_termination_check = true;
@@ -503,13 +581,13 @@ impl ParserInternal {
             }
         }
 
-        Ok(BehaviorHeader { name, is_effect, intent, takes, delivers, diminishing, memo_cache_size: None, skip_termination_check })
+        Ok(BehaviorHeader { name, is_effect, intent, takes, delivers, diminishing, memo_cache_size: None, inlining, known_behavior, skip_termination_check })
     }
 
     fn parse_block(&mut self, registry: &mut RegistryService) -> Result<Expression, OnuError> {
         let mut exprs = Vec::new();
         while let Some(t) = self.peek() {
-            if matches!(t, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled) {
+            if matches!(t, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled | Token::TheTestCalled) {
                 break;
             }
             if matches!(t, Token::As) {
@@ -586,7 +664,7 @@ impl ParserInternal {
             if self.is_expression_terminator(t) {
                 break;
             }
-            if matches!(t, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled | Token::As) {
+            if matches!(t, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled | Token::TheTestCalled | Token::As) {
                 break;
             }
             if matches!(t, Token::Derivation) {
@@ -640,6 +718,7 @@ impl ParserInternal {
                     let p = match s.as_str() {
                         "matches" | "exceeds" | "falls-short-of" => 2,
                         "added-to" | "decreased-by" => 3,
+                        "wrapping-added-to" | "saturating-added-to" => 3,
                         "scales-by" | "partitions-by" => 4,
                         "joined-with" | "joins-with" | "unites-with" | "opposes" => 4,
                         "init-of" | "tail-of" | "duplicated-as" => 4,
@@ -667,6 +746,11 @@ impl ParserInternal {
                 if let Some(sig) = registry.get_signature(&target) {
                     let additional_args_needed = sig.input_types.len().saturating_sub(1);
                     for _ in 0..additional_args_needed {
+                        // Optional connectives so multi-argument calls read as prose:
+                        // `name utilizes substring from 2 to 5`.
+                        if matches!(self.peek(), Some(Token::From) | Some(Token::To)) {
+                            self.advance();
+                        }
                         args.push(self.parse_expression(registry)?);
                     }
                 } else {
@@ -682,6 +766,7 @@ impl ParserInternal {
                 lhs = Expression::BehaviorCall { name: op, args: vec![lhs] };
             } else if op == "matches" || op == "exceeds" || op == "falls-short-of" || 
                       op == "added-to" || op == "decreased-by" || op == "scales-by" || op == "partitions-by" ||
+                      op == "wrapping-added-to" || op == "saturating-added-to" ||
                       op == "joined-with" || op == "char-at" ||
                       op == "bit-and-with" || op == "bit-or-with" || op == "bit-xor-with" ||
                       op == "shifted-right-by" || op == "shifted-left-by" {
@@ -878,7 +963,7 @@ impl ParserInternal {
         let mut args = Vec::new();
         while let Some(token) = self.peek() {
             self.log(LogLevel::Trace, &format!("Arguments loop peeking: {:?}", token));
-            if matches!(token, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled | Token::Delivers | Token::As | Token::Takes | Token::WithDiminishing) {
+            if matches!(token, Token::TheModuleCalled | Token::TheShapeCalled | Token::TheBehaviorCalled | Token::TheEffectBehaviorCalled | Token::TheTestCalled | Token::Delivers | Token::As | Token::Takes | Token::WithDiminishing | Token::WithInlining | Token::WithKnownBehavior) {
                 break;
             }
             match token {
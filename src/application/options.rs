@@ -10,6 +10,20 @@ pub struct CompilationOptions {
     pub emit_mir: bool,
     pub emit_tokens: bool,
     pub optimization_level: u32,
+    /// Trap on signed/unsigned overflow in `added-to`, `decreased-by` and
+    /// `scales-by` instead of wrapping.
+    pub checked_arithmetic: bool,
+    /// Guard `partitions-by` against a zero divisor and `char-at` /
+    /// `set-char` against out-of-range indices.  On by default; disable for
+    /// release builds that have been tested with the checks in place.
+    pub runtime_checks: bool,
+    /// Report the memo strategy chosen for each behavior at compile time and
+    /// instrument memo wrappers to print hit/miss counters at exit.
+    pub memo_report: bool,
+    /// Let codegen dispatch behaviors tagged `with known behavior:` to
+    /// hardware kernels (SHA-NI, ARMv8 crypto) when the CPU has them.
+    /// Disable to always run the software bodies.
+    pub hardware_intrinsics: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -55,6 +69,10 @@ impl Default for CompilationOptions {
             emit_mir: false,
             emit_tokens: false,
             optimization_level: 0,
+            checked_arithmetic: false,
+            runtime_checks: true,
+            memo_report: false,
+            hardware_intrinsics: true,
         }
     }
 }
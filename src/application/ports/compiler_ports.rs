@@ -19,9 +19,13 @@ pub enum Token {
     TheShapeCalled,
     TheBehaviorCalled,
     TheEffectBehaviorCalled,
+    TheTestCalled,
+    Expects,
     WithConcern,
     WithIntent,
     WithDiminishing,
+    WithInlining,
+    WithKnownBehavior,
     NoGuaranteedTermination,
     DerivesFrom,
     DecreasedBy,
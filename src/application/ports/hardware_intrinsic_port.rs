@@ -3,7 +3,8 @@
 /// This trait defines the abstract boundary between the compiler's core
 /// lowering logic and hardware-specific acceleration strategies.
 ///
-/// The Domain/Application layers tag functions with `KnownBehavior` metadata.
+/// Behaviors are tagged with the domain's `KnownBehavior` through a
+/// `with known behavior:` clause, which reaches codegen on `MirFunction`.
 /// The Infrastructure/Adapter layer provides concrete implementations that
 /// emit the appropriate LLVM intrinsics for the target architecture.
 ///
@@ -11,23 +12,11 @@
 ///
 /// The port follows the Strategy Pattern (GoF):
 /// - **Domain**: Pure mathematical behavior written in Ọ̀nụ Discourse.
-/// - **Application**: Tags functions with `KnownBehavior` via the registry.
+/// - **Application**: Carries the `KnownBehavior` tag from header to MIR.
 /// - **Infrastructure**: `IntrinsicFactory::create(target)` returns the
 ///   appropriate strategy that the codegen adapter uses transparently.
 
-/// Known computational behaviors that the compiler can accelerate with
-/// hardware intrinsics when available.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum KnownBehavior {
-    /// SHA-256 compression function (64-round Merkle-Damgård).
-    Sha256Compress,
-    /// SHA-256 message schedule expansion (σ0/σ1 transforms).
-    Sha256MessageSchedule,
-    /// AES single-round encryption.
-    AesEncryptRound,
-    /// Generic bit rotation (handled by IdiomRecognizerPass instead).
-    RotateRight,
-}
+pub use crate::domain::entities::types::KnownBehavior;
 
 /// The abstract port that hardware acceleration strategies implement.
 ///
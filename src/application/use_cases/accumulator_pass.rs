@@ -0,0 +1,406 @@
+/// Accumulator Introduction Pass: Application Use Case Layer
+///
+/// # What This Does
+/// Textbook recursion such as
+///
+/// ```text
+/// factorial(n) = if n matches 0 then 1 else n scales-by factorial(n - 1)
+/// ```
+///
+/// is not tail-recursive: the multiplication runs *after* the recursive call
+/// returns, so `TcoPass` cannot turn it into a loop. When the pending work is
+/// an associative operator (`added-to`, `scales-by`, `joined-with`) this pass
+/// carries it in accumulator slots instead and rewrites the function into the
+/// same loop shape `TcoPass` produces:
+///
+/// ```text
+/// loop head:  acc = 1
+/// recursive:  acc = acc * n;  n = n - 1;  branch to entry
+/// base case:  return acc * 1
+/// ```
+///
+/// # Why Two Accumulators for Text
+/// `added-to` and `scales-by` are commutative, so it does not matter which
+/// side of the operator the recursive call sits on. `joined-with` is only
+/// associative: `x joined-with f(..)` grows the result on the left and
+/// `f(..) joined-with x` on the right. Text functions therefore keep a left
+/// accumulator `l` and, when needed, a right accumulator `r`, and the base
+/// case delivers `l joined-with value joined-with r`.
+///
+/// # Which Functions Qualify
+/// Every self call must be either a tail call or a *site*: a non-tail call
+/// whose result (possibly copied through `Assign`s) feeds a single combine
+/// whose result the block returns. Calls nested deeper, such as the two
+/// calls in Fibonacci, leave the function untouched.
+///
+/// # Ordering
+/// Runs before `MemoPass`, so a `with diminishing:` behavior like the
+/// factorial sample becomes a loop before its cache wrapper is built, and
+/// before `TcoPass`, which then has nothing left to do for it.
+use crate::application::use_cases::inline_pass::{max_ssa_in_function, remap_instruction};
+use crate::application::use_cases::tco_pass::shift_terminator;
+use crate::domain::entities::mir::{
+    BasicBlock, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand, MirProgram,
+    MirTerminator,
+};
+use crate::domain::entities::types::OnuType;
+use std::collections::HashSet;
+
+/// Which side of the combine the non-recursive operand sits on.
+#[derive(Clone, Copy, PartialEq)]
+enum Side {
+    /// `x ⊕ f(..)`
+    Left,
+    /// `f(..) ⊕ x`
+    Right,
+}
+
+/// The associative operation pending after a recursive call.
+#[derive(Clone)]
+enum Combine {
+    /// `added-to` / `scales-by` on integers, optionally overflow-checked.
+    Arith { op: MirBinOp, checked_site: Option<String> },
+    /// The `joined-with` expansion, kept as a template that reads `left` and
+    /// `right` and writes its string to `result`.
+    Concat { template: Vec<MirInstruction>, left: usize, right: usize, result: usize },
+}
+
+impl Combine {
+    fn identity(&self) -> MirLiteral {
+        match self {
+            Combine::Arith { op: MirBinOp::Mul, .. } => MirLiteral::I64(1),
+            Combine::Arith { .. } => MirLiteral::I64(0),
+            Combine::Concat { .. } => MirLiteral::Text(String::new()),
+        }
+    }
+
+    fn return_type(&self) -> OnuType {
+        match self {
+            Combine::Arith { .. } => OnuType::I64,
+            Combine::Concat { .. } => OnuType::Strings,
+        }
+    }
+
+    fn compatible(&self, other: &Combine) -> bool {
+        match (self, other) {
+            (Combine::Arith { op: a, checked_site: x }, Combine::Arith { op: b, checked_site: y }) => {
+                a == b && x.is_some() == y.is_some()
+            }
+            (Combine::Concat { .. }, Combine::Concat { .. }) => true,
+            _ => false,
+        }
+    }
+
+    /// Appends instructions computing `lhs ⊕ rhs` and returns the result.
+    /// `fresh` is the next unused SSA id; a `Concat` claims a whole block of
+    /// `span` ids so its shifted template never collides with the function.
+    fn emit(
+        &self,
+        lhs: MirOperand,
+        rhs: MirOperand,
+        fresh: &mut usize,
+        span: usize,
+        out: &mut Vec<MirInstruction>,
+    ) -> MirOperand {
+        match self {
+            Combine::Arith { op, checked_site } => {
+                let dest = *fresh;
+                *fresh += 1;
+                out.push(match checked_site {
+                    Some(site) => MirInstruction::CheckedArithmetic {
+                        dest,
+                        op: op.clone(),
+                        lhs,
+                        rhs,
+                        dest_type: OnuType::I64,
+                        site: site.clone(),
+                    },
+                    None => MirInstruction::BinaryOperation {
+                        dest,
+                        op: op.clone(),
+                        lhs,
+                        rhs,
+                        dest_type: OnuType::I64,
+                    },
+                });
+                MirOperand::Variable(dest, false)
+            }
+            Combine::Concat { template, left, right, result } => {
+                let offset = *fresh;
+                *fresh += span;
+                out.push(MirInstruction::Assign { dest: left + offset, src: lhs });
+                out.push(MirInstruction::Assign { dest: right + offset, src: rhs });
+                out.extend(template.iter().map(|inst| remap_instruction(inst, offset)));
+                MirOperand::Variable(result + offset, false)
+            }
+        }
+    }
+}
+
+/// A block ending in a self call that the loop replaces.
+struct Site {
+    block: usize,
+    call: usize,
+    args: Vec<MirOperand>,
+    /// Assignments between the call and the combine that do not read the
+    /// call result, such as a text literal on the right-hand side.
+    independent: Vec<MirInstruction>,
+    /// The pending combine, or `None` for a call already in tail position.
+    pending: Option<(Side, MirOperand)>,
+}
+
+pub struct AccumulatorPass;
+
+impl AccumulatorPass {
+    /// Runs `run_function` on every function in the program.
+    pub fn run(program: MirProgram) -> MirProgram {
+        MirProgram {
+            functions: program.functions.into_iter().map(Self::run_function).collect(),
+        }
+    }
+
+    /// Rewrites one function into an accumulator loop when every self call
+    /// qualifies; otherwise returns it unchanged.
+    pub fn run_function(mut func: MirFunction) -> MirFunction {
+        let Some((combine, sites)) = plan(&func) else {
+            return func;
+        };
+        let span = max_ssa_in_function(&func) + 1;
+        let mut fresh = span;
+        let left = fresh;
+        fresh += 1;
+        let right = match combine {
+            Combine::Concat { .. }
+                if sites.iter().any(|s| matches!(s.pending, Some((Side::Right, _)))) =>
+            {
+                fresh += 1;
+                Some(fresh - 1)
+            }
+            _ => None,
+        };
+
+        let original_entry_id = 1usize;
+        for block in &mut func.blocks {
+            block.id += 1;
+            shift_terminator(&mut block.terminator);
+        }
+
+        let arg_ssa_vars: Vec<usize> = func.args.iter().map(|a| a.ssa_var).collect();
+        let site_blocks: HashSet<usize> = sites.iter().map(|s| s.block).collect();
+        for site in sites {
+            let mut instructions = std::mem::take(&mut func.blocks[site.block].instructions);
+            instructions.truncate(site.call);
+            instructions.extend(site.independent);
+
+            // Fold the pending operand into the accumulator before the
+            // argument slots it may read are overwritten.
+            match site.pending {
+                Some((Side::Right, x)) if right.is_some() => {
+                    let acc = right.unwrap();
+                    let value = combine.emit(
+                        x,
+                        MirOperand::Variable(acc, false),
+                        &mut fresh,
+                        span,
+                        &mut instructions,
+                    );
+                    instructions.push(MirInstruction::Assign { dest: acc, src: value });
+                }
+                Some((_, x)) => {
+                    let value = combine.emit(
+                        MirOperand::Variable(left, false),
+                        x,
+                        &mut fresh,
+                        span,
+                        &mut instructions,
+                    );
+                    instructions.push(MirInstruction::Assign { dest: left, src: value });
+                }
+                None => {}
+            }
+
+            // Arguments go through temporaries: `f(b, a)` reads the slots it writes.
+            let temps: Vec<usize> = (fresh..fresh + site.args.len()).collect();
+            fresh += site.args.len();
+            for (temp, arg) in temps.iter().zip(site.args) {
+                instructions.push(MirInstruction::Assign { dest: *temp, src: arg });
+            }
+            for (temp, arg_ssa) in temps.iter().zip(&arg_ssa_vars) {
+                instructions.push(MirInstruction::Assign {
+                    dest: *arg_ssa,
+                    src: MirOperand::Variable(*temp, false),
+                });
+            }
+
+            let block = &mut func.blocks[site.block];
+            block.instructions = instructions;
+            block.terminator = MirTerminator::Branch(original_entry_id);
+        }
+
+        // Base cases deliver `left ⊕ value ⊕ right`.
+        for (index, block) in func.blocks.iter_mut().enumerate() {
+            if site_blocks.contains(&index) {
+                continue;
+            }
+            if let MirTerminator::Return(value) = &block.terminator {
+                let value = value.clone();
+                let mut result = combine.emit(
+                    MirOperand::Variable(left, false),
+                    value,
+                    &mut fresh,
+                    span,
+                    &mut block.instructions,
+                );
+                if let Some(acc) = right {
+                    result = combine.emit(
+                        result,
+                        MirOperand::Variable(acc, false),
+                        &mut fresh,
+                        span,
+                        &mut block.instructions,
+                    );
+                }
+                block.terminator = MirTerminator::Return(result);
+            }
+        }
+
+        let identity = combine.identity();
+        let mut head = vec![MirInstruction::Assign {
+            dest: left,
+            src: MirOperand::Constant(identity.clone()),
+        }];
+        if let Some(acc) = right {
+            head.push(MirInstruction::Assign { dest: acc, src: MirOperand::Constant(identity) });
+        }
+        func.blocks.insert(
+            0,
+            BasicBlock {
+                id: 0,
+                instructions: head,
+                terminator: MirTerminator::Branch(original_entry_id),
+            },
+        );
+
+        func
+    }
+}
+
+/// Finds the combine and the sites for `func`, or `None` when some self call
+/// does not fit the pattern or nothing would be gained.
+fn plan(func: &MirFunction) -> Option<(Combine, Vec<Site>)> {
+    let mut combine: Option<Combine> = None;
+    let mut sites = Vec::new();
+    for (index, block) in func.blocks.iter().enumerate() {
+        let calls: Vec<usize> = block
+            .instructions
+            .iter()
+            .enumerate()
+            .filter(|(_, inst)| matches!(inst, MirInstruction::Call { name, .. } if name == &func.name))
+            .map(|(i, _)| i)
+            .collect();
+        match calls.as_slice() {
+            [] => continue,
+            [call] => {
+                let (site, found) = match_site(block, index, *call)?;
+                if let Some(found) = found {
+                    match &combine {
+                        Some(existing) if !existing.compatible(&found) => return None,
+                        Some(_) => {}
+                        None => combine = Some(found),
+                    }
+                }
+                sites.push(site);
+            }
+            _ => return None,
+        }
+    }
+    let combine = combine?;
+    if combine.return_type() != func.return_type {
+        return None;
+    }
+    Some((combine, sites))
+}
+
+/// Matches the self call at `call` in `block`. Returns the site and, for a
+/// non-tail call, the combine it is waiting on.
+fn match_site(block: &BasicBlock, index: usize, call: usize) -> Option<(Site, Option<Combine>)> {
+    let MirInstruction::Call { dest, args, is_tail_call, .. } = &block.instructions[call] else {
+        return None;
+    };
+    if *is_tail_call {
+        let site = Site { block: index, call, args: args.clone(), independent: vec![], pending: None };
+        return Some((site, None));
+    }
+    let MirTerminator::Return(MirOperand::Variable(result, _)) = &block.terminator else {
+        return None;
+    };
+
+    // The call result may be copied around before it is combined, and the
+    // other operand may only be materialized after the call.
+    let mut aliases = HashSet::from([*dest]);
+    let mut independent = Vec::new();
+    let mut rest = &block.instructions[call + 1..];
+    while let Some(MirInstruction::Assign { dest, src }) = rest.first() {
+        match src {
+            MirOperand::Variable(v, _) if aliases.contains(v) => {
+                aliases.insert(*dest);
+            }
+            _ => independent.push(rest[0].clone()),
+        }
+        rest = &rest[1..];
+    }
+
+    let (combine, lhs, rhs) = match_arith(rest, *result).or_else(|| match_concat(rest, *result))?;
+    let is_alias = |op: &MirOperand| matches!(op, MirOperand::Variable(v, _) if aliases.contains(v));
+    let pending = match (is_alias(&lhs), is_alias(&rhs)) {
+        (true, false) => (Side::Right, rhs),
+        (false, true) => (Side::Left, lhs),
+        _ => return None,
+    };
+    let site = Site { block: index, call, args: args.clone(), independent, pending: Some(pending) };
+    Some((site, Some(combine)))
+}
+
+/// `rest` is a single integer `added-to` / `scales-by` producing `result`.
+fn match_arith(rest: &[MirInstruction], result: usize) -> Option<(Combine, MirOperand, MirOperand)> {
+    let (dest, op, lhs, rhs, dest_type, checked_site) = match rest {
+        [MirInstruction::BinaryOperation { dest, op, lhs, rhs, dest_type }] => {
+            (dest, op, lhs, rhs, dest_type, None)
+        }
+        [MirInstruction::CheckedArithmetic { dest, op, lhs, rhs, dest_type, site }] => {
+            (dest, op, lhs, rhs, dest_type, Some(site.clone()))
+        }
+        _ => return None,
+    };
+    if *dest != result || *dest_type != OnuType::I64 || !matches!(op, MirBinOp::Add | MirBinOp::Mul) {
+        return None;
+    }
+    Some((Combine::Arith { op: op.clone(), checked_site }, lhs.clone(), rhs.clone()))
+}
+
+/// `rest` is exactly the instruction sequence `JoinedWithLowerer` emits for
+/// `left joined-with right`, producing `result`.
+fn match_concat(rest: &[MirInstruction], result: usize) -> Option<(Combine, MirOperand, MirOperand)> {
+    use MirInstruction::*;
+    let [
+        Index { subject: MirOperand::Variable(left, _), index: 0, .. },
+        Index { subject: MirOperand::Variable(right, _), index: 0, .. },
+        BinaryOperation { op: MirBinOp::Add, .. },
+        Index { subject: MirOperand::Variable(left_ptr, _), index: 1, .. },
+        Index { subject: MirOperand::Variable(right_ptr, _), index: 1, .. },
+        BinaryOperation { op: MirBinOp::Add, .. },
+        Alloc { .. },
+        MemCopy { .. },
+        PointerOffset { .. },
+        MemCopy { .. },
+        Tuple { dest, .. },
+    ] = rest
+    else {
+        return None;
+    };
+    if left == right || left_ptr != left || right_ptr != right || *dest != result {
+        return None;
+    }
+    let combine = Combine::Concat { template: rest.to_vec(), left: *left, right: *right, result };
+    Some((combine, MirOperand::Variable(*left, false), MirOperand::Variable(*right, false)))
+}
@@ -0,0 +1,65 @@
+//! Lint `constant-condition`: an `if` whose condition is a constant, so
+//! one of its branches can never run.
+//!
+//! Conditions are constant when they are a boolean literal or compare two
+//! integer literals.
+
+use crate::application::use_cases::analyzer::visitor::AnalyzerVisitor;
+use crate::domain::entities::error::{Diagnostic, Span};
+use crate::domain::entities::hir::{HirBinOp, HirDiscourse, HirExpression, HirLiteral};
+
+#[derive(Default)]
+pub struct ConstantCondition {
+    diagnostics: Vec<Diagnostic>,
+    behavior: String,
+}
+
+/// The value of `condition` if it does not depend on anything.
+fn constant_value(condition: &HirExpression) -> Option<bool> {
+    match condition {
+        HirExpression::Literal(HirLiteral::Boolean(b)) => Some(*b),
+        HirExpression::BinaryOp { op, left, right } => {
+            let (HirExpression::Literal(HirLiteral::I64(l)), HirExpression::Literal(HirLiteral::I64(r))) =
+                (left.as_ref(), right.as_ref())
+            else {
+                return None;
+            };
+            match op {
+                HirBinOp::Equal => Some(l == r),
+                HirBinOp::NotEqual => Some(l != r),
+                HirBinOp::LessThan => Some(l < r),
+                HirBinOp::GreaterThan => Some(l > r),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+impl AnalyzerVisitor for ConstantCondition {
+    fn visit_discourse(&mut self, discourse: &HirDiscourse) {
+        if let HirDiscourse::Behavior { header, body } = discourse {
+            self.behavior = header.name.clone();
+            self.visit_expression(body);
+        }
+    }
+
+    fn visit_if(&mut self, condition: &HirExpression, _then_branch: &HirExpression, _else_branch: &HirExpression) {
+        let Some(value) = constant_value(condition) else { return };
+        let (dead, live) = if value { ("else", "then") } else { ("then", "else") };
+        self.diagnostics.push(
+            Diagnostic::warning(
+                Span::default(),
+                format!(
+                    "Condition of an 'if' in '{}' is always {}; its '{}' branch never runs",
+                    self.behavior, value, dead
+                ),
+            )
+            .with_hint(format!("Replace the 'if' with its '{}' branch", live)),
+        );
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
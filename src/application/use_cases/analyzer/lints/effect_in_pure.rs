@@ -0,0 +1,63 @@
+//! Lint `effect-in-pure`: a pure behavior calls an effect behavior.
+//!
+//! A behavior declared with `the behavior called` promises to have no side
+//! effects; calling one declared with `the effect behavior called` breaks
+//! that promise.
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::application::use_cases::analyzer::visitor::AnalyzerVisitor;
+use crate::domain::entities::error::{Diagnostic, Span};
+use crate::domain::entities::hir::{HirDiscourse, HirExpression};
+
+#[derive(Default)]
+pub struct EffectInPure {
+    diagnostics: Vec<Diagnostic>,
+    effects: HashSet<String>,
+    /// The pure behavior being walked, if any.
+    caller: Option<String>,
+    reported: BTreeSet<(String, String)>,
+}
+
+impl AnalyzerVisitor for EffectInPure {
+    fn visit_program(&mut self, discourses: &[HirDiscourse]) {
+        for discourse in discourses {
+            if let HirDiscourse::Behavior { header, .. } = discourse
+                && header.is_effect
+            {
+                self.effects.insert(header.name.clone());
+            }
+        }
+        for discourse in discourses {
+            self.visit_discourse(discourse);
+        }
+    }
+
+    fn visit_discourse(&mut self, discourse: &HirDiscourse) {
+        if let HirDiscourse::Behavior { header, body } = discourse {
+            self.caller = (!header.is_effect).then(|| header.name.clone());
+            self.visit_expression(body);
+        }
+    }
+
+    fn visit_call(&mut self, name: &str, _args: &[HirExpression]) {
+        let Some(caller) = &self.caller else { return };
+        if !self.effects.contains(name) || !self.reported.insert((caller.clone(), name.to_string())) {
+            return;
+        }
+        self.diagnostics.push(
+            Diagnostic::warning(
+                Span::default(),
+                format!("Pure behavior '{}' calls effect behavior '{}'", caller, name),
+            )
+            .with_hint(format!(
+                "Declare it as 'the effect behavior called {}', or move the call to '{}' into an effect behavior",
+                caller, name
+            )),
+        );
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
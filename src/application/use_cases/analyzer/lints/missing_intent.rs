@@ -0,0 +1,35 @@
+//! Lint `missing-intent`: a behavior with no `with intent:` prose.
+//!
+//! Every behavior states what it is for; `with intent: nothing` counts as
+//! missing, since `nothing` is a keyword rather than prose.
+
+use crate::application::use_cases::analyzer::visitor::AnalyzerVisitor;
+use crate::domain::entities::error::{Diagnostic, Span};
+use crate::domain::entities::hir::{HirBehaviorHeader, HirDiscourse};
+
+#[derive(Default)]
+pub struct MissingIntent {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl AnalyzerVisitor for MissingIntent {
+    fn visit_discourse(&mut self, discourse: &HirDiscourse) {
+        if let HirDiscourse::Behavior { header, .. } = discourse {
+            self.visit_behavior_header(header);
+        }
+    }
+
+    fn visit_behavior_header(&mut self, header: &HirBehaviorHeader) {
+        if !header.intent.trim().is_empty() {
+            return;
+        }
+        self.diagnostics.push(
+            Diagnostic::warning(Span::default(), format!("Behavior '{}' has no intent", header.name))
+                .with_hint("Add 'with intent: <what it is for>' under its header"),
+        );
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
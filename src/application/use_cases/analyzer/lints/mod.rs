@@ -0,0 +1,339 @@
+//! Lints: configurable static checks over the HIR.
+//!
+//! Each rule is an `AnalyzerVisitor` of its own that walks the whole program
+//! and accumulates `Warning` diagnostics with an `actionable_hint`.  `run`
+//! drives every rule a `LintConfig` leaves enabled and tags each diagnostic
+//! with the rule that produced it, so the CLI can name the rule a user
+//! would `--allow`.
+//!
+//! - `effect_in_pure`: a pure behavior calls an effect behavior.
+//! - `needless_diminishing`: `with diminishing:` on a non-recursive behavior.
+//! - `missing_intent`: a behavior without `with intent:` prose.
+//! - `constant_condition`: an `if` whose condition is a constant.
+//! - `shadowed_derivation`: a derivation reusing a name already in scope.
+//! - `unused_definition`: a behavior or shape nothing refers to.
+//!
+//! Unused variables are reported by the existing `SemanticAnalyzer`.
+
+pub mod constant_condition;
+pub mod effect_in_pure;
+pub mod missing_intent;
+pub mod needless_diminishing;
+pub mod shadowed_derivation;
+pub mod unused_definition;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::application::use_cases::analyzer::SemanticAnalyzer;
+use crate::application::use_cases::analyzer::visitor::AnalyzerVisitor;
+use crate::domain::entities::error::Diagnostic;
+use crate::domain::entities::hir::{HirDiscourse, HirExpression};
+
+pub use constant_condition::ConstantCondition;
+pub use effect_in_pure::EffectInPure;
+pub use missing_intent::MissingIntent;
+pub use needless_diminishing::NeedlessDiminishing;
+pub use shadowed_derivation::ShadowedDerivation;
+pub use unused_definition::UnusedDefinition;
+
+/// A lint rule, named on the command line by its kebab-case name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Lint {
+    UnusedVariable,
+    EffectInPure,
+    NeedlessDiminishing,
+    MissingIntent,
+    ConstantCondition,
+    ShadowedDerivation,
+    UnusedDefinition,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 7] = [
+        Lint::UnusedVariable,
+        Lint::EffectInPure,
+        Lint::NeedlessDiminishing,
+        Lint::MissingIntent,
+        Lint::ConstantCondition,
+        Lint::ShadowedDerivation,
+        Lint::UnusedDefinition,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnusedVariable => "unused-variable",
+            Lint::EffectInPure => "effect-in-pure",
+            Lint::NeedlessDiminishing => "needless-diminishing",
+            Lint::MissingIntent => "missing-intent",
+            Lint::ConstantCondition => "constant-condition",
+            Lint::ShadowedDerivation => "shadowed-derivation",
+            Lint::UnusedDefinition => "unused-definition",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|lint| lint.name() == name)
+    }
+
+    fn analyzer(&self) -> Box<dyn AnalyzerVisitor> {
+        match self {
+            Lint::UnusedVariable => Box::new(SemanticAnalyzer::new()),
+            Lint::EffectInPure => Box::new(EffectInPure::default()),
+            Lint::NeedlessDiminishing => Box::new(NeedlessDiminishing::default()),
+            Lint::MissingIntent => Box::new(MissingIntent::default()),
+            Lint::ConstantCondition => Box::new(ConstantCondition::default()),
+            Lint::ShadowedDerivation => Box::new(ShadowedDerivation::default()),
+            Lint::UnusedDefinition => Box::new(UnusedDefinition::default()),
+        }
+    }
+}
+
+/// Which lints run.  Every lint is enabled unless allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LintConfig {
+    allowed: BTreeSet<Lint>,
+}
+
+impl LintConfig {
+    /// Turn `lint` off.
+    pub fn allow(&mut self, lint: Lint) {
+        self.allowed.insert(lint);
+    }
+
+    /// Turn `lint` back on.
+    pub fn warn(&mut self, lint: Lint) {
+        self.allowed.remove(&lint);
+    }
+
+    pub fn is_enabled(&self, lint: Lint) -> bool {
+        !self.allowed.contains(&lint)
+    }
+}
+
+/// A diagnostic and the lint that raised it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    pub lint: Lint,
+    pub diagnostic: Diagnostic,
+}
+
+/// Run every enabled lint over `discourses`, in `Lint::ALL` order.
+pub fn run(discourses: &[HirDiscourse], config: &LintConfig) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    for lint in Lint::ALL.into_iter().filter(|lint| config.is_enabled(*lint)) {
+        let mut analyzer = lint.analyzer();
+        analyzer.visit_program(discourses);
+        let mut diagnostics = analyzer.diagnostics().to_vec();
+        // Keep the report stable across runs of hash-ordered analyzers.
+        diagnostics.sort_by(|a, b| a.message.cmp(&b.message));
+        findings.extend(diagnostics.into_iter().map(|diagnostic| LintFinding { lint, diagnostic }));
+    }
+    findings
+}
+
+/// Which behaviors each behavior calls, by name.
+pub(crate) fn call_graph(discourses: &[HirDiscourse]) -> BTreeMap<String, BTreeSet<String>> {
+    let mut graph = BTreeMap::new();
+    for discourse in discourses {
+        if let HirDiscourse::Behavior { header, body } = discourse {
+            let mut calls = BTreeSet::new();
+            collect_calls(body, &mut calls);
+            graph.insert(header.name.clone(), calls);
+        }
+    }
+    graph
+}
+
+fn collect_calls(expr: &HirExpression, calls: &mut BTreeSet<String>) {
+    match expr {
+        HirExpression::Call { name, args } => {
+            calls.insert(name.clone());
+            args.iter().for_each(|arg| collect_calls(arg, calls));
+        }
+        HirExpression::BinaryOp { left, right, .. } => {
+            collect_calls(left, calls);
+            collect_calls(right, calls);
+        }
+        HirExpression::Derivation { value, body, .. } => {
+            collect_calls(value, calls);
+            collect_calls(body, calls);
+        }
+        HirExpression::If { condition, then_branch, else_branch } => {
+            collect_calls(condition, calls);
+            collect_calls(then_branch, calls);
+            collect_calls(else_branch, calls);
+        }
+        HirExpression::Tuple(items) | HirExpression::Block(items) => {
+            items.iter().for_each(|item| collect_calls(item, calls));
+        }
+        HirExpression::ActsAs { subject, .. } | HirExpression::Index { subject, .. } => collect_calls(subject, calls),
+        HirExpression::Emit(inner) | HirExpression::Drop(inner) => collect_calls(inner, calls),
+        HirExpression::Literal(_) | HirExpression::Variable(..) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::hir::{HirArgument, HirBehaviorHeader, HirLiteral};
+    use crate::domain::entities::types::{InlineHint, OnuType};
+
+    fn behavior(name: &str, is_effect: bool, diminishing: &[&str], body: HirExpression) -> HirDiscourse {
+        HirDiscourse::Behavior {
+            header: HirBehaviorHeader {
+                name: name.to_string(),
+                is_effect,
+                intent: format!("do {}", name),
+                args: vec![HirArgument { name: "n".to_string(), typ: OnuType::I64, is_observation: false }],
+                return_type: OnuType::I64,
+                diminishing: diminishing.iter().map(|d| d.to_string()).collect(),
+                memo_cache_size: None,
+                inlining: InlineHint::Auto,
+                known_behavior: None,
+            },
+            body,
+        }
+    }
+
+    fn call(name: &str) -> HirExpression {
+        HirExpression::Call { name: name.to_string(), args: vec![HirExpression::Variable("n".to_string(), false)] }
+    }
+
+    fn lints(discourses: &[HirDiscourse], config: &LintConfig) -> Vec<Lint> {
+        run(discourses, config).into_iter().map(|f| f.lint).collect()
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for lint in Lint::ALL {
+            assert_eq!(Lint::from_name(lint.name()), Some(lint));
+        }
+        assert_eq!(Lint::from_name("no-such-lint"), None);
+    }
+
+    #[test]
+    fn test_effect_called_from_pure_behavior() {
+        let program = [
+            behavior("shout", true, &[], HirExpression::Emit(Box::new(HirExpression::Variable("n".to_string(), false)))),
+            behavior("run", false, &[], call("shout")),
+        ];
+        let findings = run(&program, &LintConfig::default());
+        let finding = findings.iter().find(|f| f.lint == Lint::EffectInPure).expect("effect-in-pure");
+        assert!(finding.diagnostic.message.contains("'run'") && finding.diagnostic.message.contains("'shout'"));
+        assert!(finding.diagnostic.actionable_hint.is_some());
+    }
+
+    #[test]
+    fn test_diminishing_needs_recursion() {
+        let program = [
+            behavior("run", false, &[], call("helper")),
+            behavior("helper", false, &["n"], HirExpression::Variable("n".to_string(), false)),
+            behavior("even", false, &["n"], call("odd")),
+            behavior("odd", false, &["n"], call("even")),
+        ];
+        let findings: Vec<_> = run(&program, &LintConfig::default())
+            .into_iter()
+            .filter(|f| f.lint == Lint::NeedlessDiminishing)
+            .collect();
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].diagnostic.message.contains("'helper'"));
+    }
+
+    #[test]
+    fn test_missing_intent() {
+        let mut program = [behavior("run", false, &[], HirExpression::Variable("n".to_string(), false))];
+        if let HirDiscourse::Behavior { header, .. } = &mut program[0] {
+            header.intent = String::new();
+        }
+        assert_eq!(lints(&program, &LintConfig::default()), vec![Lint::MissingIntent]);
+    }
+
+    #[test]
+    fn test_constant_conditions() {
+        let branch = |value| Box::new(HirExpression::Literal(HirLiteral::I64(value)));
+        let body = HirExpression::Block(vec![
+            HirExpression::If {
+                condition: Box::new(HirExpression::Literal(HirLiteral::Boolean(true))),
+                then_branch: branch(1),
+                else_branch: branch(2),
+            },
+            HirExpression::If {
+                condition: Box::new(HirExpression::BinaryOp {
+                    op: crate::domain::entities::hir::HirBinOp::Equal,
+                    left: branch(1),
+                    right: branch(2),
+                }),
+                then_branch: branch(1),
+                else_branch: branch(2),
+            },
+            HirExpression::If {
+                condition: Box::new(HirExpression::Variable("n".to_string(), false)),
+                then_branch: branch(1),
+                else_branch: branch(2),
+            },
+        ]);
+        let findings = run(&[behavior("run", false, &[], body)], &LintConfig::default());
+        let messages: Vec<&str> = findings.iter().map(|f| f.diagnostic.message.as_str()).collect();
+        assert_eq!(messages.len(), 2, "{:?}", messages);
+        assert!(messages.iter().any(|m| m.contains("always true")));
+        assert!(messages.iter().any(|m| m.contains("always false")));
+    }
+
+    #[test]
+    fn test_shadowed_derivations() {
+        let derive = |name: &str, body| HirExpression::Derivation {
+            name: name.to_string(),
+            typ: OnuType::I64,
+            value: Box::new(HirExpression::Variable("n".to_string(), false)),
+            body: Box::new(body),
+        };
+        let uses = HirExpression::Variable("x".to_string(), false);
+        let body = derive("x", derive("n", derive("x", uses)));
+        let findings: Vec<_> = run(&[behavior("run", false, &[], body)], &LintConfig::default())
+            .into_iter()
+            .filter(|f| f.lint == Lint::ShadowedDerivation)
+            .collect();
+        assert_eq!(findings.len(), 2);
+    }
+
+    #[test]
+    fn test_unused_behaviors_and_shapes() {
+        let program = [
+            HirDiscourse::Shape { name: "Point".to_string(), fields: vec![], behaviors: vec![] },
+            HirDiscourse::Shape { name: "Used".to_string(), fields: vec![], behaviors: vec![] },
+            behavior("run", false, &[], call("used")),
+            behavior("used", false, &[], HirExpression::Variable("n".to_string(), false)),
+            behavior("orphan", false, &[], HirExpression::Variable("n".to_string(), false)),
+        ];
+        let mut program = program.to_vec();
+        if let HirDiscourse::Behavior { header, .. } = &mut program[3] {
+            header.return_type = OnuType::Shape("Used".to_string());
+        }
+        let findings: Vec<String> = run(&program, &LintConfig::default())
+            .into_iter()
+            .filter(|f| f.lint == Lint::UnusedDefinition)
+            .map(|f| f.diagnostic.message)
+            .collect();
+        assert_eq!(findings.len(), 2, "{:?}", findings);
+        assert!(findings.iter().any(|m| m.contains("'orphan'")));
+        assert!(findings.iter().any(|m| m.contains("'Point'")));
+
+        // Without an entry point the file is a library, and nothing is unused.
+        let library: Vec<HirDiscourse> = program.into_iter().filter(|d| !matches!(d, HirDiscourse::Behavior { header, .. } if header.name == "run")).collect();
+        assert!(!lints(&library, &LintConfig::default()).contains(&Lint::UnusedDefinition));
+    }
+
+    #[test]
+    fn test_allowed_lints_do_not_run() {
+        let mut program = [behavior("run", false, &[], HirExpression::Literal(HirLiteral::I64(0)))];
+        if let HirDiscourse::Behavior { header, .. } = &mut program[0] {
+            header.intent = String::new();
+        }
+        let mut config = LintConfig::default();
+        config.allow(Lint::MissingIntent);
+        config.allow(Lint::UnusedVariable);
+        assert!(run(&program, &config).is_empty());
+        config.warn(Lint::MissingIntent);
+        assert_eq!(lints(&program, &config), vec![Lint::MissingIntent]);
+    }
+}
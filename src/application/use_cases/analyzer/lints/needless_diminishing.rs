@@ -0,0 +1,58 @@
+//! Lint `needless-diminishing`: `with diminishing:` on a behavior that
+//! never recurses.
+//!
+//! The clause names the arguments that shrink towards a base case, which
+//! only means something for a behavior that reaches itself again, directly
+//! or through other behaviors.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::application::use_cases::analyzer::lints::call_graph;
+use crate::application::use_cases::analyzer::visitor::AnalyzerVisitor;
+use crate::domain::entities::error::{Diagnostic, Span};
+use crate::domain::entities::hir::HirDiscourse;
+
+#[derive(Default)]
+pub struct NeedlessDiminishing {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Whether `from` calls `target`, directly or through other behaviors.
+fn reaches(graph: &BTreeMap<String, BTreeSet<String>>, from: &str, target: &str) -> bool {
+    let mut seen = BTreeSet::new();
+    let mut pending: Vec<&str> = vec![from];
+    while let Some(name) = pending.pop() {
+        for callee in graph.get(name).into_iter().flatten() {
+            if callee == target {
+                return true;
+            }
+            if seen.insert(callee.as_str()) {
+                pending.push(callee);
+            }
+        }
+    }
+    false
+}
+
+impl AnalyzerVisitor for NeedlessDiminishing {
+    fn visit_program(&mut self, discourses: &[HirDiscourse]) {
+        let graph = call_graph(discourses);
+        for discourse in discourses {
+            let HirDiscourse::Behavior { header, .. } = discourse else { continue };
+            if header.diminishing.is_empty() || reaches(&graph, &header.name, &header.name) {
+                continue;
+            }
+            self.diagnostics.push(
+                Diagnostic::warning(
+                    Span::default(),
+                    format!("Behavior '{}' declares 'with diminishing:' but never recurses", header.name),
+                )
+                .with_hint("Remove the 'with diminishing:' clause; it only applies to recursive behaviors"),
+            );
+        }
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
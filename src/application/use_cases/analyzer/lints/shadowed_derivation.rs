@@ -0,0 +1,48 @@
+//! Lint `shadowed-derivation`: a derivation reusing the name of an argument
+//! or an enclosing derivation, which hides the earlier value for the rest
+//! of the scope.
+
+use crate::application::use_cases::analyzer::visitor::AnalyzerVisitor;
+use crate::domain::entities::error::{Diagnostic, Span};
+use crate::domain::entities::hir::{HirDiscourse, HirExpression};
+use crate::domain::entities::types::OnuType;
+
+#[derive(Default)]
+pub struct ShadowedDerivation {
+    diagnostics: Vec<Diagnostic>,
+    behavior: String,
+    /// The names in scope, innermost last.
+    scope: Vec<String>,
+}
+
+impl AnalyzerVisitor for ShadowedDerivation {
+    fn visit_discourse(&mut self, discourse: &HirDiscourse) {
+        if let HirDiscourse::Behavior { header, body } = discourse {
+            self.behavior = header.name.clone();
+            // Skip the synthetic arguments the lowering service injects.
+            self.scope = header.args.iter().map(|a| a.name.clone()).filter(|n| !n.starts_with("__")).collect();
+            self.visit_expression(body);
+        }
+    }
+
+    fn visit_derivation(&mut self, name: &str, _typ: &OnuType, _value: &HirExpression, _body: &HirExpression) {
+        if self.scope.iter().any(|n| n == name) {
+            self.diagnostics.push(
+                Diagnostic::warning(
+                    Span::default(),
+                    format!("Derivation '{}' in '{}' shadows an earlier '{}'", name, self.behavior, name),
+                )
+                .with_hint(format!("Rename one of the two so that '{}' keeps a single meaning", name)),
+            );
+        }
+        self.scope.push(name.to_string());
+    }
+
+    fn leave_derivation(&mut self, _name: &str) {
+        self.scope.pop();
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
@@ -0,0 +1,111 @@
+//! Lint `unused-definition`: a behavior no other behavior calls, or a shape
+//! no type mentions.
+//!
+//! Only programs with a `run` (or `main`) entry point are checked: without
+//! one the file is a library, and everything in it is there for others.
+//! Shape constructors lower to plain tuples, so a shape counts as used only
+//! when a signature, derivation or field names its type.
+
+use std::collections::BTreeSet;
+
+use crate::application::use_cases::analyzer::lints::call_graph;
+use crate::application::use_cases::analyzer::visitor::AnalyzerVisitor;
+use crate::domain::entities::error::{Diagnostic, Span};
+use crate::domain::entities::hir::{HirBehaviorHeader, HirDiscourse, HirExpression};
+use crate::domain::entities::types::OnuType;
+
+#[derive(Default)]
+pub struct UnusedDefinition {
+    diagnostics: Vec<Diagnostic>,
+    /// Shapes named by some type, with the shape that names them (if any)
+    /// so that a shape mentioning itself does not count.
+    mentioned: BTreeSet<(String, Option<String>)>,
+}
+
+fn shapes_in(typ: &OnuType, found: &mut Vec<String>) {
+    match typ {
+        OnuType::Shape(name) => found.push(name.clone()),
+        OnuType::Tuple(items) => items.iter().for_each(|t| shapes_in(t, found)),
+        OnuType::Array(t) | OnuType::Tree(t) => shapes_in(t, found),
+        OnuType::HashMap(k, v) => {
+            shapes_in(k, found);
+            shapes_in(v, found);
+        }
+        _ => {}
+    }
+}
+
+impl UnusedDefinition {
+    fn mention(&mut self, typ: &OnuType, within: Option<&str>) {
+        let mut found = Vec::new();
+        shapes_in(typ, &mut found);
+        for name in found {
+            self.mentioned.insert((name, within.map(str::to_string)));
+        }
+    }
+
+    fn is_mentioned(&self, shape: &str) -> bool {
+        self.mentioned.iter().any(|(name, within)| name == shape && within.as_deref() != Some(shape))
+    }
+}
+
+impl AnalyzerVisitor for UnusedDefinition {
+    fn visit_program(&mut self, discourses: &[HirDiscourse]) {
+        let is_entry = |name: &str| name == "run" || name == "main";
+        let has_entry = discourses
+            .iter()
+            .any(|d| matches!(d, HirDiscourse::Behavior { header, .. } if is_entry(&header.name)));
+        if !has_entry {
+            return;
+        }
+
+        for discourse in discourses {
+            self.visit_discourse(discourse);
+        }
+        let called: BTreeSet<String> = call_graph(discourses)
+            .into_iter()
+            .flat_map(|(caller, callees)| callees.into_iter().filter(move |callee| *callee != caller))
+            .collect();
+
+        for discourse in discourses {
+            match discourse {
+                HirDiscourse::Behavior { header, .. } if !is_entry(&header.name) && !called.contains(&header.name) => {
+                    self.diagnostics.push(
+                        Diagnostic::warning(Span::default(), format!("Behavior '{}' is never called", header.name))
+                            .with_hint(format!("Remove '{}', or call it from the program", header.name)),
+                    );
+                }
+                HirDiscourse::Shape { name, .. } if !self.is_mentioned(name) => {
+                    self.diagnostics.push(
+                        Diagnostic::warning(Span::default(), format!("Shape '{}' is never used", name))
+                            .with_hint(format!("Remove '{}', or use it in a signature", name)),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_shape(&mut self, discourse: &HirDiscourse) {
+        if let HirDiscourse::Shape { name, fields, .. } = discourse {
+            for field in fields {
+                self.mention(&field.typ, Some(name));
+            }
+        }
+    }
+
+    fn visit_behavior_header(&mut self, header: &HirBehaviorHeader) {
+        for arg in &header.args {
+            self.mention(&arg.typ, None);
+        }
+        self.mention(&header.return_type, None);
+    }
+
+    fn visit_derivation(&mut self, _name: &str, typ: &OnuType, _value: &HirExpression, _body: &HirExpression) {
+        self.mention(typ, None);
+    }
+
+    fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
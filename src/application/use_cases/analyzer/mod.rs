@@ -3,8 +3,10 @@
 /// - `visitor`: the `AnalyzerVisitor` trait (Visitor Pattern interface).
 /// - `semantic_analyzer`: concrete `SemanticAnalyzer` use case (unused-variable
 ///   warnings).
+/// - `lints`: the configurable lint rules run by `onu lint`.
 
 pub mod visitor;
 pub mod semantic_analyzer;
+pub mod lints;
 
 pub use semantic_analyzer::SemanticAnalyzer;
@@ -76,7 +76,7 @@ impl SemanticAnalyzer {
 
     fn flush_behavior_diagnostics(&mut self) {
         for (name, span) in &self.defined {
-            if !self.used.contains(name.as_str()) {
+            if !self.used.contains(name.as_str()) && !name.starts_with('_') {
                 self.diagnostics.push(
                     Diagnostic::warning(span.clone(), format!("Variable '{}' is defined but never used", name))
                         .with_hint(format!("Remove the binding '{}' or prefix it with '_' to silence this warning", name)),
@@ -138,7 +138,7 @@ impl AnalyzerVisitor for SemanticAnalyzer {
 mod tests {
     use super::*;
     use crate::domain::entities::hir::{HirBehaviorHeader, HirArgument, HirLiteral};
-    use crate::domain::entities::types::OnuType;
+    use crate::domain::entities::types::{InlineHint, OnuType};
     use crate::domain::entities::error::Severity;
 
     fn make_behavior(
@@ -150,10 +150,13 @@ mod tests {
             header: HirBehaviorHeader {
                 name: name.to_string(),
                 is_effect: false,
+                intent: String::new(),
                 args,
                 return_type: OnuType::I64,
                 diminishing: vec![],
                 memo_cache_size: None,
+                inlining: InlineHint::Auto,
+                known_behavior: None,
             },
             body,
         }
@@ -69,8 +69,10 @@ pub trait AnalyzerVisitor {
                 self.visit_derivation(name, typ, value, body);
                 self.visit_expression(value);
                 self.visit_expression(body);
+                self.leave_derivation(name);
             }
             HirExpression::If { condition, then_branch, else_branch } => {
+                self.visit_if(condition, then_branch, else_branch);
                 self.visit_expression(condition);
                 self.visit_expression(then_branch);
                 self.visit_expression(else_branch);
@@ -112,6 +114,18 @@ pub trait AnalyzerVisitor {
     ) {
     }
 
+    /// Called once the derivation's body has been walked, so a visitor can
+    /// close the scope `visit_derivation` opened.
+    fn leave_derivation(&mut self, _name: &str) {}
+
+    fn visit_if(
+        &mut self,
+        _condition: &HirExpression,
+        _then_branch: &HirExpression,
+        _else_branch: &HirExpression,
+    ) {
+    }
+
     // ------------------------------------------------------------------
     // Diagnostic collection
     // ------------------------------------------------------------------
@@ -27,6 +27,7 @@
 /// (future `-O2` / `-Oz` modes) can be different functions or structs
 /// passed to the adapter without touching the adapter itself.
 use crate::domain::entities::mir::{MirFunction, MirInstruction};
+use crate::application::use_cases::mir_lowering::lower_big_integer::is_big_integer_helper;
 
 // ---------------------------------------------------------------------------
 // Value types — pure data, no framework dependency
@@ -161,7 +162,14 @@ pub fn derive_profile(func: &MirFunction) -> FunctionCodegenProfile {
     // Rules 3 & 4: pure-data-leaf hints
     let mut hints = Vec::new();
     if func.is_pure_data_leaf {
-        hints.push(OptimizerHint::ReadNone);
+        // Big-integer helpers bump the arena pointer, so a leaf that calls
+        // them still writes memory.
+        let allocates = func.blocks.iter().flat_map(|block| &block.instructions).any(|inst| {
+            matches!(inst, MirInstruction::Call { name, .. } if is_big_integer_helper(name))
+        });
+        if !allocates {
+            hints.push(OptimizerHint::ReadNone);
+        }
         hints.push(OptimizerHint::NoUnwind);
         hints.push(OptimizerHint::NoFree);
         hints.push(OptimizerHint::NoSync);
@@ -192,7 +200,7 @@ mod tests {
     use crate::domain::entities::mir::{
         BasicBlock, MirFunction, MirInstruction, MirLiteral, MirOperand, MirTerminator,
     };
-    use crate::domain::entities::types::OnuType;
+    use crate::domain::entities::types::{InlineHint, OnuType};
 
     /// Builds a minimal MirFunction with the given name, is_pure_data_leaf flag,
     /// and a list of instructions in a single block.  Suitable for policy tests.
@@ -213,6 +221,8 @@ mod tests {
             is_pure_data_leaf,
             diminishing: vec![],
             memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
         }
     }
 
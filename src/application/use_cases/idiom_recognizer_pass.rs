@@ -7,22 +7,56 @@
 ///
 /// ## Recognized Idioms
 ///
-/// ### Rotate Right (rotr32)
+/// ### Rotations (rotr32, rotr64, rotl32, rotl64)
 ///
-/// Detects the pattern:
-///   `(x >> n) | (x << (32 - n))`
+/// Detects the patterns:
+///   `(x >> n) | (x << (W - n))`  and  `(x << n) | (x >> (W - n))`
 ///
-/// and replaces it with `FunnelShiftRight { hi: x, lo: x, amount: n, width: 32 }`,
-/// which emits `@llvm.fshr.i32`.  LLVM maps this to:
-/// - `ror` on x86_64
-/// - `extr` on AArch64
+/// for `W` of 32 or 64, where the 32-bit left shift may be masked with
+/// `& 0xffffffff`.  Right rotations become
+/// `FunnelShiftRight { hi: x, lo: x, amount: n, width: W }`, which emits
+/// `@llvm.fshr.iW`; left rotations with a variable amount become an
+/// `Intrinsic` call to `@llvm.fshl.iW`.  LLVM maps these to:
+/// - `ror` / `rol` on x86_64
+/// - `extr` / `ror` on AArch64
 /// - Software fallback on other targets
 ///
 /// This is critical for SHA-256 performance: each hash round uses 6 rotations,
 /// and replacing 4 instructions with 1 hardware instruction per rotation
 /// eliminates ~192 redundant ALU operations per 64-round hash.
+///
+/// ### Byte Swap (bswap32, bswap64)
+///
+/// An OR of masked, byte-aligned shifts of one value that places every byte
+/// of the low 4 (or all 8) bytes at its mirrored position becomes
+/// `@llvm.bswap.i32` (or `.i64`): one `bswap` / `rev` instruction.
+///
+/// ### Bit-Counting Loops (popcount, bit length, trailing zeros)
+///
+/// After TCO and the accumulator pass, a recursive bit counter is a loop
+/// whose body only shifts or clears `x` and bumps one accumulator.  The
+/// recognizer evaluates the body symbolically and replaces the loop with a
+/// single intrinsic:
+/// - `x == 0` exit, `acc += x & 1`, `x >>= 1`   →  `ctpop(x)`
+/// - `x == 0` exit, `acc += 1`, `x &= x - 1`    →  `ctpop(x)`
+/// - `x == 0` exit, `acc += 1`, `x >>= 1`       →  `64 - ctlz(x)`
+/// - `x & 1 == 1` exit, `acc += 1`, `x >>= 1`   →  `cttz(x)`
+///
+/// `x` must be dead once the loop exits.  The trailing-zero loop never
+/// terminates on zero; the intrinsic yields 64 there.
+///
+/// ### Select Patterns (min, max, abs)
+///
+/// A branch on `a < b` (or `a > b`) whose arms only deliver one of the two
+/// operands becomes `@llvm.smin` / `@llvm.smax`; `x < 0 ? 0 - x : x` becomes
+/// `@llvm.abs`.  Only operands known to be `i64` are matched, and a negation
+/// compiled with overflow checks keeps its branch.
 
+use std::collections::{HashMap, HashSet};
+
+use crate::application::use_cases::inline_pass::max_ssa_in_function;
 use crate::domain::entities::mir::*;
+use crate::domain::entities::types::OnuType;
 
 pub struct IdiomRecognizerPass;
 
@@ -43,13 +77,15 @@ impl IdiomRecognizerPass {
             .into_iter()
             .map(|block| Self::transform_block(block))
             .collect();
-        MirFunction { blocks, ..func }
+        let func = MirFunction { blocks, ..func };
+        let func = Self::recognize_selects(func);
+        Self::recognize_counting_loops(func)
     }
 
     fn transform_block(block: BasicBlock) -> BasicBlock {
         // We do a two-pass scan:
-        // 1. Identify rotation patterns across consecutive instructions.
-        // 2. Replace them with FunnelShiftRight.
+        // 1. Identify rotation and byte-swap patterns across consecutive instructions.
+        // 2. Replace the combining OR with the intrinsic.
 
         let instructions = block.instructions;
         let mut result: Vec<MirInstruction> = Vec::with_capacity(instructions.len());
@@ -70,6 +106,14 @@ impl IdiomRecognizerPass {
                 continue;
             }
 
+            // The shifts and masks feeding a byte swap stay behind as dead
+            // code for LLVM to remove, like the rotation's shifts.
+            if let Some(bswap_inst) = Self::try_recognize_byte_swap(&instructions, i) {
+                result.push(bswap_inst);
+                i += 1;
+                continue;
+            }
+
             result.push(instructions[i].clone());
             i += 1;
         }
@@ -85,9 +129,11 @@ impl IdiomRecognizerPass {
     ///
     /// We look for a BitOr whose two operands come from:
     ///   - a right-shift of X by N
-    ///   - a left-shift of X by (W - N) then AND with mask
+    ///   - a left-shift of X by (W - N), optionally ANDed with a 32-bit mask
     ///
-    /// Returns `Some((FunnelShiftRight instruction, number of instructions consumed))`.
+    /// or from the mirrored left-rotation.
+    ///
+    /// Returns `Some((FunnelShiftRight or fshl instruction, number of instructions consumed))`.
     fn try_recognize_rotation(
         instructions: &[MirInstruction],
         pos: usize,
@@ -122,36 +168,42 @@ impl IdiomRecognizerPass {
         let rhs_inst = Self::find_producer(instructions, pos, rhs_id)?;
 
         // Try both orderings: (shr, shl) and (shl, shr).
-        if let Some(fshr) = Self::match_shr_shl(dest, lhs_inst, rhs_inst) {
+        if let Some(fshr) = Self::match_shr_shl(dest, lhs_inst, rhs_inst, instructions, pos) {
             return Some((fshr, 1));
         }
-        if let Some(fshr) = Self::match_shr_shl(dest, rhs_inst, lhs_inst) {
+        if let Some(fshr) = Self::match_shr_shl(dest, rhs_inst, lhs_inst, instructions, pos) {
             return Some((fshr, 1));
         }
 
         None
     }
 
-    /// Find the instruction in `instructions[0..pos]` that produces SSA `target_id`.
-    fn find_producer<'a>(
-        instructions: &'a [MirInstruction],
+    /// Find the instruction in `instructions[0..pos]` that produces SSA `target_id`,
+    /// looking through plain copies (`Assign` of another variable).
+    ///
+    /// Returns the producer's position along with the instruction.
+    fn find_producer(
+        instructions: &[MirInstruction],
         pos: usize,
         target_id: usize,
-    ) -> Option<&'a MirInstruction> {
+    ) -> Option<(usize, &MirInstruction)> {
         // Search backwards from pos.
-        for i in (0..pos).rev() {
-            let produced = Self::instruction_dest(&instructions[i]);
-            if produced == Some(target_id) {
-                return Some(&instructions[i]);
+        let i = (0..pos)
+            .rev()
+            .find(|&i| Self::instruction_dest(&instructions[i]) == Some(target_id))?;
+        match &instructions[i] {
+            MirInstruction::Assign { src: MirOperand::Variable(id, _), .. } => {
+                Self::find_producer(instructions, i, *id)
             }
+            inst => Some((i, inst)),
         }
-        None
     }
 
     /// Get the destination SSA ID of an instruction, if it produces one.
     fn instruction_dest(inst: &MirInstruction) -> Option<usize> {
         match inst {
             MirInstruction::BinaryOperation { dest, .. }
+            | MirInstruction::CheckedArithmetic { dest, .. }
             | MirInstruction::Assign { dest, .. }
             | MirInstruction::Call { dest, .. }
             | MirInstruction::Alloc { dest, .. }
@@ -165,17 +217,81 @@ impl IdiomRecognizerPass {
             | MirInstruction::Tuple { dest, .. }
             | MirInstruction::GlobalAlloc { dest, .. }
             | MirInstruction::ConstantTableLoad { dest, .. }
-            | MirInstruction::FunnelShiftRight { dest, .. } => Some(*dest),
+            | MirInstruction::FunnelShiftRight { dest, .. }
+            | MirInstruction::Intrinsic { dest, .. } => Some(*dest),
             _ => None,
         }
     }
 
-    /// Match: `shr_inst` is `x >> n` and `shl_inst` is `(x << (W-n)) & mask`.
+    /// SSA variables an instruction reads.
+    fn instruction_reads(inst: &MirInstruction) -> Vec<usize> {
+        let operands: Vec<&MirOperand> = match inst {
+            MirInstruction::Assign { src, .. }
+            | MirInstruction::Promote { src, .. }
+            | MirInstruction::BitCast { src, .. } => vec![src],
+            MirInstruction::BinaryOperation { lhs, rhs, .. }
+            | MirInstruction::CheckedArithmetic { lhs, rhs, .. } => vec![lhs, rhs],
+            MirInstruction::Call { args, .. } | MirInstruction::Intrinsic { args, .. } => {
+                args.iter().collect()
+            }
+            MirInstruction::Tuple { elements, .. } => elements.iter().collect(),
+            MirInstruction::Index { subject, .. } => vec![subject],
+            MirInstruction::Emit(op)
+            | MirInstruction::RuntimeCheck { condition: op, .. }
+            | MirInstruction::RestoreArena { saved: op } => vec![op],
+            MirInstruction::Drop { ssa_var, .. } => return vec![*ssa_var],
+            MirInstruction::Alloc { size_bytes, .. } => vec![size_bytes],
+            MirInstruction::MemCopy { dest, src, size } => vec![dest, src, size],
+            MirInstruction::MemSet { ptr, value, size } => vec![ptr, value, size],
+            MirInstruction::PointerOffset { ptr, offset, .. } => vec![ptr, offset],
+            MirInstruction::Load { ptr, .. } => vec![ptr],
+            MirInstruction::Store { ptr, value } | MirInstruction::TypedStore { ptr, value, .. } => {
+                vec![ptr, value]
+            }
+            MirInstruction::ConstantTableLoad { index, .. } => vec![index],
+            MirInstruction::FunnelShiftRight { hi, lo, amount, .. } => vec![hi, lo, amount],
+            MirInstruction::BufferedWrite { ptr, len } => vec![ptr, len],
+            MirInstruction::GlobalAlloc { .. }
+            | MirInstruction::ClearMemo { .. }
+            | MirInstruction::SaveArena { .. }
+            | MirInstruction::StackAlloc { .. }
+            | MirInstruction::FlushStdout
+            | MirInstruction::MemoCount { .. } => vec![],
+        };
+        operands
+            .into_iter()
+            .filter_map(|op| match op {
+                MirOperand::Variable(id, _) => Some(*id),
+                MirOperand::Constant(_) => None,
+            })
+            .collect()
+    }
+
+    /// True when no instruction in `instructions[from..to]` redefines a
+    /// variable among `operands`, so reading them at `to` sees the same values.
+    fn unchanged_between(
+        instructions: &[MirInstruction],
+        from: usize,
+        to: usize,
+        operands: &[&MirOperand],
+    ) -> bool {
+        instructions[from..to].iter().all(|inst| {
+            let dest = Self::instruction_dest(inst);
+            operands
+                .iter()
+                .all(|op| !matches!(op, MirOperand::Variable(id, _) if Some(*id) == dest))
+        })
+    }
+
+    /// Match: `shr_inst` is `x >> n` and `shl_inst` is `(x << (W-n)) & mask`,
+    /// or the left rotation `x >> (W-n)` / `x << n`.
     /// The AND mask is optional (it may have been folded or may be implicit).
     fn match_shr_shl(
         dest: usize,
-        shr_inst: &MirInstruction,
-        shl_inst: &MirInstruction,
+        (shr_pos, shr_inst): (usize, &MirInstruction),
+        (shl_pos, shl_inst): (usize, &MirInstruction),
+        instructions: &[MirInstruction],
+        pos: usize,
     ) -> Option<MirInstruction> {
         // shr_inst must be a right-shift.
         let (shr_x, shr_n) = match shr_inst {
@@ -189,7 +305,27 @@ impl IdiomRecognizerPass {
         };
 
         // shl_inst might be a direct left-shift or an AND of a left-shift.
-        // Case 1: direct left shift.
+        let (shl_pos, shl_inst, masked) = match shl_inst {
+            // Case 1: direct left shift.
+            MirInstruction::BinaryOperation { op: MirBinOp::Shl, .. } => (shl_pos, shl_inst, false),
+            // Case 2: `(x << k) & 0xffffffff`, the 32-bit rotation written on
+            // an i64 carrier (sha256's rotr32).
+            MirInstruction::BinaryOperation {
+                op: MirBinOp::And,
+                lhs,
+                rhs,
+                ..
+            } => {
+                let shifted = match (lhs, rhs) {
+                    (MirOperand::Variable(id, _), MirOperand::Constant(MirLiteral::I64(0xffff_ffff)))
+                    | (MirOperand::Constant(MirLiteral::I64(0xffff_ffff)), MirOperand::Variable(id, _)) => *id,
+                    _ => return None,
+                };
+                let (inner_pos, inner) = Self::find_producer(instructions, shl_pos, shifted)?;
+                (inner_pos, inner, true)
+            }
+            _ => return None,
+        };
         let (shl_x, shl_amount) = match shl_inst {
             MirInstruction::BinaryOperation {
                 op: MirBinOp::Shl,
@@ -197,33 +333,40 @@ impl IdiomRecognizerPass {
                 rhs,
                 ..
             } => (lhs, rhs),
-            // Case 2: AND masking a left-shift result — this could be part of a
-            // rotation pattern where the mask is `(1 << W) - 1`, but we cannot
-            // easily resolve the intermediate SSA chain without deeper analysis.
-            // Bail and let LLVM's own pattern matcher handle it.
-            MirInstruction::BinaryOperation {
-                op: MirBinOp::And,
-                ..
-            } => {
-                return None;
-            }
             _ => return None,
         };
 
-        // Both shifts must operate on the same source variable.
-        if !Self::same_operand(shr_x, shl_x) {
+        // Both shifts must operate on the same source variable, and neither
+        // the source nor the amounts may change before the OR.
+        if !Self::same_operand(shr_x, shl_x)
+            || !Self::unchanged_between(instructions, shr_pos.min(shl_pos), pos, &[shr_x])
+            || !Self::unchanged_between(instructions, shr_pos, pos, &[shr_n])
+            || !Self::unchanged_between(instructions, shl_pos, pos, &[shl_amount])
+        {
             return None;
         }
 
         // The shift amounts must sum to a power-of-2 width (typically 32 or 64).
-        // Check: shr_n + shl_amount == 32 or 64.
-        let width = Self::detect_rotation_width(shr_n, shl_amount)?;
+        if let Some(width) = Self::detect_rotation_width(shr_n, shl_amount)
+            .or_else(|| Self::complement_width(instructions, shl_pos, shl_amount, shr_n))
+            .filter(|&w| !masked || w == 32)
+        {
+            return Some(MirInstruction::FunnelShiftRight {
+                dest,
+                hi: shr_x.clone(),
+                lo: shr_x.clone(), // rotate: hi == lo
+                amount: shr_n.clone(),
+                width,
+            });
+        }
 
-        Some(MirInstruction::FunnelShiftRight {
+        // Left rotation: the right shift is by `W - n`.
+        let width = Self::complement_width(instructions, shr_pos, shr_n, shl_amount)
+            .filter(|&w| !masked || w == 32)?;
+        Some(MirInstruction::Intrinsic {
             dest,
-            hi: shr_x.clone(),
-            lo: shr_x.clone(),  // rotate: hi == lo
-            amount: shr_n.clone(),
+            intrinsic: MirIntrinsic::FunnelShiftLeft,
+            args: vec![shl_x.clone(), shl_x.clone(), shl_amount.clone()],
             width,
         })
     }
@@ -239,7 +382,6 @@ impl IdiomRecognizerPass {
 
     /// Detect the rotation width from the shift amounts.
     /// If shr_n and shl_amount are both constants that sum to 32 or 64, return that width.
-    /// If only shr_n is a constant, check if there's a `32 - n` or `64 - n` pattern.
     fn detect_rotation_width(shr_n: &MirOperand, shl_amount: &MirOperand) -> Option<u32> {
         match (shr_n, shl_amount) {
             (
@@ -259,4 +401,617 @@ impl IdiomRecognizerPass {
             _ => None,
         }
     }
+
+    /// Width `W` when `amount` (read at `at`) was computed as `W - n` for
+    /// `W` of 32 or 64.
+    fn complement_width(
+        instructions: &[MirInstruction],
+        at: usize,
+        amount: &MirOperand,
+        n: &MirOperand,
+    ) -> Option<u32> {
+        let MirOperand::Variable(id, _) = amount else { return None };
+        let (sub_pos, sub) = Self::find_producer(instructions, at, *id)?;
+        match sub {
+            MirInstruction::BinaryOperation {
+                op: MirBinOp::Sub,
+                lhs: MirOperand::Constant(MirLiteral::I64(w @ (32 | 64))),
+                rhs,
+                ..
+            } if Self::same_operand(rhs, n) && Self::unchanged_between(instructions, sub_pos, at, &[n]) => {
+                Some(*w as u32)
+            }
+            _ => None,
+        }
+    }
+
+    /// Try to match a byte swap rooted at the OR at position `pos`.
+    ///
+    /// Each operand is traced back through byte-aligned shifts, byte masks
+    /// and disjoint ORs to the bytes of a single source value; the swap is
+    /// recognized when the result holds that value's low 4 (or all 8) bytes
+    /// in reverse order and zeros above.
+    fn try_recognize_byte_swap(instructions: &[MirInstruction], pos: usize) -> Option<MirInstruction> {
+        let MirInstruction::BinaryOperation { dest, op: MirBinOp::Or, .. } = &instructions[pos] else {
+            return None;
+        };
+        let (source, lanes) = Self::trace_bytes(instructions, pos, pos)?;
+        let source = source?;
+        let width = if lanes[4..].iter().all(|l| *l == ByteLane::Zero)
+            && (0..4).all(|k| lanes[k] == ByteLane::Source(3 - k as u8))
+        {
+            32
+        } else if (0..8).all(|k| lanes[k] == ByteLane::Source(7 - k as u8)) {
+            64
+        } else {
+            return None;
+        };
+        Some(MirInstruction::Intrinsic {
+            dest: *dest,
+            intrinsic: MirIntrinsic::ByteSwap,
+            args: vec![MirOperand::Variable(source, false)],
+            width,
+        })
+    }
+
+    /// Byte provenance of the value computed at `at`: for each result byte
+    /// (least significant first), zero or a byte of the single source variable.
+    fn trace_bytes(
+        instructions: &[MirInstruction],
+        at: usize,
+        pos: usize,
+    ) -> Option<(Option<usize>, [ByteLane; 8])> {
+        let MirInstruction::BinaryOperation {
+            op,
+            lhs,
+            rhs,
+            dest_type: OnuType::I64,
+            ..
+        } = &instructions[at]
+        else {
+            return None;
+        };
+        match (op, lhs, rhs) {
+            (MirBinOp::Or, MirOperand::Variable(a, _), MirOperand::Variable(b, _)) => {
+                let (src_a, lanes_a) = Self::trace_operand(instructions, at, *a, pos)?;
+                let (src_b, lanes_b) = Self::trace_operand(instructions, at, *b, pos)?;
+                let source = match (src_a, src_b) {
+                    (Some(x), Some(y)) if x != y => return None,
+                    (x, y) => x.or(y),
+                };
+                let mut lanes = [ByteLane::Zero; 8];
+                for k in 0..8 {
+                    lanes[k] = match (lanes_a[k], lanes_b[k]) {
+                        (ByteLane::Zero, other) | (other, ByteLane::Zero) => other,
+                        _ => return None,
+                    };
+                }
+                Some((source, lanes))
+            }
+            (MirBinOp::And, MirOperand::Variable(v, _), MirOperand::Constant(MirLiteral::I64(mask)))
+            | (MirBinOp::And, MirOperand::Constant(MirLiteral::I64(mask)), MirOperand::Variable(v, _)) => {
+                let (source, mut lanes) = Self::trace_operand(instructions, at, *v, pos)?;
+                for (k, lane) in lanes.iter_mut().enumerate() {
+                    match (mask >> (8 * k)) & 0xff {
+                        0xff => {}
+                        0 => *lane = ByteLane::Zero,
+                        _ => return None,
+                    }
+                }
+                Some((source, lanes))
+            }
+            (MirBinOp::Shl | MirBinOp::Shr, MirOperand::Variable(v, _), MirOperand::Constant(MirLiteral::I64(amount)))
+                if (0..64).contains(amount) && amount % 8 == 0 =>
+            {
+                let (source, input) = Self::trace_operand(instructions, at, *v, pos)?;
+                let bytes = (*amount / 8) as usize;
+                let mut lanes = [ByteLane::Zero; 8];
+                for (k, lane) in lanes.iter_mut().enumerate() {
+                    let from = if *op == MirBinOp::Shl { k.checked_sub(bytes) } else { Some(k + bytes) };
+                    if let Some(byte) = from.and_then(|f| input.get(f)) {
+                        *lane = *byte;
+                    }
+                }
+                Some((source, lanes))
+            }
+            _ => None,
+        }
+    }
+
+    /// Byte provenance of variable `var` as read at `at`.  A variable whose
+    /// producer is not a byte shuffle is the source itself, provided it is
+    /// not redefined before the OR at `pos`.
+    fn trace_operand(
+        instructions: &[MirInstruction],
+        at: usize,
+        var: usize,
+        pos: usize,
+    ) -> Option<(Option<usize>, [ByteLane; 8])> {
+        if let Some((producer, _)) = Self::find_producer(instructions, at, var)
+            && let Some(traced) = Self::trace_bytes(instructions, producer, pos)
+        {
+            return Some(traced);
+        }
+        let leaf = MirOperand::Variable(var, false);
+        if !Self::unchanged_between(instructions, at, pos, &[&leaf]) {
+            return None;
+        }
+        let mut lanes = [ByteLane::Zero; 8];
+        for (k, lane) in lanes.iter_mut().enumerate() {
+            *lane = ByteLane::Source(k as u8);
+        }
+        Some((Some(var), lanes))
+    }
+
+    /// Replace `if l < r` branches whose arms only deliver `l`, `r` or
+    /// `0 - x` with smin / smax / abs.  Rewritten branches may expose an
+    /// enclosing one, so matching restarts until nothing changes.
+    fn recognize_selects(mut func: MirFunction) -> MirFunction {
+        let mut next_ssa = max_ssa_in_function(&func) + 1;
+        loop {
+            let i64_vars = Self::i64_variables(&func);
+            let Some((idx, select)) = (0..func.blocks.len())
+                .find_map(|idx| Self::match_select(&func, idx, &i64_vars).map(|s| (idx, s)))
+            else {
+                return func;
+            };
+            let dest = next_ssa;
+            next_ssa += 1;
+
+            let block = &mut func.blocks[idx];
+            block.instructions.push(MirInstruction::Intrinsic {
+                dest,
+                intrinsic: select.intrinsic,
+                args: select.args,
+                width: 64,
+            });
+            block.terminator = match select.exit {
+                ArmExit::Return => MirTerminator::Return(MirOperand::Variable(dest, true)),
+                ArmExit::Merge { var, target } => {
+                    block.instructions.push(MirInstruction::Assign {
+                        dest: var,
+                        src: MirOperand::Variable(dest, false),
+                    });
+                    MirTerminator::Branch(target)
+                }
+            };
+            func.blocks.retain(|b| b.id != select.then_block && b.id != select.else_block);
+        }
+    }
+
+    fn match_select(func: &MirFunction, idx: usize, i64_vars: &HashSet<usize>) -> Option<Select> {
+        let block = &func.blocks[idx];
+        let MirTerminator::CondBranch {
+            condition: MirOperand::Variable(cond, _),
+            then_block,
+            else_block,
+        } = &block.terminator
+        else {
+            return None;
+        };
+        if then_block == else_block || [*then_block, *else_block].contains(&block.id) {
+            return None;
+        }
+
+        // Normalize the comparison to `l < r`.
+        let cmp_pos = block
+            .instructions
+            .iter()
+            .rposition(|inst| Self::instruction_dest(inst) == Some(*cond))?;
+        let (l, r) = match &block.instructions[cmp_pos] {
+            MirInstruction::BinaryOperation { op: MirBinOp::Lt, lhs, rhs, .. } => (lhs, rhs),
+            MirInstruction::BinaryOperation { op: MirBinOp::Gt, lhs, rhs, .. } => (rhs, lhs),
+            _ => return None,
+        };
+        if !Self::is_i64(l, i64_vars)
+            || !Self::is_i64(r, i64_vars)
+            || !Self::unchanged_between(&block.instructions, cmp_pos, block.instructions.len(), &[l, r])
+        {
+            return None;
+        }
+
+        let arm = |id: usize| {
+            let arm_block = func.blocks.iter().find(|b| b.id == id)?;
+            if Self::predecessors(func, id) != [block.id] {
+                return None;
+            }
+            Self::select_arm(func, arm_block)
+        };
+        let (then_value, then_exit) = arm(*then_block)?;
+        let (else_value, else_exit) = arm(*else_block)?;
+        if then_exit != else_exit {
+            return None;
+        }
+
+        let is_zero = |op: &MirOperand| *op == MirOperand::Constant(MirLiteral::I64(0));
+        let (intrinsic, args) = match (&then_value, &else_value) {
+            (ArmValue::Plain(a), ArmValue::Plain(b)) if Self::same_operand(a, l) && Self::same_operand(b, r) => {
+                (MirIntrinsic::SignedMin, vec![l.clone(), r.clone()])
+            }
+            (ArmValue::Plain(a), ArmValue::Plain(b)) if Self::same_operand(a, r) && Self::same_operand(b, l) => {
+                (MirIntrinsic::SignedMax, vec![l.clone(), r.clone()])
+            }
+            // x < 0 ? 0 - x : x
+            (ArmValue::Negated(a), ArmValue::Plain(b))
+                if is_zero(r) && Self::same_operand(a, l) && Self::same_operand(b, l) =>
+            {
+                (MirIntrinsic::Abs, vec![l.clone()])
+            }
+            // 0 < x ? x : 0 - x
+            (ArmValue::Plain(a), ArmValue::Negated(b))
+                if is_zero(l) && Self::same_operand(a, r) && Self::same_operand(b, r) =>
+            {
+                (MirIntrinsic::Abs, vec![r.clone()])
+            }
+            _ => return None,
+        };
+        Some(Select {
+            intrinsic,
+            args,
+            exit: then_exit,
+            then_block: *then_block,
+            else_block: *else_block,
+        })
+    }
+
+    /// The value a select arm delivers and where it delivers it.  An arm is
+    /// empty apart from an optional `0 - x` and the copy into the merge
+    /// variable.
+    fn select_arm(func: &MirFunction, block: &BasicBlock) -> Option<(ArmValue, ArmExit)> {
+        let (delivered, rest, exit) = match (&block.terminator, block.instructions.split_last()) {
+            (MirTerminator::Return(op), _) => (op, &block.instructions[..], ArmExit::Return),
+            (MirTerminator::Branch(target), Some((MirInstruction::Assign { dest, src }, rest))) => {
+                (src, rest, ArmExit::Merge { var: *dest, target: *target })
+            }
+            _ => return None,
+        };
+        match rest {
+            [] => Some((ArmValue::Plain(delivered.clone()), exit)),
+            [MirInstruction::BinaryOperation {
+                dest,
+                op: MirBinOp::Sub,
+                lhs: MirOperand::Constant(MirLiteral::I64(0)),
+                rhs,
+                dest_type: OnuType::I64,
+            }] if matches!(delivered, MirOperand::Variable(id, _) if id == dest)
+                && !Self::read_outside(func, *dest, &[block.id]) =>
+            {
+                Some((ArmValue::Negated(rhs.clone()), exit))
+            }
+            _ => None,
+        }
+    }
+
+    /// Variables whose every definition is an `i64`: integer arguments,
+    /// integer arithmetic, and copies of those.
+    fn i64_variables(func: &MirFunction) -> HashSet<usize> {
+        let mut defs: HashMap<usize, Vec<Option<&MirOperand>>> = HashMap::new();
+        let mut other: HashSet<usize> = HashSet::new();
+        for arg in &func.args {
+            if arg.typ == OnuType::I64 {
+                defs.entry(arg.ssa_var).or_default().push(None);
+            } else {
+                other.insert(arg.ssa_var);
+            }
+        }
+        for inst in func.blocks.iter().flat_map(|b| b.instructions.iter()) {
+            match inst {
+                MirInstruction::BinaryOperation { dest, dest_type: OnuType::I64, .. }
+                | MirInstruction::CheckedArithmetic { dest, dest_type: OnuType::I64, .. }
+                | MirInstruction::FunnelShiftRight { dest, .. }
+                | MirInstruction::Intrinsic { dest, .. } => defs.entry(*dest).or_default().push(None),
+                MirInstruction::Assign { dest, src } => defs.entry(*dest).or_default().push(Some(src)),
+                _ => {
+                    if let Some(dest) = Self::instruction_dest(inst) {
+                        other.insert(dest);
+                    }
+                }
+            }
+        }
+
+        let mut known = HashSet::new();
+        loop {
+            let before = known.len();
+            for (var, var_defs) in &defs {
+                if !other.contains(var)
+                    && !known.contains(var)
+                    && var_defs.iter().all(|d| d.is_none_or(|op| Self::is_i64(op, &known)))
+                {
+                    known.insert(*var);
+                }
+            }
+            if known.len() == before {
+                return known;
+            }
+        }
+    }
+
+    fn is_i64(op: &MirOperand, i64_vars: &HashSet<usize>) -> bool {
+        match op {
+            MirOperand::Constant(lit) => matches!(lit, MirLiteral::I64(_)),
+            MirOperand::Variable(id, _) => i64_vars.contains(id),
+        }
+    }
+
+    /// Replace bit-counting loops (see the module docs) with one intrinsic
+    /// in the loop header, which then jumps straight to the exit.
+    fn recognize_counting_loops(mut func: MirFunction) -> MirFunction {
+        let mut next_ssa = max_ssa_in_function(&func) + 1;
+        while let Some(found) = (0..func.blocks.len()).find_map(|idx| Self::match_counting_loop(&func, idx)) {
+            let (count, sum) = (next_ssa, next_ssa + 1);
+            next_ssa += 2;
+            let x = MirOperand::Variable(found.x, false);
+            let mut instructions = Vec::new();
+            let counted = match found.count {
+                BitCount::PopCount | BitCount::TrailingZeros => {
+                    let intrinsic = if found.count == BitCount::PopCount {
+                        MirIntrinsic::PopCount
+                    } else {
+                        MirIntrinsic::TrailingZeros
+                    };
+                    instructions.push(MirInstruction::Intrinsic { dest: count, intrinsic, args: vec![x], width: 64 });
+                    count
+                }
+                BitCount::BitLength => {
+                    let length = next_ssa;
+                    next_ssa += 1;
+                    instructions.push(MirInstruction::Intrinsic {
+                        dest: count,
+                        intrinsic: MirIntrinsic::LeadingZeros,
+                        args: vec![x],
+                        width: 64,
+                    });
+                    instructions.push(MirInstruction::BinaryOperation {
+                        dest: length,
+                        op: MirBinOp::Sub,
+                        lhs: MirOperand::Constant(MirLiteral::I64(64)),
+                        rhs: MirOperand::Variable(count, false),
+                        dest_type: OnuType::I64,
+                    });
+                    length
+                }
+            };
+            instructions.push(MirInstruction::BinaryOperation {
+                dest: sum,
+                op: MirBinOp::Add,
+                lhs: MirOperand::Variable(found.acc, false),
+                rhs: MirOperand::Variable(counted, false),
+                dest_type: OnuType::I64,
+            });
+            instructions.push(MirInstruction::Assign {
+                dest: found.acc,
+                src: MirOperand::Variable(sum, false),
+            });
+
+            if let Some(header) = func.blocks.iter_mut().find(|b| b.id == found.header) {
+                header.instructions = instructions;
+                header.terminator = MirTerminator::Branch(found.exit);
+            }
+            func.blocks.retain(|b| b.id != found.body);
+        }
+        func
+    }
+
+    /// Match a header holding only the loop test and a body that jumps
+    /// straight back to it, then evaluate the body symbolically.
+    fn match_counting_loop(func: &MirFunction, idx: usize) -> Option<CountingLoop> {
+        let header = &func.blocks[idx];
+        let MirTerminator::CondBranch {
+            condition: MirOperand::Variable(cond, _),
+            then_block,
+            else_block,
+        } = &header.terminator
+        else {
+            return None;
+        };
+
+        let (x, test, exit_when_true) = match &header.instructions[..] {
+            [MirInstruction::BinaryOperation {
+                dest,
+                op: op @ (MirBinOp::Eq | MirBinOp::Ne),
+                lhs: MirOperand::Variable(x, _),
+                rhs: MirOperand::Constant(MirLiteral::I64(0)),
+                ..
+            }] if dest == cond => (*x, LoopTest::Zero, *op == MirBinOp::Eq),
+            [MirInstruction::BinaryOperation {
+                dest: bit,
+                op: MirBinOp::And,
+                lhs: MirOperand::Variable(x, _),
+                rhs: MirOperand::Constant(MirLiteral::I64(1)),
+                dest_type: OnuType::I64,
+            }, MirInstruction::BinaryOperation {
+                dest,
+                op: op @ (MirBinOp::Eq | MirBinOp::Ne),
+                lhs: MirOperand::Variable(tested, _),
+                rhs: MirOperand::Constant(MirLiteral::I64(b @ (0 | 1))),
+                ..
+            }] if dest == cond && tested == bit => {
+                (*x, LoopTest::LowBitSet, (*op == MirBinOp::Eq) == (*b == 1))
+            }
+            _ => return None,
+        };
+        let (exit, body_id) = if exit_when_true {
+            (*then_block, *else_block)
+        } else {
+            (*else_block, *then_block)
+        };
+        if body_id == header.id || exit == header.id || exit == body_id {
+            return None;
+        }
+        let body = func.blocks.iter().find(|b| b.id == body_id)?;
+        if body.terminator != MirTerminator::Branch(header.id) || Self::predecessors(func, body_id) != [header.id] {
+            return None;
+        }
+        if header
+            .instructions
+            .iter()
+            .filter_map(Self::instruction_dest)
+            .any(|d| Self::read_outside(func, d, &[header.id]))
+        {
+            return None;
+        }
+
+        // The body may read only `x` and one accumulator from outside.
+        let mut written = HashSet::new();
+        let mut inputs = HashSet::new();
+        for inst in &body.instructions {
+            for var in Self::instruction_reads(inst) {
+                if !written.contains(&var) {
+                    inputs.insert(var);
+                }
+            }
+            written.extend(Self::instruction_dest(inst));
+        }
+        inputs.remove(&x);
+        let acc = match inputs.into_iter().collect::<Vec<_>>()[..] {
+            [acc] => acc,
+            _ => return None,
+        };
+        if !written.contains(&x) || !written.contains(&acc) {
+            return None;
+        }
+        let loop_blocks = [header.id, body_id];
+        if Self::read_outside(func, x, &loop_blocks)
+            || written
+                .iter()
+                .filter(|&&v| v != x && v != acc)
+                .any(|&v| Self::read_outside(func, v, &[body_id]))
+        {
+            return None;
+        }
+
+        let mut env: HashMap<usize, Sym> = HashMap::from([(x, Sym::X), (acc, Sym::Acc)]);
+        let eval = |env: &HashMap<usize, Sym>, op: &MirOperand| match op {
+            MirOperand::Variable(id, _) => env.get(id).cloned(),
+            MirOperand::Constant(MirLiteral::I64(k)) => Some(Sym::Const(*k)),
+            MirOperand::Constant(_) => None,
+        };
+        for inst in &body.instructions {
+            let (dest, value) = match inst {
+                MirInstruction::BinaryOperation {
+                    dest,
+                    op: op @ (MirBinOp::Add | MirBinOp::Sub | MirBinOp::And | MirBinOp::Shr),
+                    lhs,
+                    rhs,
+                    dest_type: OnuType::I64,
+                } => (*dest, Sym::op(op.clone(), eval(&env, lhs)?, eval(&env, rhs)?)),
+                MirInstruction::Assign { dest, src } => (*dest, eval(&env, src)?),
+                _ => return None,
+            };
+            env.insert(dest, value);
+        }
+
+        let one = || Sym::Const(1);
+        let either = |op: MirBinOp, a: Sym, b: Sym| [Sym::op(op.clone(), a.clone(), b.clone()), Sym::op(op, b, a)];
+        let halved = Sym::op(MirBinOp::Shr, Sym::X, one());
+        let cleared = either(MirBinOp::And, Sym::X, Sym::op(MirBinOp::Sub, Sym::X, one()));
+        let bumped_by = |inc: Sym| either(MirBinOp::Add, Sym::Acc, inc).contains(&env[&acc]);
+        let bumped_by_low_bit = either(MirBinOp::And, Sym::X, one()).into_iter().any(bumped_by);
+
+        let next_x = &env[&x];
+        let count = match test {
+            LoopTest::Zero if *next_x == halved && bumped_by_low_bit => BitCount::PopCount,
+            LoopTest::Zero if cleared.contains(next_x) && bumped_by(one()) => BitCount::PopCount,
+            LoopTest::Zero if *next_x == halved && bumped_by(one()) => BitCount::BitLength,
+            LoopTest::LowBitSet if *next_x == halved && bumped_by(one()) => BitCount::TrailingZeros,
+            _ => return None,
+        };
+        Some(CountingLoop { header: header.id, body: body_id, exit, x, acc, count })
+    }
+
+    /// Ids of the blocks whose terminator can jump to block `id`.
+    fn predecessors(func: &MirFunction, id: usize) -> Vec<usize> {
+        func.blocks
+            .iter()
+            .filter(|b| match &b.terminator {
+                MirTerminator::Branch(target) => *target == id,
+                MirTerminator::CondBranch { then_block, else_block, .. } => *then_block == id || *else_block == id,
+                _ => false,
+            })
+            .map(|b| b.id)
+            .collect()
+    }
+
+    /// True when `var` is read anywhere outside the blocks in `within`.
+    fn read_outside(func: &MirFunction, var: usize, within: &[usize]) -> bool {
+        func.blocks.iter().filter(|b| !within.contains(&b.id)).any(|b| {
+            b.instructions.iter().any(|inst| Self::instruction_reads(inst).contains(&var))
+                || matches!(
+                    &b.terminator,
+                    MirTerminator::Return(MirOperand::Variable(id, _))
+                    | MirTerminator::CondBranch { condition: MirOperand::Variable(id, _), .. } if *id == var
+                )
+        })
+    }
+}
+
+/// Where a byte of a traced value comes from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ByteLane {
+    Zero,
+    /// Byte `k` (least significant first) of the source value.
+    Source(u8),
+}
+
+/// A branch recognized as smin / smax / abs.
+struct Select {
+    intrinsic: MirIntrinsic,
+    args: Vec<MirOperand>,
+    exit: ArmExit,
+    then_block: usize,
+    else_block: usize,
+}
+
+enum ArmValue {
+    Plain(MirOperand),
+    /// `0 - operand`.
+    Negated(MirOperand),
+}
+
+#[derive(PartialEq)]
+enum ArmExit {
+    Return,
+    /// Copy into `var` and jump to the merge block `target`.
+    Merge { var: usize, target: usize },
+}
+
+#[derive(Clone, Copy)]
+enum LoopTest {
+    /// Exit once `x == 0`.
+    Zero,
+    /// Exit once `x & 1 == 1`.
+    LowBitSet,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum BitCount {
+    PopCount,
+    /// `64 - ctlz(x)`: the number of significant bits.
+    BitLength,
+    TrailingZeros,
+}
+
+struct CountingLoop {
+    header: usize,
+    body: usize,
+    exit: usize,
+    x: usize,
+    acc: usize,
+    count: BitCount,
+}
+
+/// Symbolic value of a loop variable after one pass of the loop body, in
+/// terms of the values `X` and `Acc` it started with.
+#[derive(Debug, Clone, PartialEq)]
+enum Sym {
+    X,
+    Acc,
+    Const(i64),
+    Op(MirBinOp, Box<Sym>, Box<Sym>),
+}
+
+impl Sym {
+    fn op(op: MirBinOp, lhs: Sym, rhs: Sym) -> Sym {
+        Sym::Op(op, Box::new(lhs), Box::new(rhs))
+    }
 }
@@ -1,12 +1,20 @@
 /// Inline Pass: Application Use Case Layer
 ///
 /// # What This Does
-/// Expands `is_pure_data_leaf` function bodies directly at their call sites
-/// in the MIR, before codegen. When a function A calls a pure function B,
-/// B's instructions are copied into A's basic blocks, eliminating the function
+/// Expands callee bodies directly at their call sites in the MIR, before
+/// codegen. When a function A calls an inlineable function B, B's
+/// instructions are copied into A's basic blocks, eliminating the function
 /// call overhead and enabling LLVM to apply whole-loop optimizations across
 /// the fused body.
 ///
+/// A callee is inlineable when it is a pure leaf (`is_pure_data_leaf`, or
+/// only calls other inlineable functions), or when it is not recursive and
+/// its body fits `INLINE_BUDGET` instructions. The second rule admits small
+/// effectful helpers such as `read-tape` / `write-tape`. A behavior's
+/// `with inlining: always` lifts the budget; `with inlining: never` keeps
+/// every call to it, as does `with known behavior:` so that codegen can
+/// route the call to a hardware kernel.
+///
 /// # Why This Matters
 /// LLVM's `alwaysinline` attribute is advisory — the inliner uses a cost model
 /// that can refuse to inline even marked functions when calling conventions or
@@ -17,26 +25,49 @@
 /// that appear in the merged function body, producing a single tight loop.
 ///
 /// # Safety Guarantees
-/// - Only pure leaf functions (`is_pure_data_leaf = true`) are expanded.
-/// - Functions with `Drop`, `Emit`, `Alloc`, or `Store` are never inlined
-///   (the `is_pure_data_leaf` flag is already false for those).
+/// - Recursive functions (any call cycle) are only expanded once TcoPass has
+///   loop-lowered their self calls; cost-model candidates must be acyclic.
+/// - Instructions are copied in order, so `Emit`, `Drop` and `Alloc` keep
+///   their sequence relative to the caller's surrounding code.
+/// - A callee that allocates gets the `SaveArena` / `RestoreArena` pair that
+///   `LifetimePass` would have given it as a separate function, so its
+///   arena memory is still reclaimed when the inlined body finishes.
 /// - SSA variables are remapped to avoid conflicts with the caller's namespace.
 /// - Block IDs are remapped to avoid conflicts with the caller's CFG.
 ///
 /// # Pattern Used: Pipeline Pass (Pure Function over Value Types)
 /// `InlinePass::run` is a pure function: it consumes a `MirProgram` and
 /// returns a transformed one. No shared state, no side effects.
+use crate::domain::entities::error::{Diagnostic, Span};
 use crate::domain::entities::mir::{
     BasicBlock, MirFunction, MirInstruction, MirOperand, MirProgram, MirTerminator,
 };
-use std::collections::HashMap;
+use crate::domain::entities::types::InlineHint;
+use std::collections::{HashMap, HashSet};
+
+/// Largest body (instructions plus terminators) the cost model inlines
+/// without a `with inlining: always` override.
+pub const INLINE_BUDGET: usize = 32;
 
 pub struct InlinePass;
 
+/// Calls to a behavior opted out with `with inlining: never`, or tagged with
+/// a `KnownBehavior`, are never expanded.
+fn keeps_calls(func: &MirFunction) -> bool {
+    func.inlining == InlineHint::Never || func.known_behavior.is_some()
+}
+
 impl InlinePass {
     /// Entry point: transform an entire program.
     /// Non-inlineable calls pass through unchanged (identity for those sites).
     pub fn run(program: MirProgram) -> MirProgram {
+        Self::run_with_diagnostics(program).0
+    }
+
+    /// As [`run`](Self::run), also returning a warning for each recursive
+    /// behavior whose `with inlining: always` had to be ignored.
+    pub fn run_with_diagnostics(program: MirProgram) -> (MirProgram, Vec<Diagnostic>) {
+        let mut diagnostics = vec![];
         // Step 1: Seed the inlineable set with directly-marked pure leaf functions.
         // Transitive purity: a function is also inlineable if every non-extern call it
         // makes targets another inlineable function. We propagate until stable (fixed-point).
@@ -53,8 +84,8 @@ impl InlinePass {
             .functions
             .iter()
             .filter(|f| {
-                if !f.is_pure_data_leaf {
-                    return false; // Not pure — hard no.
+                if !f.is_pure_data_leaf || keeps_calls(f) {
+                    return false; // Not pure, or opted out — hard no.
                 }
                 // Exclude functions that call themselves.
                 // A self-recursive function cannot be expanded at its own call sites
@@ -83,8 +114,8 @@ impl InlinePass {
         loop {
             let mut changed = false;
             for func in &program.functions {
-                if inlineable.contains(&func.name) {
-                    continue; // Already accepted.
+                if inlineable.contains(&func.name) || keeps_calls(func) {
+                    continue; // Already accepted, or opted out.
                 }
                 // Hard guard 1: visible side effects — never inline.
                 let has_side_effects = func.blocks.iter().any(|b| {
@@ -133,6 +164,32 @@ impl InlinePass {
             }
         }
 
+        // Step 2: Cost model. Effectful helpers are inlined when they are small
+        // and cannot reach themselves through any chain of calls. Added after
+        // the fixed point so callers of these helpers do not become
+        // "transitively pure" regardless of their own size.
+        let recursive = recursive_functions(&program);
+        for func in &program.functions {
+            if inlineable.contains(&func.name) || keeps_calls(func) {
+                continue;
+            }
+            if recursive.contains(&func.name) {
+                if func.inlining == InlineHint::Always {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            Span::default(),
+                            format!("'{}' is recursive; ignoring `with inlining: always`", func.name),
+                        )
+                        .with_hint("remove the hint, or make the recursion a tail call so it becomes a loop"),
+                    );
+                }
+                continue;
+            }
+            if func.inlining == InlineHint::Always || inline_cost(func) <= INLINE_BUDGET {
+                inlineable.insert(func.name.clone());
+            }
+        }
+
         // Build the inline map: only inline in-module pure-or-transitively-pure functions.
         // We exclude self-recursive functions from being inlined into themselves
         // (TcoPass handles self-recursion separately).
@@ -148,13 +205,14 @@ impl InlinePass {
             inlineable.iter().collect::<Vec<_>>()
         );
 
-        MirProgram {
+        let program = MirProgram {
             functions: program
                 .functions
                 .into_iter()
                 .map(|f| Self::inline_into(f, &pure_functions))
                 .collect(),
-        }
+        };
+        (program, diagnostics)
     }
 
     /// Expand all inlineable call sites inside `caller`.
@@ -224,7 +282,8 @@ impl InlinePass {
                 let callee = pure_functions.get(&call_name).unwrap();
 
                 // Compute safe SSA and block ID offsets that clear the caller's namespace.
-                let ssa_offset = max_ssa_in_function(&caller) + 1;
+                // The call was already removed, so its dest must be counted explicitly.
+                let ssa_offset = max_ssa_in_function(&caller).max(call_dest) + 1;
                 let block_offset = caller.blocks.iter().map(|b| b.id).max().unwrap_or(0) + 1;
 
                 // Continuation block: takes instructions after the call and the original terminator.
@@ -241,6 +300,13 @@ impl InlinePass {
                     terminator: original_terminator,
                 };
 
+                // An allocating callee keeps its own arena scope, exactly as
+                // LifetimePass would give it when called.
+                let allocates = callee.blocks.iter().any(|b| {
+                    b.instructions.iter().any(|inst| matches!(inst, MirInstruction::Alloc { .. }))
+                });
+                let arena_slot = allocates.then(|| ssa_offset + max_ssa_in_function(callee) + 1);
+
                 // Remap and prepare callee blocks.
                 let mut inlined_blocks = remap_callee(
                     callee,
@@ -249,6 +315,7 @@ impl InlinePass {
                     ssa_offset,
                     block_offset,
                     continuation_id,
+                    arena_slot,
                 );
 
                 // Point the current block end at the callee entry.
@@ -284,6 +351,7 @@ impl InlinePass {
 /// - Remap all block IDs: `original + block_offset`
 /// - Prepend Assign instructions binding callee arg SSA → caller call args.
 /// - Replace Return terminators with Assign(call_dest, return_val) + Branch(continuation).
+/// - With an `arena_slot`, open an arena scope on entry and close it on every exit.
 fn remap_callee(
     callee: &MirFunction,
     call_args: &[MirOperand],
@@ -291,6 +359,7 @@ fn remap_callee(
     ssa_offset: usize,
     block_offset: usize,
     continuation_id: usize,
+    arena_slot: Option<usize>,
 ) -> Vec<BasicBlock> {
     let mut blocks = Vec::with_capacity(callee.blocks.len());
 
@@ -315,6 +384,9 @@ fn remap_callee(
                     src,
                 });
             }
+            if let Some(slot) = arena_slot {
+                instructions.push(MirInstruction::SaveArena { dest: slot });
+            }
         }
 
         // Remap all instructions, offsetting SSA IDs.
@@ -331,6 +403,11 @@ fn remap_callee(
                     dest: call_dest,
                     src: return_val,
                 });
+                if let Some(slot) = arena_slot {
+                    instructions.push(MirInstruction::RestoreArena {
+                        saved: MirOperand::Variable(slot, false),
+                    });
+                }
                 MirTerminator::Branch(continuation_id)
             }
             MirTerminator::Branch(target) => MirTerminator::Branch(target + block_offset),
@@ -357,7 +434,7 @@ fn remap_callee(
 }
 
 /// Remap a single MIR instruction: shift all destination and source SSA IDs.
-fn remap_instruction(inst: &MirInstruction, ssa_offset: usize) -> MirInstruction {
+pub(crate) fn remap_instruction(inst: &MirInstruction, ssa_offset: usize) -> MirInstruction {
     match inst {
         MirInstruction::Assign { dest, src } => MirInstruction::Assign {
             dest: dest + ssa_offset,
@@ -370,6 +447,18 @@ fn remap_instruction(inst: &MirInstruction, ssa_offset: usize) -> MirInstruction
             rhs: remap_operand(rhs, ssa_offset),
             dest_type: dest_type.clone(),
         },
+        MirInstruction::CheckedArithmetic { dest, op, lhs, rhs, dest_type, site } => MirInstruction::CheckedArithmetic {
+            dest: dest + ssa_offset,
+            op: op.clone(),
+            lhs: remap_operand(lhs, ssa_offset),
+            rhs: remap_operand(rhs, ssa_offset),
+            dest_type: dest_type.clone(),
+            site: site.clone(),
+        },
+        MirInstruction::RuntimeCheck { condition, message } => MirInstruction::RuntimeCheck {
+            condition: remap_operand(condition, ssa_offset),
+            message: message.clone(),
+        },
         MirInstruction::Call {
             dest,
             name,
@@ -410,6 +499,7 @@ fn remap_instruction(inst: &MirInstruction, ssa_offset: usize) -> MirInstruction
             size_bytes: *size_bytes,
             name: name.clone(),
         },
+        MirInstruction::ClearMemo { function } => MirInstruction::ClearMemo { function: function.clone() },
         MirInstruction::PointerOffset { dest, ptr, offset } => MirInstruction::PointerOffset {
             dest: dest + ssa_offset,
             ptr: remap_operand(ptr, ssa_offset),
@@ -484,16 +574,26 @@ fn remap_instruction(inst: &MirInstruction, ssa_offset: usize) -> MirInstruction
             amount: remap_operand(amount, ssa_offset),
             width: *width,
         },
+        MirInstruction::Intrinsic { dest, intrinsic, args, width } => MirInstruction::Intrinsic {
+            dest: dest + ssa_offset,
+            intrinsic: *intrinsic,
+            args: args.iter().map(|a| remap_operand(a, ssa_offset)).collect(),
+            width: *width,
+        },
         MirInstruction::BufferedWrite { ptr, len } => MirInstruction::BufferedWrite {
             ptr: remap_operand(ptr, ssa_offset),
             len: remap_operand(len, ssa_offset),
         },
         MirInstruction::FlushStdout => MirInstruction::FlushStdout,
+        MirInstruction::MemoCount { function, counter } => MirInstruction::MemoCount {
+            function: function.clone(),
+            counter: *counter,
+        },
     }
 }
 
 /// Remap a single MIR operand: offset Variable SSA IDs, leave Constants unchanged.
-fn remap_operand(op: &MirOperand, ssa_offset: usize) -> MirOperand {
+pub(crate) fn remap_operand(op: &MirOperand, ssa_offset: usize) -> MirOperand {
     match op {
         MirOperand::Variable(id, consuming) => MirOperand::Variable(id + ssa_offset, *consuming),
         MirOperand::Constant(lit) => MirOperand::Constant(lit.clone()),
@@ -502,13 +602,14 @@ fn remap_operand(op: &MirOperand, ssa_offset: usize) -> MirOperand {
 
 /// Find the maximum SSA variable ID used anywhere in a function.
 /// Used to compute a safe offset for remapped callee SSA vars.
-fn max_ssa_in_function(func: &MirFunction) -> usize {
+pub(crate) fn max_ssa_in_function(func: &MirFunction) -> usize {
     let mut max = func.args.iter().map(|a| a.ssa_var).max().unwrap_or(0);
     for block in &func.blocks {
         for inst in &block.instructions {
             let dest: Option<usize> = match inst {
                 MirInstruction::Assign { dest, .. } => Some(*dest),
                 MirInstruction::BinaryOperation { dest, .. } => Some(*dest),
+                MirInstruction::CheckedArithmetic { dest, .. } => Some(*dest),
                 MirInstruction::Call { dest, .. } => Some(*dest),
                 MirInstruction::Tuple { dest, .. } => Some(*dest),
                 MirInstruction::Index { dest, .. } => Some(*dest),
@@ -519,6 +620,9 @@ fn max_ssa_in_function(func: &MirFunction) -> usize {
                 MirInstruction::Promote { dest, .. } => Some(*dest),
                 MirInstruction::BitCast { dest, .. } => Some(*dest),
                 MirInstruction::ConstantTableLoad { dest, .. } => Some(*dest),
+                MirInstruction::SaveArena { dest } => Some(*dest),
+                MirInstruction::FunnelShiftRight { dest, .. } => Some(*dest),
+                MirInstruction::Intrinsic { dest, .. } => Some(*dest),
                 _ => None,
             };
             if let Some(d) = dest {
@@ -530,3 +634,50 @@ fn max_ssa_in_function(func: &MirFunction) -> usize {
     }
     max
 }
+
+/// Size of a function body for the inlining budget: one per instruction and
+/// one per terminator.
+fn inline_cost(func: &MirFunction) -> usize {
+    func.blocks.iter().map(|b| b.instructions.len() + 1).sum()
+}
+
+/// Functions that can reach themselves through in-module calls, directly or
+/// through any chain of other functions.
+fn recursive_functions(program: &MirProgram) -> HashSet<String> {
+    let callees: HashMap<&str, Vec<&str>> = program
+        .functions
+        .iter()
+        .map(|f| {
+            let names = f
+                .blocks
+                .iter()
+                .flat_map(|b| b.instructions.iter())
+                .filter_map(|inst| match inst {
+                    MirInstruction::Call { name, .. } => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            (f.name.as_str(), names)
+        })
+        .collect();
+    program
+        .functions
+        .iter()
+        .filter(|f| {
+            let mut seen = HashSet::new();
+            let mut stack = callees[f.name.as_str()].clone();
+            while let Some(name) = stack.pop() {
+                if name == f.name {
+                    return true;
+                }
+                if seen.insert(name)
+                    && let Some(next) = callees.get(name)
+                {
+                    stack.extend(next.iter().copied());
+                }
+            }
+            false
+        })
+        .map(|f| f.name.clone())
+        .collect()
+}
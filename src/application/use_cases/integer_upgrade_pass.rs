@@ -1,43 +1,53 @@
 /// Integer Upgrade Pass: Application Use Case Layer
 ///
 /// # What This Does
-/// Automatically promotes the return type of doubly-recursive pure functions
-/// from `I64` to `WideInt(bits)` when the maximum literal call-site argument
-/// implies that the true mathematical result would overflow a 64-bit integer.
+/// Automatically promotes the return type of recursive pure integer functions
+/// from `I64` to `WideInt(bits)` when the values they produce for their
+/// literal call sites would overflow a 64-bit integer.
 ///
-/// The canonical example is `fib-naive`: `fib(93)` already exceeds `i64::MAX`,
-/// so any call with `n > 92` needs a wider type to produce the correct answer.
+/// `factorial 30` is about 2¹⁰⁸ and `fib-naive 93` already exceeds
+/// `i64::MAX`; both need a wider type to produce the correct answer.
 ///
 /// # Algorithm
-/// 1. **Detect upgrade candidates** — functions that are:
-///    - `is_pure_data_leaf`, `diminishing.is_some()`, single `I64` argument,
-///      `I64` return type, and at least two recursive self-calls (doubly recursive).
-/// 2. **Find max literal call argument** across all functions in the program.
-/// 3. **Compute required bit width** using `ceil(n * log₂(φ) + 4)` rounded to
-///    the next multiple of 64 (φ ≈ 1.618, log₂(φ) ≈ 0.6942).
-/// 4. **Rewrite the function body** via backward SSA analysis from `Return`
+/// 1. **Detect upgrade candidates** — functions that are `is_pure_data_leaf`,
+///    take only `I64` arguments, return `I64` and call themselves.
+/// 2. **Collect literal call sites** — every call from another function whose
+///    arguments are constants, directly or through a constant `Assign`.
+/// 3. **Bound the result** with `RangeAnalysis`, which evaluates the function
+///    abstractly at each call site and reports the widest value on its result
+///    path.  When the analysis cannot prove a bound, a single-argument doubly
+///    recursive function still falls back to the Fibonacci estimate
+///    `ceil(n * log₂(φ) + 4)` (φ ≈ 1.618, log₂(φ) ≈ 0.6942); anything else
+///    keeps `I64`.  The reason becomes a warning only when every call site
+///    is literal, since a run-time argument leaves nothing to bound.
+/// 4. **Round the width** up to the next multiple of 64 when it exceeds 64.
+/// 5. **Rewrite the function body** via backward SSA analysis from `Return`
 ///    terminators:  literal base cases → `WideInt` constants, recursive calls →
-///    `WideInt` return type, the final addition → `WideInt` dest type.
-/// 5. **Rewrite every caller** using forward SSA propagation from the upgraded
+///    `WideInt` return type, arithmetic on the result → `WideInt` dest type.
+/// 6. **Rewrite every caller** using forward SSA propagation from the upgraded
 ///    call result, updating arithmetic dest types to `WideInt`.  Also repairs the
 ///    fixed-size buffer and index constants that `AsTextLowerer` inlined for the
 ///    `as-text` digit-extraction loop, so decimal printing stays correct.
-/// 6. **Set `memo_cache_size`** on the upgraded function so `MemoPass` allocates
-///    exactly `max_n + 2` cache entries (indices 0 … max_n plus one safety slot)
-///    — keeping the arena footprint well under the 1 MB limit even for 128-byte
-///    `WideInt(1024)` entries.
+/// 7. **Set `memo_cache_size`** on an upgraded single-argument function so
+///    `MemoPass` allocates exactly `max_n + 2` cache entries (indices 0 … max_n
+///    plus one safety slot) — keeping the arena footprint well under the 1 MB
+///    limit even for 128-byte `WideInt(1024)` entries.
 ///
 /// # Why Native LLVM Wide Integers
 /// No external BigInt library is required.  LLVM's `iN` type supports
 /// arbitrary integer widths; add/sub/mul are lowered by the backend directly,
 /// and div/mod on widths > 128 bits are handled by `WideDivLegalizationPass`
-/// which emits a software long-division helper.
+/// which emits a software long-division helper.  Callers zero-extend `I64`
+/// operands into the wide type, so a function is only widened when every
+/// value on its result path is non-negative.
 ///
 /// # Pattern Used: Pipeline Pass (Pure Function over Value Types)
 /// `IntegerUpgradePass::run` consumes a `MirProgram` and returns a transformed
 /// one with no shared mutable state.
 use std::collections::{HashMap, HashSet};
 
+use crate::application::use_cases::range_analysis::RangeAnalysis;
+use crate::domain::entities::error::{Diagnostic, Span};
 use crate::domain::entities::mir::{
     BasicBlock, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand, MirProgram,
     MirTerminator,
@@ -51,38 +61,110 @@ pub struct IntegerUpgradePass;
 const AS_TEXT_OLD_BUF_SIZE: i64 = 32;
 const AS_TEXT_OLD_IDX_MAX: i64 = 30;
 
+/// Widest `WideInt` the pass will introduce from a proved bound.
+const MAX_PROVED_BITS: u32 = 16_384;
+
+/// What `IntegerUpgradePass` decided for one candidate and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeDecision {
+    pub function: String,
+    /// `Ok(None)` when every result fits in `I64`, `Ok(Some(bits))` when the
+    /// function is widened to `WideInt(bits)`, `Err` when no bound was proved.
+    pub width: Result<Option<u32>, String>,
+}
+
 impl IntegerUpgradePass {
     pub fn run(program: MirProgram) -> MirProgram {
+        Self::run_with_diagnostics(program).0
+    }
+
+    /// As [`run`](Self::run), also returning a warning for each candidate
+    /// whose literal call sites could not be bounded.
+    pub fn run_with_diagnostics(program: MirProgram) -> (MirProgram, Vec<Diagnostic>) {
         // Step 1: find functions that need upgrading and the WideInt width to use.
-        let upgrades: Vec<(String, u32, usize)> = program
-            .functions
-            .iter()
-            .filter(|f| Self::is_upgrade_candidate(f))
-            .filter_map(|f| {
-                let max_n = Self::find_max_literal_call_arg(&program, &f.name);
-                if max_n > 92 {
-                    let bits = Self::required_bits(max_n);
-                    Some((f.name.clone(), bits, max_n as usize))
-                } else {
-                    None
+        let mut upgrades: Vec<(String, u32, Option<usize>)> = vec![];
+        let mut diagnostics = vec![];
+        for decision in Self::report(&program) {
+            match decision.width {
+                Ok(Some(bits)) => {
+                    let cache_size = Self::memo_cache_size(&program, &decision.function);
+                    upgrades.push((decision.function, bits, cache_size));
                 }
-            })
-            .collect();
+                Ok(None) => {}
+                Err(reason) => diagnostics.push(
+                    Diagnostic::warning(
+                        Span::default(),
+                        format!("Cannot bound '{}': {}; results stay 64-bit", decision.function, reason),
+                    )
+                    .with_hint("deliver a `big-integer` to size the result at run time"),
+                ),
+            }
+        }
 
         if upgrades.is_empty() {
-            return program;
+            return (program, diagnostics);
         }
 
         let mut functions = program.functions;
-        for (fn_name, bits, max_n) in &upgrades {
+        for (fn_name, bits, cache_size) in &upgrades {
             let wide_type = OnuType::WideInt(*bits);
             // 2a. Upgrade the target function itself.
-            functions = Self::upgrade_function_body(functions, fn_name, *bits, &wide_type, *max_n);
+            functions = Self::upgrade_function_body(functions, fn_name, *bits, &wide_type, *cache_size);
             // 2b. Upgrade every caller (forward propagation + buffer fix).
             functions = Self::upgrade_callers(functions, fn_name, *bits, &wide_type);
         }
 
-        MirProgram { functions }
+        (MirProgram { functions }, diagnostics)
+    }
+
+    /// The decision `run` makes for every candidate with literal call sites,
+    /// in program order.  A candidate also called with a run-time argument
+    /// has nothing to bound and is left out unless the Fibonacci estimate
+    /// sizes it.
+    pub fn report(program: &MirProgram) -> Vec<UpgradeDecision> {
+        program
+            .functions
+            .iter()
+            .filter(|f| Self::is_upgrade_candidate(f))
+            .filter_map(|f| {
+                let proved = match Self::literal_call_sites(program, &f.name) {
+                    Some(sites) if sites.is_empty() => return None,
+                    Some(sites) => Some(Self::proved_width(program, f, &sites)),
+                    None => None,
+                };
+                let width = match proved {
+                    Some(Ok(width)) => Ok(width),
+                    _ if Self::is_fibonacci_shaped(f) => {
+                        let max_n = Self::find_max_literal_call_arg(program, &f.name);
+                        Ok((max_n > 92).then(|| Self::required_bits(max_n)))
+                    }
+                    Some(Err(reason)) => Err(reason),
+                    None => return None,
+                };
+                Some(UpgradeDecision { function: f.name.clone(), width })
+            })
+            .collect()
+    }
+
+    fn proved_width(
+        program: &MirProgram,
+        func: &MirFunction,
+        sites: &[Vec<i64>],
+    ) -> Result<Option<u32>, String> {
+        let result_path = Self::find_result_chain(func);
+        let bound = RangeAnalysis::bound(program, &func.name, &result_path, sites)?;
+        if bound.bits <= 64 {
+            return Ok(None);
+        }
+        Self::check_widening_is_contained(func, &result_path)?;
+        if bound.negative {
+            return Err("its result path goes negative, which a widened result cannot hold".to_string());
+        }
+        let bits = bound.bits.div_ceil(64) * 64;
+        if bits > MAX_PROVED_BITS {
+            return Err(format!("its results need {} bits, more than {}", bits, MAX_PROVED_BITS));
+        }
+        Ok(Some(bits))
     }
 
     // -------------------------------------------------------------------------
@@ -91,10 +173,17 @@ impl IntegerUpgradePass {
 
     fn is_upgrade_candidate(func: &MirFunction) -> bool {
         func.is_pure_data_leaf
-            && !func.diminishing.is_empty()
-            && func.args.len() == 1
-            && func.args[0].typ == OnuType::I64
+            && !func.args.is_empty()
+            && func.args.iter().all(|a| a.typ == OnuType::I64)
             && func.return_type == OnuType::I64
+            && Self::count_recursive_calls(func) >= 1
+    }
+
+    /// The shape the φ estimate applies to: a diminishing single-argument
+    /// function with at least two recursive self-calls.
+    fn is_fibonacci_shaped(func: &MirFunction) -> bool {
+        !func.diminishing.is_empty()
+            && func.args.len() == 1
             && Self::count_recursive_calls(func) >= 2
     }
 
@@ -108,6 +197,114 @@ impl IntegerUpgradePass {
             .count()
     }
 
+    /// Argument tuples of every call to `fn_name` from another function, or
+    /// `None` when some call passes an argument that is not a literal.
+    fn literal_call_sites(program: &MirProgram, fn_name: &str) -> Option<Vec<Vec<i64>>> {
+        let mut sites = vec![];
+        for func in program.functions.iter().filter(|f| f.name != fn_name) {
+            // SSA variables assigned exactly once, from an integer literal.
+            let mut defs: HashMap<usize, Option<i64>> = HashMap::new();
+            for inst in func.blocks.iter().flat_map(|b| b.instructions.iter()) {
+                let Some(dest) = Self::defined_var(inst) else { continue };
+                let value = match inst {
+                    MirInstruction::Assign { src: MirOperand::Constant(MirLiteral::I64(v)), .. } => Some(*v),
+                    _ => None,
+                };
+                defs.entry(dest).and_modify(|d| *d = None).or_insert(value);
+            }
+
+            for inst in func.blocks.iter().flat_map(|b| b.instructions.iter()) {
+                let MirInstruction::Call { name, args, .. } = inst else { continue };
+                if name != fn_name {
+                    continue;
+                }
+                let site = args
+                    .iter()
+                    .map(|arg| match arg {
+                        MirOperand::Constant(MirLiteral::I64(n)) => Some(*n),
+                        MirOperand::Variable(ssa, _) => defs.get(ssa).copied().flatten(),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<i64>>>()?;
+                sites.push(site);
+            }
+        }
+        Some(sites)
+    }
+
+    fn defined_var(inst: &MirInstruction) -> Option<usize> {
+        match inst {
+            MirInstruction::Assign { dest, .. }
+            | MirInstruction::BinaryOperation { dest, .. }
+            | MirInstruction::CheckedArithmetic { dest, .. }
+            | MirInstruction::Call { dest, .. } => Some(*dest),
+            _ => None,
+        }
+    }
+
+    /// Largest literal argument across all call sites, used for the cache
+    /// size of a widened single-argument function.
+    fn memo_cache_size(program: &MirProgram, fn_name: &str) -> Option<usize> {
+        let func = program.functions.iter().find(|f| f.name == fn_name)?;
+        if func.args.len() != 1 {
+            return None;
+        }
+        let max_n = Self::find_max_literal_call_arg(program, fn_name);
+        Some(max_n.max(0) as usize + 2)
+    }
+
+    /// Widening only retypes instructions on the result path, so every value
+    /// derived from a widened one must stay on that path: it may be returned,
+    /// compared, or combined into another result-path value, but never passed
+    /// to a call or stored in an `I64` slot.
+    fn check_widening_is_contained(func: &MirFunction, result_path: &HashSet<usize>) -> Result<(), String> {
+        let insts: Vec<&MirInstruction> = func.blocks.iter().flat_map(|b| b.instructions.iter()).collect();
+        let mut wide: HashSet<usize> = insts
+            .iter()
+            .filter_map(|inst| match inst {
+                MirInstruction::Call { dest, name, .. } if name == &func.name => Some(*dest),
+                MirInstruction::Assign { dest, src: MirOperand::Constant(_) } if result_path.contains(dest) => {
+                    Some(*dest)
+                }
+                _ => None,
+            })
+            .collect();
+        let reads_wide = |op: &MirOperand, wide: &HashSet<usize>| matches!(op, MirOperand::Variable(x, _) if wide.contains(x));
+
+        loop {
+            let before = wide.len();
+            for inst in &insts {
+                match inst {
+                    MirInstruction::Assign { dest, src } if reads_wide(src, &wide) => {
+                        wide.insert(*dest);
+                    }
+                    MirInstruction::BinaryOperation { dest, op, lhs, rhs, .. }
+                    | MirInstruction::CheckedArithmetic { dest, op, lhs, rhs, .. }
+                        if !Self::is_comparison(op) && (reads_wide(lhs, &wide) || reads_wide(rhs, &wide)) =>
+                    {
+                        wide.insert(*dest);
+                    }
+                    _ => {}
+                }
+            }
+            if wide.len() == before {
+                break;
+            }
+        }
+
+        if let Some(ssa) = wide.iter().find(|ssa| !result_path.contains(ssa)) {
+            return Err(format!("the widened value in SSA {} would flow off the result path", ssa));
+        }
+        for inst in &insts {
+            if let MirInstruction::Call { name, args, .. } = inst
+                && args.iter().any(|a| reads_wide(a, &wide))
+            {
+                return Err(format!("it passes its own result to '{}'", name));
+            }
+        }
+        Ok(())
+    }
+
     fn find_max_literal_call_arg(program: &MirProgram, fn_name: &str) -> i64 {
         // Build a constant-propagation map: SSA → constant value (for assignments
         // of the form `dest = Constant(I64(n))`).  This handles the common pattern
@@ -178,7 +375,7 @@ impl IntegerUpgradePass {
         fn_name: &str,
         bits: u32,
         wide_type: &OnuType,
-        max_n: usize,
+        cache_size: Option<usize>,
     ) -> Vec<MirFunction> {
         functions
             .into_iter()
@@ -194,7 +391,9 @@ impl IntegerUpgradePass {
                 // arena is not exhausted by 128-byte WideInt(1024) entries.
                 // cache_size = max_n + 2: one entry for index 0 through max_n
                 // plus one extra slot as a safety margin.
-                func.memo_cache_size = Some(max_n + 2);
+                if cache_size.is_some() {
+                    func.memo_cache_size = cache_size;
+                }
 
                 // Backward SSA analysis: collect SSA vars that flow into returns.
                 let result_chain = Self::find_result_chain(&func);
@@ -248,6 +447,22 @@ impl IntegerUpgradePass {
                                     }
                                 }
 
+                                MirInstruction::CheckedArithmetic {
+                                    dest,
+                                    op,
+                                    lhs,
+                                    rhs,
+                                    dest_type: OnuType::I64,
+                                    site,
+                                } if result_chain.contains(&dest) => MirInstruction::CheckedArithmetic {
+                                    dest,
+                                    op,
+                                    lhs,
+                                    rhs,
+                                    dest_type: wide_type.clone(),
+                                    site,
+                                },
+
                                 // Recursive calls → WideInt return type.
                                 MirInstruction::Call {
                                     dest,
@@ -290,6 +505,7 @@ impl IntegerUpgradePass {
                 match inst {
                     MirInstruction::Assign { dest, .. }
                     | MirInstruction::BinaryOperation { dest, .. }
+                    | MirInstruction::CheckedArithmetic { dest, .. }
                     | MirInstruction::Call { dest, .. } => {
                         def_map.insert(*dest, inst);
                     }
@@ -321,6 +537,7 @@ impl IntegerUpgradePass {
                         }
                     }
                     MirInstruction::BinaryOperation { op, lhs, rhs, .. }
+                    | MirInstruction::CheckedArithmetic { op, lhs, rhs, .. }
                         if !Self::is_comparison(op) =>
                     {
                         if let MirOperand::Variable(x, _) = lhs {
@@ -473,6 +690,7 @@ impl IntegerUpgradePass {
                             upgraded_ssas.insert(*dest);
                         }
                         MirInstruction::BinaryOperation { dest, op, lhs, rhs, .. }
+                        | MirInstruction::CheckedArithmetic { dest, op, lhs, rhs, .. }
                             if !Self::is_comparison(op) =>
                         {
                             let lhs_wide = if let MirOperand::Variable(x, _) = lhs {
@@ -542,6 +760,24 @@ impl IntegerUpgradePass {
                             }
                         }
 
+                        MirInstruction::CheckedArithmetic {
+                            dest,
+                            op,
+                            lhs,
+                            rhs,
+                            dest_type: OnuType::I64,
+                            site,
+                        } if upgraded_ssas.contains(&dest) && !Self::is_comparison(&op) => {
+                            MirInstruction::CheckedArithmetic {
+                                dest,
+                                op,
+                                lhs,
+                                rhs,
+                                dest_type: wide_type.clone(),
+                                site,
+                            }
+                        }
+
                         // Fix as-text buffer allocation: grow to fit max_digits.
                         MirInstruction::Alloc {
                             dest,
@@ -661,6 +897,7 @@ impl IntegerUpgradePass {
 mod tests {
     use super::*;
     use crate::domain::entities::mir::{BasicBlock, MirArgument, MirTerminator};
+    use crate::domain::entities::types::InlineHint;
 
     fn fib_naive_mir() -> MirFunction {
         // A minimal fib-naive skeleton:
@@ -679,6 +916,8 @@ mod tests {
             is_pure_data_leaf: true,
             diminishing: vec!["n".to_string()],
             memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
             blocks: vec![
                 BasicBlock {
                     id: 0,
@@ -744,6 +983,8 @@ mod tests {
             is_pure_data_leaf: false,
             diminishing: vec![],
             memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
             blocks: vec![BasicBlock {
                 id: 0,
                 instructions: vec![MirInstruction::Call {
@@ -759,6 +1000,142 @@ mod tests {
         }
     }
 
+    fn factorial_mir() -> MirFunction {
+        // Block 0: check = n==0 → CondBranch(check, base, recurse)
+        // Block 1: Return(1)
+        // Block 2: m=n-1, rest=Call(factorial,[m]), res=Mul(n,rest), Return(res)
+        MirFunction {
+            name: "factorial".to_string(),
+            args: vec![MirArgument {
+                name: "n".to_string(),
+                typ: OnuType::I64,
+                ssa_var: 0,
+            }],
+            return_type: OnuType::I64,
+            is_pure_data_leaf: true,
+            diminishing: vec!["n".to_string()],
+            memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
+            blocks: vec![
+                BasicBlock {
+                    id: 0,
+                    instructions: vec![MirInstruction::BinaryOperation {
+                        dest: 1,
+                        op: MirBinOp::Eq,
+                        lhs: MirOperand::Variable(0, false),
+                        rhs: MirOperand::Constant(MirLiteral::I64(0)),
+                        dest_type: OnuType::Boolean,
+                    }],
+                    terminator: MirTerminator::CondBranch {
+                        condition: MirOperand::Variable(1, false),
+                        then_block: 1,
+                        else_block: 2,
+                    },
+                },
+                BasicBlock {
+                    id: 1,
+                    instructions: vec![],
+                    terminator: MirTerminator::Return(MirOperand::Constant(MirLiteral::I64(1))),
+                },
+                BasicBlock {
+                    id: 2,
+                    instructions: vec![
+                        MirInstruction::BinaryOperation {
+                            dest: 2,
+                            op: MirBinOp::Sub,
+                            lhs: MirOperand::Variable(0, false),
+                            rhs: MirOperand::Constant(MirLiteral::I64(1)),
+                            dest_type: OnuType::I64,
+                        },
+                        MirInstruction::Call {
+                            dest: 3,
+                            name: "factorial".to_string(),
+                            args: vec![MirOperand::Variable(2, false)],
+                            return_type: OnuType::I64,
+                            arg_types: vec![OnuType::I64],
+                            is_tail_call: false,
+                        },
+                        MirInstruction::BinaryOperation {
+                            dest: 4,
+                            op: MirBinOp::Mul,
+                            lhs: MirOperand::Variable(0, false),
+                            rhs: MirOperand::Variable(3, false),
+                            dest_type: OnuType::I64,
+                        },
+                    ],
+                    terminator: MirTerminator::Return(MirOperand::Variable(4, false)),
+                },
+            ],
+        }
+    }
+
+    fn run_func_calling(name: &str, n: i64) -> MirFunction {
+        let mut run = run_func_with_call(n);
+        if let MirInstruction::Call { name: callee, .. } = &mut run.blocks[0].instructions[0] {
+            *callee = name.to_string();
+        }
+        run
+    }
+
+    #[test]
+    fn test_factorial_upgraded_by_range_analysis() {
+        let prog = MirProgram {
+            functions: vec![factorial_mir(), run_func_calling("factorial", 30)],
+        };
+        // 30! ≈ 2^107.7 needs 109 signed bits, rounded up to 128.
+        assert_eq!(
+            IntegerUpgradePass::report(&prog)[0].width,
+            Ok(Some(128)),
+        );
+        let after = IntegerUpgradePass::run(prog);
+        let fact = after.functions.iter().find(|f| f.name == "factorial").unwrap();
+        assert_eq!(fact.return_type, OnuType::WideInt(128));
+        assert_eq!(fact.memo_cache_size, Some(32));
+        assert!(matches!(
+            fact.blocks[2].instructions[2],
+            MirInstruction::BinaryOperation { dest_type: OnuType::WideInt(128), .. }
+        ));
+    }
+
+    #[test]
+    fn test_factorial_within_i64_not_upgraded() {
+        let prog = MirProgram {
+            functions: vec![factorial_mir(), run_func_calling("factorial", 20)],
+        };
+        assert_eq!(IntegerUpgradePass::report(&prog)[0].width, Ok(None));
+        let after = IntegerUpgradePass::run(prog);
+        assert_eq!(after.functions[0].return_type, OnuType::I64);
+    }
+
+    #[test]
+    fn test_unprovable_bound_is_reported() {
+        let prog = MirProgram {
+            functions: vec![factorial_mir(), run_func_calling("factorial", 5000)],
+        };
+        let report = IntegerUpgradePass::report(&prog);
+        let reason = report[0].width.clone().unwrap_err();
+        assert!(reason.contains("deeper than"), "got {}", reason);
+        let (after, diagnostics) = IntegerUpgradePass::run_with_diagnostics(prog);
+        assert_eq!(after.functions[0].return_type, OnuType::I64);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.starts_with("Cannot bound 'factorial': recursion is deeper than"));
+    }
+
+    #[test]
+    fn test_run_time_argument_is_not_reported() {
+        let mut run = run_func_calling("factorial", 30);
+        run.args.push(MirArgument { name: "n".to_string(), typ: OnuType::I64, ssa_var: 19 });
+        if let MirInstruction::Call { args, .. } = &mut run.blocks[0].instructions[0] {
+            args[0] = MirOperand::Variable(19, false);
+        }
+        let prog = MirProgram { functions: vec![factorial_mir(), run] };
+        assert!(IntegerUpgradePass::report(&prog).is_empty());
+        let (after, diagnostics) = IntegerUpgradePass::run_with_diagnostics(prog);
+        assert_eq!(after.functions[0].return_type, OnuType::I64);
+        assert!(diagnostics.is_empty());
+    }
+
     #[test]
     fn test_no_upgrade_for_small_n() {
         let prog = MirProgram {
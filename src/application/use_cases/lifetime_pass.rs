@@ -22,6 +22,7 @@
 /// registers, yielding zero-allocation performance.
 
 use crate::domain::entities::mir::*;
+use crate::domain::entities::types::OnuType;
 
 pub struct LifetimePass;
 
@@ -46,6 +47,12 @@ impl LifetimePass {
 
         let func = Self::promote_stack_allocs(func);
 
+        // Big-integer values live in the arena and may be returned or kept in
+        // a memo table, so their region is never reclaimed on return.
+        if Self::handles_big_integers(&func) {
+            return func;
+        }
+
         // Re-check after promotion — if all allocs were promoted, no save/restore needed.
         let still_has_arena_alloc = func.blocks.iter().any(|b| {
             b.instructions.iter().any(|inst| matches!(inst, MirInstruction::Alloc { .. }))
@@ -60,7 +67,14 @@ impl LifetimePass {
         Self::insert_scoped_arena(func)
     }
 
-    /// Promote `Alloc { size_bytes: Constant(N) }` to `StackAlloc { size_bytes: N }`
+    fn handles_big_integers(func: &MirFunction) -> bool {
+        func.return_type == OnuType::BigInt
+            || func.blocks.iter().flat_map(|b| &b.instructions).any(|inst| {
+                matches!(inst, MirInstruction::Call { return_type: OnuType::BigInt, .. })
+            })
+    }
+
+        /// Promote `Alloc { size_bytes: Constant(N) }` to `StackAlloc { size_bytes: N }`
     /// when the size is a compile-time constant and small enough for the stack.
     fn promote_stack_allocs(func: MirFunction) -> MirFunction {
         // Maximum size we're willing to put on the stack (4 KiB).
@@ -164,7 +178,8 @@ impl LifetimePass {
                 if let MirOperand::Variable(id, _) = src { ids.push(*id); }
                 ids
             }
-            MirInstruction::BinaryOperation { dest, lhs, rhs, .. } => {
+            MirInstruction::BinaryOperation { dest, lhs, rhs, .. }
+            | MirInstruction::CheckedArithmetic { dest, lhs, rhs, .. } => {
                 let mut ids = vec![*dest];
                 if let MirOperand::Variable(id, _) = lhs { ids.push(*id); }
                 if let MirOperand::Variable(id, _) = rhs { ids.push(*id); }
@@ -194,7 +209,14 @@ impl LifetimePass {
                 if let MirOperand::Variable(id, _) = amount { ids.push(*id); }
                 ids
             }
-            MirInstruction::Emit(op) => {
+            MirInstruction::Intrinsic { dest, args, .. } => {
+                let mut ids = vec![*dest];
+                for a in args {
+                    if let MirOperand::Variable(id, _) = a { ids.push(*id); }
+                }
+                ids
+            }
+            MirInstruction::Emit(op) | MirInstruction::RuntimeCheck { condition: op, .. } => {
                 if let MirOperand::Variable(id, _) = op { vec![*id] } else { vec![] }
             }
             MirInstruction::Drop { ssa_var, .. } => vec![*ssa_var],
@@ -269,7 +291,9 @@ impl LifetimePass {
                 if let MirOperand::Variable(id, _) = len { ids.push(*id); }
                 ids
             }
-            MirInstruction::FlushStdout => vec![],
+            MirInstruction::FlushStdout
+            | MirInstruction::ClearMemo { .. }
+            | MirInstruction::MemoCount { .. } => vec![],
         }
     }
 }
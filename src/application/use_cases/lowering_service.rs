@@ -11,6 +11,9 @@ use crate::application::use_cases::registry_service::RegistryService;
 pub struct LoweringService;
 
 impl LoweringService {
+    /// Lowers one top-level declaration.  Tests are not part of the program
+    /// (`onu test` rewrites each one into a `run` behavior of its own), so
+    /// callers filter `Discourse::Test` out before lowering.
     pub fn lower_discourse(discourse: &Discourse, registry: &RegistryService) -> HirDiscourse {
         match discourse {
             Discourse::Module { name, concern } => HirDiscourse::Module {
@@ -34,6 +37,9 @@ impl LoweringService {
                 fields: fields.iter().map(Self::lower_argument).collect(),
                 behaviors: behaviors.iter().map(Self::lower_header).collect() 
             },
+            Discourse::Test { name, .. } => {
+                unreachable!("test '{}' reached HIR lowering; tests are filtered out first", name)
+            }
         }
     }
 
@@ -41,10 +47,13 @@ impl LoweringService {
         HirBehaviorHeader {
             name: header.name.clone(),
             is_effect: header.is_effect,
+            intent: header.intent.clone(),
             args: header.takes.iter().map(Self::lower_argument).collect(),
             return_type: header.delivers.0.clone(),
             diminishing: header.diminishing.clone(),
             memo_cache_size: header.memo_cache_size,
+            inlining: header.inlining,
+            known_behavior: header.known_behavior,
         }
     }
 
@@ -120,6 +129,8 @@ impl LoweringService {
                     "bit-xor-with" => Some(HirBinOp::BitXor),
                     "shifted-right-by" => Some(HirBinOp::Shr),
                     "shifted-left-by" => Some(HirBinOp::Shl),
+                    "wrapping-added-to" => Some(HirBinOp::WrappingAdd),
+                    "saturating-added-to" => Some(HirBinOp::SaturatingAdd),
                     _ => None,
                 };
 
@@ -1,45 +1,101 @@
 use crate::application::use_cases::memo_strategies::{
-    MemoStrategy, compound_memo_strategy::CompoundMemoStrategy,
+    MemoStrategy, max_ssa_in_function, compound_memo_strategy::CompoundMemoStrategy,
     hash_memo_strategy::HashMemoStrategy,
+    keyed_memo_strategy::{KeyedMemoStrategy, is_keyable},
     primitive_memo_strategy::PrimitiveMemoStrategy,
 };
-use crate::domain::entities::mir::{MirFunction, MirProgram};
+use crate::domain::entities::mir::{MirFunction, MirInstruction, MirLiteral, MirOperand, MirProgram};
 use crate::domain::entities::types::OnuType;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::application::use_cases::registry_service::RegistryService;
+use crate::application::use_cases::mir_lowering::lower_big_integer::is_big_integer_helper;
 
 pub struct MemoPass;
 
 const DEFAULT_MEMO_CACHE_SIZE: usize = 10000;
 
+/// What `MemoPass` decided for one behavior and why, as printed by
+/// `--memo-report`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoDecision {
+    pub function: String,
+    /// Strategy name and cache slot count, or `None` when not memoized.
+    pub plan: Option<(&'static str, usize)>,
+    pub reason: String,
+}
+
+impl std::fmt::Display for MemoDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.plan {
+            Some((strategy, entries)) => write!(
+                f,
+                "`{}`: {} cache, {} entries ({})",
+                self.function, strategy, entries, self.reason
+            ),
+            None => write!(f, "`{}`: not memoized ({})", self.function, self.reason),
+        }
+    }
+}
+
 impl MemoPass {
     pub fn run(program: MirProgram, registry: &RegistryService) -> MirProgram {
-        let mut new_functions = vec![];
-        for func in program.functions {
-            if Self::is_memoizable(&func) {
-                // Multi-dimensional functions use HashMemoStrategy: a direct-mapped
-                // hash table that caches ANY (a₀,…,aₙ) pair regardless of magnitude.
-                // This eliminates the 99.99% out-of-bounds fallthrough that
-                // CompoundMemoStrategy suffered for Ackermann-like functions where one
-                // argument (spiral_step) grows exponentially beyond dim_size.
-                // 1-D functions use PrimitiveMemoStrategy for primitive return types.
-                let strategy: Box<dyn MemoStrategy> = if func.diminishing.len() > 1 {
-                    Box::new(HashMemoStrategy)
-                } else {
-                    match func.return_type {
-                        OnuType::I64 | OnuType::Boolean | OnuType::Ptr | OnuType::WideInt(_) => {
-                            Box::new(PrimitiveMemoStrategy)
-                        }
-                        _ => Box::new(CompoundMemoStrategy),
+        Self::run_with(program, registry, false)
+    }
+
+    /// Like `run`, but keeps the `MemoCount` probes in each wrapper so the
+    /// program prints per-function cache statistics when it exits.
+    pub fn run_instrumented(program: MirProgram, registry: &RegistryService) -> MirProgram {
+        Self::run_with(program, registry, true)
+    }
+
+    /// The decision `run` would make for every function, in program order.
+    pub fn report(program: &MirProgram, registry: &RegistryService) -> Vec<MemoDecision> {
+        program
+            .functions
+            .iter()
+            .map(|func| match Self::choose(func, registry) {
+                Ok((strategy, reason)) => {
+                    let cache_size = func.memo_cache_size.unwrap_or(DEFAULT_MEMO_CACHE_SIZE);
+                    MemoDecision {
+                        function: func.name.clone(),
+                        plan: Some((strategy.name(), strategy.cache_entries(func, cache_size, registry))),
+                        reason: reason.to_string(),
                     }
-                };
+                }
+                Err(reason) => MemoDecision { function: func.name.clone(), plan: None, reason },
+            })
+            .collect()
+    }
+
+    fn run_with(program: MirProgram, registry: &RegistryService, instrument: bool) -> MirProgram {
+        // `clears` may name a behavior defined after its caller, so find every
+        // cache's reset table before rewriting any function.
+        let resets: HashMap<String, (String, usize)> = program
+            .functions
+            .iter()
+            .filter_map(|func| {
+                let (strategy, _) = Self::choose(func, registry).ok()?;
+                let cache_size = func.memo_cache_size.unwrap_or(DEFAULT_MEMO_CACHE_SIZE);
+                Some((func.name.clone(), strategy.reset_table(func, cache_size, registry)))
+            })
+            .collect();
+
+        let mut new_functions = vec![];
+        for mut func in program.functions {
+            let choice = Self::choose(&func, registry);
+            Self::lower_clears(&mut func, &resets);
+            if let Ok((strategy, _)) = choice {
                 // Use function-specific cache size when set (e.g. by IntegerUpgradePass
                 // to cap arena usage for large WideInt entries), else fall back to the
                 // global default.
                 let cache_size = func.memo_cache_size.unwrap_or(DEFAULT_MEMO_CACHE_SIZE);
-                let (wrapper, inner) =
+                let (mut wrapper, mut inner) =
                     strategy.create_wrapper_and_inner(func, cache_size, registry);
+                if !instrument {
+                    Self::strip_probes(&mut wrapper);
+                    Self::strip_probes(&mut inner);
+                }
                 new_functions.push(wrapper);
                 new_functions.push(inner);
             } else {
@@ -51,29 +107,118 @@ impl MemoPass {
         }
     }
 
-    fn is_memoizable(func: &MirFunction) -> bool {
+    /// Replaces each `ClearMemo` with a `MemSet` that zeroes the target's
+    /// reset table.  Clearing a behavior that was not memoized does nothing.
+    fn lower_clears(func: &mut MirFunction, resets: &HashMap<String, (String, usize)>) {
+        let mut next_ssa = max_ssa_in_function(func) + 1;
+        for block in &mut func.blocks {
+            let mut lowered = Vec::with_capacity(block.instructions.len());
+            for inst in block.instructions.drain(..) {
+                match inst {
+                    MirInstruction::ClearMemo { function } => {
+                        if let Some((table, size)) = resets.get(&function) {
+                            let ptr = next_ssa;
+                            next_ssa += 1;
+                            lowered.push(MirInstruction::GlobalAlloc {
+                                dest: ptr,
+                                size_bytes: *size,
+                                name: table.clone(),
+                            });
+                            lowered.push(MirInstruction::MemSet {
+                                ptr: MirOperand::Variable(ptr, false),
+                                value: MirOperand::Constant(MirLiteral::I64(0)),
+                                size: MirOperand::Constant(MirLiteral::I64(*size as i64)),
+                            });
+                        }
+                    }
+                    other => lowered.push(other),
+                }
+            }
+            block.instructions = lowered;
+        }
+    }
+
+    fn strip_probes(func: &mut MirFunction) {
+        for block in &mut func.blocks {
+            block.instructions.retain(|inst| !matches!(inst, MirInstruction::MemoCount { .. }));
+        }
+    }
+
+    /// Picks the memo strategy for `func`, or explains why it is left alone.
+    fn choose(
+        func: &MirFunction,
+        registry: &RegistryService,
+    ) -> Result<(Box<dyn MemoStrategy>, &'static str), String> {
+        Self::check_memoizable(func, registry)?;
+        // Multi-dimensional functions use HashMemoStrategy: a direct-mapped
+        // hash table that caches ANY (a₀,…,aₙ) pair regardless of magnitude.
+        // This eliminates the 99.99% out-of-bounds fallthrough that
+        // CompoundMemoStrategy suffered for Ackermann-like functions where one
+        // argument (spiral_step) grows exponentially beyond dim_size.
+        // 1-D functions use PrimitiveMemoStrategy for primitive return types.
+        // Any text, boolean or shape argument needs KeyedMemoStrategy, which
+        // hashes argument contents and compares full keys on lookup.
+        Ok(if func.args.iter().any(|a| a.typ != OnuType::I64) {
+            (Box::new(KeyedMemoStrategy), "text, boolean or shape arguments are hashed by content")
+        } else if func.diminishing.len() > 1 {
+            (Box::new(HashMemoStrategy), "several diminishing arguments share one hash table")
+        } else {
+            match func.return_type {
+                OnuType::I64 | OnuType::Boolean | OnuType::Ptr | OnuType::WideInt(_) | OnuType::BigInt => {
+                    (Box::new(PrimitiveMemoStrategy), "one diminishing argument with a scalar result")
+                }
+                _ => (Box::new(CompoundMemoStrategy), "one diminishing argument with a compound result"),
+            }
+        })
+    }
+
+    fn check_memoizable(func: &MirFunction, registry: &RegistryService) -> Result<(), String> {
         // Nothing (void) functions have no return value to cache and size_of(Nothing) == 0,
         // which would produce a zero-byte arena and invalid memory accesses in the cache.
+        if func.return_type == OnuType::Nothing {
+            return Err("delivers nothing".to_string());
+        }
         let dim = &func.diminishing;
-        // Build a set for O(1) per-arg lookup, avoiding O(n²) in the all() call below.
+        if dim.is_empty() {
+            return Err("no `with diminishing:` clause".to_string());
+        }
+        if func.args.is_empty() {
+            return Err("takes no arguments".to_string());
+        }
+        // Build a set for O(1) per-arg lookup, avoiding O(n²) in the scan below.
         let dim_set: HashSet<&str> = dim.iter().map(String::as_str).collect();
-        let r = func.return_type != OnuType::Nothing
-            && func.is_pure_data_leaf
-            && !dim.is_empty()
-            && !func.args.is_empty()
-            && func.args.len() == dim.len()
-            && func.args.iter().all(|a| a.typ == OnuType::I64)
-            && func.args.iter().all(|a| dim_set.contains(a.name.as_str()));
-        eprintln!(
-            "[MemoPass] fn='{}' pure={} dim={:?} args={} ret={:?} => {}",
-            func.name,
-            func.is_pure_data_leaf,
-            func.diminishing,
-            func.args.len(),
-            func.return_type,
-            r
-        );
-        r
+        // Integer arguments must be named in `with diminishing:`.  Text, boolean
+        // and shape arguments are observed keys that KeyedMemoStrategy hashes.
+        for a in &func.args {
+            if a.typ == OnuType::I64 {
+                if !dim_set.contains(a.name.as_str()) {
+                    return Err(format!("integer argument `{}` is not diminishing", a.name));
+                }
+            } else if !is_keyable(&a.typ, registry) {
+                return Err(format!("argument `{}` cannot be a cache key", a.name));
+            }
+        }
+        // Text arguments are resources, so such a behavior is never marked as a
+        // pure data leaf; it still qualifies when its body only reads.
+        let has_text = func.args.iter().any(|a| a.typ.is_resource());
+        if !(func.is_pure_data_leaf || (has_text && Self::only_reads(func))) {
+            return Err("has side effects".to_string());
+        }
+        Ok(())
+    }
+
+    /// Mirrors the side-effect audit in `MirLoweringService::lower_function`:
+    /// no allocation, stores, output, drops, or calls to other behaviors.
+    fn only_reads(func: &MirFunction) -> bool {
+        func.blocks.iter().flat_map(|b| &b.instructions).all(|inst| match inst {
+            MirInstruction::Alloc { .. }
+            | MirInstruction::Store { .. }
+            | MirInstruction::Emit(_)
+            | MirInstruction::Drop { .. }
+            | MirInstruction::ClearMemo { .. } => false,
+            MirInstruction::Call { name, .. } => name == &func.name || is_big_integer_helper(name),
+            _ => true,
+        })
     }
 }
 
@@ -81,6 +226,7 @@ impl MemoPass {
 mod tests {
     use super::*;
     use crate::domain::entities::mir::{BasicBlock, MirArgument, MirOperand, MirTerminator};
+    use crate::domain::entities::types::InlineHint;
 
     #[test]
     fn test_compound_memo_strategy_panics_for_tuple_return() {
@@ -100,6 +246,8 @@ mod tests {
             is_pure_data_leaf: true,
             diminishing: vec!["x".to_string()],
             memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
         };
 
         let program = MirProgram {
@@ -132,6 +280,8 @@ mod tests {
             is_pure_data_leaf: true,
             diminishing: vec!["x".to_string()],
             memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
         };
 
         let program = MirProgram {
@@ -144,4 +294,48 @@ mod tests {
         assert_eq!(program.functions.len(), 1);
         assert_eq!(program.functions[0].name, "void_fn");
     }
+
+    fn leaf(name: &str, args: Vec<MirArgument>, diminishing: &[&str]) -> MirFunction {
+        MirFunction {
+            name: name.to_string(),
+            args,
+            return_type: OnuType::I64,
+            blocks: vec![BasicBlock {
+                id: 0,
+                instructions: vec![],
+                terminator: MirTerminator::Return(MirOperand::Variable(0, false)),
+            }],
+            is_pure_data_leaf: true,
+            diminishing: diminishing.iter().map(|d| d.to_string()).collect(),
+            memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
+        }
+    }
+
+    fn int_arg(name: &str, ssa_var: usize) -> MirArgument {
+        MirArgument { name: name.to_string(), typ: OnuType::I64, ssa_var }
+    }
+
+    #[test]
+    fn test_report_explains_each_decision() {
+        let mut effectful = leaf("effectful", vec![int_arg("n", 0)], &["n"]);
+        effectful.is_pure_data_leaf = false;
+        let program = MirProgram {
+            functions: vec![
+                leaf("fib", vec![int_arg("n", 0)], &["n"]),
+                leaf("ack", vec![int_arg("m", 0), int_arg("n", 1)], &["m", "n"]),
+                leaf("scale", vec![int_arg("n", 0), int_arg("k", 1)], &["n"]),
+                leaf("plain", vec![int_arg("n", 0)], &[]),
+                effectful,
+            ],
+        };
+        let registry = RegistryService::new();
+        let report: Vec<String> = MemoPass::report(&program, &registry).iter().map(|d| d.to_string()).collect();
+        assert_eq!(report[0], "`fib`: primitive cache, 10000 entries (one diminishing argument with a scalar result)");
+        assert!(report[1].starts_with("`ack`: hash cache, "), "{}", report[1]);
+        assert_eq!(report[2], "`scale`: not memoized (integer argument `k` is not diminishing)");
+        assert_eq!(report[3], "`plain`: not memoized (no `with diminishing:` clause)");
+        assert_eq!(report[4], "`effectful`: not memoized (has side effects)");
+    }
 }
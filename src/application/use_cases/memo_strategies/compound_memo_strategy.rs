@@ -1,7 +1,7 @@
-use super::{MemoStrategy, max_ssa_in_function};
+use super::{MemoStrategy, max_ssa_in_function, memo_probe, memo_table};
 use crate::domain::entities::mir::{
-    BasicBlock, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand,
-    MirTerminator,
+    BasicBlock, MemoCounter, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral,
+    MirOperand, MirTerminator,
 };
 use crate::domain::entities::types::OnuType;
 use crate::domain::entities::ARENA_SIZE_BYTES;
@@ -123,6 +123,31 @@ impl<'a> CacheProvider<'a> {
 pub struct CompoundMemoStrategy;
 
 impl MemoStrategy for CompoundMemoStrategy {
+    fn name(&self) -> &'static str {
+        "compound"
+    }
+
+    fn cache_entries(
+        &self,
+        func: &MirFunction,
+        cache_size: usize,
+        registry: &crate::application::use_cases::registry_service::RegistryService,
+    ) -> usize {
+        let n_dims = func.args.len();
+        let stride = registry.size_of(&func.return_type);
+        Self::safe_dim_size(n_dims, stride, cache_size).saturating_pow(n_dims as u32)
+    }
+
+    fn reset_table(
+        &self,
+        func: &MirFunction,
+        cache_size: usize,
+        registry: &crate::application::use_cases::registry_service::RegistryService,
+    ) -> (String, usize) {
+        // One padded 8-byte occupancy flag per entry, as in build_wrapper.
+        (memo_table(&func.name, "occ"), self.cache_entries(func, cache_size, registry) * 8)
+    }
+
     fn create_wrapper_and_inner(
         &self,
         func: MirFunction,
@@ -236,8 +261,6 @@ impl CompoundMemoStrategy {
     ) -> (MirFunction, usize, usize) {
         let cache_ptr = builder.alloc_ssa();
         let occ_ptr = builder.alloc_ssa();
-        let cache_size_ssa = builder.alloc_ssa();
-        let occ_size_ssa = builder.alloc_ssa();
 
         let call_id = builder.alloc_block();
 
@@ -250,28 +273,18 @@ impl CompoundMemoStrategy {
         // Use an 8-byte stride for occupancy flags to maintain alignment.
         let occ_bytes = total_entries.saturating_mul(8);
 
-        // 1. Entry Block
+        // 1. Entry Block: pointers to the global tables.  The occupancy
+        //    flags start zeroed and persist, so repeated calls reuse entries.
         let entry_insts = vec![
-            MirInstruction::Assign {
-                dest: cache_size_ssa,
-                src: MirOperand::Constant(MirLiteral::I64(total_bytes)),
-            },
-            MirInstruction::Alloc {
+            MirInstruction::GlobalAlloc {
                 dest: cache_ptr,
-                size_bytes: MirOperand::Variable(cache_size_ssa, false),
-            },
-            MirInstruction::Assign {
-                dest: occ_size_ssa,
-                src: MirOperand::Constant(MirLiteral::I64(occ_bytes)),
+                size_bytes: total_bytes as usize,
+                name: memo_table(&func.name, "val"),
             },
-            MirInstruction::Alloc {
+            MirInstruction::GlobalAlloc {
                 dest: occ_ptr,
-                size_bytes: MirOperand::Variable(occ_size_ssa, false),
-            },
-            MirInstruction::MemSet {
-                ptr: MirOperand::Variable(occ_ptr, false),
-                value: MirOperand::Constant(MirLiteral::I64(0)),
-                size: MirOperand::Variable(occ_size_ssa, false),
+                size_bytes: occ_bytes as usize,
+                name: memo_table(&func.name, "occ"),
             },
         ];
 
@@ -531,10 +544,13 @@ impl CompoundMemoStrategy {
                         // --- 4. HIT BLOCK ---
                         rewritten.push(BasicBlock {
                             id: hit_id,
-                            instructions: vec![MirInstruction::Assign {
-                                dest,
-                                src: MirOperand::Variable(val_ssa, false),
-                            }],
+                            instructions: vec![
+                                memo_probe(orig_name, MemoCounter::Hit),
+                                MirInstruction::Assign {
+                                    dest,
+                                    src: MirOperand::Variable(val_ssa, false),
+                                },
+                            ],
                             terminator: MirTerminator::Branch(cont_id),
                         });
 
@@ -549,28 +565,34 @@ impl CompoundMemoStrategy {
                         // Path A: In-Bounds. Call and then store the result.
                         rewritten.push(BasicBlock {
                             id: miss_in_bounds_id,
-                            instructions: vec![MirInstruction::Call {
-                                name: format!("{}.inner", orig_name),
-                                dest,
-                                args: new_args.clone(),
-                                is_tail_call: false,
-                                return_type: return_type.clone(),
-                                arg_types: new_arg_types.clone(),
-                            }],
+                            instructions: vec![
+                                memo_probe(orig_name, MemoCounter::Miss),
+                                MirInstruction::Call {
+                                    name: format!("{}.inner", orig_name),
+                                    dest,
+                                    args: new_args.clone(),
+                                    is_tail_call: false,
+                                    return_type: return_type.clone(),
+                                    arg_types: new_arg_types.clone(),
+                                },
+                            ],
                             terminator: MirTerminator::Branch(store_id),
                         });
 
                         // Path B: Out-of-Bounds. Call and then skip the store.
                         rewritten.push(BasicBlock {
                             id: miss_out_of_bounds_id,
-                            instructions: vec![MirInstruction::Call {
-                                name: format!("{}.inner", orig_name),
-                                dest,
-                                args: new_args,
-                                is_tail_call: false,
-                                return_type: return_type.clone(),
-                                arg_types: new_arg_types,
-                            }],
+                            instructions: vec![
+                                memo_probe(orig_name, MemoCounter::Miss),
+                                MirInstruction::Call {
+                                    name: format!("{}.inner", orig_name),
+                                    dest,
+                                    args: new_args,
+                                    is_tail_call: false,
+                                    return_type: return_type.clone(),
+                                    arg_types: new_arg_types,
+                                },
+                            ],
                             terminator: MirTerminator::Branch(cont_id),
                         });
 
@@ -36,21 +36,23 @@
 /// factor → virtually zero collisions.
 ///
 /// ## Slot layout (keys_ptr)
-/// ```
+/// ```text
 /// byte  0 ..  7  : key₀  (i64)
 /// byte  8 .. 15  : key₁  (i64)
 /// …
 /// byte  N*8      : valid  (i8, stored as i8 but in an 8-byte slot)
 /// ```
 ///
-/// The `valid` flag at `keys_ptr + slot*key_stride + N*8` is initialised to
-/// 0 by the wrapper's `MemSet` and set to 1 after the first computation.
+/// The `valid` flag at `keys_ptr + slot*key_stride + N*8` starts at 0 (both
+/// tables are zero-initialised globals) and is set to 1 after the first
+/// computation.  The tables persist across wrapper calls, so later calls
+/// reuse earlier results; `clears` zeroes the key table to start over.
 
-use super::{MemoStrategy, max_ssa_in_function};
+use super::{MemoStrategy, max_ssa_in_function, memo_probe, memo_table};
 use crate::application::use_cases::registry_service::RegistryService;
 use crate::domain::entities::mir::{
-    BasicBlock, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand,
-    MirTerminator,
+    BasicBlock, MemoCounter, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral,
+    MirOperand, MirTerminator,
 };
 use crate::domain::entities::types::OnuType;
 use crate::domain::entities::ARENA_SIZE_BYTES;
@@ -58,12 +60,12 @@ use crate::domain::entities::ARENA_SIZE_BYTES;
 /// Knuth's multiplicative hashing constant (golden-ratio derivation).
 /// Provides excellent bit avalanche for small integer keys like Ackermann's
 /// (m, n) pairs.
-const HASH_PRIME: i64 = 2_654_435_769_i64;
+pub(super) const HASH_PRIME: i64 = 2_654_435_769_i64;
 
 /// Byte width of each key field stored in the keys/valid table.
 /// All function arguments are I64 (8 bytes) and the valid flag occupies one
 /// padded 8-byte slot for alignment.
-const KEY_FIELD_BYTES: i64 = 8;
+pub(super) const KEY_FIELD_BYTES: i64 = 8;
 
 // ---------------------------------------------------------------------------
 // Memory layout helper
@@ -123,6 +125,19 @@ impl MirBuilder {
 pub struct HashMemoStrategy;
 
 impl MemoStrategy for HashMemoStrategy {
+    fn name(&self) -> &'static str {
+        "hash"
+    }
+
+    fn cache_entries(&self, func: &MirFunction, _cache_size: usize, registry: &RegistryService) -> usize {
+        safe_table_size(func.args.len(), registry.size_of(&func.return_type))
+    }
+
+    fn reset_table(&self, func: &MirFunction, cache_size: usize, registry: &RegistryService) -> (String, usize) {
+        let table_size = self.cache_entries(func, cache_size, registry);
+        (memo_table(&func.name, "keys"), table_size * keys_stride(func.args.len()))
+    }
+
     fn create_wrapper_and_inner(
         &self,
         func: MirFunction,
@@ -181,8 +196,13 @@ impl MemoStrategy for HashMemoStrategy {
 // Wrapper builder
 // ---------------------------------------------------------------------------
 
-/// Emit a wrapper that allocates both tables, zeroes the key/valid table,
-/// then delegates to `{name}.inner`.
+/// Bytes per slot in the key/valid table: N keys plus the valid flag.
+fn keys_stride(n_dims: usize) -> usize {
+    (n_dims + 1) * KEY_FIELD_BYTES as usize
+}
+
+/// Emit a wrapper that takes pointers to both global tables, then delegates
+/// to `{name}.inner`.
 fn build_wrapper(
     func: &MirFunction,
     builder: &mut MirBuilder,
@@ -192,37 +212,20 @@ fn build_wrapper(
 ) -> (MirFunction, usize, usize) {
     let cache_ptr = builder.alloc_ssa();
     let keys_ptr = builder.alloc_ssa();
-    let cache_size_ssa = builder.alloc_ssa();
-    let keys_size_ssa = builder.alloc_ssa();
     let call_id = builder.alloc_block();
 
-    let cache_bytes = (table_size as i64) * (stride as i64);
-    let key_stride = ((n_dims + 1) as i64) * KEY_FIELD_BYTES;
-    let keys_bytes = (table_size as i64) * key_stride;
-
+    // The key/valid table is zeroed by the loader, and the cache values are
+    // written before being read, so neither needs initialising here.
     let entry_insts = vec![
-        MirInstruction::Assign {
-            dest: cache_size_ssa,
-            src: MirOperand::Constant(MirLiteral::I64(cache_bytes)),
-        },
-        MirInstruction::Alloc {
+        MirInstruction::GlobalAlloc {
             dest: cache_ptr,
-            size_bytes: MirOperand::Variable(cache_size_ssa, false),
-        },
-        MirInstruction::Assign {
-            dest: keys_size_ssa,
-            src: MirOperand::Constant(MirLiteral::I64(keys_bytes)),
+            size_bytes: table_size * stride,
+            name: memo_table(&func.name, "val"),
         },
-        MirInstruction::Alloc {
+        MirInstruction::GlobalAlloc {
             dest: keys_ptr,
-            size_bytes: MirOperand::Variable(keys_size_ssa, false),
-        },
-        // Zero only the key/valid table; the cache values are written before
-        // being read, so they need not be initialised.
-        MirInstruction::MemSet {
-            ptr: MirOperand::Variable(keys_ptr, false),
-            value: MirOperand::Constant(MirLiteral::I64(0)),
-            size: MirOperand::Variable(keys_size_ssa, false),
+            size_bytes: table_size * keys_stride(n_dims),
+            name: memo_table(&func.name, "keys"),
         },
     ];
 
@@ -505,6 +508,7 @@ fn rewrite_calls(
                     rewritten.push(BasicBlock {
                         id: hit_id,
                         instructions: vec![
+                            memo_probe(orig_name, MemoCounter::Hit),
                             MirInstruction::PointerOffset {
                                 dest: cache_hit_ptr_ssa,
                                 ptr: MirOperand::Variable(cache_ptr_ssa, false),
@@ -534,14 +538,17 @@ fn rewrite_calls(
                     // ── MISS_EMPTY block (slot was free → compute + store) ───
                     rewritten.push(BasicBlock {
                         id: miss_empty_id,
-                        instructions: vec![MirInstruction::Call {
-                            name: format!("{}.inner", orig_name),
-                            dest,
-                            args: new_args.clone(),
-                            is_tail_call: false,
-                            return_type: return_type.clone(),
-                            arg_types: new_arg_types.clone(),
-                        }],
+                        instructions: vec![
+                            memo_probe(orig_name, MemoCounter::Miss),
+                            MirInstruction::Call {
+                                name: format!("{}.inner", orig_name),
+                                dest,
+                                args: new_args.clone(),
+                                is_tail_call: false,
+                                return_type: return_type.clone(),
+                                arg_types: new_arg_types.clone(),
+                            },
+                        ],
                         terminator: MirTerminator::Branch(store_id),
                     });
 
@@ -549,14 +556,18 @@ fn rewrite_calls(
                     // Just recompute; do NOT evict the existing entry.
                     rewritten.push(BasicBlock {
                         id: miss_collision_id,
-                        instructions: vec![MirInstruction::Call {
-                            name: format!("{}.inner", orig_name),
-                            dest,
-                            args: new_args,
-                            is_tail_call: false,
-                            return_type: return_type.clone(),
-                            arg_types: new_arg_types,
-                        }],
+                        instructions: vec![
+                            memo_probe(orig_name, MemoCounter::Miss),
+                            memo_probe(orig_name, MemoCounter::Collision),
+                            MirInstruction::Call {
+                                name: format!("{}.inner", orig_name),
+                                dest,
+                                args: new_args,
+                                is_tail_call: false,
+                                return_type: return_type.clone(),
+                                arg_types: new_arg_types,
+                            },
+                        ],
                         terminator: MirTerminator::Branch(cont_id),
                     });
 
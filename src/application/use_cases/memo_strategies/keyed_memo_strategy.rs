@@ -0,0 +1,665 @@
+/// # KeyedMemoStrategy
+///
+/// A hash-table–based memoization strategy for functions whose arguments are
+/// not all plain integers: observed `text`, `boolean` and shape values.
+///
+/// ## Why it exists
+///
+/// `HashMemoStrategy` keys its table on raw `i64` arguments, so a memoized
+/// edit distance over two texts, or a grid walk keyed on a `Point`, could not
+/// be cached at all.  `KeyedMemoStrategy` keeps the same direct-mapped table
+/// but first flattens every argument into *key fields*:
+///
+/// | argument type   | key fields                                        |
+/// |-----------------|---------------------------------------------------|
+/// | `integer`       | one word                                          |
+/// | `boolean`       | one word (zero-extended)                          |
+/// | `text`          | one text field: `(len, data pointer)`             |
+/// | shape           | the key fields of each of its fields, in order    |
+///
+/// ## Hashing and comparison
+///
+/// ```text
+/// h = 0
+/// word:  h = h * PRIME + word
+/// text:  h = h * PRIME + len;  for each byte b: h = h * PRIME + b
+/// slot = h & (table_size − 1)
+/// ```
+///
+/// Texts hash by content, so equal texts at different addresses share an
+/// entry.  A hit is only taken after every key field compares equal — texts
+/// byte by byte — so two keys that collide in the same slot can never return
+/// each other's results.  As in `HashMemoStrategy`, a collision recomputes
+/// without evicting the current occupant.
+///
+/// ## Why storing the text pointer is safe
+///
+/// `MemoPass` only selects this strategy for bodies that never allocate,
+/// store or drop, so every text key reaching a recursive call is storage
+/// owned by a caller of the wrapper.  Entries only count for the wrapper
+/// call that filled them, so the stored pointers cannot outlive the bytes
+/// they point at and the key bytes need not be copied.
+///
+/// ## Epochs
+///
+/// Both tables are globals shared by every call.  Rather than zeroing the
+/// key table on entry, the wrapper bumps an epoch word stored after the last
+/// slot, and a slot is only valid when its flag equals the current epoch.
+/// Starting a new call is therefore O(1), and the zeroed table never matches
+/// because the first call runs in epoch 1.
+///
+/// ## Slot layout (keys_ptr)
+/// ```text
+/// word field : 8 bytes   (i64)
+/// text field : 16 bytes  (i64 len, i8* data)
+/// …
+/// valid      : 8 bytes   (i64 epoch that filled the slot)
+/// ```
+use super::{MemoStrategy, max_ssa_in_function, memo_probe, memo_table};
+use super::hash_memo_strategy::{HASH_PRIME, KEY_FIELD_BYTES};
+use crate::application::use_cases::registry_service::RegistryService;
+use crate::domain::entities::mir::{
+    BasicBlock, MemoCounter, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral,
+    MirOperand, MirTerminator,
+};
+use crate::domain::entities::types::OnuType;
+use crate::domain::entities::ARENA_SIZE_BYTES;
+
+// ---------------------------------------------------------------------------
+// Key layout
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KeyKind {
+    /// An integer or boolean, stored as one i64.
+    Word,
+    /// A text, stored as its length and data pointer.
+    Text,
+}
+
+impl KeyKind {
+    fn slot_bytes(self) -> i64 {
+        match self {
+            KeyKind::Word => KEY_FIELD_BYTES,
+            KeyKind::Text => 2 * KEY_FIELD_BYTES,
+        }
+    }
+}
+
+/// One flattened key component: the argument it is read from, the chain of
+/// shape-field indices leading to it, and how it is stored.
+#[derive(Debug, Clone, PartialEq)]
+struct KeyField {
+    arg: usize,
+    path: Vec<usize>,
+    kind: KeyKind,
+}
+
+/// Returns true when values of `typ` can take part in a memo key.
+pub fn is_keyable(typ: &OnuType, registry: &RegistryService) -> bool {
+    let mut fields = vec![];
+    flatten(typ, 0, vec![], registry, &mut fields)
+}
+
+fn flatten(
+    typ: &OnuType,
+    arg: usize,
+    path: Vec<usize>,
+    registry: &RegistryService,
+    out: &mut Vec<KeyField>,
+) -> bool {
+    match typ {
+        OnuType::I64 | OnuType::Boolean => {
+            out.push(KeyField { arg, path, kind: KeyKind::Word });
+            true
+        }
+        OnuType::Strings => {
+            out.push(KeyField { arg, path, kind: KeyKind::Text });
+            true
+        }
+        OnuType::Shape(name) => match registry.get_shape(name) {
+            Some(def) if !def.fields.is_empty() => {
+                def.fields.iter().enumerate().all(|(i, (_, field_type))| {
+                    let mut field_path = path.clone();
+                    field_path.push(i);
+                    flatten(field_type, arg, field_path, registry, out)
+                })
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn key_fields(func: &MirFunction, registry: &RegistryService) -> Vec<KeyField> {
+    let mut fields = vec![];
+    for (i, arg) in func.args.iter().enumerate() {
+        let keyable = flatten(&arg.typ, i, vec![], registry, &mut fields);
+        assert!(keyable, "KeyedMemoStrategy: argument '{}' of '{}' cannot be a memo key", arg.name, func.name);
+    }
+    fields
+}
+
+/// Byte offset of each key field within a slot, plus the offset of the
+/// valid flag (which is also the size of the key area).
+fn field_offsets(fields: &[KeyField]) -> (Vec<i64>, i64) {
+    let mut offsets = Vec::with_capacity(fields.len());
+    let mut at = 0;
+    for field in fields {
+        offsets.push(at);
+        at += field.kind.slot_bytes();
+    }
+    (offsets, at)
+}
+
+/// Size of the key table: every slot plus the trailing epoch word.
+fn keys_bytes(table_size: usize, key_stride: usize) -> usize {
+    table_size * key_stride + KEY_FIELD_BYTES as usize
+}
+
+/// Largest power-of-2 table size whose value table plus key/valid table
+/// fits within `ARENA_SIZE_BYTES`.
+fn safe_table_size(stride: usize, key_stride: usize) -> usize {
+    let bytes_per_slot = (stride + key_stride).max(1);
+    let max_entries = ARENA_SIZE_BYTES / bytes_per_slot;
+    let mut p = 1usize;
+    while p * 2 <= max_entries {
+        p *= 2;
+    }
+    p
+}
+
+// ---------------------------------------------------------------------------
+// Private MIR emitter
+// ---------------------------------------------------------------------------
+
+/// Like the builders in the sibling strategies, but also tracks the block
+/// being filled so the byte loops over text keys read top to bottom.
+struct Emitter {
+    next_ssa: usize,
+    next_block_id: usize,
+    current: usize,
+    insts: Vec<MirInstruction>,
+    blocks: Vec<BasicBlock>,
+}
+
+impl Emitter {
+    fn new(func: &MirFunction) -> Self {
+        Self {
+            next_ssa: max_ssa_in_function(func) + 1,
+            next_block_id: func.blocks.iter().map(|b| b.id).max().unwrap_or(0) + 1,
+            current: 0,
+            insts: vec![],
+            blocks: vec![],
+        }
+    }
+
+    fn alloc_ssa(&mut self) -> usize {
+        let id = self.next_ssa;
+        self.next_ssa += 1;
+        id
+    }
+
+    fn alloc_block(&mut self) -> usize {
+        let id = self.next_block_id;
+        self.next_block_id += 1;
+        id
+    }
+
+    fn open(&mut self, id: usize) {
+        self.current = id;
+    }
+
+    fn close(&mut self, terminator: MirTerminator) {
+        self.blocks.push(BasicBlock {
+            id: self.current,
+            instructions: std::mem::take(&mut self.insts),
+            terminator,
+        });
+    }
+
+    fn emit(&mut self, inst: MirInstruction) {
+        self.insts.push(inst);
+    }
+
+    fn assign(&mut self, dest: usize, src: MirOperand) {
+        self.emit(MirInstruction::Assign { dest, src });
+    }
+
+    fn binop(&mut self, op: MirBinOp, lhs: MirOperand, rhs: MirOperand) -> MirOperand {
+        let dest_type = match op {
+            MirBinOp::Eq | MirBinOp::Ne | MirBinOp::Lt => OnuType::Boolean,
+            _ => OnuType::I64,
+        };
+        let dest = self.alloc_ssa();
+        self.emit(MirInstruction::BinaryOperation { dest, op, lhs, rhs, dest_type });
+        var(dest)
+    }
+
+    fn index(&mut self, subject: MirOperand, index: usize) -> MirOperand {
+        let dest = self.alloc_ssa();
+        self.emit(MirInstruction::Index { dest, subject, index });
+        var(dest)
+    }
+
+    fn load(&mut self, base: usize, offset: MirOperand, typ: OnuType) -> MirOperand {
+        let ptr = self.offset(var(base), offset);
+        let dest = self.alloc_ssa();
+        self.emit(MirInstruction::Load { dest, ptr, typ });
+        var(dest)
+    }
+
+    fn store(&mut self, base: usize, offset: MirOperand, value: MirOperand, typ: OnuType) {
+        let ptr = self.offset(var(base), offset);
+        self.emit(MirInstruction::TypedStore { ptr, value, typ });
+    }
+
+    fn offset(&mut self, ptr: MirOperand, offset: MirOperand) -> MirOperand {
+        let dest = self.alloc_ssa();
+        self.emit(MirInstruction::PointerOffset { dest, ptr, offset });
+        var(dest)
+    }
+
+    /// Emits `for i in 0..len { body(i) }`, leaving the emitter in the exit
+    /// block.  `body` may branch to `break_to` instead of continuing.
+    fn byte_loop(&mut self, len: &MirOperand, mut body: impl FnMut(&mut Self, MirOperand)) {
+        let i = self.alloc_ssa();
+        self.assign(i, int(0));
+        let cond_id = self.alloc_block();
+        let body_id = self.alloc_block();
+        let exit_id = self.alloc_block();
+        self.close(MirTerminator::Branch(cond_id));
+
+        self.open(cond_id);
+        let more = self.binop(MirBinOp::Lt, var(i), len.clone());
+        self.close(MirTerminator::CondBranch { condition: more, then_block: body_id, else_block: exit_id });
+
+        self.open(body_id);
+        body(self, var(i));
+        let next = self.binop(MirBinOp::Add, var(i), int(1));
+        self.assign(i, next);
+        self.close(MirTerminator::Branch(cond_id));
+
+        self.open(exit_id);
+    }
+
+    /// Branches to a fresh block when `condition` holds, else to `otherwise`.
+    fn continue_if(&mut self, condition: MirOperand, otherwise: usize) {
+        let next = self.alloc_block();
+        self.close(MirTerminator::CondBranch { condition, then_block: next, else_block: otherwise });
+        self.open(next);
+    }
+}
+
+fn int(n: i64) -> MirOperand {
+    MirOperand::Constant(MirLiteral::I64(n))
+}
+
+fn var(ssa: usize) -> MirOperand {
+    MirOperand::Variable(ssa, false)
+}
+
+/// A key field's value at one call site.
+enum KeyValue {
+    Word(MirOperand),
+    Text { len: MirOperand, ptr: MirOperand },
+}
+
+// ---------------------------------------------------------------------------
+// Strategy
+// ---------------------------------------------------------------------------
+
+pub struct KeyedMemoStrategy;
+
+impl MemoStrategy for KeyedMemoStrategy {
+    fn name(&self) -> &'static str {
+        "keyed"
+    }
+
+    fn cache_entries(&self, func: &MirFunction, _cache_size: usize, registry: &RegistryService) -> usize {
+        let (_, valid_offset) = field_offsets(&key_fields(func, registry));
+        let key_stride = valid_offset + KEY_FIELD_BYTES;
+        safe_table_size(registry.size_of(&func.return_type), key_stride as usize)
+    }
+
+    fn reset_table(&self, func: &MirFunction, cache_size: usize, registry: &RegistryService) -> (String, usize) {
+        let (_, valid_offset) = field_offsets(&key_fields(func, registry));
+        let key_stride = (valid_offset + KEY_FIELD_BYTES) as usize;
+        let table_size = self.cache_entries(func, cache_size, registry);
+        (memo_table(&func.name, "keys"), keys_bytes(table_size, key_stride))
+    }
+
+    fn create_wrapper_and_inner(
+        &self,
+        func: MirFunction,
+        _cache_size: usize,
+        registry: &RegistryService,
+    ) -> (MirFunction, MirFunction) {
+        let mut emitter = Emitter::new(&func);
+        let fields = key_fields(&func, registry);
+        let (offsets, valid_offset) = field_offsets(&fields);
+        let key_stride = valid_offset + KEY_FIELD_BYTES;
+        let stride = registry.size_of(&func.return_type);
+        let table_size = safe_table_size(stride, key_stride as usize);
+
+        let wrapper = build_wrapper(&func, &mut emitter, stride, key_stride, table_size);
+
+        let mut inner = func.clone();
+        inner.name = format!("{}.inner", func.name);
+        // Reads and writes the tables: must not be treated as ReadNone.
+        inner.is_pure_data_leaf = false;
+
+        let cache_ptr = emitter.alloc_ssa();
+        let keys_ptr = emitter.alloc_ssa();
+        inner.args.push(MirArgument { name: "cache_ptr".to_string(), typ: OnuType::Ptr, ssa_var: cache_ptr });
+        inner.args.push(MirArgument { name: "keys_ptr".to_string(), typ: OnuType::Ptr, ssa_var: keys_ptr });
+
+        let layout = Layout {
+            function: &func.name,
+            fields: &fields,
+            offsets: &offsets,
+            valid_offset,
+            key_stride,
+            stride: stride as i64,
+            mask: (table_size - 1) as i64,
+            epoch_offset: table_size as i64 * key_stride,
+            cache_ptr,
+            keys_ptr,
+        };
+        inner.blocks = rewrite_calls(inner.blocks, &mut emitter, &func.name, &func.return_type, &layout);
+
+        (wrapper, inner)
+    }
+}
+
+/// Emit a wrapper that takes pointers to both global tables, starts a new
+/// epoch, then delegates to `{name}.inner`.
+fn build_wrapper(
+    func: &MirFunction,
+    emitter: &mut Emitter,
+    stride: usize,
+    key_stride: i64,
+    table_size: usize,
+) -> MirFunction {
+    let cache_ptr = emitter.alloc_ssa();
+    let keys_ptr = emitter.alloc_ssa();
+    let epoch_ptr = emitter.alloc_ssa();
+    let epoch = emitter.alloc_ssa();
+    let next_epoch = emitter.alloc_ssa();
+    let res = emitter.alloc_ssa();
+
+    let mut args: Vec<MirOperand> = func.args.iter().map(|a| var(a.ssa_var)).collect();
+    args.push(var(cache_ptr));
+    args.push(var(keys_ptr));
+    let mut arg_types: Vec<OnuType> = func.args.iter().map(|a| a.typ.clone()).collect();
+    arg_types.push(OnuType::Ptr);
+    arg_types.push(OnuType::Ptr);
+
+    let instructions = vec![
+        MirInstruction::GlobalAlloc {
+            dest: cache_ptr,
+            size_bytes: table_size * stride,
+            name: memo_table(&func.name, "val"),
+        },
+        MirInstruction::GlobalAlloc {
+            dest: keys_ptr,
+            size_bytes: keys_bytes(table_size, key_stride as usize),
+            name: memo_table(&func.name, "keys"),
+        },
+        MirInstruction::PointerOffset {
+            dest: epoch_ptr,
+            ptr: var(keys_ptr),
+            offset: int(table_size as i64 * key_stride),
+        },
+        MirInstruction::Load { dest: epoch, ptr: var(epoch_ptr), typ: OnuType::I64 },
+        MirInstruction::BinaryOperation {
+            dest: next_epoch,
+            op: MirBinOp::Add,
+            lhs: var(epoch),
+            rhs: int(1),
+            dest_type: OnuType::I64,
+        },
+        MirInstruction::TypedStore { ptr: var(epoch_ptr), value: var(next_epoch), typ: OnuType::I64 },
+        MirInstruction::Call {
+            name: format!("{}.inner", func.name),
+            dest: res,
+            args,
+            return_type: func.return_type.clone(),
+            arg_types,
+            is_tail_call: false,
+        },
+    ];
+
+    MirFunction {
+        blocks: vec![BasicBlock { id: 0, instructions, terminator: MirTerminator::Return(var(res)) }],
+        is_pure_data_leaf: false,
+        ..func.clone()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Call-site rewriter
+// ---------------------------------------------------------------------------
+
+struct Layout<'a> {
+    function: &'a str,
+    fields: &'a [KeyField],
+    offsets: &'a [i64],
+    valid_offset: i64,
+    key_stride: i64,
+    stride: i64,
+    mask: i64,
+    epoch_offset: i64,
+    cache_ptr: usize,
+    keys_ptr: usize,
+}
+
+/// Rewrite every call to `orig_name(args…)` inside `blocks` to a keyed
+/// lookup with a `{orig_name}.inner(args…, cache_ptr, keys_ptr)` fallback.
+///
+/// ```text
+/// PREAMBLE   → read key fields → hash → slot → valid? → CHECK : MISS_EMPTY
+/// CHECK      → compare every key field (texts byte by byte)
+///            → all equal? → HIT : MISS_COLLISION
+/// HIT        → load cached value → CONT
+/// MISS_EMPTY → call .inner → STORE → CONT
+/// MISS_COLLISION → call .inner (no eviction) → CONT
+/// ```
+fn rewrite_calls(
+    blocks: Vec<BasicBlock>,
+    emitter: &mut Emitter,
+    orig_name: &str,
+    ret_type: &OnuType,
+    layout: &Layout,
+) -> Vec<BasicBlock> {
+    let inner_name = format!("{}.inner", orig_name);
+    for block in blocks {
+        emitter.open(block.id);
+        for inst in block.instructions {
+            match inst {
+                MirInstruction::Call { ref name, dest, ref args, ref arg_types, .. }
+                    if name == orig_name =>
+                {
+                    let mut inner_args = args.clone();
+                    inner_args.push(var(layout.cache_ptr));
+                    inner_args.push(var(layout.keys_ptr));
+                    let mut inner_arg_types = arg_types.clone();
+                    inner_arg_types.push(OnuType::Ptr);
+                    inner_arg_types.push(OnuType::Ptr);
+                    let inner_call = MirInstruction::Call {
+                        name: inner_name.clone(),
+                        dest,
+                        args: inner_args,
+                        return_type: ret_type.clone(),
+                        arg_types: inner_arg_types,
+                        is_tail_call: false,
+                    };
+                    emit_lookup(emitter, args, dest, ret_type, inner_call, layout);
+                }
+                inst => emitter.emit(inst),
+            }
+        }
+        emitter.close(block.terminator);
+    }
+    std::mem::take(&mut emitter.blocks)
+}
+
+fn emit_lookup(
+    e: &mut Emitter,
+    args: &[MirOperand],
+    dest: usize,
+    ret_type: &OnuType,
+    inner_call: MirInstruction,
+    layout: &Layout,
+) {
+    let hit_id = e.alloc_block();
+    let miss_empty_id = e.alloc_block();
+    let miss_collision_id = e.alloc_block();
+    let cont_id = e.alloc_block();
+
+    // ── PREAMBLE: read each key field out of its argument ────────────────
+    let values: Vec<KeyValue> = layout
+        .fields
+        .iter()
+        .map(|field| {
+            let mut value = args[field.arg].clone();
+            for &i in &field.path {
+                value = e.index(value, i);
+            }
+            match field.kind {
+                // `+ 0` widens booleans to the i64 the key table stores.
+                KeyKind::Word => KeyValue::Word(e.binop(MirBinOp::Add, value, int(0))),
+                KeyKind::Text => KeyValue::Text { len: e.index(value.clone(), 0), ptr: e.index(value, 1) },
+            }
+        })
+        .collect();
+
+    // hash = Horner polynomial over every word, text length and text byte
+    let hash = e.alloc_ssa();
+    e.assign(hash, int(0));
+    let mix = |e: &mut Emitter, word: MirOperand| {
+        let scaled = e.binop(MirBinOp::Mul, var(hash), int(HASH_PRIME));
+        let mixed = e.binop(MirBinOp::Add, scaled, word);
+        e.assign(hash, mixed);
+    };
+    for value in &values {
+        match value {
+            KeyValue::Word(word) => mix(e, word.clone()),
+            KeyValue::Text { len, ptr } => {
+                mix(e, len.clone());
+                e.byte_loop(len, |e, i| {
+                    let at = e.offset(ptr.clone(), i);
+                    let byte = e.alloc_ssa();
+                    e.emit(MirInstruction::Load { dest: byte, ptr: at, typ: OnuType::I8 });
+                    mix(e, var(byte));
+                });
+            }
+        }
+    }
+
+    let slot = e.binop(MirBinOp::And, var(hash), int(layout.mask));
+    let key_base = e.binop(MirBinOp::Mul, slot.clone(), int(layout.key_stride));
+    let cache_byte = e.binop(MirBinOp::Mul, slot, int(layout.stride));
+    let valid_byte = e.binop(MirBinOp::Add, key_base.clone(), int(layout.valid_offset));
+    let epoch = e.load(layout.keys_ptr, int(layout.epoch_offset), OnuType::I64);
+    let valid = e.load(layout.keys_ptr, valid_byte.clone(), OnuType::I64);
+    let occupied = e.binop(MirBinOp::Eq, valid, epoch.clone());
+    e.continue_if(occupied, miss_empty_id);
+
+    // ── CHECK: every stored key field must equal the current one ─────────
+    for (value, &field_offset) in values.iter().zip(layout.offsets) {
+        let at = e.binop(MirBinOp::Add, key_base.clone(), int(field_offset));
+        match value {
+            KeyValue::Word(word) => {
+                let stored = e.load(layout.keys_ptr, at, OnuType::I64);
+                let same = e.binop(MirBinOp::Eq, stored, word.clone());
+                e.continue_if(same, miss_collision_id);
+            }
+            KeyValue::Text { len, ptr } => {
+                let stored_len = e.load(layout.keys_ptr, at.clone(), OnuType::I64);
+                let same_len = e.binop(MirBinOp::Eq, stored_len, len.clone());
+                e.continue_if(same_len, miss_collision_id);
+                let ptr_at = e.binop(MirBinOp::Add, at, int(KEY_FIELD_BYTES));
+                let stored_ptr = e.load(layout.keys_ptr, ptr_at, OnuType::Ptr);
+                e.byte_loop(len, |e, i| {
+                    let stored_at = e.offset(stored_ptr.clone(), i.clone());
+                    let current_at = e.offset(ptr.clone(), i);
+                    let stored_byte = e.alloc_ssa();
+                    e.emit(MirInstruction::Load { dest: stored_byte, ptr: stored_at, typ: OnuType::I8 });
+                    let current_byte = e.alloc_ssa();
+                    e.emit(MirInstruction::Load { dest: current_byte, ptr: current_at, typ: OnuType::I8 });
+                    let same = e.binop(MirBinOp::Eq, var(stored_byte), var(current_byte));
+                    e.continue_if(same, miss_collision_id);
+                });
+            }
+        }
+    }
+    e.close(MirTerminator::Branch(hit_id));
+
+    // ── HIT ──────────────────────────────────────────────────────────────
+    e.open(hit_id);
+    e.emit(memo_probe(layout.function, MemoCounter::Hit));
+    let cached = e.load(layout.cache_ptr, cache_byte.clone(), ret_type.clone());
+    e.assign(dest, cached);
+    e.close(MirTerminator::Branch(cont_id));
+
+    // ── MISS_COLLISION: recompute, keep the current occupant ─────────────
+    e.open(miss_collision_id);
+    e.emit(memo_probe(layout.function, MemoCounter::Miss));
+    e.emit(memo_probe(layout.function, MemoCounter::Collision));
+    e.emit(inner_call.clone());
+    e.close(MirTerminator::Branch(cont_id));
+
+    // ── MISS_EMPTY: compute, then fill the slot ──────────────────────────
+    e.open(miss_empty_id);
+    e.emit(memo_probe(layout.function, MemoCounter::Miss));
+    e.emit(inner_call);
+    e.store(layout.cache_ptr, cache_byte, var(dest), ret_type.clone());
+    for (value, &field_offset) in values.iter().zip(layout.offsets) {
+        let at = e.binop(MirBinOp::Add, key_base.clone(), int(field_offset));
+        match value {
+            KeyValue::Word(word) => e.store(layout.keys_ptr, at, word.clone(), OnuType::I64),
+            KeyValue::Text { len, ptr } => {
+                e.store(layout.keys_ptr, at.clone(), len.clone(), OnuType::I64);
+                let ptr_at = e.binop(MirBinOp::Add, at, int(KEY_FIELD_BYTES));
+                e.store(layout.keys_ptr, ptr_at, ptr.clone(), OnuType::Ptr);
+            }
+        }
+    }
+    e.store(layout.keys_ptr, valid_byte, epoch, OnuType::I64);
+    e.close(MirTerminator::Branch(cont_id));
+
+    e.open(cont_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shapes_flatten_into_their_fields() {
+        let mut registry = RegistryService::new();
+        registry.add_shape(
+            "Cell",
+            vec![
+                ("label".to_string(), OnuType::Strings),
+                ("row".to_string(), OnuType::I64),
+                ("lit".to_string(), OnuType::Boolean),
+            ],
+            vec![],
+        );
+        let mut fields = vec![];
+        assert!(flatten(&OnuType::Shape("Cell".to_string()), 1, vec![], &registry, &mut fields));
+        let kinds: Vec<(Vec<usize>, KeyKind)> = fields.iter().map(|f| (f.path.clone(), f.kind)).collect();
+        assert_eq!(kinds, vec![(vec![0], KeyKind::Text), (vec![1], KeyKind::Word), (vec![2], KeyKind::Word)]);
+        assert_eq!(field_offsets(&fields), (vec![0, 16, 24], 32));
+    }
+
+    #[test]
+    fn test_unkeyable_types_are_rejected() {
+        let registry = RegistryService::new();
+        assert!(is_keyable(&OnuType::Strings, &registry));
+        assert!(!is_keyable(&OnuType::Matrix, &registry));
+        assert!(!is_keyable(&OnuType::Shape("Unknown".to_string()), &registry));
+    }
+}
@@ -1,8 +1,9 @@
 pub mod compound_memo_strategy;
 pub mod hash_memo_strategy;
+pub mod keyed_memo_strategy;
 pub mod primitive_memo_strategy;
 
-use crate::domain::entities::mir::MirFunction;
+use crate::domain::entities::mir::{MemoCounter, MirFunction, MirInstruction};
 
 use crate::application::use_cases::registry_service::RegistryService;
 
@@ -13,6 +14,32 @@ pub trait MemoStrategy {
         cache_size: usize,
         registry: &RegistryService,
     ) -> (MirFunction, MirFunction);
+
+    /// Short name shown by `--memo-report`.
+    fn name(&self) -> &'static str;
+
+    /// Number of cache slots the wrapper allocates for `func`.
+    fn cache_entries(&self, _func: &MirFunction, cache_size: usize, _registry: &RegistryService) -> usize {
+        cache_size
+    }
+
+    /// The global table whose zeroing empties the cache (occupancy flags or
+    /// stored keys) and its size in bytes.  `clears` lowers to a `MemSet`
+    /// over it.
+    fn reset_table(&self, func: &MirFunction, cache_size: usize, registry: &RegistryService) -> (String, usize);
+}
+
+/// Name of the module-level global that holds one of `function`'s memo
+/// tables.  Every table is a `GlobalAlloc`, so it is sized once and shared by
+/// all calls to the wrapper.
+pub fn memo_table(function: &str, table: &str) -> String {
+    format!("{}_cache_{}", function, table)
+}
+
+/// Marks a cache lookup outcome for `--memo-report`.  Every strategy emits
+/// these probes; `MemoPass` strips them from uninstrumented builds.
+pub fn memo_probe(function: &str, counter: MemoCounter) -> MirInstruction {
+    MirInstruction::MemoCount { function: function.to_string(), counter }
 }
 
 pub fn max_ssa_in_function(func: &MirFunction) -> usize {
@@ -24,10 +51,14 @@ pub fn max_ssa_in_function(func: &MirFunction) -> usize {
                 crate::domain::entities::mir::MirInstruction::BinaryOperation { dest, .. } => {
                     Some(*dest)
                 }
+                crate::domain::entities::mir::MirInstruction::CheckedArithmetic { dest, .. } => {
+                    Some(*dest)
+                }
                 crate::domain::entities::mir::MirInstruction::Call { dest, .. } => Some(*dest),
                 crate::domain::entities::mir::MirInstruction::Tuple { dest, .. } => Some(*dest),
                 crate::domain::entities::mir::MirInstruction::Index { dest, .. } => Some(*dest),
                 crate::domain::entities::mir::MirInstruction::Alloc { dest, .. } => Some(*dest),
+                crate::domain::entities::mir::MirInstruction::GlobalAlloc { dest, .. } => Some(*dest),
                 crate::domain::entities::mir::MirInstruction::PointerOffset { dest, .. } => {
                     Some(*dest)
                 }
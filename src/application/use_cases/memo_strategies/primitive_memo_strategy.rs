@@ -1,8 +1,8 @@
-use super::{MemoStrategy, max_ssa_in_function};
+use super::{MemoStrategy, max_ssa_in_function, memo_probe, memo_table};
 use crate::application::use_cases::registry_service::RegistryService;
 use crate::domain::entities::mir::{
-    BasicBlock, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand,
-    MirTerminator,
+    BasicBlock, MemoCounter, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral,
+    MirOperand, MirTerminator,
 };
 use crate::domain::entities::types::OnuType;
 
@@ -114,6 +114,14 @@ impl<'a> CacheAccessor<'a> {
 pub struct PrimitiveMemoStrategy;
 
 impl MemoStrategy for PrimitiveMemoStrategy {
+    fn name(&self) -> &'static str {
+        "primitive"
+    }
+
+    fn reset_table(&self, func: &MirFunction, cache_size: usize, _registry: &RegistryService) -> (String, usize) {
+        (memo_table(&func.name, "occ"), cache_size)
+    }
+
     fn create_wrapper_and_inner(
         &self,
         func: MirFunction,
@@ -192,12 +200,12 @@ impl PrimitiveMemoStrategy {
             MirInstruction::GlobalAlloc {
                 dest: cache_ptr,
                 size_bytes: total_bytes,
-                name: format!("{}_cache_val", func.name),
+                name: memo_table(&func.name, "val"),
             },
             MirInstruction::GlobalAlloc {
                 dest: occ_ptr,
                 size_bytes: cache_size,
-                name: format!("{}_cache_occ", func.name),
+                name: memo_table(&func.name, "occ"),
             },
         ];
 
@@ -377,7 +385,7 @@ impl PrimitiveMemoStrategy {
                         });
 
                         // 4. HIT BLOCK
-                        let mut hit_insts = vec![];
+                        let mut hit_insts = vec![memo_probe(orig_name, MemoCounter::Hit)];
                         let val = accessor.emit_load(&mut hit_insts, val_offset);
                         hit_insts.push(MirInstruction::Assign {
                             dest,
@@ -399,14 +407,17 @@ impl PrimitiveMemoStrategy {
 
                         rewritten.push(BasicBlock {
                             id: raw_call_id,
-                            instructions: vec![MirInstruction::Call {
-                                name: format!("{}.inner", orig_name),
-                                dest,
-                                args: raw_args,
-                                is_tail_call: false, // Must be false as we are not in tail position
-                                return_type: return_type.clone(),
-                                arg_types: raw_arg_types,
-                            }],
+                            instructions: vec![
+                                memo_probe(orig_name, MemoCounter::Miss),
+                                MirInstruction::Call {
+                                    name: format!("{}.inner", orig_name),
+                                    dest,
+                                    args: raw_args,
+                                    is_tail_call: false, // Must be false as we are not in tail position
+                                    return_type: return_type.clone(),
+                                    arg_types: raw_arg_types,
+                                },
+                            ],
                             terminator: MirTerminator::Branch(cont_id),
                         });
 
@@ -420,14 +431,17 @@ impl PrimitiveMemoStrategy {
 
                         rewritten.push(BasicBlock {
                             id: miss_id,
-                            instructions: vec![MirInstruction::Call {
-                                name: format!("{}.inner", orig_name),
-                                dest,
-                                args: miss_args,
-                                is_tail_call: false, // Must be false as we follow with store
-                                return_type: return_type.clone(),
-                                arg_types: miss_arg_types,
-                            }],
+                            instructions: vec![
+                                memo_probe(orig_name, MemoCounter::Miss),
+                                MirInstruction::Call {
+                                    name: format!("{}.inner", orig_name),
+                                    dest,
+                                    args: miss_args,
+                                    is_tail_call: false, // Must be false as we follow with store
+                                    return_type: return_type.clone(),
+                                    arg_types: miss_arg_types,
+                                },
+                            ],
                             terminator: MirTerminator::Branch(store_id),
                         });
 
@@ -3,8 +3,9 @@
 /// This struct encapsulates the state and logic for constructing a single MIR function.
 /// It handles block management, SSA variable generation, and variable scoping.
 
-use crate::domain::entities::mir::{MirFunction, BasicBlock, MirInstruction, MirTerminator};
-use crate::domain::entities::types::OnuType;
+use crate::domain::entities::mir::{MirFunction, BasicBlock, MirInstruction, MirLiteral, MirOperand, MirTerminator};
+use crate::application::use_cases::mir_lowering::lower_big_integer::BIG_FROM_INTEGER;
+use crate::domain::entities::types::{InlineHint, OnuType};
 use std::collections::HashMap;
 
 pub struct MirBuilder {
@@ -46,6 +47,16 @@ impl MirBuilder {
         }
     }
 
+    /// Name of the behavior being built.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Declared return type of the behavior being built.
+    pub fn return_type(&self) -> &OnuType {
+        &self.return_type
+    }
+
     pub fn add_arg(&mut self, name: String, typ: OnuType, ssa_var: usize) {
         self.ssa_types.insert(ssa_var, typ.clone());
         self.ssa_is_dynamic.insert(ssa_var, false); // Arguments are typically not owned by the caller in a way that requires free
@@ -77,6 +88,38 @@ impl MirBuilder {
         self.ssa_types.insert(ssa_var, typ.clone());
     }
 
+    /// Type of an operand: the recorded SSA type, or the literal's own type.
+    pub fn operand_type(&self, op: &MirOperand) -> Option<OnuType> {
+        match op {
+            MirOperand::Variable(ssa_var, _) => self.resolve_ssa_type(*ssa_var),
+            MirOperand::Constant(MirLiteral::I64(_)) => Some(OnuType::I64),
+            MirOperand::Constant(MirLiteral::Boolean(_)) => Some(OnuType::Boolean),
+            MirOperand::Constant(MirLiteral::Text(_)) => Some(OnuType::Strings),
+            MirOperand::Constant(MirLiteral::WideInt(_, bits)) => Some(OnuType::WideInt(*bits)),
+            MirOperand::Constant(MirLiteral::F64(_)) => Some(OnuType::F64),
+            MirOperand::Constant(MirLiteral::Nothing) => Some(OnuType::Nothing),
+        }
+    }
+
+    /// Converts an integer operand to `big-integer` when `target` asks for
+    /// one; any other operand is returned unchanged.
+    pub fn promote_to(&mut self, op: MirOperand, target: &OnuType) -> MirOperand {
+        if *target != OnuType::BigInt || self.operand_type(&op) != Some(OnuType::I64) {
+            return op;
+        }
+        let dest = self.new_ssa();
+        self.emit(MirInstruction::Call {
+            dest,
+            name: BIG_FROM_INTEGER.to_string(),
+            args: vec![op],
+            return_type: OnuType::BigInt,
+            arg_types: vec![OnuType::I64],
+            is_tail_call: false,
+        });
+        self.set_ssa_type(dest, OnuType::BigInt);
+        MirOperand::Variable(dest, true)
+    }
+
     pub fn set_ssa_is_dynamic(&mut self, ssa_var: usize, is_dynamic: bool) {
         self.ssa_is_dynamic.insert(ssa_var, is_dynamic);
     }
@@ -222,11 +265,22 @@ impl MirBuilder {
         self.emit(MirInstruction::BinaryOperation { dest, op, lhs, rhs, dest_type });
     }
 
+    pub fn build_runtime_check(&mut self, condition: crate::domain::entities::mir::MirOperand, message: String) {
+        self.emit(MirInstruction::RuntimeCheck { condition, message });
+    }
+
     pub fn build_assign(&mut self, dest: usize, src: crate::domain::entities::mir::MirOperand) {
         self.emit(MirInstruction::Assign { dest, src });
     }
 
     pub fn terminate(&mut self, term: MirTerminator) {
+        // A `big-integer` behavior may return plain integers from some paths.
+        let term = match term {
+            MirTerminator::Return(op) if self.return_type == OnuType::BigInt => {
+                MirTerminator::Return(self.promote_to(op, &OnuType::BigInt))
+            }
+            term => term,
+        };
         if let Some(idx) = self.current_block_idx {
             eprintln!("[DEBUG] Terminating block {} with {:?}", idx, term);
             self.blocks[idx].terminator = term;
@@ -250,6 +304,8 @@ impl MirBuilder {
             is_pure_data_leaf: self.is_pure_data_leaf,
             diminishing: self.diminishing,
             memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
         }
     }
 }
@@ -0,0 +1,111 @@
+/// Big-Integer Lowering: `big-integer` Operations as Runtime Calls
+///
+/// Arithmetic, comparison and `as-text` on `big-integer` operands become
+/// calls to the `__onu_big_*` helpers that codegen emits; an `integer`
+/// operand is promoted with `__onu_big_from_integer` first.  The helpers
+/// only allocate their results in the arena, so calling them does not make
+/// a behavior impure.
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::error::OnuError;
+use crate::domain::entities::hir::HirBinOp;
+use crate::domain::entities::mir::{MirBinOp, MirInstruction, MirLiteral, MirOperand};
+use crate::domain::entities::types::OnuType;
+
+pub const BIG_FROM_INTEGER: &str = "__onu_big_from_integer";
+const BIG_COMPARE: &str = "__onu_big_compare";
+const BIG_AS_TEXT: &str = "__onu_big_as_text";
+
+/// True for the runtime helpers behind `big-integer`.
+pub fn is_big_integer_helper(name: &str) -> bool {
+    name.starts_with("__onu_big_")
+}
+
+pub struct BigIntegerLowerer;
+
+impl BigIntegerLowerer {
+    /// Lowers `op` to a helper call when either operand is a `big-integer`,
+    /// or returns `None` to leave integer arithmetic to `BinaryOpLowerer`.
+    /// `zero_check` carries the runtime-check message for division.
+    pub fn lower_binop(
+        op: &HirBinOp,
+        lhs: MirOperand,
+        rhs: MirOperand,
+        builder: &mut MirBuilder,
+        zero_check: Option<String>,
+    ) -> Result<Option<MirOperand>, OnuError> {
+        let is_big = |builder: &MirBuilder, o: &MirOperand| builder.operand_type(o) == Some(OnuType::BigInt);
+        if !is_big(builder, &lhs) && !is_big(builder, &rhs) {
+            return Ok(None);
+        }
+        let (helper, comparison) = match op {
+            HirBinOp::Add => ("__onu_big_add", None),
+            HirBinOp::Sub => ("__onu_big_sub", None),
+            HirBinOp::Mul => ("__onu_big_mul", None),
+            HirBinOp::Div => ("__onu_big_div", None),
+            HirBinOp::Equal => (BIG_COMPARE, Some(MirBinOp::Eq)),
+            HirBinOp::NotEqual => (BIG_COMPARE, Some(MirBinOp::Ne)),
+            HirBinOp::LessThan => (BIG_COMPARE, Some(MirBinOp::Lt)),
+            HirBinOp::GreaterThan => (BIG_COMPARE, Some(MirBinOp::Gt)),
+            _ => {
+                return Err(OnuError::GrammarViolation {
+                    message: format!(
+                        "bitwise and wrapping operators are not defined for big-integer values in {}",
+                        builder.name()
+                    ),
+                    span: Default::default(),
+                });
+            }
+        };
+        let lhs = builder.promote_to(lhs, &OnuType::BigInt);
+        let rhs = builder.promote_to(rhs, &OnuType::BigInt);
+
+        if let Some(message) = zero_check
+            && matches!(op, HirBinOp::Div)
+        {
+            let zero = builder.promote_to(MirOperand::Constant(MirLiteral::I64(0)), &OnuType::BigInt);
+            let order = Self::call(builder, BIG_COMPARE, vec![rhs.clone(), zero], OnuType::I64);
+            let nonzero = builder.new_ssa();
+            builder.set_ssa_type(nonzero, OnuType::Boolean);
+            builder.build_binop(nonzero, MirBinOp::Ne, order, MirOperand::Constant(MirLiteral::I64(0)));
+            builder.build_runtime_check(MirOperand::Variable(nonzero, false), message);
+        }
+
+        let Some(cmp) = comparison else {
+            return Ok(Some(Self::call(builder, helper, vec![lhs, rhs], OnuType::BigInt)));
+        };
+        // The comparison helper returns -1, 0 or 1; compare that with zero.
+        let order = Self::call(builder, helper, vec![lhs, rhs], OnuType::I64);
+        let dest = builder.new_ssa();
+        builder.build_binop(dest, cmp, order, MirOperand::Constant(MirLiteral::I64(0)));
+        builder.set_ssa_type(dest, OnuType::Boolean);
+        Ok(Some(MirOperand::Variable(dest, true)))
+    }
+
+    /// `as-text` of a `big-integer`: decimal digits with a leading `-` when
+    /// negative.
+    pub fn lower_as_text(arg: MirOperand, builder: &mut MirBuilder) -> MirOperand {
+        let text = Self::call(builder, BIG_AS_TEXT, vec![arg], OnuType::Strings);
+        if let MirOperand::Variable(ssa_var, _) = text {
+            builder.set_ssa_is_dynamic(ssa_var, true);
+        }
+        text
+    }
+
+    fn call(builder: &mut MirBuilder, name: &str, args: Vec<MirOperand>, return_type: OnuType) -> MirOperand {
+        let dest = builder.new_ssa();
+        let arg_types = args
+            .iter()
+            .map(|a| builder.operand_type(a).unwrap_or(OnuType::BigInt))
+            .collect();
+        builder.emit(MirInstruction::Call {
+            dest,
+            name: name.to_string(),
+            args,
+            return_type: return_type.clone(),
+            arg_types,
+            is_tail_call: false,
+        });
+        builder.set_ssa_type(dest, return_type);
+        MirOperand::Variable(dest, true)
+    }
+}
@@ -64,6 +64,7 @@ impl ExprLowerer for DerivationLowerer {
     ) -> Result<MirOperand, OnuError> {
         if let HirExpression::Derivation { name, typ, value, body } = expr {
             let val_op = context.lower_expression(value, builder, false)?;
+            let val_op = builder.promote_to(val_op, typ);
             
             let mut is_val_dyn = false;
 
@@ -1,14 +1,36 @@
-use crate::domain::entities::hir::HirExpression;
-use crate::domain::entities::mir::{MirInstruction, MirOperand, MirTerminator};
+use crate::domain::entities::hir::{HirExpression, HirLiteral};
+use crate::domain::entities::mir::{MirBinOp, MirInstruction, MirLiteral, MirOperand, MirTerminator};
 use crate::domain::entities::types::OnuType;
 use crate::application::use_cases::mir_builder::MirBuilder;
 use crate::domain::entities::error::OnuError;
 use super::super::mir_lowering_service::{MirLoweringService, LoweringContext};
 use crate::application::ports::environment::EnvironmentPort;
 use super::ExprLowerer;
+use super::lower_big_integer::BigIntegerLowerer;
 
 pub struct CallLowerer;
 
+/// Behaviors whose text comes from outside the program (stdin, argv).
+const INPUT_TEXT_CALLS: [&str; 2] = ["receives-line", "receives-argument"];
+
+/// Emits `RuntimeCheck(index > -1 && index < len(text))`.
+fn emit_text_bounds_check(builder: &mut MirBuilder, text: &MirOperand, index: &MirOperand, message: String) {
+    let len = builder.new_ssa();
+    builder.set_ssa_type(len, OnuType::I64);
+    builder.build_index(len, text.clone(), 0);
+
+    fn flag(builder: &mut MirBuilder, op: MirBinOp, lhs: MirOperand, rhs: MirOperand) -> MirOperand {
+        let dest = builder.new_ssa();
+        builder.set_ssa_type(dest, OnuType::Boolean);
+        builder.build_binop(dest, op, lhs, rhs);
+        MirOperand::Variable(dest, false)
+    }
+    let not_negative = flag(builder, MirBinOp::Gt, index.clone(), MirOperand::Constant(MirLiteral::I64(-1)));
+    let below_len = flag(builder, MirBinOp::Lt, index.clone(), MirOperand::Variable(len, false));
+    let in_bounds = flag(builder, MirBinOp::And, not_negative, below_len);
+    builder.build_runtime_check(in_bounds, message);
+}
+
 impl ExprLowerer for CallLowerer {
     fn lower<'a, E: EnvironmentPort>(
         &self,
@@ -18,6 +40,15 @@ impl ExprLowerer for CallLowerer {
         is_tail: bool,
     ) -> Result<MirOperand, OnuError> {
         if let HirExpression::Call { name, args } = expr {
+            // `"fib" utilizes clears` names the behavior whose memo cache to empty;
+            // the safety pass has already checked the name.
+            if name == "clears"
+                && let [HirExpression::Literal(HirLiteral::Text(function))] = args.as_slice()
+            {
+                builder.emit(MirInstruction::ClearMemo { function: function.clone() });
+                return Ok(MirOperand::Constant(MirLiteral::Nothing));
+            }
+
             let mut mir_args = Vec::new();
             for arg in args {
                 mir_args.push(context.lower_expression(arg, builder, false)?);
@@ -31,7 +62,33 @@ impl ExprLowerer for CallLowerer {
                 (OnuType::Nothing, Vec::new(), Vec::new())
             };
 
+            if name == "as-text"
+                && let [arg] = mir_args.as_slice()
+                && builder.operand_type(arg) == Some(OnuType::BigInt)
+            {
+                return Ok(BigIntegerLowerer::lower_as_text(arg.clone(), builder));
+            }
+
+            // Integer arguments to `big-integer` parameters are promoted.
+            let mir_args: Vec<MirOperand> = mir_args
+                .into_iter()
+                .enumerate()
+                .map(|(i, arg)| match arg_types.get(i) {
+                    Some(typ) => builder.promote_to(arg, typ),
+                    None => arg,
+                })
+                .collect();
+
             if let Some(lowerer) = context.stdlib_registry.get(name) {
+                if context.runtime_checks && let Some(idx) = lowerer.text_index_arg() {
+                    let site = context.registry.describe_site(builder.name());
+                    emit_text_bounds_check(
+                        builder,
+                        &mir_args[0],
+                        &mir_args[idx],
+                        format!("index out of bounds in `{}` within {}", name, site),
+                    );
+                }
                 let res = lowerer.lower(mir_args.clone(), builder);
                 eprintln!("[DEBUG] Stdlib op {} result: {:?}", name, res);
                 if let MirOperand::Variable(ssa_id, _) = &res {
@@ -62,6 +119,16 @@ impl ExprLowerer for CallLowerer {
                 return Ok(res);
             }
 
+            // Text read from outside the program is checked as UTF-8 before
+            // any code-point operation can decode it.
+            let validates_input = context.runtime_checks && INPUT_TEXT_CALLS.contains(&name.as_str());
+
+            // An integer result returned from a `big-integer` behavior is
+            // promoted after the call, so the call itself is not in tail position.
+            let is_tail = is_tail
+                && !validates_input
+                && !(*builder.return_type() == OnuType::BigInt && return_type == OnuType::I64);
+
             let dest = builder.new_ssa();
             builder.emit(MirInstruction::Call {
                 dest,
@@ -73,6 +140,12 @@ impl ExprLowerer for CallLowerer {
             });
             builder.set_ssa_type(dest, return_type);
 
+            if validates_input && let Some(validator) = context.stdlib_registry.get("is-valid-utf8") {
+                let site = context.registry.describe_site(builder.name());
+                let valid = validator.lower(vec![MirOperand::Variable(dest, false)], builder);
+                builder.build_runtime_check(valid, format!("invalid UTF-8 from `{}` within {}", name, site));
+            }
+
             if is_tail {
                 builder.terminate(MirTerminator::Return(MirOperand::Variable(dest, true)));
                 builder.clear_current_block();
@@ -1,5 +1,6 @@
 use crate::domain::entities::hir::HirExpression;
 use crate::domain::entities::mir::{MirInstruction, MirOperand, MirTerminator};
+use crate::domain::entities::types::OnuType;
 use crate::application::use_cases::mir_builder::MirBuilder;
 use crate::domain::entities::error::OnuError;
 use super::super::mir_lowering_service::{MirLoweringService, LoweringContext};
@@ -100,8 +101,22 @@ impl ExprLowerer for IfLowerer {
                 }
             }
 
+            // A branch yielding a plain integer is promoted when the other
+            // yields a `big-integer`.
+            let is_big = |builder: &MirBuilder, o: &MirOperand| builder.operand_type(o) == Some(OnuType::BigInt);
+            let merge_type = if is_big(builder, &then_res) || is_big(builder, &else_res) {
+                builder.set_ssa_type(dest, OnuType::BigInt);
+                Some(OnuType::BigInt)
+            } else {
+                None
+            };
+
             if let Some(id) = then_end_id {
                 builder.switch_to_block(id);
+                let then_res = match &merge_type {
+                    Some(typ) => builder.promote_to(then_res, typ),
+                    None => then_res,
+                };
                 // CUSTODY TRANSFER: If branch result is a resource, mark it consumed as it moves to 'dest'
                 if let MirOperand::Variable(ssa_id, _) = &then_res {
                     if builder.resolve_ssa_type(*ssa_id).map(|t| t.is_resource()).unwrap_or(false) {
@@ -113,6 +128,10 @@ impl ExprLowerer for IfLowerer {
             }
             if let Some(id) = else_end_id {
                 builder.switch_to_block(id);
+                let else_res = match &merge_type {
+                    Some(typ) => builder.promote_to(else_res, typ),
+                    None => else_res,
+                };
                 // CUSTODY TRANSFER: If branch result is a resource, mark it consumed as it moves to 'dest'
                 if let MirOperand::Variable(ssa_id, _) = &else_res {
                     if builder.resolve_ssa_type(*ssa_id).map(|t| t.is_resource()).unwrap_or(false) {
@@ -6,6 +6,7 @@ use crate::domain::entities::error::OnuError;
 use super::super::mir_lowering_service::{MirLoweringService, LoweringContext};
 use crate::application::ports::environment::EnvironmentPort;
 use super::ExprLowerer;
+use super::lower_big_integer::BigIntegerLowerer;
 
 pub struct LiteralLowerer;
 pub struct VariableLowerer;
@@ -107,6 +108,42 @@ impl ExprLowerer for BinaryOpLowerer {
             let lhs = context.lower_expression(left, builder, false)?;
             let rhs = context.lower_expression(right, builder, false)?;
 
+            let site = (context.runtime_checks && matches!(op, HirBinOp::Div))
+                .then(|| context.registry.describe_site(builder.name()));
+            let zero_check = site.as_ref().map(|site| format!("division by zero in `partitions-by` within {}", site));
+            if let Some(res) = BigIntegerLowerer::lower_binop(op, lhs.clone(), rhs.clone(), builder, zero_check.clone())? {
+                return Ok(res);
+            }
+
+            if let Some(message) = zero_check {
+                let nonzero = builder.new_ssa();
+                builder.build_binop(nonzero, MirBinOp::Ne, rhs.clone(), MirOperand::Constant(MirLiteral::I64(0)));
+                builder.set_ssa_type(nonzero, OnuType::Boolean);
+                builder.build_runtime_check(MirOperand::Variable(nonzero, false), message);
+            }
+            // The one quotient that does not fit: `sdiv` faults on
+            // i64::MIN / -1 just as it does on a zero divisor.
+            if let Some(site) = site {
+                let not_min = builder.new_ssa();
+                builder.build_binop(not_min, MirBinOp::Ne, lhs.clone(), MirOperand::Constant(MirLiteral::I64(i64::MIN)));
+                builder.set_ssa_type(not_min, OnuType::Boolean);
+                let not_minus_one = builder.new_ssa();
+                builder.build_binop(not_minus_one, MirBinOp::Ne, rhs.clone(), MirOperand::Constant(MirLiteral::I64(-1)));
+                builder.set_ssa_type(not_minus_one, OnuType::Boolean);
+                let fits = builder.new_ssa();
+                builder.build_binop(
+                    fits,
+                    MirBinOp::Or,
+                    MirOperand::Variable(not_min, false),
+                    MirOperand::Variable(not_minus_one, false),
+                );
+                builder.set_ssa_type(fits, OnuType::Boolean);
+                builder.build_runtime_check(
+                    MirOperand::Variable(fits, false),
+                    format!("integer overflow in `partitions-by` within {}", site),
+                );
+            }
+
             let dest = builder.new_ssa();
             let mir_op = match op {
                 HirBinOp::Add => MirBinOp::Add,
@@ -122,6 +159,8 @@ impl ExprLowerer for BinaryOpLowerer {
                 HirBinOp::BitXor => MirBinOp::Xor,
                 HirBinOp::Shr => MirBinOp::Shr,
                 HirBinOp::Shl => MirBinOp::Shl,
+                HirBinOp::WrappingAdd => MirBinOp::WrappingAdd,
+                HirBinOp::SaturatingAdd => MirBinOp::SaturatingAdd,
             };
             
             // Register type for the result
@@ -130,13 +169,26 @@ impl ExprLowerer for BinaryOpLowerer {
                 _ => OnuType::I64,
             };
 
-            builder.emit(MirInstruction::BinaryOperation { 
-                dest, 
-                op: mir_op, 
-                lhs: lhs.clone(), 
-                rhs: rhs.clone(),
-                dest_type: res_type.clone()
-            });
+            let is_checked = context.checked_arithmetic
+                && matches!(mir_op, MirBinOp::Add | MirBinOp::Sub | MirBinOp::Mul);
+            if is_checked {
+                builder.emit(MirInstruction::CheckedArithmetic {
+                    dest,
+                    op: mir_op,
+                    lhs: lhs.clone(),
+                    rhs: rhs.clone(),
+                    dest_type: res_type.clone(),
+                    site: context.registry.describe_site(builder.name()),
+                });
+            } else {
+                builder.emit(MirInstruction::BinaryOperation {
+                    dest,
+                    op: mir_op,
+                    lhs: lhs.clone(),
+                    rhs: rhs.clone(),
+                    dest_type: res_type.clone()
+                });
+            }
             
             // Parent cleanup: mark inputs as consumed and drop if resources
             if let MirOperand::Variable(ssa_id, is_consuming) = &lhs {
@@ -2,6 +2,7 @@ pub mod lower_expr;
 pub mod lower_blocks;
 pub mod lower_calls;
 pub mod lower_control_flow;
+pub mod lower_big_integer;
 
 use crate::domain::entities::hir::HirExpression;
 use crate::domain::entities::mir::MirOperand;
@@ -17,11 +17,17 @@ use super::mir_lowering::lower_expr::{LiteralLowerer, VariableLowerer, BinaryOpL
 use super::mir_lowering::lower_calls::CallLowerer;
 use super::mir_lowering::lower_blocks::{BlockLowerer, DerivationLowerer};
 use super::mir_lowering::lower_control_flow::IfLowerer;
+use super::mir_lowering::lower_big_integer::is_big_integer_helper;
 
 pub struct LoweringContext<'a, E: EnvironmentPort> {
     pub env: &'a E,
     pub registry: &'a RegistryService,
     pub stdlib_registry: crate::application::use_cases::stdlib::StdlibOpRegistry,
+    /// Lower `added-to` / `decreased-by` / `scales-by` to overflow-trapping
+    /// `CheckedArithmetic` instead of plain `BinaryOperation`.
+    pub checked_arithmetic: bool,
+    /// Emit `RuntimeCheck` guards for division and indexed text access.
+    pub runtime_checks: bool,
 }
 
 impl<'a, E: EnvironmentPort> LoweringContext<'a, E> {
@@ -31,6 +37,8 @@ impl<'a, E: EnvironmentPort> LoweringContext<'a, E> {
                 env: self.env,
                 registry: self.registry,
                 stdlib_registry: crate::application::use_cases::stdlib::StdlibOpRegistry::new(), // We're reconstructing it here to avoid borrowing issues for now.
+                checked_arithmetic: self.checked_arithmetic,
+                runtime_checks: self.runtime_checks,
             }
         };
         let res = service.lower_expression(expr, builder, is_tail)?;
@@ -49,11 +57,25 @@ impl<'a, E: EnvironmentPort> MirLoweringService<'a, E> {
             context: LoweringContext {
                 env,
                 registry,
-                stdlib_registry: crate::application::use_cases::stdlib::StdlibOpRegistry::new()
+                stdlib_registry: crate::application::use_cases::stdlib::StdlibOpRegistry::new(),
+                checked_arithmetic: false,
+                runtime_checks: false,
             }
         }
     }
 
+    /// Enables overflow trapping for integer arithmetic written in the source.
+    pub fn with_checked_arithmetic(mut self, enabled: bool) -> Self {
+        self.context.checked_arithmetic = enabled;
+        self
+    }
+
+    /// Enables divide-by-zero and text bounds guards.
+    pub fn with_runtime_checks(mut self, enabled: bool) -> Self {
+        self.context.runtime_checks = enabled;
+        self
+    }
+
     pub(crate) fn log(&self, level: LogLevel, message: &str) {
         self.context.env.log(level, &format!("[MirLowering] {}", message));
     }
@@ -70,6 +92,14 @@ impl<'a, E: EnvironmentPort> MirLoweringService<'a, E> {
         Ok(MirProgram { functions })
     }
 
+    /// Type of the value `expr` delivers, found by lowering it into a
+    /// throwaway function.  The REPL uses it to display bare expressions.
+    pub fn expression_type(&self, expr: &HirExpression) -> Result<OnuType, OnuError> {
+        let mut builder = MirBuilder::new("expression".to_string(), OnuType::Nothing, Vec::new());
+        let result = self.lower_expression(expr, &mut builder, false)?;
+        Ok(builder.operand_type(&result).unwrap_or(OnuType::Nothing))
+    }
+
     fn lower_function(&self, header: &HirBehaviorHeader, body: &HirExpression) -> Result<MirFunction, OnuError> {
         self.log(LogLevel::Debug, &format!("Lowering behavior: {}", header.name));
         let mut builder = MirBuilder::new(header.name.clone(), header.return_type.clone(), header.diminishing.clone());
@@ -101,6 +131,8 @@ impl<'a, E: EnvironmentPort> MirLoweringService<'a, E> {
         if let Some(cache_size) = header.memo_cache_size {
             func.memo_cache_size = Some(cache_size);
         }
+        func.inlining = header.inlining;
+        func.known_behavior = header.known_behavior;
 
         // Audit MIR for side effects
         if is_pure_candidate {
@@ -110,14 +142,16 @@ impl<'a, E: EnvironmentPort> MirLoweringService<'a, E> {
                         MirInstruction::Alloc { .. } | 
                         MirInstruction::Store { .. } |
                         MirInstruction::Emit(_) |
-                        MirInstruction::Drop { .. } => {
+                        MirInstruction::Drop { .. } |
+                        MirInstruction::ClearMemo { .. } => {
                             self.log(LogLevel::Debug, &format!("Behavior {} unmarked as pure: side-effecting instruction {:?}", func.name, inst));
                             is_pure_candidate = false;
                             break;
                         }
                         MirInstruction::Call { name, .. } => {
-                            // If it calls anything other than itself, we are cautious
-                            if name != &func.name {
+                            // If it calls anything other than itself, we are cautious.
+                            // Big-integer helpers only allocate their results.
+                            if name != &func.name && !is_big_integer_helper(name) {
                                 self.log(LogLevel::Debug, &format!("Behavior {} unmarked as pure: calls external behavior {}", func.name, name));
                                 is_pure_candidate = false;
                                 break;
@@ -1,4 +1,5 @@
 pub mod memo_strategies;
+pub mod accumulator_pass;
 pub mod analysis_service;
 pub mod analyzer;
 pub mod codegen_profile;
@@ -13,6 +14,7 @@ pub mod mir_lowering;
 pub mod mir_lowering_service;
 pub mod module_bootstrap;
 pub mod module_service;
+pub mod range_analysis;
 pub mod registry_service;
 pub mod safety_pass;
 pub mod stages;
@@ -0,0 +1,394 @@
+/// Range Analysis: Application Use Case Layer
+///
+/// # What This Does
+/// Bounds the results of a pure integer behavior by evaluating its MIR
+/// abstractly for every argument tuple it is called with.  `IntegerUpgradePass`
+/// uses the bound to decide whether the behavior must return a
+/// `WideInt(bits)` instead of an `I64`, and how many bits it needs.
+///
+/// # Domain
+/// Values below 2¹²⁷ in magnitude are carried exactly as `i128`, so arguments,
+/// branch conditions and anything that fits a machine word are evaluated
+/// precisely.  Past that point a value is summarized by bounds `[lo, hi]` on
+/// its base-2 logarithm:
+///
+/// - `a added-to b`  → `hi = log₂(2^hi_a + 2^hi_b)`
+/// - `a scales-by b` → `hi = hi_a + hi_b`
+/// - `a partitions-by b` → `hi = hi_a − lo_b`
+///
+/// Wide values are always positive.  Anything that could make one negative
+/// (subtraction, a negative factor) or that needs its exact bits (comparison,
+/// shifts, passing it as an argument) stops the analysis with a reason.
+///
+/// # Limits
+/// Calls are memoized on their argument tuples, so doubly recursive
+/// definitions such as Fibonacci or Pascal's binomial cost one evaluation per
+/// distinct tuple.  The analysis gives up after `MAX_STEPS` instructions or
+/// `MAX_DEPTH` nested calls, which is what happens for Ackermann-like growth.
+///
+/// # Pattern Used: Abstract Interpreter
+/// `RangeAnalysis::bound` owns all evaluation state for a single query and
+/// borrows the program read-only.
+use std::collections::{HashMap, HashSet};
+
+use crate::domain::entities::mir::{
+    BasicBlock, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand, MirProgram, MirTerminator,
+};
+
+/// Instructions evaluated per query before the analysis gives up.
+pub const MAX_STEPS: usize = 1_000_000;
+
+/// Nested calls evaluated before the analysis gives up.
+pub const MAX_DEPTH: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    Exact(i128),
+    /// A positive value with `2^lo <= v <= 2^hi`.
+    Wide { lo: f64, hi: f64 },
+}
+
+impl Value {
+    fn fits_i64(self) -> bool {
+        matches!(self, Value::Exact(v) if i64::try_from(v).is_ok())
+    }
+
+    /// Width of the smallest signed integer holding this value.  Wide values
+    /// get one guard bit on top of the sign bit to absorb rounding in `hi`.
+    fn signed_bits(self) -> u32 {
+        match self {
+            Value::Exact(v) if v >= 0 => 129 - v.leading_zeros(),
+            Value::Exact(v) => 129 - (!v).leading_zeros(),
+            Value::Wide { hi, .. } => hi.ceil() as u32 + 2,
+        }
+    }
+
+    /// Logarithm bounds of a positive value.
+    fn log2(self) -> (f64, f64) {
+        match self {
+            Value::Exact(v) => {
+                let l = (v as f64).log2();
+                (l, l)
+            }
+            Value::Wide { lo, hi } => (lo, hi),
+        }
+    }
+}
+
+/// The bound proved for one behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResultBound {
+    /// Signed width that holds every value on the result path.
+    pub bits: u32,
+    /// Whether some value on the result path was negative.
+    pub negative: bool,
+}
+
+type CallKey<'a> = (&'a str, Vec<i64>);
+
+pub struct RangeAnalysis<'a> {
+    functions: HashMap<&'a str, &'a MirFunction>,
+    target: &'a str,
+    /// SSA variables of `target` that carry its result and would be widened.
+    result_path: &'a HashSet<usize>,
+    memo: HashMap<CallKey<'a>, Value>,
+    active: HashSet<CallKey<'a>>,
+    steps: usize,
+    bound: ResultBound,
+}
+
+struct Frame<'a> {
+    func: &'a MirFunction,
+    key: CallKey<'a>,
+    env: HashMap<usize, Value>,
+    block: &'a BasicBlock,
+    pc: usize,
+}
+
+impl<'a> RangeAnalysis<'a> {
+    /// Bound every value `target` places on `result_path` across the calls
+    /// `target(args)` for each tuple in `calls`.
+    pub fn bound(
+        program: &'a MirProgram,
+        target: &'a str,
+        result_path: &'a HashSet<usize>,
+        calls: &[Vec<i64>],
+    ) -> Result<ResultBound, String> {
+        let mut analysis = RangeAnalysis {
+            functions: program.functions.iter().map(|f| (f.name.as_str(), f)).collect(),
+            target,
+            result_path,
+            memo: HashMap::new(),
+            active: HashSet::new(),
+            steps: 0,
+            bound: ResultBound { bits: 0, negative: false },
+        };
+        let func = analysis.functions[target];
+        for args in calls {
+            if !analysis.memo.contains_key(&(target, args.clone())) {
+                analysis.call(func, args.clone())?;
+            }
+        }
+        Ok(analysis.bound)
+    }
+
+    /// Evaluate `func(args)`.  Nested calls live on an explicit frame stack
+    /// rather than the Rust stack, so `MAX_DEPTH` is the only depth limit.
+    fn call(&mut self, func: &'a MirFunction, args: Vec<i64>) -> Result<Value, String> {
+        let mut stack = vec![self.enter(func, args)?];
+        // Result of the most recently finished callee, awaiting delivery to
+        // the `Call` instruction its caller is paused on.
+        let mut returned: Option<Value> = None;
+
+        loop {
+            let frame = stack.last_mut().expect("the entry frame returns last");
+            let on_target = frame.func.name == self.target;
+
+            if let Some(inst) = frame.block.instructions.get(frame.pc) {
+                let (dest, value) = match inst {
+                    MirInstruction::Call { dest, name, args, .. } => {
+                        let value = match returned.take() {
+                            Some(value) => value,
+                            None => {
+                                let callee = *self.functions.get(name.as_str()).ok_or_else(|| {
+                                    format!("calls '{}', which range analysis cannot see", name)
+                                })?;
+                                let mut values = Vec::with_capacity(args.len());
+                                for arg in args {
+                                    match Self::operand(&frame.env, arg)? {
+                                        Value::Exact(v) if i64::try_from(v).is_ok() => values.push(v as i64),
+                                        _ => {
+                                            return Err(format!(
+                                                "passes a value wider than 64 bits to '{}'",
+                                                name
+                                            ));
+                                        }
+                                    }
+                                }
+                                match self.memo.get(&(callee.name.as_str(), values.clone())) {
+                                    Some(value) => *value,
+                                    None => {
+                                        let callee_frame = self.enter(callee, values)?;
+                                        stack.push(callee_frame);
+                                        continue;
+                                    }
+                                }
+                            }
+                        };
+                        if name != self.target && !value.fits_i64() {
+                            return Err(format!("'{}' itself exceeds 64 bits", name));
+                        }
+                        (*dest, value)
+                    }
+                    _ => {
+                        self.steps += 1;
+                        if self.steps > MAX_STEPS {
+                            return Err(format!("evaluation needs more than {} steps", MAX_STEPS));
+                        }
+                        match Self::step(&frame.env, inst)? {
+                            Some(assigned) => assigned,
+                            None => {
+                                frame.pc += 1;
+                                continue;
+                            }
+                        }
+                    }
+                };
+                if on_target && self.result_path.contains(&dest) {
+                    self.record(value);
+                } else if !value.fits_i64() {
+                    return Err(format!(
+                        "an intermediate value in '{}' exceeds 64 bits off the result path",
+                        frame.func.name
+                    ));
+                }
+                frame.env.insert(dest, value);
+                frame.pc += 1;
+                continue;
+            }
+
+            let next = match &frame.block.terminator {
+                MirTerminator::Return(op) => {
+                    let value = Self::operand(&frame.env, op)?;
+                    if on_target {
+                        self.record(value);
+                    }
+                    let frame = stack.pop().expect("checked above");
+                    self.active.remove(&frame.key);
+                    self.memo.insert(frame.key, value);
+                    if stack.is_empty() {
+                        return Ok(value);
+                    }
+                    returned = Some(value);
+                    continue;
+                }
+                MirTerminator::Branch(id) => *id,
+                MirTerminator::CondBranch { condition, then_block, else_block } => {
+                    match Self::operand(&frame.env, condition)? {
+                        Value::Exact(0) => *else_block,
+                        Value::Exact(_) => *then_block,
+                        Value::Wide { .. } => return Err("branches on a value beyond 128 bits".to_string()),
+                    }
+                }
+                MirTerminator::Unreachable => return Err("reaches an unreachable block".to_string()),
+            };
+            frame.block = frame
+                .func
+                .blocks
+                .iter()
+                .find(|b| b.id == next)
+                .ok_or_else(|| format!("branches to missing block {}", next))?;
+            frame.pc = 0;
+        }
+    }
+
+    /// Open a frame for `func(args)`, which must not already be in progress.
+    fn enter(&mut self, func: &'a MirFunction, args: Vec<i64>) -> Result<Frame<'a>, String> {
+        if !func.is_pure_data_leaf {
+            return Err(format!("calls '{}', which is not pure", func.name));
+        }
+        if self.active.len() == MAX_DEPTH {
+            return Err(format!("recursion is deeper than {} calls", MAX_DEPTH));
+        }
+        let key = (func.name.as_str(), args);
+        if !self.active.insert(key.clone()) {
+            return Err(format!(
+                "'{}' calls itself again with the same arguments {:?}",
+                func.name, key.1
+            ));
+        }
+        let env = func
+            .args
+            .iter()
+            .zip(&key.1)
+            .map(|(arg, v)| (arg.ssa_var, Value::Exact(*v as i128)))
+            .collect();
+        let block = func.blocks.first().ok_or_else(|| format!("'{}' has no body", func.name))?;
+        Ok(Frame { func, key, env, block, pc: 0 })
+    }
+
+    /// Evaluate one non-call instruction, yielding the variable it assigns.
+    fn step(env: &HashMap<usize, Value>, inst: &MirInstruction) -> Result<Option<(usize, Value)>, String> {
+        match inst {
+            MirInstruction::Assign { dest, src } => Ok(Some((*dest, Self::operand(env, src)?))),
+            MirInstruction::BinaryOperation { dest, op, lhs, rhs, .. }
+            | MirInstruction::CheckedArithmetic { dest, op, lhs, rhs, .. } => {
+                let l = Self::operand(env, lhs)?;
+                let r = Self::operand(env, rhs)?;
+                Ok(Some((*dest, Self::binary(op, l, r)?)))
+            }
+            MirInstruction::RuntimeCheck { condition, message } => {
+                if Self::operand(env, condition)? == Value::Exact(0) {
+                    return Err(format!("fails a runtime check ({})", message));
+                }
+                Ok(None)
+            }
+            _ => Err("uses memory, which range analysis does not model".to_string()),
+        }
+    }
+
+    fn record(&mut self, value: Value) {
+        self.bound.bits = self.bound.bits.max(value.signed_bits());
+        self.bound.negative |= matches!(value, Value::Exact(v) if v < 0);
+    }
+
+    fn operand(env: &HashMap<usize, Value>, op: &MirOperand) -> Result<Value, String> {
+        match op {
+            MirOperand::Variable(ssa, _) => env
+                .get(ssa)
+                .copied()
+                .ok_or_else(|| format!("reads SSA {} before it is assigned", ssa)),
+            MirOperand::Constant(MirLiteral::I64(v)) => Ok(Value::Exact(*v as i128)),
+            MirOperand::Constant(MirLiteral::Boolean(b)) => Ok(Value::Exact(*b as i128)),
+            MirOperand::Constant(MirLiteral::WideInt(digits, _)) => digits
+                .parse::<i128>()
+                .map(Value::Exact)
+                .map_err(|_| "uses a wide literal beyond 128 bits".to_string()),
+            MirOperand::Constant(_) => Err("uses a non-integer value".to_string()),
+        }
+    }
+
+    fn binary(op: &MirBinOp, l: Value, r: Value) -> Result<Value, String> {
+        if let (Value::Exact(a), Value::Exact(b)) = (l, r) {
+            let exact = match op {
+                MirBinOp::Add => a.checked_add(b),
+                MirBinOp::Sub => a.checked_sub(b),
+                MirBinOp::Mul => a.checked_mul(b),
+                MirBinOp::Div if b == 0 => return Err("divides by zero".to_string()),
+                MirBinOp::Div => a.checked_div(b),
+                MirBinOp::Eq => Some((a == b) as i128),
+                MirBinOp::Ne => Some((a != b) as i128),
+                MirBinOp::Gt => Some((a > b) as i128),
+                MirBinOp::Lt => Some((a < b) as i128),
+                _ => Some(Self::word_op(op, l, r)? as i128),
+            };
+            if let Some(v) = exact {
+                return Ok(Value::Exact(v));
+            }
+        }
+        Self::wide(op, l, r)
+    }
+
+    /// Bitwise, shift, wrapping and saturating operators, which only make
+    /// sense on 64-bit words.
+    fn word_op(op: &MirBinOp, l: Value, r: Value) -> Result<i64, String> {
+        let (Value::Exact(a), Value::Exact(b)) = (l, r) else {
+            unreachable!("word operators are only reached with exact operands")
+        };
+        let (Ok(a), Ok(b)) = (i64::try_from(a), i64::try_from(b)) else {
+            return Err(format!("applies {:?} to a value wider than 64 bits", op));
+        };
+        let shift = || u32::try_from(b).ok().filter(|s| *s < 64).ok_or("shifts by more than 63 bits");
+        Ok(match op {
+            MirBinOp::And => a & b,
+            MirBinOp::Or => a | b,
+            MirBinOp::Xor => a ^ b,
+            MirBinOp::Shr => ((a as u64) >> shift()?) as i64,
+            MirBinOp::Shl => a << shift()?,
+            MirBinOp::WrappingAdd => a.wrapping_add(b),
+            MirBinOp::SaturatingAdd => a.saturating_add(b),
+            _ => unreachable!("arithmetic and comparisons are handled by the caller"),
+        })
+    }
+
+    /// Arithmetic on or beyond 128 bits, tracked through logarithm bounds.
+    fn wide(op: &MirBinOp, l: Value, r: Value) -> Result<Value, String> {
+        if *op == MirBinOp::Div && r == Value::Exact(0) {
+            return Err("divides by zero".to_string());
+        }
+        if matches!(l, Value::Exact(0)) || matches!(r, Value::Exact(0)) {
+            match op {
+                MirBinOp::Mul => return Ok(Value::Exact(0)),
+                MirBinOp::Add => return Ok(if l == Value::Exact(0) { r } else { l }),
+                _ => {}
+            }
+        }
+        if matches!(l, Value::Exact(v) if v < 0) || matches!(r, Value::Exact(v) if v < 0) {
+            return Err("combines a negative value with one beyond 128 bits".to_string());
+        }
+        let ((lo_a, hi_a), (lo_b, hi_b)) = (l.log2(), r.log2());
+        let (lo, hi) = match op {
+            MirBinOp::Add => {
+                let (big, small) = if hi_a >= hi_b { (hi_a, hi_b) } else { (hi_b, hi_a) };
+                (lo_a.max(lo_b), big + (1.0 + (small - big).exp2()).log2())
+            }
+            MirBinOp::Mul => (lo_a + lo_b, hi_a + hi_b),
+            MirBinOp::Div => {
+                // Truncation can lose up to one from the quotient, hence the
+                // extra bit off the lower bound.
+                if let Value::Exact(a) = l
+                    && (a as f64).log2() < lo_b
+                {
+                    return Ok(Value::Exact(0));
+                }
+                (lo_a - hi_b - 1.0, hi_a - lo_b)
+            }
+            MirBinOp::Sub => return Err("subtracts from a value beyond 128 bits".to_string()),
+            _ => return Err(format!("applies {:?} to a value beyond 128 bits", op)),
+        };
+        if lo < 0.0 {
+            return Err("divides a value beyond 128 bits by one of similar size".to_string());
+        }
+        Ok(Value::Wide { lo, hi })
+    }
+}
@@ -17,6 +17,8 @@ pub struct ShapeDefinition {
 pub struct RegistryService {
     symbols: SymbolTable,
     shapes: HashMap<String, ShapeDefinition>,
+    /// 1-based source line of each behavior header, recorded by `scan_headers`.
+    behavior_lines: HashMap<String, usize>,
     pub log_level: LogLevel,
 }
 
@@ -31,6 +33,7 @@ impl RegistryService {
         Self {
             symbols: SymbolTable::new(),
             shapes: HashMap::new(),
+            behavior_lines: HashMap::new(),
             log_level: LogLevel::Info,
         }
     }
@@ -90,6 +93,24 @@ impl RegistryService {
         &mut self.symbols
     }
 
+    pub fn record_behavior_line(&mut self, name: &str, line: usize) {
+        self.behavior_lines.insert(name.to_string(), line);
+    }
+
+    pub fn behavior_line(&self, name: &str) -> Option<usize> {
+        self.behavior_lines.get(name).copied()
+    }
+
+    /// Human-readable location of a behavior for runtime diagnostics,
+    /// e.g. "`sum-to` (line 3)".  Expressions carry no spans, so the
+    /// behavior's header line is the finest location available.
+    pub fn describe_site(&self, name: &str) -> String {
+        match self.behavior_line(name) {
+            Some(line) => format!("`{}` (line {})", name, line),
+            None => format!("`{}`", name),
+        }
+    }
+
     pub fn mark_implemented(&mut self, name: &str) {
         self.log(LogLevel::Trace, &format!("Marking implemented: {}", name));
         self.symbols.mark_implemented(name);
@@ -102,7 +123,7 @@ impl RegistryService {
             OnuType::I8 | OnuType::U8 | OnuType::Boolean => 1,
             OnuType::I16 | OnuType::U16 => 2,
             OnuType::I32 | OnuType::U32 | OnuType::F32 => 4,
-            OnuType::I64 | OnuType::U64 | OnuType::F64 | OnuType::Ptr => 8,
+            OnuType::I64 | OnuType::U64 | OnuType::F64 | OnuType::Ptr | OnuType::BigInt => 8,
             OnuType::I128 | OnuType::U128 => 16,
             OnuType::WideInt(bits) => (*bits as usize + 7) / 8,
             OnuType::Strings => {
@@ -149,7 +170,7 @@ impl RegistryService {
             OnuType::I8 | OnuType::U8 | OnuType::Boolean => 1,
             OnuType::I16 | OnuType::U16 => 2,
             OnuType::I32 | OnuType::U32 | OnuType::F32 => 4,
-            OnuType::I64 | OnuType::U64 | OnuType::F64 | OnuType::Ptr => 8,
+            OnuType::I64 | OnuType::U64 | OnuType::F64 | OnuType::Ptr | OnuType::BigInt => 8,
             OnuType::I128 | OnuType::U128 => 16,
             OnuType::WideInt(bits) => (*bits as usize + 7) / 8,
             OnuType::Strings => 8, // Max alignment of { i64, i8*, i1 } is 8
@@ -179,6 +200,7 @@ impl Clone for RegistryService {
         Self {
             symbols: self.symbols.clone(),
             shapes: self.shapes.clone(),
+            behavior_lines: self.behavior_lines.clone(),
             log_level: self.log_level,
         }
     }
@@ -14,13 +14,12 @@
 /// │     │ never changes and the program loops │                            │
 /// │     │ forever or crashes.                 │                            │
 /// ├─────┼────────────────────────────────────┼────────────────────────────┤
-/// │ S-2 │ A `with diminishing` function is   │ SOLID (SRP/LSP) + KISS     │
-/// │     │ called more than once per session.  │                            │
-/// │     │ Each top-level call allocates the   │                            │
-/// │     │ entire 16 MB arena for its memo     │                            │
-/// │     │ table — a second call finds the     │                            │
-/// │     │ arena full and writes off the end,  │                            │
-/// │     │ producing a segfault.               │                            │
+/// │ S-2 │ `clears` names something that is    │ Pure Grammar Violation     │
+/// │     │ not a `with diminishing` behavior.  │ + KISS (invisible rule)    │
+/// │     │ There is no cache to empty, so the  │                            │
+/// │     │ call would silently do nothing and  │                            │
+/// │     │ the coder would believe stale       │                            │
+/// │     │ answers had been discarded.         │                            │
 /// ├─────┼────────────────────────────────────┼────────────────────────────┤
 /// │ S-3 │ A derivation's type defaults to I64 │ Pure Grammar Violation     │
 /// │     │ when the expression clearly returns │                            │
@@ -47,13 +46,15 @@ use std::collections::{HashMap, HashSet};
 
 /// Run all safety rules on a compiled HIR program.
 ///
-/// Returns `Err` on the first hard violation.  All violations are collected
-/// before returning so the coder sees every problem at once.
+/// Every rule runs over every behavior, and the first hard violation is
+/// returned as `Err`.  No rule emits a warning yet, so the `Ok` list is
+/// always empty; it is what fills `CompiledSource::diagnostics`.
 pub fn run(discourses: &[HirDiscourse]) -> Result<Vec<Diagnostic>, OnuError> {
-    let mut diagnostics: Vec<Diagnostic> = Vec::new();
     let mut hard_errors: Vec<OnuError> = Vec::new();
 
     // Build the set of memoized function names once (used by S-2).
+    // Memo tables are module-level globals reused by every call, so a
+    // memoized behavior may be called any number of times.
     let memoized_fns: HashSet<String> = discourses
         .iter()
         .filter_map(|d| {
@@ -76,20 +77,8 @@ pub fn run(discourses: &[HirDiscourse]) -> Result<Vec<Diagnostic>, OnuError> {
             collect_literal_bindings(body, &mut literal_vars, &mut arena_copies);
             find_mutating_calls(body, &literal_vars, &arena_copies, name, &mut hard_errors);
 
-            // S-2: memoized function called more than once — only count calls
-            // from *other* behaviors (i.e. callers, not the function itself).
-            // A memoized function calling itself recursively is correct usage.
-            for memo_fn in &memoized_fns {
-                if memo_fn == name {
-                    continue; // skip self — recursive calls are fine
-                }
-                let count = count_calls(body, memo_fn);
-                if count > 1 {
-                    hard_errors.push(memo_called_multiple_times(name, memo_fn, count));
-                } else if count == 1 {
-                    diagnostics.push(memo_single_call_hint(name, memo_fn));
-                }
-            }
+            // S-2: `clears` must name a memoized behavior.
+            find_bad_clears(body, &memoized_fns, name, &mut hard_errors);
 
             // S-3: literal text used without type annotation
             find_untyped_text_derivations(body, name, &mut hard_errors);
@@ -99,7 +88,7 @@ pub fn run(discourses: &[HirDiscourse]) -> Result<Vec<Diagnostic>, OnuError> {
     if !hard_errors.is_empty() {
         return Err(hard_errors.remove(0));
     }
-    Ok(diagnostics)
+    Ok(Vec::new())
 }
 
 // ---------------------------------------------------------------------------
@@ -237,84 +226,91 @@ and you are trying to change it using '{op}'.\n\
 }
 
 // ---------------------------------------------------------------------------
-// S-2: Memoized Function Called More Than Once
+// S-2: `clears` Must Name a Memoized Behavior
 // ---------------------------------------------------------------------------
 
-fn count_calls(expr: &HirExpression, target: &str) -> usize {
+fn find_bad_clears(
+    expr: &HirExpression,
+    memoized_fns: &HashSet<String>,
+    behavior_name: &str,
+    hard_errors: &mut Vec<OnuError>,
+) {
     match expr {
         HirExpression::Call { name, args } => {
-            let self_count = if name == target { 1 } else { 0 };
-            self_count + args.iter().map(|a| count_calls(a, target)).sum::<usize>()
+            if name == "clears" {
+                let target = match args.as_slice() {
+                    [HirExpression::Literal(HirLiteral::Text(target))] => Some(target.as_str()),
+                    _ => None,
+                };
+                if !target.is_some_and(|t| memoized_fns.contains(t)) {
+                    hard_errors.push(clears_without_cache_error(behavior_name, target));
+                }
+            }
+            for a in args {
+                find_bad_clears(a, memoized_fns, behavior_name, hard_errors);
+            }
+        }
+        HirExpression::Block(exprs) => {
+            for e in exprs {
+                find_bad_clears(e, memoized_fns, behavior_name, hard_errors);
+            }
         }
-        HirExpression::Block(exprs) => exprs.iter().map(|e| count_calls(e, target)).sum(),
         HirExpression::Derivation { value, body, .. } => {
-            count_calls(value, target) + count_calls(body, target)
+            find_bad_clears(value, memoized_fns, behavior_name, hard_errors);
+            find_bad_clears(body, memoized_fns, behavior_name, hard_errors);
         }
         HirExpression::If { condition, then_branch, else_branch } => {
-            count_calls(condition, target)
-                + count_calls(then_branch, target)
-                + count_calls(else_branch, target)
+            find_bad_clears(condition, memoized_fns, behavior_name, hard_errors);
+            find_bad_clears(then_branch, memoized_fns, behavior_name, hard_errors);
+            find_bad_clears(else_branch, memoized_fns, behavior_name, hard_errors);
+        }
+        HirExpression::Emit(e) | HirExpression::Drop(e) => {
+            find_bad_clears(e, memoized_fns, behavior_name, hard_errors);
+        }
+        HirExpression::Tuple(elems) => {
+            for e in elems {
+                find_bad_clears(e, memoized_fns, behavior_name, hard_errors);
+            }
         }
-        HirExpression::Emit(e) | HirExpression::Drop(e) => count_calls(e, target),
-        HirExpression::Tuple(elems) => elems.iter().map(|e| count_calls(e, target)).sum(),
         HirExpression::BinaryOp { left, right, .. } => {
-            count_calls(left, target) + count_calls(right, target)
+            find_bad_clears(left, memoized_fns, behavior_name, hard_errors);
+            find_bad_clears(right, memoized_fns, behavior_name, hard_errors);
         }
-        _ => 0,
+        _ => {}
     }
 }
 
-fn memo_called_multiple_times(behavior: &str, memo_fn: &str, count: usize) -> OnuError {
+fn clears_without_cache_error(behavior: &str, target: Option<&str>) -> OnuError {
+    let named = match target {
+        Some(t) => format!("'{t}', which is not declared 'with diminishing'"),
+        None => "something other than a behavior name in quotes".to_string(),
+    };
     OnuError::GrammarViolation {
         message: format!(
-            "═══ Onu Safety Rule S-2: Speed-Up Behavior Called Too Many Times ═══\n\
+            "═══ Onu Safety Rule S-2: Only Speed-Up Behaviors Can Be Cleared ═══\n\
 \n\
-In behavior '{behavior}': '{memo_fn}' (declared with 'with diminishing') is called \
-{count} times, but it can only be called once per program run.\n\
+In behavior '{behavior}': 'clears' is given {named}.\n\
 \n\
   ✗  The problem:\n\
-     'with diminishing' reserves a large block of memory to remember previous\n\
-     answers (memoization). It can only reserve this memory once.\n\
-     Calling it a second time fills up that block and crashes the program.\n\
-\n\
-  ✓  How to fix it (choose one):\n\
+     'clears' forgets the answers a 'with diminishing' behavior has remembered.\n\
+     Anything else has nothing remembered, so nothing would happen.\n\
 \n\
-     Option A — call it once and store the result:\n\
-       derivation: result derives-from ... utilizes {memo_fn} ...\n\
-       -- use 'result' everywhere you need the value\n\
+  ✓  How to fix it:\n\
+     Name the behavior in quotes, and make sure it says 'with diminishing':\n\
 \n\
-     Option B — if you don't need the speed-up, remove 'with diminishing':\n\
-       the behavior {memo_fn}\n\
-       with no guaranteed termination:   ← remove 'with diminishing', add this\n\
+       \"fib\" utilizes clears\n\
 \n\
-  (Technical: HashMemoStrategy wraps '{memo_fn}' in an entry function that\n\
-   bump-allocates its entire hash table (~16 MB) from @onu_arena on the first\n\
-   call. The arena is never freed. A second call moves the bump pointer past\n\
-   the end of the arena, writing to unmapped memory and causing a segfault.\n\
-   SOLID SRP violation: the function definition conflates 'compute value' with\n\
-   'manage a fixed-size global memo table'.)\n\
+  (Technical: each memoized behavior keeps its cache in module-level globals\n\
+   (@fib_cache_occ, @fib_cache_keys, ...) that every call shares.  'clears'\n\
+   lowers to a MemSet over the table that marks filled slots; without a\n\
+   memo wrapper there is no such table to reset.)\n\
 \n\
-  [S-2 | SOLID SRP+LSP violation + KISS violation]",
+  [S-2 | Pure Grammar Violation + KISS violation]",
         ),
         span: Span::default(),
     }
 }
 
-fn memo_single_call_hint(behavior: &str, memo_fn: &str) -> Diagnostic {
-    Diagnostic::hint(
-        Span::default(),
-        format!(
-            "In '{behavior}': '{memo_fn}' uses 'with diminishing' (memoization). \
-It can only be called once per program run — calling it a second time will crash. \
-This behavior calls it exactly once, so you are safe."
-        ),
-    )
-    .with_hint(format!(
-        "[S-2 advisory] HashMemoStrategy allocates the full 16 MB arena on the first call to '{memo_fn}'. \
-Only one top-level call per program run is allowed."
-    ))
-}
-
 // ---------------------------------------------------------------------------
 // S-3: Literal Text Used Without Type Annotation
 // ---------------------------------------------------------------------------
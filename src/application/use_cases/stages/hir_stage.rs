@@ -27,7 +27,8 @@ impl<'a, E: EnvironmentPort> PipelineStage for HirStage<'a, E> {
     fn execute(&mut self, discourses: Vec<Discourse>) -> Result<Vec<HirDiscourse>, OnuError> {
         let analysis_service = AnalysisService::new(self.env, self.registry);
         let mut hir_discourses = Vec::new();
-        for discourse in discourses {
+        // Tests are compiled only by `onu test`, never as part of the program.
+        for discourse in discourses.into_iter().filter(|d| !matches!(d, Discourse::Test { .. })) {
             let mut hir = LoweringService::lower_discourse(&discourse, self.registry);
             analysis_service.analyze_discourse(&mut hir)?;
             if self.emit_hir {
@@ -0,0 +1,159 @@
+use super::StdlibOpLowerer;
+use super::text_support::{binop, byte_at, in_range, int, mutable, text_len, text_ptr, var};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// Result of parsing a text as an integer: the value (zero on failure) and
+/// a `0`/`1` flag telling whether the whole text was a well-formed number.
+pub(super) struct ParsedInteger {
+    pub value: MirOperand,
+    pub ok: MirOperand,
+}
+
+/// Emits a parse of `text` as a signed integer in `radix` (2, 10 or 16).
+///
+/// Accepted form: an optional `+`/`-` followed by at least one digit of the
+/// radix, nothing else — no whitespace, no `0x`/`0b` prefix.  Empty input,
+/// stray characters and values that do not fit in 64 bits all fail, which
+/// yields `value = 0, ok = 0`.
+pub(super) fn lower_radix_parse(builder: &mut MirBuilder, text: &MirOperand, radix: i64) -> ParsedInteger {
+    let len = text_len(builder, text);
+    let ptr = text_ptr(builder, text);
+
+    let acc = mutable(builder, int(0));
+    let idx = mutable(builder, int(0));
+    let neg = mutable(builder, int(0));
+    let ok = mutable(builder, int(1));
+
+    let sign_bb = builder.create_block();
+    let cond_bb = builder.create_block();
+    let body_bb = builder.create_block();
+    let step_bb = builder.create_block();
+    let sign_check_bb = builder.create_block();
+    let fail_bb = builder.create_block();
+    let done_bb = builder.create_block();
+
+    let non_empty = binop(builder, MirBinOp::Gt, len.clone(), int(0));
+    builder.terminate(MirTerminator::CondBranch { condition: non_empty, then_block: sign_bb, else_block: fail_bb });
+
+    // -- Optional leading sign --
+    builder.switch_to_block(sign_bb);
+    let first = byte_at(builder, ptr.clone(), int(0));
+    let is_minus = binop(builder, MirBinOp::Eq, first.clone(), int(45));
+    let is_plus = binop(builder, MirBinOp::Eq, first, int(43));
+    let has_sign = binop(builder, MirBinOp::Or, is_minus.clone(), is_plus);
+    builder.build_assign(neg, is_minus);
+    builder.build_assign(idx, has_sign.clone());
+    let has_digits = binop(builder, MirBinOp::Gt, len.clone(), has_sign);
+    builder.terminate(MirTerminator::CondBranch { condition: has_digits, then_block: cond_bb, else_block: fail_bb });
+
+    // -- Loop condition --
+    builder.switch_to_block(cond_bb);
+    let more = binop(builder, MirBinOp::Lt, var(idx), len);
+    builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: sign_check_bb });
+
+    // -- A positive result cannot be i64::MIN's magnitude --
+    builder.switch_to_block(sign_check_bb);
+    let positive = binop(builder, MirBinOp::Xor, var(neg), int(1));
+    let at_min = binop(builder, MirBinOp::Eq, var(acc), int(i64::MIN));
+    let too_big = binop(builder, MirBinOp::And, positive, at_min);
+    builder.terminate(MirTerminator::CondBranch { condition: too_big, then_block: fail_bb, else_block: done_bb });
+
+    // -- Classify the digit; branch-free so every radix shares one path --
+    builder.switch_to_block(body_bb);
+    let c = byte_at(builder, ptr, var(idx));
+    let is_num = in_range(builder, c.clone(), 48, 57);
+    let is_lower = in_range(builder, c.clone(), 97, 102);
+    let is_upper = in_range(builder, c.clone(), 65, 70);
+    let num_val = binop(builder, MirBinOp::Sub, c.clone(), int(48));
+    let lower_val = binop(builder, MirBinOp::Sub, c.clone(), int(87));
+    let upper_val = binop(builder, MirBinOp::Sub, c, int(55));
+    let num_part = binop(builder, MirBinOp::Mul, num_val, is_num.clone());
+    let lower_part = binop(builder, MirBinOp::Mul, lower_val, is_lower.clone());
+    let upper_part = binop(builder, MirBinOp::Mul, upper_val, is_upper.clone());
+    let partial = binop(builder, MirBinOp::Add, num_part, lower_part);
+    let digit = binop(builder, MirBinOp::Add, partial, upper_part);
+    let known_any = binop(builder, MirBinOp::Or, is_num, is_lower);
+    let known = binop(builder, MirBinOp::Or, known_any, is_upper);
+    let in_radix = binop(builder, MirBinOp::Lt, digit.clone(), int(radix));
+    let valid = binop(builder, MirBinOp::And, known, in_radix);
+
+    // Overflow guard: the magnitude accumulates negatively, so that
+    // i64::MIN is reachable, and acc * radix - digit must stay within it.
+    // Division truncates toward zero, which rounds this negative limit up.
+    let floor = binop(builder, MirBinOp::Add, int(i64::MIN), digit.clone());
+    let limit = binop(builder, MirBinOp::Div, floor, int(radix));
+    let overflows = binop(builder, MirBinOp::Lt, var(acc), limit);
+    let fits = binop(builder, MirBinOp::Xor, overflows, int(1));
+    let accept = binop(builder, MirBinOp::And, valid, fits);
+    builder.terminate(MirTerminator::CondBranch { condition: accept, then_block: step_bb, else_block: fail_bb });
+
+    // -- Accumulate --
+    builder.switch_to_block(step_bb);
+    let scaled = binop(builder, MirBinOp::Mul, var(acc), int(radix));
+    let next_acc = binop(builder, MirBinOp::Sub, scaled, digit);
+    builder.build_assign(acc, next_acc);
+    let next_idx = binop(builder, MirBinOp::Add, var(idx), int(1));
+    builder.build_assign(idx, next_idx);
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    // -- Malformed input --
+    builder.switch_to_block(fail_bb);
+    builder.build_assign(ok, int(0));
+    builder.build_assign(acc, int(0));
+    builder.terminate(MirTerminator::Branch(done_bb));
+
+    // -- Apply the sign: acc holds -|value|, so value = acc * (2 * neg - 1) --
+    builder.switch_to_block(done_bb);
+    let twice_neg = binop(builder, MirBinOp::Mul, var(neg), int(2));
+    let factor = binop(builder, MirBinOp::Sub, twice_neg, int(1));
+    let value = binop(builder, MirBinOp::Mul, var(acc), factor);
+
+    ParsedInteger { value, ok: var(ok) }
+}
+
+pub struct AsIntegerLowerer;
+
+impl StdlibOpLowerer for AsIntegerLowerer {
+    fn name(&self) -> &str { "as-integer" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 1 { panic!("as-integer requires 1 argument"); }
+        lower_radix_parse(builder, &args[0], 10).value
+    }
+}
+
+pub struct IsIntegerLowerer;
+
+impl StdlibOpLowerer for IsIntegerLowerer {
+    fn name(&self) -> &str { "is-integer" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 1 { panic!("is-integer requires 1 argument"); }
+        // `ok` is an integer slot; `is-integer` delivers a boolean.
+        let ok = lower_radix_parse(builder, &args[0], 10).ok;
+        binop(builder, MirBinOp::Ne, ok, int(0))
+    }
+}
+
+pub struct HexAsIntegerLowerer;
+
+impl StdlibOpLowerer for HexAsIntegerLowerer {
+    fn name(&self) -> &str { "hex-as-integer" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 1 { panic!("hex-as-integer requires 1 argument"); }
+        lower_radix_parse(builder, &args[0], 16).value
+    }
+}
+
+pub struct BinaryAsIntegerLowerer;
+
+impl StdlibOpLowerer for BinaryAsIntegerLowerer {
+    fn name(&self) -> &str { "binary-as-integer" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 1 { panic!("binary-as-integer requires 1 argument"); }
+        lower_radix_parse(builder, &args[0], 2).value
+    }
+}
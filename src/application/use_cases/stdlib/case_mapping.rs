@@ -0,0 +1,62 @@
+use super::StdlibOpLowerer;
+use super::text_support::{alloc, binop, byte_at, in_range, int, mutable, offset, owned, text_len, text_ptr, var};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// Emits a copy of `text` with every byte in `[lo, hi]` shifted by `delta`.
+/// Non-ASCII bytes are never in range, so multi-byte sequences pass through.
+fn lower_map_range(builder: &mut MirBuilder, text: &MirOperand, lo: i64, hi: i64, delta: i64) -> MirOperand {
+    let len = text_len(builder, text);
+    let ptr = text_ptr(builder, text);
+    let size = binop(builder, MirBinOp::Add, len.clone(), int(1));
+    let buf = alloc(builder, size);
+    let idx = mutable(builder, int(0));
+
+    let cond_bb = builder.create_block();
+    let body_bb = builder.create_block();
+    let done_bb = builder.create_block();
+
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(cond_bb);
+    let more = binop(builder, MirBinOp::Lt, var(idx), len.clone());
+    builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: done_bb });
+
+    builder.switch_to_block(body_bb);
+    let byte = byte_at(builder, ptr, var(idx));
+    let hit = in_range(builder, byte.clone(), lo, hi);
+    let shift = binop(builder, MirBinOp::Mul, hit, int(delta));
+    let mapped = binop(builder, MirBinOp::Add, byte, shift);
+    let target = offset(builder, buf.clone(), var(idx));
+    builder.build_store(target, mapped);
+    let next = binop(builder, MirBinOp::Add, var(idx), int(1));
+    builder.build_assign(idx, next);
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(done_bb);
+    owned(builder, buf, len)
+}
+
+/// `uppercased`: copy with ASCII `a`–`z` mapped to `A`–`Z`.
+pub struct UppercasedLowerer;
+
+impl StdlibOpLowerer for UppercasedLowerer {
+    fn name(&self) -> &str { "uppercased" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 1 { panic!("uppercased requires 1 argument"); }
+        lower_map_range(builder, &args[0], 97, 122, -32)
+    }
+}
+
+/// `lowercased`: copy with ASCII `A`–`Z` mapped to `a`–`z`.
+pub struct LowercasedLowerer;
+
+impl StdlibOpLowerer for LowercasedLowerer {
+    fn name(&self) -> &str { "lowercased" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 1 { panic!("lowercased requires 1 argument"); }
+        lower_map_range(builder, &args[0], 65, 90, 32)
+    }
+}
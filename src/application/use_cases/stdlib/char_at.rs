@@ -9,6 +9,8 @@ pub struct CharAtLowerer;
 impl StdlibOpLowerer for CharAtLowerer {
     fn name(&self) -> &str { "char-at" }
 
+    fn text_index_arg(&self) -> Option<usize> { Some(1) }
+
     fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
         if args.len() != 2 { panic!("char-at requires 2 arguments"); }
         let str_op = &args[0];
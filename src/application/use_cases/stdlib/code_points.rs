@@ -0,0 +1,165 @@
+use super::StdlibOpLowerer;
+use super::text_support::{binop, byte_at, int, mutable, text_len, text_ptr, var, view};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// `1` when `byte` starts a code point, i.e. is not a `10xxxxxx` continuation.
+fn is_lead_byte(builder: &mut MirBuilder, byte: MirOperand) -> MirOperand {
+    let top_bits = binop(builder, MirBinOp::And, byte, int(0xC0));
+    binop(builder, MirBinOp::Ne, top_bits, int(0x80))
+}
+
+/// Emits a scan for the byte offset at which code point number `n` starts.
+/// Yields `len` when the text has `n` or fewer code points (or `n < 0`).
+fn lower_code_point_offset(builder: &mut MirBuilder, len: &MirOperand, ptr: &MirOperand, n: &MirOperand) -> usize {
+    let pos = mutable(builder, int(0));
+    let count = mutable(builder, int(0));
+
+    let cond_bb = builder.create_block();
+    let body_bb = builder.create_block();
+    let step_bb = builder.create_block();
+    let done_bb = builder.create_block();
+
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(cond_bb);
+    let more = binop(builder, MirBinOp::Lt, var(pos), len.clone());
+    builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: done_bb });
+
+    builder.switch_to_block(body_bb);
+    let byte = byte_at(builder, ptr.clone(), var(pos));
+    let is_lead = is_lead_byte(builder, byte);
+    let is_target = binop(builder, MirBinOp::Eq, var(count), n.clone());
+    let hit = binop(builder, MirBinOp::And, is_lead.clone(), is_target);
+    builder.terminate(MirTerminator::CondBranch { condition: hit, then_block: done_bb, else_block: step_bb });
+
+    builder.switch_to_block(step_bb);
+    let next_count = binop(builder, MirBinOp::Add, var(count), is_lead);
+    builder.build_assign(count, next_count);
+    let next_pos = binop(builder, MirBinOp::Add, var(pos), int(1));
+    builder.build_assign(pos, next_pos);
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(done_bb);
+    pos
+}
+
+/// `character-count`: number of code points (not bytes) in the text.
+pub struct CharacterCountLowerer;
+
+impl StdlibOpLowerer for CharacterCountLowerer {
+    fn name(&self) -> &str { "character-count" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 1 { panic!("character-count requires 1 argument"); }
+        let len = text_len(builder, &args[0]);
+        let ptr = text_ptr(builder, &args[0]);
+        let pos = mutable(builder, int(0));
+        let count = mutable(builder, int(0));
+
+        let cond_bb = builder.create_block();
+        let body_bb = builder.create_block();
+        let done_bb = builder.create_block();
+
+        builder.terminate(MirTerminator::Branch(cond_bb));
+
+        builder.switch_to_block(cond_bb);
+        let more = binop(builder, MirBinOp::Lt, var(pos), len);
+        builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: done_bb });
+
+        builder.switch_to_block(body_bb);
+        let byte = byte_at(builder, ptr, var(pos));
+        let is_lead = is_lead_byte(builder, byte);
+        let next_count = binop(builder, MirBinOp::Add, var(count), is_lead);
+        builder.build_assign(count, next_count);
+        let next_pos = binop(builder, MirBinOp::Add, var(pos), int(1));
+        builder.build_assign(pos, next_pos);
+        builder.terminate(MirTerminator::Branch(cond_bb));
+
+        builder.switch_to_block(done_bb);
+        var(count)
+    }
+}
+
+/// `code-point-at`: the Unicode scalar value of code point number `n`,
+/// or `-1` when `n` is out of range.
+pub struct CodePointAtLowerer;
+
+impl StdlibOpLowerer for CodePointAtLowerer {
+    fn name(&self) -> &str { "code-point-at" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("code-point-at requires 2 arguments"); }
+        let len = text_len(builder, &args[0]);
+        let ptr = text_ptr(builder, &args[0]);
+        let start = lower_code_point_offset(builder, &len, &ptr, &args[1]);
+        let result = mutable(builder, int(-1));
+
+        let decode_bb = builder.create_block();
+        let cond_bb = builder.create_block();
+        let body_bb = builder.create_block();
+        let done_bb = builder.create_block();
+
+        let in_range = binop(builder, MirBinOp::Lt, var(start), len.clone());
+        builder.terminate(MirTerminator::CondBranch { condition: in_range, then_block: decode_bb, else_block: done_bb });
+
+        // -- Lead byte: width = 1 + [b0 >= C0] + [b0 >= E0] + [b0 >= F0] --
+        builder.switch_to_block(decode_bb);
+        let lead = byte_at(builder, ptr.clone(), var(start));
+        let ge_two = binop(builder, MirBinOp::Gt, lead.clone(), int(0xBF));
+        let ge_three = binop(builder, MirBinOp::Gt, lead.clone(), int(0xDF));
+        let ge_four = binop(builder, MirBinOp::Gt, lead.clone(), int(0xEF));
+        let width_two = binop(builder, MirBinOp::Add, ge_two.clone(), int(1));
+        let width_three = binop(builder, MirBinOp::Add, width_two, ge_three);
+        let width = binop(builder, MirBinOp::Add, width_three, ge_four);
+        // Payload mask 0x7F, 0x1F, 0x0F, 0x07 for widths 1..4.
+        let shift_base = binop(builder, MirBinOp::Add, width.clone(), ge_two);
+        let shift = binop(builder, MirBinOp::Sub, shift_base, int(1));
+        let mask = binop(builder, MirBinOp::Shr, int(0x7F), shift);
+        let payload = binop(builder, MirBinOp::And, lead, mask);
+        builder.build_assign(result, payload);
+        let k = mutable(builder, int(1));
+        builder.terminate(MirTerminator::Branch(cond_bb));
+
+        // -- Continuation bytes, never reading past the end of the text --
+        builder.switch_to_block(cond_bb);
+        let within_width = binop(builder, MirBinOp::Lt, var(k), width);
+        let at = binop(builder, MirBinOp::Add, var(start), var(k));
+        let within_text = binop(builder, MirBinOp::Lt, at.clone(), len);
+        let more = binop(builder, MirBinOp::And, within_width, within_text);
+        builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: done_bb });
+
+        builder.switch_to_block(body_bb);
+        let cont = byte_at(builder, ptr, at);
+        let bits = binop(builder, MirBinOp::And, cont, int(0x3F));
+        let shifted = binop(builder, MirBinOp::Shl, var(result), int(6));
+        let next = binop(builder, MirBinOp::Or, shifted, bits);
+        builder.build_assign(result, next);
+        let next_k = binop(builder, MirBinOp::Add, var(k), int(1));
+        builder.build_assign(k, next_k);
+        builder.terminate(MirTerminator::Branch(cond_bb));
+
+        builder.switch_to_block(done_bb);
+        var(result)
+    }
+}
+
+/// `characters-between`: borrowed view of code points `[from, to)`.
+/// Indices past the end clamp to the end; `to <= from` yields empty text.
+pub struct CharactersBetweenLowerer;
+
+impl StdlibOpLowerer for CharactersBetweenLowerer {
+    fn name(&self) -> &str { "characters-between" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 3 { panic!("characters-between requires 3 arguments"); }
+        let len = text_len(builder, &args[0]);
+        let ptr = text_ptr(builder, &args[0]);
+        let start = lower_code_point_offset(builder, &len, &ptr, &args[1]);
+        let end = lower_code_point_offset(builder, &len, &ptr, &args[2]);
+        let span = binop(builder, MirBinOp::Sub, var(end), var(start));
+        let positive = binop(builder, MirBinOp::Gt, span.clone(), int(0));
+        let span_len = binop(builder, MirBinOp::Mul, span, positive);
+        view(builder, ptr, var(start), span_len)
+    }
+}
@@ -0,0 +1,116 @@
+use super::StdlibOpLowerer;
+use super::text_support::{binop, byte_at, in_range, int, mutable, text_len, text_ptr, var};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// `is-valid-utf8`: strict UTF-8 validation (RFC 3629).
+///
+/// Rejects stray continuation bytes, truncated sequences, overlong forms,
+/// UTF-16 surrogates (U+D800..U+DFFF) and values above U+10FFFF — the
+/// second byte's legal range is narrowed for the `E0`, `ED`, `F0` and `F4`
+/// lead bytes exactly as in the RFC's table.
+pub struct IsValidUtf8Lowerer;
+
+impl StdlibOpLowerer for IsValidUtf8Lowerer {
+    fn name(&self) -> &str { "is-valid-utf8" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 1 { panic!("is-valid-utf8 requires 1 argument"); }
+        let len = text_len(builder, &args[0]);
+        let ptr = text_ptr(builder, &args[0]);
+
+        let pos = mutable(builder, int(0));
+        let ok = mutable(builder, int(1));
+        let width = mutable(builder, int(0));
+        let k = mutable(builder, int(1));
+        let lo = mutable(builder, int(0x80));
+        let hi = mutable(builder, int(0xBF));
+
+        let cond_bb = builder.create_block();
+        let lead_bb = builder.create_block();
+        let cont_cond_bb = builder.create_block();
+        let cont_body_bb = builder.create_block();
+        let cont_step_bb = builder.create_block();
+        let advance_bb = builder.create_block();
+        let fail_bb = builder.create_block();
+        let done_bb = builder.create_block();
+
+        builder.terminate(MirTerminator::Branch(cond_bb));
+
+        builder.switch_to_block(cond_bb);
+        let more = binop(builder, MirBinOp::Lt, var(pos), len.clone());
+        builder.terminate(MirTerminator::CondBranch { condition: more, then_block: lead_bb, else_block: done_bb });
+
+        // -- Lead byte: sequence width, or 0 for an illegal lead --
+        builder.switch_to_block(lead_bb);
+        let b0 = byte_at(builder, ptr.clone(), var(pos));
+        let ascii = binop(builder, MirBinOp::Lt, b0.clone(), int(0x80));
+        let two = in_range(builder, b0.clone(), 0xC2, 0xDF);
+        let three = in_range(builder, b0.clone(), 0xE0, 0xEF);
+        let four = in_range(builder, b0.clone(), 0xF0, 0xF4);
+        let two_w = binop(builder, MirBinOp::Mul, two, int(2));
+        let three_w = binop(builder, MirBinOp::Mul, three, int(3));
+        let four_w = binop(builder, MirBinOp::Mul, four, int(4));
+        let w_a = binop(builder, MirBinOp::Add, ascii, two_w);
+        let w_b = binop(builder, MirBinOp::Add, w_a, three_w);
+        let w = binop(builder, MirBinOp::Add, w_b, four_w);
+        builder.build_assign(width, w.clone());
+        let legal_lead = binop(builder, MirBinOp::Gt, w.clone(), int(0));
+        let seq_end = binop(builder, MirBinOp::Add, var(pos), w);
+        let len_plus_one = binop(builder, MirBinOp::Add, len, int(1));
+        let fits = binop(builder, MirBinOp::Lt, seq_end, len_plus_one);
+        let accept_lead = binop(builder, MirBinOp::And, legal_lead, fits);
+
+        // Second-byte range: E0 → A0..BF, ED → 80..9F, F0 → 90..BF, F4 → 80..8F.
+        let is_e0 = binop(builder, MirBinOp::Eq, b0.clone(), int(0xE0));
+        let is_ed = binop(builder, MirBinOp::Eq, b0.clone(), int(0xED));
+        let is_f0 = binop(builder, MirBinOp::Eq, b0.clone(), int(0xF0));
+        let is_f4 = binop(builder, MirBinOp::Eq, b0, int(0xF4));
+        let lo_e0 = binop(builder, MirBinOp::Mul, is_e0, int(0x20));
+        let lo_f0 = binop(builder, MirBinOp::Mul, is_f0, int(0x10));
+        let lo_a = binop(builder, MirBinOp::Add, int(0x80), lo_e0);
+        let lo_first = binop(builder, MirBinOp::Add, lo_a, lo_f0);
+        builder.build_assign(lo, lo_first);
+        let hi_ed = binop(builder, MirBinOp::Mul, is_ed, int(0x20));
+        let hi_f4 = binop(builder, MirBinOp::Mul, is_f4, int(0x30));
+        let hi_a = binop(builder, MirBinOp::Sub, int(0xBF), hi_ed);
+        let hi_first = binop(builder, MirBinOp::Sub, hi_a, hi_f4);
+        builder.build_assign(hi, hi_first);
+        builder.build_assign(k, int(1));
+        builder.terminate(MirTerminator::CondBranch { condition: accept_lead, then_block: cont_cond_bb, else_block: fail_bb });
+
+        // -- Continuation bytes --
+        builder.switch_to_block(cont_cond_bb);
+        let more_cont = binop(builder, MirBinOp::Lt, var(k), var(width));
+        builder.terminate(MirTerminator::CondBranch { condition: more_cont, then_block: cont_body_bb, else_block: advance_bb });
+
+        builder.switch_to_block(cont_body_bb);
+        let at = binop(builder, MirBinOp::Add, var(pos), var(k));
+        let c = byte_at(builder, ptr, at);
+        let lo_bound = binop(builder, MirBinOp::Sub, var(lo), int(1));
+        let hi_bound = binop(builder, MirBinOp::Add, var(hi), int(1));
+        let above = binop(builder, MirBinOp::Gt, c.clone(), lo_bound);
+        let below = binop(builder, MirBinOp::Lt, c, hi_bound);
+        let good = binop(builder, MirBinOp::And, above, below);
+        builder.terminate(MirTerminator::CondBranch { condition: good, then_block: cont_step_bb, else_block: fail_bb });
+
+        builder.switch_to_block(cont_step_bb);
+        builder.build_assign(lo, int(0x80));
+        builder.build_assign(hi, int(0xBF));
+        let next_k = binop(builder, MirBinOp::Add, var(k), int(1));
+        builder.build_assign(k, next_k);
+        builder.terminate(MirTerminator::Branch(cont_cond_bb));
+
+        builder.switch_to_block(advance_bb);
+        let next_pos = binop(builder, MirBinOp::Add, var(pos), var(width));
+        builder.build_assign(pos, next_pos);
+        builder.terminate(MirTerminator::Branch(cond_bb));
+
+        builder.switch_to_block(fail_bb);
+        builder.build_assign(ok, int(0));
+        builder.terminate(MirTerminator::Branch(done_bb));
+
+        builder.switch_to_block(done_bb);
+        binop(builder, MirBinOp::Ne, var(ok), int(0))
+    }
+}
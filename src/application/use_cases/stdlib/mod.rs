@@ -15,6 +15,18 @@ pub mod char_from_code;
 pub mod init_of;
 pub mod sha256_k;
 pub mod write_hex_word;
+pub mod as_integer;
+pub mod trimmed;
+pub mod split_at_first;
+pub mod code_points;
+pub mod is_valid_utf8;
+pub mod text_search;
+pub mod text_order;
+pub mod substring;
+pub mod replaced_with;
+pub mod case_mapping;
+pub mod repeated_times;
+mod text_support;
 
 pub trait StdlibOpLowerer {
     fn name(&self) -> &str;
@@ -23,6 +35,13 @@ pub trait StdlibOpLowerer {
         args: Vec<MirOperand>,
         builder: &mut MirBuilder,
     ) -> MirOperand;
+
+    /// Position of an argument that indexes into the text passed as the
+    /// first argument.  When runtime checks are enabled the call site
+    /// guards it with `0 <= index < len` before the op runs.
+    fn text_index_arg(&self) -> Option<usize> {
+        None
+    }
 }
 
 pub struct StdlibOpRegistry {
@@ -42,6 +61,29 @@ impl StdlibOpRegistry {
         ops.insert("init-of".into(), Box::new(init_of::InitOfLowerer));
         ops.insert("sha256-k-table".into(), Box::new(sha256_k::Sha256KTableLowerer));
         ops.insert("write-hex-word".into(), Box::new(write_hex_word::WriteHexWordLowerer));
+        ops.insert("as-integer".into(), Box::new(as_integer::AsIntegerLowerer));
+        ops.insert("is-integer".into(), Box::new(as_integer::IsIntegerLowerer));
+        ops.insert("hex-as-integer".into(), Box::new(as_integer::HexAsIntegerLowerer));
+        ops.insert("binary-as-integer".into(), Box::new(as_integer::BinaryAsIntegerLowerer));
+        ops.insert("trimmed".into(), Box::new(trimmed::TrimmedLowerer));
+        ops.insert("before-first".into(), Box::new(split_at_first::BeforeFirstLowerer));
+        ops.insert("after-first".into(), Box::new(split_at_first::AfterFirstLowerer));
+        ops.insert("character-count".into(), Box::new(code_points::CharacterCountLowerer));
+        ops.insert("code-point-at".into(), Box::new(code_points::CodePointAtLowerer));
+        ops.insert("characters-between".into(), Box::new(code_points::CharactersBetweenLowerer));
+        ops.insert("is-valid-utf8".into(), Box::new(is_valid_utf8::IsValidUtf8Lowerer));
+        ops.insert("compared-with".into(), Box::new(text_order::ComparedWithLowerer));
+        ops.insert("precedes".into(), Box::new(text_order::PrecedesLowerer));
+        ops.insert("follows".into(), Box::new(text_order::FollowsLowerer));
+        ops.insert("contains".into(), Box::new(text_search::ContainsLowerer));
+        ops.insert("starts-with".into(), Box::new(text_search::StartsWithLowerer));
+        ops.insert("ends-with".into(), Box::new(text_search::EndsWithLowerer));
+        ops.insert("index-of".into(), Box::new(text_search::IndexOfLowerer));
+        ops.insert("substring".into(), Box::new(substring::SubstringLowerer));
+        ops.insert("replaced-with".into(), Box::new(replaced_with::ReplacedWithLowerer));
+        ops.insert("uppercased".into(), Box::new(case_mapping::UppercasedLowerer));
+        ops.insert("lowercased".into(), Box::new(case_mapping::LowercasedLowerer));
+        ops.insert("repeated-times".into(), Box::new(repeated_times::RepeatedTimesLowerer));
         Self { ops }
     }
 
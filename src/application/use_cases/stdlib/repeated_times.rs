@@ -0,0 +1,44 @@
+use super::StdlibOpLowerer;
+use super::text_support::{alloc, binop, int, mutable, offset, owned, text_len, text_ptr, var};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// `repeated-times`: the text concatenated with itself `n` times.
+/// `n <= 0` yields empty text.
+pub struct RepeatedTimesLowerer;
+
+impl StdlibOpLowerer for RepeatedTimesLowerer {
+    fn name(&self) -> &str { "repeated-times" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("repeated-times requires 2 arguments"); }
+        let len = text_len(builder, &args[0]);
+        let ptr = text_ptr(builder, &args[0]);
+        let positive = binop(builder, MirBinOp::Gt, args[1].clone(), int(0));
+        let count = binop(builder, MirBinOp::Mul, args[1].clone(), positive);
+        let total = binop(builder, MirBinOp::Mul, len.clone(), count.clone());
+        let size = binop(builder, MirBinOp::Add, total.clone(), int(1));
+        let buf = alloc(builder, size);
+        let written = mutable(builder, int(0));
+
+        let cond_bb = builder.create_block();
+        let body_bb = builder.create_block();
+        let done_bb = builder.create_block();
+
+        builder.terminate(MirTerminator::Branch(cond_bb));
+
+        builder.switch_to_block(cond_bb);
+        let more = binop(builder, MirBinOp::Lt, var(written), total.clone());
+        builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: done_bb });
+
+        builder.switch_to_block(body_bb);
+        let target = offset(builder, buf.clone(), var(written));
+        builder.build_memcpy(target, ptr, len.clone());
+        let next = binop(builder, MirBinOp::Add, var(written), len);
+        builder.build_assign(written, next);
+        builder.terminate(MirTerminator::Branch(cond_bb));
+
+        builder.switch_to_block(done_bb);
+        owned(builder, buf, total)
+    }
+}
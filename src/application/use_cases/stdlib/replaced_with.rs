@@ -0,0 +1,131 @@
+use super::StdlibOpLowerer;
+use super::text_search::lower_bytes_equal;
+use super::text_support::{alloc, binop, byte_at, int, mutable, offset, owned, text_len, text_ptr, var};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// Emits "does `pattern` occur at `pos`?" and leaves the builder in a block
+/// that branches to `hit_bb` or `miss_bb`.  An empty pattern never matches,
+/// which keeps `replaced-with ""` from looping forever.
+fn branch_on_match(
+    builder: &mut MirBuilder,
+    ptr: &MirOperand,
+    pos: usize,
+    last_start: &MirOperand,
+    (pattern_ptr, pattern_len): (&MirOperand, &MirOperand),
+    (hit_bb, miss_bb): (usize, usize),
+) {
+    let compare_bb = builder.create_block();
+    let non_empty = binop(builder, MirBinOp::Gt, pattern_len.clone(), int(0));
+    let bound = binop(builder, MirBinOp::Add, last_start.clone(), int(1));
+    let fits = binop(builder, MirBinOp::Lt, var(pos), bound);
+    let can_match = binop(builder, MirBinOp::And, non_empty, fits);
+    builder.terminate(MirTerminator::CondBranch { condition: can_match, then_block: compare_bb, else_block: miss_bb });
+
+    builder.switch_to_block(compare_bb);
+    let candidate = offset(builder, ptr.clone(), var(pos));
+    let equal = lower_bytes_equal(builder, candidate, pattern_ptr.clone(), pattern_len.clone());
+    builder.terminate(MirTerminator::CondBranch { condition: var(equal), then_block: hit_bb, else_block: miss_bb });
+}
+
+/// `replaced-with`: copy of the text with every non-overlapping occurrence
+/// of the pattern (scanning left to right) replaced by the replacement,
+/// written `text utilizes replaced-with "old" "new"`.
+///
+/// Two passes: the first counts matches to size the arena allocation
+/// exactly, the second copies.
+pub struct ReplacedWithLowerer;
+
+impl StdlibOpLowerer for ReplacedWithLowerer {
+    fn name(&self) -> &str { "replaced-with" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 3 { panic!("replaced-with requires 3 arguments"); }
+        let len = text_len(builder, &args[0]);
+        let ptr = text_ptr(builder, &args[0]);
+        let old_len = text_len(builder, &args[1]);
+        let old_ptr = text_ptr(builder, &args[1]);
+        let new_len = text_len(builder, &args[2]);
+        let new_ptr = text_ptr(builder, &args[2]);
+        let last_start = binop(builder, MirBinOp::Sub, len.clone(), old_len.clone());
+
+        // -- Pass 1: count matches --
+        let pos = mutable(builder, int(0));
+        let matches = mutable(builder, int(0));
+
+        let count_cond_bb = builder.create_block();
+        let count_body_bb = builder.create_block();
+        let count_hit_bb = builder.create_block();
+        let count_miss_bb = builder.create_block();
+        let sized_bb = builder.create_block();
+
+        builder.terminate(MirTerminator::Branch(count_cond_bb));
+
+        builder.switch_to_block(count_cond_bb);
+        let more = binop(builder, MirBinOp::Lt, var(pos), len.clone());
+        builder.terminate(MirTerminator::CondBranch { condition: more, then_block: count_body_bb, else_block: sized_bb });
+
+        builder.switch_to_block(count_body_bb);
+        branch_on_match(builder, &ptr, pos, &last_start, (&old_ptr, &old_len), (count_hit_bb, count_miss_bb));
+
+        builder.switch_to_block(count_hit_bb);
+        let next_matches = binop(builder, MirBinOp::Add, var(matches), int(1));
+        builder.build_assign(matches, next_matches);
+        let skip = binop(builder, MirBinOp::Add, var(pos), old_len.clone());
+        builder.build_assign(pos, skip);
+        builder.terminate(MirTerminator::Branch(count_cond_bb));
+
+        builder.switch_to_block(count_miss_bb);
+        let step = binop(builder, MirBinOp::Add, var(pos), int(1));
+        builder.build_assign(pos, step);
+        builder.terminate(MirTerminator::Branch(count_cond_bb));
+
+        // -- Allocate: len + matches * (new_len - old_len) --
+        builder.switch_to_block(sized_bb);
+        let growth = binop(builder, MirBinOp::Sub, new_len.clone(), old_len.clone());
+        let total_growth = binop(builder, MirBinOp::Mul, var(matches), growth);
+        let result_len = binop(builder, MirBinOp::Add, len.clone(), total_growth);
+        let size = binop(builder, MirBinOp::Add, result_len.clone(), int(1));
+        let buf = alloc(builder, size);
+        builder.build_assign(pos, int(0));
+        let out = mutable(builder, int(0));
+
+        // -- Pass 2: copy --
+        let copy_cond_bb = builder.create_block();
+        let copy_body_bb = builder.create_block();
+        let copy_hit_bb = builder.create_block();
+        let copy_miss_bb = builder.create_block();
+        let done_bb = builder.create_block();
+
+        builder.terminate(MirTerminator::Branch(copy_cond_bb));
+
+        builder.switch_to_block(copy_cond_bb);
+        let more = binop(builder, MirBinOp::Lt, var(pos), len);
+        builder.terminate(MirTerminator::CondBranch { condition: more, then_block: copy_body_bb, else_block: done_bb });
+
+        builder.switch_to_block(copy_body_bb);
+        branch_on_match(builder, &ptr, pos, &last_start, (&old_ptr, &old_len), (copy_hit_bb, copy_miss_bb));
+
+        builder.switch_to_block(copy_hit_bb);
+        let target = offset(builder, buf.clone(), var(out));
+        builder.build_memcpy(target, new_ptr, new_len.clone());
+        let next_out = binop(builder, MirBinOp::Add, var(out), new_len);
+        builder.build_assign(out, next_out);
+        let skip = binop(builder, MirBinOp::Add, var(pos), old_len);
+        builder.build_assign(pos, skip);
+        builder.terminate(MirTerminator::Branch(copy_cond_bb));
+
+        builder.switch_to_block(copy_miss_bb);
+        let byte = byte_at(builder, ptr, var(pos));
+        let target = offset(builder, buf.clone(), var(out));
+        builder.build_store(target, byte);
+        let next_out = binop(builder, MirBinOp::Add, var(out), int(1));
+        builder.build_assign(out, next_out);
+        let step = binop(builder, MirBinOp::Add, var(pos), int(1));
+        builder.build_assign(pos, step);
+        builder.terminate(MirTerminator::Branch(copy_cond_bb));
+
+        builder.switch_to_block(done_bb);
+        owned(builder, buf, result_len)
+    }
+}
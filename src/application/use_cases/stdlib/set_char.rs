@@ -8,6 +8,8 @@ pub struct SetCharLowerer;
 impl StdlibOpLowerer for SetCharLowerer {
     fn name(&self) -> &str { "set-char" }
 
+    fn text_index_arg(&self) -> Option<usize> { Some(1) }
+
     fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
         if args.len() != 3 {
             panic!("set-char requires 3 arguments: string, index, char_code");
@@ -0,0 +1,69 @@
+use super::StdlibOpLowerer;
+use super::text_support::{binop, byte_at, int, mutable, text_len, text_ptr, var, view};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// Emits a scan for the first byte of `text` equal to `code` and returns
+/// `(len, ptr, position)`.  `position` is `len` when the byte is absent.
+fn lower_find_byte(builder: &mut MirBuilder, text: &MirOperand, code: &MirOperand) -> (MirOperand, MirOperand, usize) {
+    let len = text_len(builder, text);
+    let ptr = text_ptr(builder, text);
+    let pos = mutable(builder, int(0));
+
+    let cond_bb = builder.create_block();
+    let body_bb = builder.create_block();
+    let step_bb = builder.create_block();
+    let done_bb = builder.create_block();
+
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(cond_bb);
+    let more = binop(builder, MirBinOp::Lt, var(pos), len.clone());
+    builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: done_bb });
+
+    builder.switch_to_block(body_bb);
+    let byte = byte_at(builder, ptr.clone(), var(pos));
+    let found = binop(builder, MirBinOp::Eq, byte, code.clone());
+    builder.terminate(MirTerminator::CondBranch { condition: found, then_block: done_bb, else_block: step_bb });
+
+    builder.switch_to_block(step_bb);
+    let next = binop(builder, MirBinOp::Add, var(pos), int(1));
+    builder.build_assign(pos, next);
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(done_bb);
+    (len, ptr, pos)
+}
+
+/// `before-first`: the part of the text preceding the first occurrence of
+/// the given character code, or the whole text when it does not occur.
+pub struct BeforeFirstLowerer;
+
+impl StdlibOpLowerer for BeforeFirstLowerer {
+    fn name(&self) -> &str { "before-first" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("before-first requires 2 arguments"); }
+        let (_, ptr, pos) = lower_find_byte(builder, &args[0], &args[1]);
+        view(builder, ptr, int(0), var(pos))
+    }
+}
+
+/// `after-first`: the part of the text following the first occurrence of
+/// the given character code, or empty text when it does not occur.
+pub struct AfterFirstLowerer;
+
+impl StdlibOpLowerer for AfterFirstLowerer {
+    fn name(&self) -> &str { "after-first" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("after-first requires 2 arguments"); }
+        let (len, ptr, pos) = lower_find_byte(builder, &args[0], &args[1]);
+        // Skip the delimiter itself, clamping to `len` when it was absent.
+        let past = binop(builder, MirBinOp::Add, var(pos), int(1));
+        let absent = binop(builder, MirBinOp::Eq, var(pos), len.clone());
+        let start = binop(builder, MirBinOp::Sub, past, absent);
+        let rest = binop(builder, MirBinOp::Sub, len, start.clone());
+        view(builder, ptr, start, rest)
+    }
+}
@@ -0,0 +1,36 @@
+use super::StdlibOpLowerer;
+use super::text_support::{binop, int, text_len, text_ptr, view};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand};
+
+/// Clamps `value` into `[0, hi]` without branching.
+fn clamp(builder: &mut MirBuilder, value: MirOperand, hi: MirOperand) -> MirOperand {
+    let positive = binop(builder, MirBinOp::Gt, value.clone(), int(0));
+    let low_clamped = binop(builder, MirBinOp::Mul, value, positive);
+    let over = binop(builder, MirBinOp::Gt, low_clamped.clone(), hi.clone());
+    let excess = binop(builder, MirBinOp::Sub, low_clamped.clone(), hi);
+    let cut = binop(builder, MirBinOp::Mul, excess, over);
+    binop(builder, MirBinOp::Sub, low_clamped, cut)
+}
+
+/// `substring`: borrowed view of bytes `[from, to)`, written
+/// `text utilizes substring from 2 to 5`.  Offsets clamp to the text and
+/// `to <= from` yields empty text.  See `characters-between` for the
+/// code-point equivalent.
+pub struct SubstringLowerer;
+
+impl StdlibOpLowerer for SubstringLowerer {
+    fn name(&self) -> &str { "substring" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 3 { panic!("substring requires 3 arguments"); }
+        let len = text_len(builder, &args[0]);
+        let ptr = text_ptr(builder, &args[0]);
+        let start = clamp(builder, args[1].clone(), len.clone());
+        let end = clamp(builder, args[2].clone(), len);
+        let span = binop(builder, MirBinOp::Sub, end, start.clone());
+        let positive = binop(builder, MirBinOp::Gt, span.clone(), int(0));
+        let span_len = binop(builder, MirBinOp::Mul, span, positive);
+        view(builder, ptr, start, span_len)
+    }
+}
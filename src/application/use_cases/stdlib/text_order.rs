@@ -0,0 +1,104 @@
+use super::StdlibOpLowerer;
+use super::text_support::{binop, byte_at, int, mutable, text_len, text_ptr, var};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// `(x > y) - (x < y)`: the sign of the comparison as `-1`, `0` or `1`.
+fn sign_of_comparison(builder: &mut MirBuilder, x: MirOperand, y: MirOperand) -> MirOperand {
+    let greater = binop(builder, MirBinOp::Gt, x.clone(), y.clone());
+    let less = binop(builder, MirBinOp::Lt, x, y);
+    binop(builder, MirBinOp::Sub, greater, less)
+}
+
+/// Emits a lexicographic comparison of two texts by unsigned byte value
+/// (which for UTF-8 is also code point order).  Yields `-1`, `0` or `1`.
+fn lower_compare(builder: &mut MirBuilder, a: &MirOperand, b: &MirOperand) -> MirOperand {
+    let a_len = text_len(builder, a);
+    let a_ptr = text_ptr(builder, a);
+    let b_len = text_len(builder, b);
+    let b_ptr = text_ptr(builder, b);
+
+    // Shared prefix length: min(a_len, b_len).
+    let a_shorter = binop(builder, MirBinOp::Lt, a_len.clone(), b_len.clone());
+    let len_diff = binop(builder, MirBinOp::Sub, a_len.clone(), b_len.clone());
+    let trim = binop(builder, MirBinOp::Mul, len_diff, a_shorter);
+    let shared = binop(builder, MirBinOp::Add, b_len.clone(), trim);
+
+    let idx = mutable(builder, int(0));
+    let result = mutable(builder, int(0));
+
+    let cond_bb = builder.create_block();
+    let body_bb = builder.create_block();
+    let step_bb = builder.create_block();
+    let differ_bb = builder.create_block();
+    let prefix_bb = builder.create_block();
+    let done_bb = builder.create_block();
+
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(cond_bb);
+    let more = binop(builder, MirBinOp::Lt, var(idx), shared);
+    builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: prefix_bb });
+
+    builder.switch_to_block(body_bb);
+    let a_byte = byte_at(builder, a_ptr, var(idx));
+    let b_byte = byte_at(builder, b_ptr, var(idx));
+    let same = binop(builder, MirBinOp::Eq, a_byte.clone(), b_byte.clone());
+    builder.terminate(MirTerminator::CondBranch { condition: same, then_block: step_bb, else_block: differ_bb });
+
+    builder.switch_to_block(step_bb);
+    let next = binop(builder, MirBinOp::Add, var(idx), int(1));
+    builder.build_assign(idx, next);
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(differ_bb);
+    let byte_order = sign_of_comparison(builder, a_byte, b_byte);
+    builder.build_assign(result, byte_order);
+    builder.terminate(MirTerminator::Branch(done_bb));
+
+    // One text is a prefix of the other: the shorter one sorts first.
+    builder.switch_to_block(prefix_bb);
+    let len_order = sign_of_comparison(builder, a_len, b_len);
+    builder.build_assign(result, len_order);
+    builder.terminate(MirTerminator::Branch(done_bb));
+
+    builder.switch_to_block(done_bb);
+    var(result)
+}
+
+/// `compared-with`: `-1`, `0` or `1` as the first text sorts before, equal
+/// to, or after the second.
+pub struct ComparedWithLowerer;
+
+impl StdlibOpLowerer for ComparedWithLowerer {
+    fn name(&self) -> &str { "compared-with" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("compared-with requires 2 arguments"); }
+        lower_compare(builder, &args[0], &args[1])
+    }
+}
+
+pub struct PrecedesLowerer;
+
+impl StdlibOpLowerer for PrecedesLowerer {
+    fn name(&self) -> &str { "precedes" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("precedes requires 2 arguments"); }
+        let order = lower_compare(builder, &args[0], &args[1]);
+        binop(builder, MirBinOp::Lt, order, int(0))
+    }
+}
+
+pub struct FollowsLowerer;
+
+impl StdlibOpLowerer for FollowsLowerer {
+    fn name(&self) -> &str { "follows" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("follows requires 2 arguments"); }
+        let order = lower_compare(builder, &args[0], &args[1]);
+        binop(builder, MirBinOp::Gt, order, int(0))
+    }
+}
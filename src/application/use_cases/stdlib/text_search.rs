@@ -0,0 +1,169 @@
+use super::StdlibOpLowerer;
+use super::text_support::{binop, byte_at, int, mutable, offset, text_len, text_ptr, var};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// Emits a byte-wise comparison of `n` bytes at `a` and `b`.
+/// Returns a slot holding `1` when all bytes are equal, `0` otherwise.
+/// The caller guarantees both ranges are in bounds.
+pub(super) fn lower_bytes_equal(builder: &mut MirBuilder, a: MirOperand, b: MirOperand, n: MirOperand) -> usize {
+    let idx = mutable(builder, int(0));
+    let equal = mutable(builder, int(1));
+
+    let cond_bb = builder.create_block();
+    let body_bb = builder.create_block();
+    let step_bb = builder.create_block();
+    let differ_bb = builder.create_block();
+    let done_bb = builder.create_block();
+
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(cond_bb);
+    let more = binop(builder, MirBinOp::Lt, var(idx), n);
+    builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: done_bb });
+
+    builder.switch_to_block(body_bb);
+    let a_byte = byte_at(builder, a, var(idx));
+    let b_byte = byte_at(builder, b, var(idx));
+    let same = binop(builder, MirBinOp::Eq, a_byte, b_byte);
+    builder.terminate(MirTerminator::CondBranch { condition: same, then_block: step_bb, else_block: differ_bb });
+
+    builder.switch_to_block(step_bb);
+    let next = binop(builder, MirBinOp::Add, var(idx), int(1));
+    builder.build_assign(idx, next);
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(differ_bb);
+    builder.build_assign(equal, int(0));
+    builder.terminate(MirTerminator::Branch(done_bb));
+
+    builder.switch_to_block(done_bb);
+    equal
+}
+
+/// Emits a naive left-to-right search and returns the byte offset of the
+/// first occurrence of `needle` in `haystack`, or `-1`.  The empty needle
+/// is found at offset 0.
+fn lower_index_of(builder: &mut MirBuilder, haystack: &MirOperand, needle: &MirOperand) -> MirOperand {
+    let hay_len = text_len(builder, haystack);
+    let hay_ptr = text_ptr(builder, haystack);
+    let needle_len = text_len(builder, needle);
+    let needle_ptr = text_ptr(builder, needle);
+    let last_start = binop(builder, MirBinOp::Sub, hay_len, needle_len.clone());
+
+    let pos = mutable(builder, int(0));
+    let found = mutable(builder, int(-1));
+
+    let cond_bb = builder.create_block();
+    let body_bb = builder.create_block();
+    let step_bb = builder.create_block();
+    let hit_bb = builder.create_block();
+    let done_bb = builder.create_block();
+
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(cond_bb);
+    let bound = binop(builder, MirBinOp::Add, last_start, int(1));
+    let more = binop(builder, MirBinOp::Lt, var(pos), bound);
+    builder.terminate(MirTerminator::CondBranch { condition: more, then_block: body_bb, else_block: done_bb });
+
+    builder.switch_to_block(body_bb);
+    let candidate = offset(builder, hay_ptr, var(pos));
+    let equal = lower_bytes_equal(builder, candidate, needle_ptr, needle_len);
+    builder.terminate(MirTerminator::CondBranch { condition: var(equal), then_block: hit_bb, else_block: step_bb });
+
+    builder.switch_to_block(step_bb);
+    let next = binop(builder, MirBinOp::Add, var(pos), int(1));
+    builder.build_assign(pos, next);
+    builder.terminate(MirTerminator::Branch(cond_bb));
+
+    builder.switch_to_block(hit_bb);
+    builder.build_assign(found, var(pos));
+    builder.terminate(MirTerminator::Branch(done_bb));
+
+    builder.switch_to_block(done_bb);
+    var(found)
+}
+
+/// Emits a comparison of `needle` against `haystack` at byte offset `at`,
+/// where `at = -1` marks a needle that does not fit and can never match.
+fn lower_matches_at(builder: &mut MirBuilder, haystack: &MirOperand, needle: &MirOperand, at: MirOperand) -> MirOperand {
+    let hay_ptr = text_ptr(builder, haystack);
+    let needle_len = text_len(builder, needle);
+    let needle_ptr = text_ptr(builder, needle);
+    let result = mutable(builder, int(0));
+
+    let compare_bb = builder.create_block();
+    let done_bb = builder.create_block();
+
+    let fits = binop(builder, MirBinOp::Gt, at.clone(), int(-1));
+    builder.terminate(MirTerminator::CondBranch { condition: fits, then_block: compare_bb, else_block: done_bb });
+
+    builder.switch_to_block(compare_bb);
+    let start = offset(builder, hay_ptr, at);
+    let equal = lower_bytes_equal(builder, start, needle_ptr, needle_len);
+    builder.build_assign(result, var(equal));
+    builder.terminate(MirTerminator::Branch(done_bb));
+
+    builder.switch_to_block(done_bb);
+    var(result)
+}
+
+/// `index-of`: byte offset of the first occurrence of the needle, or `-1`.
+pub struct IndexOfLowerer;
+
+impl StdlibOpLowerer for IndexOfLowerer {
+    fn name(&self) -> &str { "index-of" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("index-of requires 2 arguments"); }
+        lower_index_of(builder, &args[0], &args[1])
+    }
+}
+
+pub struct ContainsLowerer;
+
+impl StdlibOpLowerer for ContainsLowerer {
+    fn name(&self) -> &str { "contains" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("contains requires 2 arguments"); }
+        let idx = lower_index_of(builder, &args[0], &args[1]);
+        binop(builder, MirBinOp::Gt, idx, int(-1))
+    }
+}
+
+pub struct StartsWithLowerer;
+
+impl StdlibOpLowerer for StartsWithLowerer {
+    fn name(&self) -> &str { "starts-with" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("starts-with requires 2 arguments"); }
+        // at = 0 when the prefix fits, -1 otherwise.
+        let hay_len = text_len(builder, &args[0]);
+        let needle_len = text_len(builder, &args[1]);
+        let too_long = binop(builder, MirBinOp::Gt, needle_len, hay_len);
+        let at = binop(builder, MirBinOp::Sub, int(0), too_long);
+        lower_matches_at(builder, &args[0], &args[1], at)
+    }
+}
+
+pub struct EndsWithLowerer;
+
+impl StdlibOpLowerer for EndsWithLowerer {
+    fn name(&self) -> &str { "ends-with" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 2 { panic!("ends-with requires 2 arguments"); }
+        // at = hay_len - needle_len, forced to -1 when negative.
+        let hay_len = text_len(builder, &args[0]);
+        let needle_len = text_len(builder, &args[1]);
+        let diff = binop(builder, MirBinOp::Sub, hay_len, needle_len);
+        let fits = binop(builder, MirBinOp::Gt, diff.clone(), int(-1));
+        let kept = binop(builder, MirBinOp::Mul, diff, fits.clone());
+        let at = binop(builder, MirBinOp::Sub, kept, int(1));
+        let at = binop(builder, MirBinOp::Add, at, fits);
+        lower_matches_at(builder, &args[0], &args[1], at)
+    }
+}
@@ -0,0 +1,106 @@
+/// Ọ̀nụ Text Support: Application Layer
+///
+/// Small MIR emission helpers shared by the text-scanning stdlib lowerers
+/// (`as-integer`, `trimmed`, `before-first`, ...).  Every helper returns a
+/// fresh SSA operand so the lowerers read as straight-line arithmetic.
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirInstruction, MirLiteral, MirOperand};
+use crate::domain::entities::types::OnuType;
+
+pub(super) fn int(n: i64) -> MirOperand {
+    MirOperand::Constant(MirLiteral::I64(n))
+}
+
+pub(super) fn var(ssa: usize) -> MirOperand {
+    MirOperand::Variable(ssa, false)
+}
+
+/// Emits `lhs op rhs` into a fresh SSA.  Comparisons yield `0`/`1` integers,
+/// so their results can be combined with `And`/`Or`/`Mul` directly.
+pub(super) fn binop(builder: &mut MirBuilder, op: MirBinOp, lhs: MirOperand, rhs: MirOperand) -> MirOperand {
+    let typ = match op {
+        MirBinOp::Eq | MirBinOp::Ne | MirBinOp::Gt | MirBinOp::Lt => OnuType::Boolean,
+        _ => OnuType::I64,
+    };
+    let dest = builder.new_ssa();
+    builder.set_ssa_type(dest, typ);
+    builder.build_binop(dest, op, lhs, rhs);
+    var(dest)
+}
+
+/// Declares a mutable integer slot, re-assigned with `build_assign` inside loops.
+pub(super) fn mutable(builder: &mut MirBuilder, init: MirOperand) -> usize {
+    let slot = builder.new_ssa();
+    builder.set_ssa_type(slot, OnuType::I64);
+    builder.build_assign(slot, init);
+    slot
+}
+
+pub(super) fn text_len(builder: &mut MirBuilder, text: &MirOperand) -> MirOperand {
+    let dest = builder.new_ssa();
+    builder.set_ssa_type(dest, OnuType::I64);
+    builder.build_index(dest, text.clone(), 0);
+    var(dest)
+}
+
+pub(super) fn text_ptr(builder: &mut MirBuilder, text: &MirOperand) -> MirOperand {
+    let dest = builder.new_ssa();
+    builder.set_ssa_type(dest, OnuType::Nothing);
+    builder.build_index(dest, text.clone(), 1);
+    var(dest)
+}
+
+pub(super) fn offset(builder: &mut MirBuilder, ptr: MirOperand, by: MirOperand) -> MirOperand {
+    let dest = builder.new_ssa();
+    builder.set_ssa_type(dest, OnuType::Nothing);
+    builder.build_pointer_offset(dest, ptr, by);
+    var(dest)
+}
+
+/// Loads the byte at `ptr[idx]`, zero-extended to an integer.
+pub(super) fn byte_at(builder: &mut MirBuilder, ptr: MirOperand, idx: MirOperand) -> MirOperand {
+    let at = offset(builder, ptr, idx);
+    let dest = builder.new_ssa();
+    builder.set_ssa_type(dest, OnuType::I64);
+    builder.emit(MirInstruction::Index { dest, subject: at, index: 0 });
+    var(dest)
+}
+
+/// `1` when `lo <= value <= hi`, else `0`.
+pub(super) fn in_range(builder: &mut MirBuilder, value: MirOperand, lo: i64, hi: i64) -> MirOperand {
+    let above = binop(builder, MirBinOp::Gt, value.clone(), int(lo - 1));
+    let below = binop(builder, MirBinOp::Lt, value, int(hi + 1));
+    binop(builder, MirBinOp::And, above, below)
+}
+
+/// `1` for space, tab, line feed and carriage return.
+pub(super) fn is_whitespace(builder: &mut MirBuilder, byte: MirOperand) -> MirOperand {
+    let space = binop(builder, MirBinOp::Eq, byte.clone(), int(32));
+    let control = in_range(builder, byte, 9, 13);
+    binop(builder, MirBinOp::Or, space, control)
+}
+
+/// Builds a borrowed (non-dynamic) view `{len, ptr + start}` over `text`.
+pub(super) fn view(builder: &mut MirBuilder, ptr: MirOperand, start: MirOperand, len: MirOperand) -> MirOperand {
+    let view_ptr = offset(builder, ptr, start);
+    let dest = builder.new_ssa();
+    builder.set_ssa_type(dest, OnuType::Strings);
+    builder.build_string_tuple(dest, len, view_ptr, false);
+    var(dest)
+}
+
+/// Bump-allocates `size` bytes from the arena.
+pub(super) fn alloc(builder: &mut MirBuilder, size: MirOperand) -> MirOperand {
+    let dest = builder.new_ssa();
+    builder.set_ssa_type(dest, OnuType::Nothing);
+    builder.build_alloc(dest, size);
+    var(dest)
+}
+
+/// Builds an owned (dynamic) text `{len, buf}` over a freshly allocated buffer.
+pub(super) fn owned(builder: &mut MirBuilder, buf: MirOperand, len: MirOperand) -> MirOperand {
+    let dest = builder.new_ssa();
+    builder.set_ssa_type(dest, OnuType::Strings);
+    builder.build_string_tuple(dest, len, buf, true);
+    var(dest)
+}
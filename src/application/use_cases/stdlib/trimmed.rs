@@ -0,0 +1,65 @@
+use super::StdlibOpLowerer;
+use super::text_support::{binop, byte_at, int, is_whitespace, mutable, text_len, text_ptr, var, view};
+use crate::application::use_cases::mir_builder::MirBuilder;
+use crate::domain::entities::mir::{MirBinOp, MirOperand, MirTerminator};
+
+/// `trimmed`: a borrowed view of the text without leading or trailing
+/// ASCII whitespace (space, tab, line feed, carriage return, ...).
+pub struct TrimmedLowerer;
+
+impl StdlibOpLowerer for TrimmedLowerer {
+    fn name(&self) -> &str { "trimmed" }
+
+    fn lower(&self, args: Vec<MirOperand>, builder: &mut MirBuilder) -> MirOperand {
+        if args.len() != 1 { panic!("trimmed requires 1 argument"); }
+        let len = text_len(builder, &args[0]);
+        let ptr = text_ptr(builder, &args[0]);
+
+        let start = mutable(builder, int(0));
+        let end = mutable(builder, len);
+
+        let lead_cond_bb = builder.create_block();
+        let lead_body_bb = builder.create_block();
+        let lead_step_bb = builder.create_block();
+        let trail_cond_bb = builder.create_block();
+        let trail_body_bb = builder.create_block();
+        let trail_step_bb = builder.create_block();
+        let done_bb = builder.create_block();
+
+        builder.terminate(MirTerminator::Branch(lead_cond_bb));
+
+        // -- Skip leading whitespace --
+        builder.switch_to_block(lead_cond_bb);
+        let has_lead = binop(builder, MirBinOp::Lt, var(start), var(end));
+        builder.terminate(MirTerminator::CondBranch { condition: has_lead, then_block: lead_body_bb, else_block: trail_cond_bb });
+
+        builder.switch_to_block(lead_body_bb);
+        let lead_byte = byte_at(builder, ptr.clone(), var(start));
+        let lead_ws = is_whitespace(builder, lead_byte);
+        builder.terminate(MirTerminator::CondBranch { condition: lead_ws, then_block: lead_step_bb, else_block: trail_cond_bb });
+
+        builder.switch_to_block(lead_step_bb);
+        let next_start = binop(builder, MirBinOp::Add, var(start), int(1));
+        builder.build_assign(start, next_start);
+        builder.terminate(MirTerminator::Branch(lead_cond_bb));
+
+        // -- Skip trailing whitespace --
+        builder.switch_to_block(trail_cond_bb);
+        let has_trail = binop(builder, MirBinOp::Gt, var(end), var(start));
+        builder.terminate(MirTerminator::CondBranch { condition: has_trail, then_block: trail_body_bb, else_block: done_bb });
+
+        builder.switch_to_block(trail_body_bb);
+        let last = binop(builder, MirBinOp::Sub, var(end), int(1));
+        let trail_byte = byte_at(builder, ptr.clone(), last.clone());
+        let trail_ws = is_whitespace(builder, trail_byte);
+        builder.terminate(MirTerminator::CondBranch { condition: trail_ws, then_block: trail_step_bb, else_block: done_bb });
+
+        builder.switch_to_block(trail_step_bb);
+        builder.build_assign(end, last);
+        builder.terminate(MirTerminator::Branch(trail_cond_bb));
+
+        builder.switch_to_block(done_bb);
+        let trimmed_len = binop(builder, MirBinOp::Sub, var(end), var(start));
+        view(builder, ptr, var(start), trimmed_len)
+    }
+}
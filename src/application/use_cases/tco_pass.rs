@@ -13,6 +13,14 @@
 /// GCC emits with `-foptimize-sibling-calls`. This is the canonical approach
 /// used by Erlang, Scheme, and Haskell compilers.
 ///
+/// # Mutual Tail Calls
+/// Behaviors that tail-call each other in a cycle (`even` → `odd` → `even`)
+/// form a strongly-connected group. Each member of such a group is rebuilt as
+/// a single looping function holding a copy of every member's blocks; a tail
+/// call to another member becomes argument assignments plus a `Branch` into
+/// that member's copied entry block. The original call graph outside the
+/// group is untouched, so every member stays callable by name.
+///
 /// # Memory Safety
 /// The transformation mutates only local stack-allocated SSA slots (integers,
 /// booleans). No heap allocation, no pointer arithmetic, no arena involvement.
@@ -23,9 +31,17 @@
 /// `MirFunction`, transforms it, and returns the (possibly rewritten) function.
 /// No shared mutable state, no side effects. This is deliberately simple — a
 /// stateful pass would be a maze; this is a bridge.
+use crate::application::use_cases::inline_pass::{
+    max_ssa_in_function, remap_instruction, remap_operand,
+};
 use crate::domain::entities::mir::{
-    BasicBlock, MirFunction, MirInstruction, MirProgram, MirTerminator,
+    BasicBlock, MirFunction, MirInstruction, MirOperand, MirProgram, MirTerminator,
 };
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Largest mutually tail-recursive group that gets merged. Every member
+/// receives a copy of the whole group, so code size grows quadratically.
+const MAX_MERGED_GROUP: usize = 8;
 
 /// Returns true if the given block terminates with a self-tail-call —
 /// that is, its last instruction is a `Call` to the function itself
@@ -42,9 +58,11 @@ fn block_has_self_tail_call(block: &BasicBlock, fn_name: &str) -> bool {
 pub struct TcoPass;
 
 impl TcoPass {
-    /// Transforms an entire `MirProgram`, running `run_function` on every
-    /// function. Non-recursive functions pass through unchanged (identity).
+    /// Transforms an entire `MirProgram`: mutually tail-recursive groups are
+    /// merged first, then `run_function` runs on every function.
+    /// Non-recursive functions pass through unchanged (identity).
     pub fn run(program: MirProgram) -> MirProgram {
+        let program = Self::merge_mutual_tail_calls(program);
         MirProgram {
             functions: program
                 .functions
@@ -54,6 +72,48 @@ impl TcoPass {
         }
     }
 
+    /// Rebuilds every member of a mutually tail-recursive group as a looping
+    /// function that dispatches between the members' bodies with branches.
+    ///
+    /// # Algorithm
+    ///
+    /// 1. Build the tail-call graph (edges only for `is_tail_call` calls to
+    ///    functions defined in the program) and collect the groups of two or
+    ///    more functions that reach each other. Groups whose members disagree
+    ///    on the return type, or that exceed `MAX_MERGED_GROUP`, are skipped.
+    /// 2. For each member, lay out a loop head followed by the member's own
+    ///    blocks and then a copy of every other member, each copy shifted to
+    ///    fresh SSA and block IDs (see `merge_group`).
+    /// 3. In-group tail calls become `Assign`s into the target copy's argument
+    ///    slots and a `Branch` to its entry block; whatever followed the call
+    ///    in its block is dropped.
+    pub fn merge_mutual_tail_calls(program: MirProgram) -> MirProgram {
+        let groups = tail_call_groups(&program);
+        if groups.is_empty() {
+            return program;
+        }
+
+        let by_name: HashMap<&str, &MirFunction> = program
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f))
+            .collect();
+        let mut merged: HashMap<String, MirFunction> = HashMap::new();
+        for group in &groups {
+            for member in group {
+                merged.insert(member.clone(), merge_group(member, group, &by_name));
+            }
+        }
+
+        MirProgram {
+            functions: program
+                .functions
+                .into_iter()
+                .map(|f| merged.remove(&f.name).unwrap_or(f))
+                .collect(),
+        }
+    }
+
     /// Core transformation: rewrites a single `MirFunction`.
     ///
     /// # Algorithm
@@ -161,7 +221,7 @@ impl TcoPass {
 
 /// Increments all block IDs referenced in a terminator by 1,
 /// used when we shift the existing CFG to make room for the loop head.
-fn shift_terminator(term: &mut MirTerminator) {
+pub(crate) fn shift_terminator(term: &mut MirTerminator) {
     match term {
         MirTerminator::Branch(id) => *id += 1,
         MirTerminator::CondBranch {
@@ -175,3 +235,189 @@ fn shift_terminator(term: &mut MirTerminator) {
         MirTerminator::Return(_) | MirTerminator::Unreachable => {}
     }
 }
+
+/// Names of the program functions that `func` tail-calls.
+fn tail_callees<'a>(func: &'a MirFunction, defined: &HashSet<&str>) -> Vec<&'a str> {
+    let mut callees = Vec::new();
+    for block in &func.blocks {
+        for inst in &block.instructions {
+            if let MirInstruction::Call { name, is_tail_call: true, .. } = inst
+                && defined.contains(name.as_str())
+                && !callees.contains(&name.as_str())
+            {
+                callees.push(name.as_str());
+            }
+        }
+    }
+    callees
+}
+
+/// Groups of two or more functions that reach each other through tail calls,
+/// in program order. Only groups that can be merged are returned.
+fn tail_call_groups(program: &MirProgram) -> Vec<Vec<String>> {
+    let defined: HashSet<&str> = program.functions.iter().map(|f| f.name.as_str()).collect();
+    let edges: HashMap<&str, Vec<&str>> = program
+        .functions
+        .iter()
+        .map(|f| (f.name.as_str(), tail_callees(f, &defined)))
+        .collect();
+    let reach = |start: &str| -> HashSet<&str> {
+        let mut seen = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(name) = stack.pop() {
+            for &next in &edges[name] {
+                if seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    };
+    let reachable: HashMap<&str, HashSet<&str>> =
+        defined.iter().map(|&name| (name, reach(name))).collect();
+
+    let mut grouped: HashSet<&str> = HashSet::new();
+    let mut groups = Vec::new();
+    for func in &program.functions {
+        let name = func.name.as_str();
+        if grouped.contains(name) {
+            continue;
+        }
+        let members: Vec<&MirFunction> = program
+            .functions
+            .iter()
+            .filter(|g| {
+                g.name == name
+                    || (reachable[name].contains(g.name.as_str())
+                        && reachable[g.name.as_str()].contains(name))
+            })
+            .collect();
+        grouped.extend(members.iter().map(|g| g.name.as_str()));
+        if members.len() < 2 || members.len() > MAX_MERGED_GROUP {
+            continue;
+        }
+        if members.iter().any(|g| g.return_type != func.return_type) {
+            continue;
+        }
+        groups.push(members.iter().map(|g| g.name.clone()).collect());
+    }
+    groups
+}
+
+/// Where one group member's copy lives inside a merged function.
+struct Placement {
+    ssa_offset: usize,
+    block_offset: usize,
+}
+
+/// Builds the merged looping version of `entry`.
+///
+/// Members are laid out in breadth-first tail-call order from `entry`, so the
+/// block that first assigns a copy's argument slots is always emitted before
+/// the copy itself. Tail-call arguments go through fresh temporaries before
+/// landing in the target's argument slots, because a call may read the very
+/// slots it is about to overwrite (`f(b, a)` inside `f`).
+fn merge_group(
+    entry: &str,
+    group: &[String],
+    by_name: &HashMap<&str, &MirFunction>,
+) -> MirFunction {
+    let members: HashSet<&str> = group.iter().map(String::as_str).collect();
+    let mut order: Vec<&str> = vec![entry];
+    let mut queue = VecDeque::from([entry]);
+    while let Some(name) = queue.pop_front() {
+        for next in tail_callees(by_name[name], &members) {
+            if !order.contains(&next) {
+                order.push(next);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    // Block 0 is the loop head; the entry member keeps its own SSA numbering.
+    let mut placements: HashMap<&str, Placement> = HashMap::new();
+    let mut next_ssa = 0;
+    let mut next_block = 1;
+    for &name in &order {
+        let func = by_name[name];
+        placements.insert(name, Placement { ssa_offset: next_ssa, block_offset: next_block });
+        next_ssa += max_ssa_in_function(func) + 1;
+        next_block += func.blocks.iter().map(|b| b.id).max().unwrap_or(0) + 1;
+    }
+    let mut next_temp = next_ssa;
+
+    let entry_block = |name: &str| by_name[name].blocks[0].id + placements[name].block_offset;
+    let mut blocks = vec![BasicBlock {
+        id: 0,
+        instructions: vec![],
+        terminator: MirTerminator::Branch(entry_block(entry)),
+    }];
+
+    for &name in &order {
+        let func = by_name[name];
+        let Placement { ssa_offset, block_offset } = placements[name];
+        for block in &func.blocks {
+            let mut instructions = Vec::with_capacity(block.instructions.len());
+            let mut jump_to = None;
+            for inst in &block.instructions {
+                match inst {
+                    MirInstruction::Call { name: target, args, is_tail_call: true, .. }
+                        if members.contains(target.as_str()) =>
+                    {
+                        let target_fn = by_name[target.as_str()];
+                        let target_offset = placements[target.as_str()].ssa_offset;
+                        let temps: Vec<usize> = (next_temp..next_temp + args.len()).collect();
+                        next_temp += args.len();
+                        for (temp, arg) in temps.iter().zip(args) {
+                            instructions.push(MirInstruction::Assign {
+                                dest: *temp,
+                                src: remap_operand(arg, ssa_offset),
+                            });
+                        }
+                        for (temp, param) in temps.iter().zip(&target_fn.args) {
+                            instructions.push(MirInstruction::Assign {
+                                dest: param.ssa_var + target_offset,
+                                src: MirOperand::Variable(*temp, false),
+                            });
+                        }
+                        jump_to = Some(entry_block(target));
+                        // What followed the call only handled its result
+                        // (copies, drops), which no longer exists.
+                        break;
+                    }
+                    _ => instructions.push(remap_instruction(inst, ssa_offset)),
+                }
+            }
+
+            let terminator = match (jump_to, &block.terminator) {
+                (Some(target), _) => MirTerminator::Branch(target),
+                (None, MirTerminator::Return(op)) => {
+                    MirTerminator::Return(remap_operand(op, ssa_offset))
+                }
+                (None, MirTerminator::Branch(id)) => MirTerminator::Branch(id + block_offset),
+                (None, MirTerminator::CondBranch { condition, then_block, else_block }) => {
+                    MirTerminator::CondBranch {
+                        condition: remap_operand(condition, ssa_offset),
+                        then_block: then_block + block_offset,
+                        else_block: else_block + block_offset,
+                    }
+                }
+                (None, MirTerminator::Unreachable) => MirTerminator::Unreachable,
+            };
+            blocks.push(BasicBlock { id: block.id + block_offset, instructions, terminator });
+        }
+    }
+
+    let func = by_name[entry];
+    MirFunction {
+        name: func.name.clone(),
+        args: func.args.clone(),
+        return_type: func.return_type.clone(),
+        blocks,
+        is_pure_data_leaf: order.iter().all(|name| by_name[name].is_pure_data_leaf),
+        diminishing: func.diminishing.clone(),
+        memo_cache_size: func.memo_cache_size,
+        inlining: func.inlining,
+        known_behavior: func.known_behavior,
+    }
+}
@@ -103,6 +103,7 @@ mod tests {
         BasicBlock, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand,
         MirTerminator,
     };
+    use crate::domain::entities::types::InlineHint;
 
     fn make_wide_div_function(bits: u32) -> MirFunction {
         MirFunction {
@@ -134,6 +135,8 @@ mod tests {
             is_pure_data_leaf: true,
             diminishing: vec![],
             memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
         }
     }
 
@@ -209,6 +212,8 @@ mod tests {
             is_pure_data_leaf: true,
             diminishing: vec![],
             memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
         };
         let program = MirProgram {
             functions: vec![func],
@@ -256,6 +261,8 @@ mod tests {
             is_pure_data_leaf: true,
             diminishing: vec![],
             memo_cache_size: None,
+            inlining: InlineHint::Auto,
+            known_behavior: None,
         };
         let program = MirProgram {
             functions: vec![func],
@@ -3,7 +3,7 @@
 /// This module defines the structural units of the Ọ̀nụ language.
 /// These are pure data structures representing the "Proposition" and "Discourse."
 
-use crate::domain::entities::types::OnuType;
+use crate::domain::entities::types::{InlineHint, KnownBehavior, OnuType};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Discourse {
@@ -14,6 +14,9 @@ pub enum Discourse {
         behaviors: Vec<BehaviorHeader> 
     },
     Behavior { header: BehaviorHeader, body: Expression },
+    /// `the test called <name> expects: <value> as: <body>`.  Compiled only
+    /// by `onu test`, which checks that `body` delivers `expects`.
+    Test { name: String, expects: Expression, body: Expression, line: usize },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -97,5 +100,7 @@ pub struct BehaviorHeader {
     pub delivers: ReturnType,
     pub diminishing: Vec<String>,
     pub memo_cache_size: Option<usize>,
+    pub inlining: InlineHint,
+    pub known_behavior: Option<KnownBehavior>,
     pub skip_termination_check: bool,
 }
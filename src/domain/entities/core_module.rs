@@ -22,10 +22,32 @@ impl BuiltInModule for CoreModule {
             ("init-of", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::Strings, arg_is_observation: vec![false] }),
             ("char-from-code", BehaviorSignature { input_types: vec![OnuType::I64], return_type: OnuType::Strings, arg_is_observation: vec![false] }),
             ("duplicated-as", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::Strings, arg_is_observation: vec![true] }),
-            ("clears", BehaviorSignature { input_types: vec![OnuType::Nothing], return_type: OnuType::Nothing, arg_is_observation: vec![false] }),
+            ("clears", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::Nothing, arg_is_observation: vec![true] }),
             ("creates-map", BehaviorSignature { input_types: vec![], return_type: OnuType::HashMap(Box::new(OnuType::Nothing), Box::new(OnuType::Nothing)), arg_is_observation: vec![] }),
             ("creates-tree", BehaviorSignature { input_types: vec![], return_type: OnuType::Tree(Box::new(OnuType::Nothing)), arg_is_observation: vec![] }),
             ("as-integer", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::I64, arg_is_observation: vec![true] }),
+            ("is-integer", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::Boolean, arg_is_observation: vec![true] }),
+            ("hex-as-integer", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::I64, arg_is_observation: vec![true] }),
+            ("binary-as-integer", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::I64, arg_is_observation: vec![true] }),
+            ("trimmed", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::Strings, arg_is_observation: vec![true] }),
+            ("before-first", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::I64], return_type: OnuType::Strings, arg_is_observation: vec![true, false] }),
+            ("after-first", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::I64], return_type: OnuType::Strings, arg_is_observation: vec![true, false] }),
+            ("character-count", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::I64, arg_is_observation: vec![true] }),
+            ("code-point-at", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::I64], return_type: OnuType::I64, arg_is_observation: vec![true, false] }),
+            ("characters-between", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::I64, OnuType::I64], return_type: OnuType::Strings, arg_is_observation: vec![true, false, false] }),
+            ("is-valid-utf8", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::Boolean, arg_is_observation: vec![true] }),
+            ("compared-with", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::Strings], return_type: OnuType::I64, arg_is_observation: vec![true, true] }),
+            ("precedes", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::Strings], return_type: OnuType::Boolean, arg_is_observation: vec![true, true] }),
+            ("follows", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::Strings], return_type: OnuType::Boolean, arg_is_observation: vec![true, true] }),
+            ("contains", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::Strings], return_type: OnuType::Boolean, arg_is_observation: vec![true, true] }),
+            ("starts-with", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::Strings], return_type: OnuType::Boolean, arg_is_observation: vec![true, true] }),
+            ("ends-with", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::Strings], return_type: OnuType::Boolean, arg_is_observation: vec![true, true] }),
+            ("index-of", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::Strings], return_type: OnuType::I64, arg_is_observation: vec![true, true] }),
+            ("substring", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::I64, OnuType::I64], return_type: OnuType::Strings, arg_is_observation: vec![true, false, false] }),
+            ("replaced-with", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::Strings, OnuType::Strings], return_type: OnuType::Strings, arg_is_observation: vec![true, true, true] }),
+            ("uppercased", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::Strings, arg_is_observation: vec![true] }),
+            ("lowercased", BehaviorSignature { input_types: vec![OnuType::Strings], return_type: OnuType::Strings, arg_is_observation: vec![true] }),
+            ("repeated-times", BehaviorSignature { input_types: vec![OnuType::Strings, OnuType::I64], return_type: OnuType::Strings, arg_is_observation: vec![true, false] }),
             ("receives-entropy", BehaviorSignature { input_types: vec![], return_type: OnuType::I64, arg_is_observation: vec![] }),
         ];
         for (name, sig) in core_builtins {
@@ -42,6 +64,8 @@ impl BuiltInModule for StandardMathModule {
     fn register(&self, table: &mut SymbolTable) {
         let math_signatures = vec![
             ("added-to", BehaviorSignature { input_types: vec![OnuType::I64, OnuType::I64], return_type: OnuType::I64, arg_is_observation: vec![false, false] }),
+            ("wrapping-added-to", BehaviorSignature { input_types: vec![OnuType::I64, OnuType::I64], return_type: OnuType::I64, arg_is_observation: vec![false, false] }),
+            ("saturating-added-to", BehaviorSignature { input_types: vec![OnuType::I64, OnuType::I64], return_type: OnuType::I64, arg_is_observation: vec![false, false] }),
             ("decreased-by", BehaviorSignature { input_types: vec![OnuType::I64, OnuType::I64], return_type: OnuType::I64, arg_is_observation: vec![false, false] }),
             ("scales-by", BehaviorSignature { input_types: vec![OnuType::I64, OnuType::I64], return_type: OnuType::I64, arg_is_observation: vec![false, false] }),
             ("partitions-by", BehaviorSignature { input_types: vec![OnuType::I64, OnuType::I64], return_type: OnuType::I64, arg_is_observation: vec![false, false] }),
@@ -3,7 +3,7 @@
 /// This module defines the High-level Intermediate Representation.
 /// HIR is used for semantic analysis, ownership checking, and liveness analysis.
 
-use crate::domain::entities::types::OnuType;
+use crate::domain::entities::types::{InlineHint, KnownBehavior, OnuType};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum HirDiscourse {
@@ -20,10 +20,14 @@ pub enum HirDiscourse {
 pub struct HirBehaviorHeader {
     pub name: String,
     pub is_effect: bool,
+    /// The `with intent:` prose, kept for lints and documentation.
+    pub intent: String,
     pub args: Vec<HirArgument>,
     pub return_type: OnuType,
     pub diminishing: Vec<String>,
     pub memo_cache_size: Option<usize>,
+    pub inlining: InlineHint,
+    pub known_behavior: Option<KnownBehavior>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -48,6 +52,8 @@ pub enum HirBinOp {
     BitXor,
     Shr,
     Shl,
+    WrappingAdd,
+    SaturatingAdd,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -3,7 +3,7 @@
 /// This module defines the Mid-level Intermediate Representation.
 /// MIR is a flat, SSA-based representation suitable for optimizations
 /// and machine code generation.
-use crate::domain::entities::types::OnuType;
+use crate::domain::entities::types::{InlineHint, KnownBehavior, OnuType};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct MirProgram {
@@ -23,6 +23,11 @@ pub struct MirFunction {
     /// `max_call_arg + 1` so that the per-entry WideInt allocation stays well
     /// within the 1 MB arena.
     pub memo_cache_size: Option<usize>,
+    /// `with inlining:` override consulted by `InlinePass`.
+    pub inlining: InlineHint,
+    /// `with known behavior:` tag; codegen may dispatch to a hardware
+    /// kernel and keeps the body as the software fallback.
+    pub known_behavior: Option<KnownBehavior>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -92,6 +97,14 @@ pub enum MirInstruction {
         size_bytes: usize,
         name: String,
     },
+    /// Empties the memo cache of `function`, lowered from `"name" utilizes clears`.
+    ///
+    /// `MemoPass` replaces it with a `GlobalAlloc` + `MemSet` over the
+    /// table that marks filled slots, or drops it when `function` was not
+    /// memoized; it never reaches codegen.
+    ClearMemo {
+        function: String,
+    },
     MemCopy {
         dest: MirOperand,
         src: MirOperand,
@@ -208,6 +221,19 @@ pub enum MirInstruction {
         width: u32,
     },
 
+    /// A bit-manipulation or selection idiom recognized by
+    /// `IdiomRecognizerPass` (population count, byte swap, leading/trailing
+    /// zeros, rotate-left, min/max/abs).  Operands are `width`-bit values
+    /// carried in `i64`; narrower results are zero-extended back to `i64`.
+    ///
+    /// Emits: `%dest = call iN @llvm.<intrinsic>.iN(args...)`
+    Intrinsic {
+        dest: usize,
+        intrinsic: MirIntrinsic,
+        args: Vec<MirOperand>,
+        width: u32,
+    },
+
     // ── Phase 4: Buffered I/O ───────────────────────────────────────────
 
     /// Write bytes to the internal stdout buffer instead of issuing a syscall.
@@ -224,6 +250,98 @@ pub enum MirInstruction {
     /// Emitted at program exit (in the `run`/`main` teardown) to ensure all
     /// buffered output reaches the terminal.
     FlushStdout,
+
+    // ── Phase 5: Runtime Checks ─────────────────────────────────────────
+
+    /// Overflow-checked integer arithmetic, emitted for `added-to`,
+    /// `decreased-by` and `scales-by` when the program is compiled with
+    /// checked arithmetic.  `op` is one of `Add`, `Sub` or `Mul`.
+    ///
+    /// Emits: `call {iN, i1} @llvm.{s,u}{add,sub,mul}.with.overflow.iN`
+    /// followed by a branch to `__onu_trap` when the overflow bit is set.
+    /// `site` names the behavior the arithmetic was written in and is
+    /// reported on stderr before the program exits.
+    CheckedArithmetic {
+        dest: usize,
+        op: MirBinOp,
+        lhs: MirOperand,
+        rhs: MirOperand,
+        dest_type: OnuType,
+        site: String,
+    },
+
+    /// Guard emitted ahead of an operation that would otherwise fault or
+    /// corrupt memory (`partitions-by` with a zero divisor, `char-at` /
+    /// `set-char` outside the text).  When `condition` is `0` the program
+    /// calls `__onu_trap` with `message`.  Omitted entirely when runtime
+    /// checks are disabled.
+    RuntimeCheck {
+        condition: MirOperand,
+        message: String,
+    },
+
+    // ── Phase 6: Instrumentation ────────────────────────────────────────
+
+    /// Bumps one of the four per-function memo counters.  Emitted by the memo
+    /// strategies at every cache lookup and kept only in `--memo-report`
+    /// builds; `MemoPass` strips it otherwise.
+    ///
+    /// Emits: an increment of field `counter` in the module-level global
+    /// `__onu_memo_stats.{function}`, summarized on stderr when `run` returns.
+    MemoCount {
+        function: String,
+        counter: MemoCounter,
+    },
+}
+
+/// The per-function memo counters reported by `--memo-report`.
+///
+/// Every lookup is either a hit or a miss.  A collision is a miss whose slot
+/// already held a different key.  The strategies are direct-mapped and keep
+/// the occupant on a collision, so no entry is ever evicted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoCounter {
+    Hit,
+    Miss,
+    Collision,
+}
+
+impl MemoCounter {
+    pub const ALL: [MemoCounter; 3] = [MemoCounter::Hit, MemoCounter::Miss, MemoCounter::Collision];
+
+    /// Field index within the `__onu_memo_stats.*` global.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MemoCounter::Hit => "hits",
+            MemoCounter::Miss => "misses",
+            MemoCounter::Collision => "collisions",
+        }
+    }
+}
+
+/// The operations behind `MirInstruction::Intrinsic`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirIntrinsic {
+    /// Number of set bits (`llvm.ctpop`).
+    PopCount,
+    /// Zero bits above the highest set bit; `width` for zero (`llvm.ctlz`).
+    LeadingZeros,
+    /// Zero bits below the lowest set bit; `width` for zero (`llvm.cttz`).
+    TrailingZeros,
+    /// Reverse the byte order (`llvm.bswap`).
+    ByteSwap,
+    /// `fshl(hi, lo, amount)`; a rotate-left when `hi == lo` (`llvm.fshl`).
+    FunnelShiftLeft,
+    /// Smaller of two signed values (`llvm.smin`).
+    SignedMin,
+    /// Larger of two signed values (`llvm.smax`).
+    SignedMax,
+    /// Absolute value, wrapping at the minimum (`llvm.abs`).
+    Abs,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -247,6 +365,12 @@ pub enum MirBinOp {
     Shr,
     /// Left shift.
     Shl,
+    /// Two's-complement wrapping addition (`wrapping-added-to`).  Unlike
+    /// `Add` it carries no `nsw` flag, so overflow is defined behaviour.
+    WrappingAdd,
+    /// Addition clamped to the range of the result type
+    /// (`saturating-added-to`).  Emits `llvm.{s,u}add.sat.iN`.
+    SaturatingAdd,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -17,6 +17,10 @@ pub enum OnuType {
     U64,
     U128,
     WideInt(u32),
+    /// Arbitrary-precision integer sized at runtime (`big-integer`).
+    /// A pointer to an arena block whose first word is the signed limb
+    /// count, followed by little-endian 64-bit magnitude limbs.
+    BigInt,
 
     // --- Floats ---
     F32,
@@ -50,9 +54,10 @@ impl OnuType {
     pub fn from_name(name: &str) -> Option<Self> {
         match name {
             "integer" | "i64" => Some(OnuType::I64),
+            "big-integer" => Some(OnuType::BigInt),
             "float" | "f64" => Some(OnuType::F64),
             "boolean" => Some(OnuType::Boolean),
-            "string" => Some(OnuType::Strings),
+            "string" | "text" => Some(OnuType::Strings),
             "nothing" => Some(OnuType::Nothing),
             _ => None,
         }
@@ -70,3 +75,54 @@ impl OnuType {
         )
     }
 }
+
+/// How a behavior's `with inlining:` clause steers `InlinePass`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InlineHint {
+    /// No clause: pure leaves are inlined, other behaviors by cost.
+    #[default]
+    Auto,
+    /// `with inlining: always` — inline regardless of size.
+    Always,
+    /// `with inlining: never` — keep every call.
+    Never,
+}
+
+/// Known computational behaviors that the compiler can accelerate with
+/// hardware intrinsics when available.  A behavior opts in with a
+/// `with known behavior:` clause; codegen keeps its body as the software
+/// fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownBehavior {
+    /// SHA-256 compression function (64-round Merkle-Damgård).
+    Sha256Compress,
+    /// SHA-256 message schedule expansion (σ0/σ1 transforms).
+    Sha256MessageSchedule,
+    /// AES single-round encryption.
+    AesEncryptRound,
+    /// Generic bit rotation (handled by IdiomRecognizerPass instead).
+    RotateRight,
+}
+
+impl KnownBehavior {
+    /// The name used in a `with known behavior:` clause.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha256-compress" => Some(KnownBehavior::Sha256Compress),
+            "sha256-message-schedule" => Some(KnownBehavior::Sha256MessageSchedule),
+            "aes-encrypt-round" => Some(KnownBehavior::AesEncryptRound),
+            "rotate-right" => Some(KnownBehavior::RotateRight),
+            _ => None,
+        }
+    }
+
+    /// The inverse of [`KnownBehavior::from_name`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            KnownBehavior::Sha256Compress => "sha256-compress",
+            KnownBehavior::Sha256MessageSchedule => "sha256-message-schedule",
+            KnownBehavior::AesEncryptRound => "aes-encrypt-round",
+            KnownBehavior::RotateRight => "rotate-right",
+        }
+    }
+}
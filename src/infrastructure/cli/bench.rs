@@ -0,0 +1,466 @@
+//! Ọ̀nụ Benchmark Runner: Infrastructure Implementation
+//!
+//! `onu bench` compiles Ọ̀nụ programs and their C counterparts to native
+//! executables, runs each one a number of times, and reports the mean,
+//! median and standard deviation of the wall time together with the Ọ̀nụ/C
+//! ratio.  Results can be written as JSON and compared against an earlier
+//! JSON run to flag regressions.
+//!
+//! An Ọ̀nụ program is compiled in memory, emitted as a native object by the
+//! LLVM target machine, and linked with the C compiler, so the only external
+//! tool needed is the compiler that also builds the C twins (`$CC`, or `cc`).
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use inkwell::OptimizationLevel;
+use inkwell::context::Context;
+use inkwell::memory_buffer::MemoryBuffer;
+use inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine};
+
+use crate::CompilationPipeline;
+use crate::adapters::codegen::OnuCodegen;
+use crate::adapters::lexer::OnuLexer;
+use crate::adapters::parser::OnuParser;
+use crate::application::options::{CompilationOptions, LogLevel};
+use crate::application::ports::environment::EnvironmentPort;
+use crate::domain::entities::error::OnuError;
+use crate::infrastructure::os::NativeOsEnvironment;
+
+/// The Ọ̀nụ samples that have a C twin in the repository root.
+const DEFAULT_PAIRS: &[(&str, &str)] = &[
+    ("fib_naive_only", "cbench_fib_naive"),
+    ("collatz_bench", "cbench_collatz"),
+    ("ackermann_bench", "cbench_ackermann"),
+    ("sha256", "cbench_sha256"),
+    ("gcd_measure", "cbench_gcd"),
+    ("tak_balance", "cbench_tak"),
+    ("mccarthy91", "cbench_mccarthy91"),
+    ("rule110", "cbench_rule110"),
+    ("pepin_test", "cbench_pepin"),
+    ("hanoi", "cbench_hanoi"),
+    ("primal_scroll", "cbench_primal_scroll"),
+];
+
+/// One benchmark: an Ọ̀nụ program and, optionally, its C counterpart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchCase {
+    pub name: String,
+    pub onu: PathBuf,
+    pub c: Option<PathBuf>,
+}
+
+impl BenchCase {
+    /// Parse `<file.onu>` or `<file.onu>=<file.c>`.
+    pub fn parse(spec: &str) -> Self {
+        let (onu, c) = match spec.split_once('=') {
+            Some((onu, c)) => (PathBuf::from(onu), Some(PathBuf::from(c))),
+            None => (PathBuf::from(spec), None),
+        };
+        let name = onu.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        Self { name, onu, c }
+    }
+}
+
+/// The samples benchmarked when no programs are named.
+pub fn default_cases() -> Vec<BenchCase> {
+    DEFAULT_PAIRS
+        .iter()
+        .map(|(onu, c)| BenchCase {
+            name: onu.to_string(),
+            onu: PathBuf::from(format!("samples/{}.onu", onu)),
+            c: Some(PathBuf::from(format!("{}.c", c))),
+        })
+        .collect()
+}
+
+/// How a benchmark run is carried out.
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Timed runs of each executable.
+    pub runs: usize,
+    /// Builds the C twins and links the Ọ̀nụ objects.
+    pub cc: String,
+    /// Where the executables are built.
+    pub build_dir: PathBuf,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            runs: 5,
+            cc: std::env::var("CC").unwrap_or_else(|_| "cc".to_string()),
+            build_dir: std::env::temp_dir().join(format!("onu_bench_{}", std::process::id())),
+        }
+    }
+}
+
+/// Summary statistics of a set of timed runs, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    /// `None` for an empty set of samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+        ms.sort_by(f64::total_cmp);
+        let n = ms.len() as f64;
+        let mean = ms.iter().sum::<f64>() / n;
+        let median = if ms.len().is_multiple_of(2) {
+            (ms[ms.len() / 2 - 1] + ms[ms.len() / 2]) / 2.0
+        } else {
+            ms[ms.len() / 2]
+        };
+        let stddev = (ms.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+        Some(Self { mean, median, stddev })
+    }
+}
+
+/// The outcome of one benchmark.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub name: String,
+    pub onu: Result<Stats, String>,
+    /// `None` when the case has no C counterpart.
+    pub c: Option<Result<Stats, String>>,
+}
+
+impl BenchResult {
+    /// Ọ̀nụ median over C median; below 1.0 means Ọ̀nụ was faster.
+    pub fn ratio(&self) -> Option<f64> {
+        match (&self.onu, &self.c) {
+            (Ok(onu), Some(Ok(c))) if c.median > 0.0 => Some(onu.median / c.median),
+            _ => None,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.onu.is_err() || matches!(self.c, Some(Err(_)))
+    }
+}
+
+/// Build and time every case, printing a table as it goes.
+pub fn run_all(cases: &[BenchCase], options: &BenchOptions) -> Vec<BenchResult> {
+    println!(
+        "\nbenchmarking {} program{} ({} runs each)\n",
+        cases.len(),
+        if cases.len() == 1 { "" } else { "s" },
+        options.runs
+    );
+    println!(
+        "{:<18} {:>12} {:>12} {:>10} {:>12} {:>12} {:>10} {:>8}",
+        "benchmark", "onu mean", "onu median", "onu sd", "c mean", "c median", "c sd", "onu/c"
+    );
+    let results: Vec<BenchResult> = cases
+        .iter()
+        .map(|case| {
+            let result = run_case(case, options);
+            println!("{}", table_row(&result));
+            result
+        })
+        .collect();
+
+    let failures: Vec<&BenchResult> = results.iter().filter(|r| r.is_failure()).collect();
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for result in failures {
+            if let Err(e) = &result.onu {
+                println!("\n---- {} (onu) ----\n{}", result.name, e);
+            }
+            if let Some(Err(e)) = &result.c {
+                println!("\n---- {} (c) ----\n{}", result.name, e);
+            }
+        }
+    }
+    println!();
+    results
+}
+
+/// Build and time a single case.
+pub fn run_case(case: &BenchCase, options: &BenchOptions) -> BenchResult {
+    let onu = build_onu(&case.onu, &case.name, options).and_then(|exe| time_runs(&exe, options.runs));
+    let c = case.c.as_ref().map(|c| build_c(c, &case.name, options).and_then(|exe| time_runs(&exe, options.runs)));
+    BenchResult { name: case.name.clone(), onu, c }
+}
+
+fn table_row(result: &BenchResult) -> String {
+    let cells = |stats: Option<&Result<Stats, String>>| match stats {
+        Some(Ok(s)) => {
+            (format!("{:.2}ms", s.mean), format!("{:.2}ms", s.median), format!("{:.2}ms", s.stddev))
+        }
+        Some(Err(_)) => ("FAILED".to_string(), "-".to_string(), "-".to_string()),
+        None => ("-".to_string(), "-".to_string(), "-".to_string()),
+    };
+    let (onu_mean, onu_median, onu_sd) = cells(Some(&result.onu));
+    let (c_mean, c_median, c_sd) = cells(result.c.as_ref());
+    let ratio = result.ratio().map_or("-".to_string(), |r| format!("{:.2}x", r));
+    format!(
+        "{:<18} {:>12} {:>12} {:>10} {:>12} {:>12} {:>10} {:>8}",
+        result.name, onu_mean, onu_median, onu_sd, c_mean, c_median, c_sd, ratio
+    )
+}
+
+/// Compile `source` to a native executable named after `name`.
+fn build_onu(source: &Path, name: &str, options: &BenchOptions) -> Result<PathBuf, String> {
+    let compile_options = CompilationOptions { log_level: LogLevel::Error, ..CompilationOptions::default() };
+    let env = NativeOsEnvironment::new(compile_options.log_level);
+    let text = env.read_file(&source.to_string_lossy()).map_err(|e| format!("{:?}", e))?;
+    let mut pipeline = CompilationPipeline::new(
+        env,
+        OnuCodegen::new(),
+        Box::new(OnuLexer::new(compile_options.log_level)),
+        Box::new(OnuParser::new(compile_options.log_level)),
+        compile_options,
+    );
+    let ir = pipeline
+        .compile_source(&text)
+        .map_err(|e| format!("{:?}", e))?
+        .ir
+        .unwrap_or_default();
+
+    std::fs::create_dir_all(&options.build_dir).map_err(|e| e.to_string())?;
+    let object = options.build_dir.join(format!("{}.o", name));
+    emit_object(&ir, &object).map_err(|e| format!("{:?}", e))?;
+    let exe = options.build_dir.join(format!("{}_onu", name));
+    run_compiler(&options.cc, &[object.as_os_str(), "-o".as_ref(), exe.as_os_str()])?;
+    Ok(exe)
+}
+
+fn build_c(source: &Path, name: &str, options: &BenchOptions) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(&options.build_dir).map_err(|e| e.to_string())?;
+    let exe = options.build_dir.join(format!("{}_c", name));
+    run_compiler(&options.cc, &[source.as_os_str(), "-O3".as_ref(), "-o".as_ref(), exe.as_os_str()])?;
+    Ok(exe)
+}
+
+/// Write `ir` to `path` as a native object file for the host.
+fn emit_object(ir: &str, path: &Path) -> Result<(), OnuError> {
+    let codegen_error = |message: String| OnuError::CodeGenError { message };
+    Target::initialize_native(&InitializationConfig::default()).map_err(codegen_error)?;
+    let triple = TargetMachine::get_default_triple();
+    let target = Target::from_triple(&triple).map_err(|e| codegen_error(e.to_string()))?;
+    let machine = target
+        .create_target_machine(
+            &triple,
+            &TargetMachine::get_host_cpu_name().to_string(),
+            &TargetMachine::get_host_cpu_features().to_string(),
+            OptimizationLevel::Aggressive,
+            RelocMode::PIC,
+            CodeModel::Default,
+        )
+        .ok_or_else(|| codegen_error(format!("no target machine for {}", triple)))?;
+
+    let context = Context::create();
+    let buf = MemoryBuffer::create_from_memory_range_copy(ir.as_bytes(), "onu_bench");
+    let module = context
+        .create_module_from_ir(buf)
+        .map_err(|e| codegen_error(format!("LLVM IR parse error: {}", e)))?;
+    machine
+        .write_to_file(&module, FileType::Object, path)
+        .map_err(|e| codegen_error(e.to_string()))
+}
+
+fn run_compiler(cc: &str, args: &[&std::ffi::OsStr]) -> Result<(), String> {
+    let output = Command::new(cc).args(args).output().map_err(|e| format!("could not run {}: {}", cc, e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} failed:\n{}", cc, String::from_utf8_lossy(&output.stderr)))
+    }
+}
+
+/// Run `exe` `runs` times with its output discarded, timing each run.
+fn time_runs(exe: &Path, runs: usize) -> Result<Stats, String> {
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let started = Instant::now();
+        let status = Command::new(exe)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|e| format!("could not run {}: {}", exe.display(), e))?;
+        samples.push(started.elapsed());
+        // Ọ̀nụ programs exit with the value `run` delivers; only a signal
+        // means the run did not finish.
+        if status.code().is_none() {
+            return Err(format!("{} was killed by a signal", exe.display()));
+        }
+    }
+    Stats::from_samples(&samples).ok_or_else(|| "no runs".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// JSON results and regression checks
+// ---------------------------------------------------------------------------
+
+/// The results as a JSON array, one benchmark object per line.  Failed
+/// measurements are `null`.
+pub fn to_json(results: &[BenchResult]) -> String {
+    let stats = |prefix: &str, stats: Option<&Result<Stats, String>>| match stats {
+        Some(Ok(s)) => format!(
+            "\"{p}_mean_ms\": {:.4}, \"{p}_median_ms\": {:.4}, \"{p}_stddev_ms\": {:.4}",
+            s.mean,
+            s.median,
+            s.stddev,
+            p = prefix
+        ),
+        _ => format!("\"{p}_mean_ms\": null, \"{p}_median_ms\": null, \"{p}_stddev_ms\": null", p = prefix),
+    };
+    let lines: Vec<String> = results
+        .iter()
+        .map(|r| {
+            format!(
+                "  {{\"name\": {}, {}, {}, \"ratio\": {}}}",
+                json_string(&r.name),
+                stats("onu", Some(&r.onu)),
+                stats("c", r.c.as_ref()),
+                r.ratio().map_or("null".to_string(), |ratio| format!("{:.4}", ratio))
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", lines.join(",\n"))
+}
+
+/// The Ọ̀nụ median of each benchmark in JSON written by [`to_json`].
+pub fn parse_baseline(json: &str) -> Vec<(String, f64)> {
+    json.lines()
+        .filter_map(|line| {
+            let name = string_field(line, "name")?;
+            let median = field(line, "onu_median_ms")?.parse().ok()?;
+            Some((name, median))
+        })
+        .collect()
+}
+
+/// `text` as a quoted JSON string: `"` and `\` are backslash-escaped and
+/// control characters become `\uXXXX`.
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The string value of `"key": "<value>"` on a single-line JSON object,
+/// undoing [`json_string`].
+fn string_field(line: &str, key: &str) -> Option<String> {
+    let start = line.find(&format!("\"{}\":", key))? + key.len() + 3;
+    let mut chars = line[start..].trim_start().strip_prefix('"')?.chars();
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                c => value.push(c),
+            },
+            c => value.push(c),
+        }
+    }
+}
+
+/// The raw text of `"key": <value>` on a single-line JSON object.
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(&format!("\"{}\":", key))? + key.len() + 3;
+    let rest = line[start..].trim_start();
+    let end = rest.find([',', '}']).unwrap_or(rest.len());
+    Some(rest[..end].trim())
+}
+
+/// Benchmarks whose Ọ̀nụ median grew by more than `tolerance` (a fraction,
+/// e.g. `0.1` for 10%) over `baseline`.
+pub fn regressions(results: &[BenchResult], baseline: &[(String, f64)], tolerance: f64) -> Vec<String> {
+    results
+        .iter()
+        .filter_map(|result| {
+            let onu = result.onu.as_ref().ok()?;
+            let (_, before) = baseline.iter().find(|(name, _)| *name == result.name)?;
+            (onu.median > before * (1.0 + tolerance)).then(|| {
+                format!(
+                    "{}: {:.2}ms -> {:.2}ms (+{:.1}%)",
+                    result.name,
+                    before,
+                    onu.median,
+                    (onu.median / before - 1.0) * 100.0
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_millis(*v)).collect()
+    }
+
+    #[test]
+    fn test_stats_mean_median_and_stddev() {
+        let stats = Stats::from_samples(&ms(&[4, 2, 6, 8])).unwrap();
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.median, 5.0);
+        assert!((stats.stddev - 5.0f64.sqrt()).abs() < 1e-9);
+        assert_eq!(Stats::from_samples(&ms(&[3, 1, 2])).unwrap().median, 2.0);
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_json_round_trips_the_onu_median_and_flags_regressions() {
+        let stats = |median| Stats { mean: median, median, stddev: 0.0 };
+        let results = vec![
+            BenchResult { name: "fast".to_string(), onu: Ok(stats(10.0)), c: Some(Ok(stats(5.0))) },
+            BenchResult { name: "broken".to_string(), onu: Err("no".to_string()), c: None },
+        ];
+        let json = to_json(&results);
+        assert!(json.contains("\"ratio\": 2.0000"));
+        assert_eq!(parse_baseline(&json), vec![("fast".to_string(), 10.0)]);
+
+        let slower = vec![BenchResult { name: "fast".to_string(), onu: Ok(stats(12.0)), c: None }];
+        assert!(regressions(&slower, &parse_baseline(&json), 0.25).is_empty());
+        assert_eq!(regressions(&slower, &parse_baseline(&json), 0.1), vec!["fast: 10.00ms -> 12.00ms (+20.0%)"]);
+    }
+
+    #[test]
+    fn test_json_names_are_escaped_and_read_back() {
+        let name = "say \"hi\", c:\\tmp\n\tdone";
+        let results = vec![BenchResult {
+            name: name.to_string(),
+            onu: Ok(Stats { mean: 1.0, median: 1.0, stddev: 0.0 }),
+            c: None,
+        }];
+        let json = to_json(&results);
+        assert!(json.contains(r#""name": "say \"hi\", c:\\tmp\u000a\u0009done""#), "{}", json);
+        assert_eq!(parse_baseline(&json), vec![(name.to_string(), 1.0)]);
+    }
+
+    #[test]
+    fn test_case_spec_pairs_onu_with_c() {
+        let case = BenchCase::parse("samples/tak_balance.onu=cbench_tak.c");
+        assert_eq!(case.name, "tak_balance");
+        assert_eq!(case.c, Some(PathBuf::from("cbench_tak.c")));
+        assert_eq!(BenchCase::parse("samples/fib.onu").c, None);
+    }
+}
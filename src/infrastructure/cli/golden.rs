@@ -0,0 +1,314 @@
+//! Ọ̀nụ Golden-Output Harness: Infrastructure Implementation
+//!
+//! Compiles `.onu` programs through [`CompilationPipeline`], runs each one
+//! under a timeout, and compares its stdout and exit code with what was
+//! recorded for it.  Used by `onu golden` and by the samples tests.
+//!
+//! A program's expectations come from, in order of precedence:
+//!
+//! * sidecar files next to it: `<name>.stdout` holds the exact stdout and
+//!   `<name>.exit` the exit code;
+//! * comment annotations in the program itself: `-- expect-stdout: <line>`
+//!   (one per output line) and `-- expect-exit: <code>`.
+//!
+//! `-- args: <a> <b> …` passes arguments to the program.  A program with no
+//! recorded stdout is reported as unblessed and is not compiled or run;
+//! running with `update` writes the sidecars from what every program
+//! actually did.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::CompilationPipeline;
+use crate::adapters::codegen::OnuCodegen;
+use crate::adapters::lexer::OnuLexer;
+use crate::adapters::parser::OnuParser;
+use crate::application::options::{CompilationOptions, LogLevel};
+use crate::domain::entities::error::OnuError;
+use crate::infrastructure::cli::jit::run_in_child;
+use crate::infrastructure::os::NativeOsEnvironment;
+
+/// How a golden run is carried out.
+#[derive(Debug, Clone)]
+pub struct GoldenOptions {
+    /// Programs still running after this long are killed and fail.
+    pub timeout: Duration,
+    /// Record actual output as the expectation instead of checking it.
+    pub update: bool,
+}
+
+impl Default for GoldenOptions {
+    fn default() -> Self {
+        Self { timeout: Duration::from_secs(10), update: false }
+    }
+}
+
+/// One program and what it is expected to do.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoldenCase {
+    pub source: PathBuf,
+    pub args: Vec<String>,
+    /// `None` until the program's output has been blessed.
+    pub expected_stdout: Option<String>,
+    pub expected_exit: i32,
+}
+
+impl GoldenCase {
+    /// Read `source` and its sidecars.
+    pub fn load(source: &Path) -> Result<Self, OnuError> {
+        let text = read(source)?;
+        let mut case = GoldenCase {
+            source: source.to_path_buf(),
+            args: Vec::new(),
+            expected_stdout: None,
+            expected_exit: 0,
+        };
+
+        let mut annotated = Vec::new();
+        for line in text.lines().map(str::trim) {
+            if let Some(expected) = line.strip_prefix("-- expect-stdout:") {
+                annotated.push(format!("{}\n", expected.strip_prefix(' ').unwrap_or(expected)));
+            } else if let Some(code) = line.strip_prefix("-- expect-exit:") {
+                case.expected_exit = parse_exit(code, source)?;
+            } else if let Some(args) = line.strip_prefix("-- args:") {
+                case.args = args.split_whitespace().map(str::to_string).collect();
+            }
+        }
+        if !annotated.is_empty() {
+            case.expected_stdout = Some(annotated.concat());
+        }
+
+        let stdout_path = case.sidecar("stdout");
+        if stdout_path.exists() {
+            case.expected_stdout = Some(read(&stdout_path)?);
+        }
+        let exit_path = case.sidecar("exit");
+        if exit_path.exists() {
+            case.expected_exit = parse_exit(&read(&exit_path)?, &exit_path)?;
+        }
+        Ok(case)
+    }
+
+    /// The name the case is reported under.
+    pub fn name(&self) -> String {
+        self.source.display().to_string()
+    }
+
+    fn sidecar(&self, extension: &str) -> PathBuf {
+        self.source.with_extension(extension)
+    }
+}
+
+/// How a single case ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoldenOutcome {
+    Passed,
+    /// The program ran but did not do what was recorded.
+    Mismatch { diff: String },
+    /// Nothing is recorded for the program yet.
+    Unblessed,
+    /// `update` recorded the program's output.
+    Blessed,
+    CompileError(String),
+    TimedOut,
+}
+
+impl GoldenOutcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Mismatch { .. } | Self::CompileError(_) | Self::TimedOut)
+    }
+}
+
+/// Every `.onu` file directly under `dir`, in name order.
+pub fn discover(dir: &Path) -> Result<Vec<GoldenCase>, OnuError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| resource_error(dir, e))?;
+    let mut sources: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "onu"))
+        .collect();
+    sources.sort();
+    sources.iter().map(|source| GoldenCase::load(source)).collect()
+}
+
+/// Compile and run `case` with the `onu` binary, then check or bless it.
+pub fn check(case: &GoldenCase, onu: &Path, options: &GoldenOptions) -> GoldenOutcome {
+    if case.expected_stdout.is_none() && !options.update {
+        return GoldenOutcome::Unblessed;
+    }
+    let ir = match compile(&case.source) {
+        Ok(ir) => ir,
+        Err(e) => return GoldenOutcome::CompileError(format!("{:?}", e)),
+    };
+    let stem = case.source.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let output = match run_in_child(onu, &ir, &format!("golden_{}", stem), &case.args, Some(options.timeout)) {
+        Ok(Some(output)) => output,
+        Ok(None) => return GoldenOutcome::TimedOut,
+        Err(e) => return GoldenOutcome::CompileError(format!("{:?}", e)),
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    // A signal is reported as -1 so it never matches a recorded code.
+    let exit = output.status.code().unwrap_or(-1);
+
+    if options.update {
+        return match bless(case, &stdout, exit) {
+            Ok(()) => GoldenOutcome::Blessed,
+            Err(e) => GoldenOutcome::CompileError(format!("{:?}", e)),
+        };
+    }
+    let expected = case.expected_stdout.as_deref().unwrap_or_default();
+
+    let mut diff = String::new();
+    if expected != stdout {
+        diff.push_str(&line_diff(expected, &stdout));
+    }
+    if exit != case.expected_exit {
+        diff.push_str(&format!("exit code: expected {}, got {}\n", case.expected_exit, exit));
+    }
+    if diff.is_empty() { GoldenOutcome::Passed } else { GoldenOutcome::Mismatch { diff } }
+}
+
+/// Check every case, printing a line per case, any diffs, and a summary.
+pub fn run_all(cases: &[GoldenCase], onu: &Path, options: &GoldenOptions) -> Vec<GoldenOutcome> {
+    println!("\nrunning {} golden case{}", cases.len(), if cases.len() == 1 { "" } else { "s" });
+    let outcomes: Vec<GoldenOutcome> = cases
+        .iter()
+        .map(|case| {
+            let outcome = check(case, onu, options);
+            let verdict = match &outcome {
+                GoldenOutcome::Passed => "ok",
+                GoldenOutcome::Blessed => "blessed",
+                GoldenOutcome::Unblessed => "unblessed",
+                GoldenOutcome::TimedOut => "TIMED OUT",
+                GoldenOutcome::Mismatch { .. } | GoldenOutcome::CompileError(_) => "FAILED",
+            };
+            println!("golden {} ... {}", case.name(), verdict);
+            outcome
+        })
+        .collect();
+
+    let failures: Vec<(&GoldenCase, &GoldenOutcome)> =
+        cases.iter().zip(&outcomes).filter(|(_, outcome)| outcome.is_failure()).collect();
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (case, outcome) in &failures {
+            println!("\n---- {} ----", case.name());
+            match outcome {
+                GoldenOutcome::Mismatch { diff } => print!("{}", diff),
+                GoldenOutcome::CompileError(message) => println!("{}", message),
+                GoldenOutcome::TimedOut => println!("still running after {:?}", options.timeout),
+                _ => {}
+            }
+        }
+    }
+
+    let count = |wanted: fn(&GoldenOutcome) -> bool| outcomes.iter().filter(|o| wanted(o)).count();
+    println!(
+        "\ngolden result: {}. {} passed; {} failed; {} blessed; {} unblessed\n",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        count(|o| *o == GoldenOutcome::Passed),
+        failures.len(),
+        count(|o| *o == GoldenOutcome::Blessed),
+        count(|o| *o == GoldenOutcome::Unblessed),
+    );
+    outcomes
+}
+
+fn compile(source: &Path) -> Result<String, OnuError> {
+    let options = CompilationOptions { log_level: LogLevel::Error, ..CompilationOptions::default() };
+    let mut pipeline = CompilationPipeline::new(
+        NativeOsEnvironment::new(options.log_level),
+        OnuCodegen::new(),
+        Box::new(OnuLexer::new(options.log_level)),
+        Box::new(OnuParser::new(options.log_level)),
+        options,
+    );
+    let compiled = pipeline.compile_source(&read(source)?)?;
+    Ok(compiled.ir.unwrap_or_default())
+}
+
+/// Write the sidecars for `case`; `.exit` only when the code is not 0.
+fn bless(case: &GoldenCase, stdout: &str, exit: i32) -> Result<(), OnuError> {
+    let stdout_path = case.sidecar("stdout");
+    std::fs::write(&stdout_path, stdout).map_err(|e| resource_error(&stdout_path, e))?;
+    let exit_path = case.sidecar("exit");
+    if exit != 0 {
+        std::fs::write(&exit_path, format!("{}\n", exit)).map_err(|e| resource_error(&exit_path, e))?;
+    } else if exit_path.exists() {
+        std::fs::remove_file(&exit_path).map_err(|e| resource_error(&exit_path, e))?;
+    }
+    Ok(())
+}
+
+/// The lines that differ, position by position, as `-expected` / `+actual`.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.split_inclusive('\n').collect();
+    let actual: Vec<&str> = actual.split_inclusive('\n').collect();
+    let mut diff = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        let (e, a) = (expected.get(i), actual.get(i));
+        if e == a {
+            continue;
+        }
+        diff.push_str(&format!("line {}:\n", i + 1));
+        if let Some(e) = e {
+            diff.push_str(&format!("-{}\n", shown(e)));
+        }
+        if let Some(a) = a {
+            diff.push_str(&format!("+{}\n", shown(a)));
+        }
+    }
+    diff
+}
+
+fn shown(line: &str) -> String {
+    match line.strip_suffix('\n') {
+        Some(line) => line.to_string(),
+        None => format!("{} (no newline at end)", line),
+    }
+}
+
+fn parse_exit(code: &str, path: &Path) -> Result<i32, OnuError> {
+    code.trim().parse().map_err(|_| OnuError::GrammarViolation {
+        message: format!("{}: '{}' is not an exit code", path.display(), code.trim()),
+        span: Default::default(),
+    })
+}
+
+fn read(path: &Path) -> Result<String, OnuError> {
+    std::fs::read_to_string(path).map_err(|e| resource_error(path, e))
+}
+
+fn resource_error(path: &Path, e: std::io::Error) -> OnuError {
+    OnuError::ResourceViolation { message: format!("{}: {}", path.display(), e), span: Default::default() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotations_give_stdout_exit_and_args() {
+        let dir = std::env::temp_dir().join(format!("onu_golden_unit_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("annotated.onu");
+        std::fs::write(&source, "-- args: a b\n-- expect-stdout: first\n-- expect-stdout: second\n-- expect-exit: 3\n")
+            .unwrap();
+
+        let case = GoldenCase::load(&source).unwrap();
+        assert_eq!(case.args, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(case.expected_stdout.as_deref(), Some("first\nsecond\n"));
+        assert_eq!(case.expected_exit, 3);
+
+        // Sidecars take precedence over annotations.
+        std::fs::write(dir.join("annotated.stdout"), "sidecar\n").unwrap();
+        let case = GoldenCase::load(&source).unwrap();
+        assert_eq!(case.expected_stdout.as_deref(), Some("sidecar\n"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_line_diff_shows_changed_and_missing_lines() {
+        let diff = line_diff("a\nb\nc\n", "a\nB");
+        assert_eq!(diff, "line 2:\n-b\n+B (no newline at end)\nline 3:\n-c\n");
+    }
+}
@@ -0,0 +1,167 @@
+//! Ọ̀nụ JIT Runner: Infrastructure Implementation
+//!
+//! Executes the LLVM IR of a whole program in-process through Inkwell's
+//! `ExecutionEngine`, without writing a `.ll` file or invoking clang.  Used by
+//! `onu run` and by the REPL.
+//!
+//! The emitted `main(i32 argc, i64 argv) -> i32` stores its arguments into the
+//! globals read by `argument-count` / `receives-argument`, so the runner builds
+//! a C `argv` for it.  `receives-line` and `broadcasts` use raw syscalls on
+//! fds 0 and 1, which the JIT-compiled code shares with this process; the
+//! program flushes its own stdout buffer before `main` returns.
+//!
+//! [`run_in_child`] runs IR through `onu run` in a separate process instead,
+//! for callers that must survive a program that traps or never finishes.
+
+use std::ffi::{CString, c_char};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+use inkwell::context::Context;
+use inkwell::execution_engine::JitFunction;
+use inkwell::memory_buffer::MemoryBuffer;
+use inkwell::OptimizationLevel;
+
+use crate::domain::entities::error::OnuError;
+
+/// The C-ABI signature of the `main` every Ọ̀nụ program emits.
+pub type EntryPoint = unsafe extern "C" fn(i32, i64) -> i32;
+
+/// A NUL-terminated C `argv` whose strings live as long as it does.
+pub struct ProgramArguments {
+    _strings: Vec<CString>,
+    pointers: Vec<*const c_char>,
+}
+
+impl ProgramArguments {
+    /// `argv[0]` is `program`; the rest are `args` in order.  Interior NUL
+    /// bytes cannot be passed through a C `argv` and truncate the argument.
+    pub fn new(program: &str, args: &[String]) -> Self {
+        let strings: Vec<CString> = std::iter::once(program)
+            .chain(args.iter().map(String::as_str))
+            .map(|arg| CString::new(arg.split('\0').next().unwrap_or_default()).unwrap_or_default())
+            .collect();
+        let pointers = strings
+            .iter()
+            .map(|s| s.as_ptr())
+            .chain(std::iter::once(std::ptr::null()))
+            .collect();
+        Self { _strings: strings, pointers }
+    }
+
+    pub fn argc(&self) -> i32 {
+        (self.pointers.len() - 1) as i32
+    }
+
+    /// The `argv` array as the `i64` the entry point takes.
+    pub fn argv(&self) -> i64 {
+        self.pointers.as_ptr() as i64
+    }
+}
+
+/// JIT `ir` and hand its `main` to `call`.
+pub fn with_entry_point<T>(
+    ir: &str,
+    call: impl FnOnce(JitFunction<'_, EntryPoint>) -> T,
+) -> Result<T, OnuError> {
+    let context = Context::create();
+
+    // Parse the IR text into an in-memory module.
+    let buf = MemoryBuffer::create_from_memory_range_copy(ir.as_bytes(), "onu_jit");
+    let module = context
+        .create_module_from_ir(buf)
+        .map_err(|e| OnuError::CodeGenError { message: format!("LLVM IR parse error: {}", e) })?;
+
+    // Create a JIT execution engine.
+    let ee = module
+        .create_jit_execution_engine(OptimizationLevel::Default)
+        .map_err(|e| OnuError::CodeGenError { message: format!("JIT engine creation failed: {}", e) })?;
+
+    let main = unsafe { ee.get_function::<EntryPoint>("main") }.map_err(|_| OnuError::CodeGenError {
+        message: "JIT: 'main' function not found".to_string(),
+    })?;
+    Ok(call(main))
+}
+
+/// Run the program in `ir` with `args` and return its exit code.
+pub fn run_program(ir: &str, program: &str, args: &[String]) -> Result<i32, OnuError> {
+    let arguments = ProgramArguments::new(program, args);
+    // Anything this process has buffered must reach stdout before the
+    // program's own syscall writes do.
+    std::io::stdout().flush().ok();
+    with_entry_point(ir, |main| unsafe { main.call(arguments.argc(), arguments.argv()) })
+}
+
+/// Run `ir` with `onu run` in a child process, so that a trap or a runaway
+/// loop cannot take this process down.  `name` keeps the temporary `.ll`
+/// files of concurrent callers apart.  Returns `None` if the program was
+/// still running after `timeout` and had to be killed.
+pub fn run_in_child(
+    onu: &Path,
+    ir: &str,
+    name: &str,
+    args: &[String],
+    timeout: Option<Duration>,
+) -> Result<Option<Output>, OnuError> {
+    let path = std::env::temp_dir().join(format!("onu_{}_{}.ll", name, std::process::id()));
+    std::fs::write(&path, ir).map_err(|e| OnuError::ResourceViolation {
+        message: format!("could not write {}: {}", path.display(), e),
+        span: Default::default(),
+    })?;
+    let result = wait_for(onu, &path, args, timeout);
+    let _ = std::fs::remove_file(&path);
+    result.map_err(|e| OnuError::ResourceViolation {
+        message: format!("could not run {}: {}", onu.display(), e),
+        span: Default::default(),
+    })
+}
+
+fn wait_for(
+    onu: &Path,
+    path: &Path,
+    args: &[String],
+    timeout: Option<Duration>,
+) -> std::io::Result<Option<Output>> {
+    let mut child = Command::new(onu)
+        .arg("run")
+        .arg(path)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Drain both pipes while waiting so a chatty program cannot block on a
+    // full pipe before it exits.
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut bytes = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut bytes);
+            }
+            bytes
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = drain(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if timeout.is_some_and(|limit| started.elapsed() >= limit) {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    };
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
+}
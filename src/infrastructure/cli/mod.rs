@@ -1,4 +1,8 @@
+pub mod bench;
+pub mod golden;
+pub mod jit;
 pub mod parser;
 pub mod repl;
+pub mod test_runner;
 pub use parser::CliParser;
 pub use repl::Repl;
@@ -1,10 +1,14 @@
 /// Ọ̀nụ REPL: Interactive JIT Read-Eval-Print Loop
 ///
 /// This module implements a benchmarked interactive REPL using the State
-/// Pattern to manage evaluation lifecycle.  The REPL compiles each entry
-/// through the full pipeline, then executes the resulting LLVM IR via
-/// Inkwell's JIT `ExecutionEngine`, printing both the output and the wall
-/// time elapsed.
+/// Pattern to manage evaluation lifecycle.  Shapes and behaviors entered at
+/// the prompt stay registered for the rest of the session; a bare expression
+/// is wrapped in a `run` behavior, compiled together with the session's
+/// definitions, executed via Inkwell's JIT `ExecutionEngine`, and its value
+/// printed with its type alongside the wall time elapsed.
+///
+/// Every entry runs in a child `onu run` process, so an entry that traps or
+/// never finishes ends only itself, not the session.
 ///
 /// State transitions:
 /// ```text
@@ -15,25 +19,25 @@
 /// compiler domain (`lib.rs`, MIR, HIR, etc.) is unaware of REPL semantics.
 
 use std::io::{self, BufRead, Write};
-use std::time::Instant;
-
-use inkwell::context::Context;
-use inkwell::memory_buffer::MemoryBuffer;
-use inkwell::OptimizationLevel;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
+use crate::CompilationPipeline;
 use crate::adapters::codegen::OnuCodegen;
 use crate::adapters::lexer::OnuLexer;
 use crate::adapters::parser::OnuParser;
 use crate::application::options::{CompilationOptions, LogLevel};
-use crate::application::ports::compiler_ports::LexerPort;
-use crate::application::use_cases::analysis_service::AnalysisService;
+use crate::application::ports::compiler_ports::Token;
+use crate::application::ports::environment::EnvironmentPort;
 use crate::application::use_cases::lowering_service::LoweringService;
 use crate::application::use_cases::mir_lowering_service::MirLoweringService;
-use crate::application::use_cases::module_service::ModuleService;
 use crate::application::use_cases::registry_service::RegistryService;
-use crate::domain::entities::core_module::{CoreModule, StandardMathModule};
+use crate::domain::entities::ast::{Discourse, Expression, ReturnType};
 use crate::domain::entities::error::OnuError;
-use crate::infrastructure::extensions::io::OnuIoModule;
+use crate::domain::entities::mir::MirProgram;
+use crate::domain::entities::types::OnuType;
+use crate::infrastructure::cli::jit::{ProgramArguments, run_in_child, with_entry_point};
 use crate::infrastructure::os::NativeOsEnvironment;
 
 // ---------------------------------------------------------------------------
@@ -56,17 +60,54 @@ pub enum ReplState {
 /// Interactive JIT REPL for the Ọ̀nụ language.
 pub struct Repl {
     state: ReplState,
-    lexer: OnuLexer,
-    parser: OnuParser,
+    /// The shared compilation pipeline; its registry holds the signatures of
+    /// the standard modules plus every session definition.
+    pipeline: CompilationPipeline<NativeOsEnvironment, OnuCodegen>,
+    /// Shapes and behaviors entered so far, in entry order.
+    definitions: Vec<Discourse>,
+    /// The last submitted entry, inspected by `:mir` and friends by default.
+    last_entry: Option<String>,
+    /// The `onu` executable that runs each entry in a child process.
+    onu: PathBuf,
+}
+
+/// How long an entry may run before the REPL stops it.
+const ENTRY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Numbers each entry's temporary `.ll` file, so sessions in one process
+/// never share one.
+static ENTRIES: AtomicUsize = AtomicUsize::new(0);
+
+/// What a session entry compiled to.
+#[derive(Debug)]
+pub enum ReplEntry {
+    /// Definitions that joined the session, by name.
+    Defined(Vec<String>),
+    /// The session defined `run`/`main`; the whole session as LLVM IR.
+    Program(String),
+    /// A bare expression wrapped in a `run` that broadcasts its value.
+    Expression { ir: String, typ: OnuType },
 }
 
 impl Repl {
-    /// Construct a new REPL in the `Idle` state.
-    pub fn new() -> Self {
+    /// Construct a new REPL in the `Idle` state whose entries run through
+    /// the `onu` executable at `onu`.
+    pub fn new(onu: impl Into<PathBuf>) -> Self {
+        let options = CompilationOptions { log_level: LogLevel::Error, ..CompilationOptions::default() };
+        let pipeline = CompilationPipeline::new(
+            NativeOsEnvironment::new(options.log_level),
+            OnuCodegen::new(),
+            Box::new(OnuLexer::new(options.log_level)),
+            Box::new(OnuParser::new(options.log_level)),
+            options,
+        );
+
         Self {
             state: ReplState::Idle,
-            lexer: OnuLexer::new(LogLevel::Error),
-            parser: OnuParser::new(LogLevel::Error),
+            pipeline,
+            definitions: Vec::new(),
+            last_entry: None,
+            onu: onu.into(),
         }
     }
 
@@ -77,8 +118,9 @@ impl Repl {
 
     /// Run the interactive loop, reading from `stdin` until EOF or `quit`.
     pub fn run(&mut self) {
-        println!("Ọ̀nụ REPL — enter a complete Ọ̀nụ program, then submit with a blank line.");
-        println!("Type ':run' on its own line to submit without a trailing blank line.");
+        println!("Ọ̀nụ REPL — enter definitions or an expression, then submit with a blank line.");
+        println!("Definitions persist for the session; a bare expression is evaluated and printed.");
+        println!("Type ':run' on its own line to submit without a trailing blank line, ':help' for commands.");
         println!("Type 'quit' or 'exit' to leave.");
         println!();
 
@@ -93,6 +135,13 @@ impl Repl {
                 None => break,
                 // Blank submission (user just pressed Enter with no content)
                 Some(src) if src.trim().is_empty() => continue,
+                Some(src) if src.starts_with(':') => {
+                    self.state = ReplState::Evaluating;
+                    match self.meta_command(&src) {
+                        Ok(output) => println!("{}", output),
+                        Err(e) => println!("Error: {:?}", e),
+                    }
+                }
                 Some(src) => {
                     self.state = ReplState::Evaluating;
                     let start = Instant::now();
@@ -100,7 +149,9 @@ impl Repl {
                     match self.compile_and_jit(src.trim()) {
                         Ok(result_msg) => {
                             let elapsed = start.elapsed();
-                            println!("{}", result_msg);
+                            if !result_msg.is_empty() {
+                                println!("{}", result_msg);
+                            }
                             println!("[JIT benchmark: {}µs]", elapsed.as_micros());
                         }
                         Err(e) => {
@@ -121,8 +172,9 @@ impl Repl {
     ///
     /// The first prompt is `onu> `; continuation lines show `  .. `.
     /// Input is submitted (returned) when:
-    /// - The user enters a **blank line** after at least one non-blank line, or
-    /// - The user enters `:run` on its own line.
+    /// - The user enters a **blank line** after at least one non-blank line,
+    /// - The user enters `:run` on its own line, or
+    /// - The first line is any other `:command`, which is returned alone.
     ///
     /// Returns `None` on EOF or when `quit`/`exit` is typed as the very first
     /// line (so the caller can break the REPL loop).
@@ -162,6 +214,11 @@ impl Repl {
                 return Some(accumulated);
             }
 
+            // Any other `:command` is a single line of its own.
+            if accumulated.is_empty() && trimmed.starts_with(':') {
+                return Some(trimmed.to_string());
+            }
+
             // A blank line after content submits; a blank line at the very
             // start is silently skipped (avoids submitting an empty program).
             if trimmed.is_empty() {
@@ -184,123 +241,440 @@ impl Repl {
     // Compilation & JIT execution
     // -----------------------------------------------------------------------
 
-    /// Compile `source` through the full pipeline, JIT-execute the resulting
-    /// LLVM IR via Inkwell's `ExecutionEngine`, and return a display string.
-    fn compile_and_jit(&self, source: &str) -> Result<String, OnuError> {
-        let ir = self.compile_to_ir(source)?;
-        self.jit_execute(&ir)
+    /// Compile `source` as the next session entry and JIT-execute it when it
+    /// produces a program, returning the line to display.
+    fn compile_and_jit(&mut self, source: &str) -> Result<String, OnuError> {
+        self.last_entry = Some(source.to_string());
+        match self.compile_entry(source)? {
+            ReplEntry::Defined(names) => Ok(format!("defined {}", names.join(", "))),
+            ReplEntry::Program(ir) => self.jit_execute(&ir),
+            // The wrapper broadcasts the value itself.
+            ReplEntry::Expression { ir, .. } => self.jit_execute(&ir).map(|_| String::new()),
+        }
     }
 
-    /// Run source through lex → parse → HIR → MIR → LLVM IR.
-    fn compile_to_ir(&self, source: &str) -> Result<String, OnuError> {
-        let env = NativeOsEnvironment::new(LogLevel::Error);
-        let options = CompilationOptions::default();
-
-        let mut registry = RegistryService::new();
-        let module_service = ModuleService::new(&env, options.log_level);
-        module_service.register_module(&mut registry, &CoreModule);
-        module_service.register_module(&mut registry, &StandardMathModule);
-        module_service.register_module(&mut registry, &OnuIoModule);
+    /// Compile one session entry.
+    ///
+    /// An entry that opens with a module, shape or behavior header is a
+    /// definition: it joins the session (replacing any earlier definition of
+    /// the same name) and the whole session is compiled to check it.  Anything
+    /// else is a bare expression, wrapped in a `run` behavior that broadcasts
+    /// its value and type.  A rejected entry leaves the session unchanged.
+    pub fn compile_entry(&mut self, source: &str) -> Result<ReplEntry, OnuError> {
+        let prepared = self.prepare(source, ValueUse::Display)?;
+        let ir = self.compile(&prepared, &mut |_, _| {})?;
+        match prepared.kind {
+            EntryKind::Definitions(names) => {
+                self.pipeline.registry = prepared.registry;
+                self.definitions = prepared.program;
+                if names.iter().any(|n| is_entry_point(n)) {
+                    Ok(ReplEntry::Program(ir))
+                } else {
+                    Ok(ReplEntry::Defined(names))
+                }
+            }
+            EntryKind::Expression(typ) => Ok(ReplEntry::Expression { ir, typ }),
+        }
+    }
 
-        // Lex
-        let tokens = self.lexer.lex(source)?;
+    /// Compile a prepared entry through the shared pipeline, against the
+    /// entry's registry rather than the session's.
+    fn compile(
+        &mut self,
+        prepared: &PreparedEntry,
+        observe: &mut dyn FnMut(&str, &MirProgram),
+    ) -> Result<String, OnuError> {
+        let compiled = self.with_registry(&prepared.registry, |pipeline| {
+            pipeline.compile_discourses_observed(prepared.program.clone(), observe)
+        })?;
+        for d in &compiled.diagnostics {
+            eprintln!("[onu warning] {}", d.message);
+        }
+        Ok(compiled.ir.unwrap_or_default())
+    }
 
-        // Scan headers (registers behavior signatures for forward references)
-        self.parser.scan_headers(&tokens, &mut registry)?;
+    /// Run `f` on the pipeline with `registry` standing in for the session's.
+    fn with_registry<T>(
+        &mut self,
+        registry: &RegistryService,
+        f: impl FnOnce(&mut CompilationPipeline<NativeOsEnvironment, OnuCodegen>) -> T,
+    ) -> T {
+        let session = std::mem::replace(&mut self.pipeline.registry, registry.clone());
+        let result = f(&mut self.pipeline);
+        self.pipeline.registry = session;
+        result
+    }
 
-        // Parse
-        let discourses = self.parser.parse_with_registry(tokens, &mut registry)?;
+    /// Parse `source` against the session without changing it.
+    fn prepare(&self, source: &str, value_use: ValueUse) -> Result<PreparedEntry, OnuError> {
+        let tokens = self.pipeline.lex(source)?;
+        let is_definition = matches!(
+            tokens.iter().find(|t| **t != Token::NewLine),
+            Some(Token::TheModuleCalled)
+                | Some(Token::TheShapeCalled)
+                | Some(Token::TheBehaviorCalled)
+                | Some(Token::TheEffectBehaviorCalled)
+        );
+        if is_definition {
+            self.prepare_definitions(tokens)
+        } else {
+            self.prepare_expression(source, value_use)
+        }
+    }
 
-        // HIR
-        let analysis_service = AnalysisService::new(&env, &registry);
-        let mut hir_discourses = Vec::new();
-        for discourse in discourses {
-            let mut hir = LoweringService::lower_discourse(&discourse, &registry);
-            analysis_service.analyze_discourse(&mut hir)?;
-            hir_discourses.push(hir);
+    fn prepare_definitions(&self, tokens: Vec<Token>) -> Result<PreparedEntry, OnuError> {
+        let mut registry = self.pipeline.registry.clone();
+        self.pipeline.parser.scan_headers(&tokens, &mut registry)?;
+        let entered = self.pipeline.parser.parse_with_registry(tokens, &mut registry)?;
+
+        let mut program = self.definitions.clone();
+        let mut names = Vec::new();
+        for discourse in &entered {
+            let Some(name) = definition_name(discourse) else { continue };
+            names.push(name.to_string());
+            program.retain(|d| definition_name(d) != Some(name));
+            program.push(discourse.clone());
         }
 
-        // MIR (with all optimization passes)
-        use crate::application::use_cases::inline_pass::InlinePass;
-        use crate::application::use_cases::integer_upgrade_pass::IntegerUpgradePass;
-        use crate::application::use_cases::memo_pass::MemoPass;
-        use crate::application::use_cases::tco_pass::TcoPass;
-        use crate::application::use_cases::wide_div_legalization_pass::WideDivLegalizationPass;
-        use crate::application::ports::compiler_ports::CodegenPort;
-
-        let mir_service = MirLoweringService::new(&env, &registry);
-        let mir = mir_service.lower_program(&hir_discourses)?;
-        let mir = IntegerUpgradePass::run(mir);
-        let mir = MemoPass::run(mir, &registry);
-        let mir = TcoPass::run(mir);
-        let mir = InlinePass::run(mir);
-        // Second TcoPass: catches tail calls exposed by inlining.
-        let mir = TcoPass::run(mir);
-        let mir = WideDivLegalizationPass::run(mir);
-
-        // Codegen
-        let mut codegen = OnuCodegen::new();
-        codegen.set_registry(registry);
-        let ir = codegen.generate(&mir)?;
-
-        Ok(ir)
-    }
-
-    /// Parse the LLVM IR string into an Inkwell module, create a JIT
-    /// `ExecutionEngine`, and call the `main` function.
+        Ok(PreparedEntry { entered, program, registry, kind: EntryKind::Definitions(names) })
+    }
+
+    fn prepare_expression(
+        &self,
+        source: &str,
+        value_use: ValueUse,
+    ) -> Result<PreparedEntry, OnuError> {
+        let wrapped = format!(
+            "the effect behavior called run\nwith intent: evaluate a REPL entry\n\
+             takes: nothing\ndelivers: nothing\nas:\n{}",
+            source
+        );
+        let wrapped_tokens = self.pipeline.lex(&wrapped)?;
+        let mut registry = self.pipeline.registry.clone();
+        self.pipeline.parser.scan_headers(&wrapped_tokens, &mut registry)?;
+        let entered = self.pipeline.parser.parse_with_registry(wrapped_tokens, &mut registry)?;
+        let Some(Discourse::Behavior { header, body }) = entered.last().cloned() else {
+            return Err(OnuError::GrammarViolation {
+                message: "REPL: expected an expression".to_string(),
+                span: Default::default(),
+            });
+        };
+
+        let typ = MirLoweringService::new(&self.pipeline.env, &registry)
+            .expression_type(&LoweringService::lower_expression(&body, &registry))?;
+        let mut header = header;
+        let body = match value_use {
+            ValueUse::Display => display_value(body, &typ)?,
+            // Delivering an integer keeps the work observable to the optimizer.
+            ValueUse::Deliver if typ == OnuType::I64 => {
+                header.delivers = ReturnType(OnuType::I64);
+                body
+            }
+            ValueUse::Deliver => body,
+        };
+
+        let mut program: Vec<Discourse> = self
+            .definitions
+            .iter()
+            .filter(|d| !definition_name(d).is_some_and(is_entry_point))
+            .cloned()
+            .collect();
+        program.push(Discourse::Behavior { header, body });
+
+        Ok(PreparedEntry { entered, program, registry, kind: EntryKind::Expression(typ) })
+    }
+
+    /// JIT-execute the program's `main` with no arguments in a child
+    /// process, passing on what it prints.
     ///
     /// Returns a display string with the exit code / result.
     fn jit_execute(&self, ir: &str) -> Result<String, OnuError> {
-        let context = Context::create();
-
-        // Parse the IR text into an in-memory module.
-        let buf = MemoryBuffer::create_from_memory_range(ir.as_bytes(), "repl_snippet");
-        let module = context
-            .create_module_from_ir(buf)
-            .map_err(|e| OnuError::GrammarViolation {
-                message: format!("LLVM IR parse error: {}", e),
+        let output = self.run_isolated(ir)?;
+        io::stdout().write_all(&output.stdout).ok();
+        io::stdout().flush().ok();
+        io::stderr().write_all(&output.stderr).ok();
+        match output.status.code() {
+            Some(code) => Ok(format!("=> {}", code)),
+            None => Err(OnuError::ResourceViolation {
+                message: format!("REPL: entry was killed ({})", output.status),
                 span: Default::default(),
-            })?;
+            }),
+        }
+    }
 
-        // Create a JIT execution engine.
-        let ee = module
-            .create_jit_execution_engine(OptimizationLevel::Default)
-            .map_err(|e| OnuError::GrammarViolation {
-                message: format!("JIT engine creation failed: {}", e),
-                span: Default::default(),
-            })?;
-
-        // Locate and call `main`.  Ọ̀nụ programs always emit a C-ABI `main`.
-        let result = unsafe {
-            match ee.get_function::<unsafe extern "C" fn() -> i64>("main") {
-                Ok(f) => f.call(),
-                Err(_) => {
-                    // Try a void main for effect-only (nothing-returning) programs.
-                    match ee.get_function::<unsafe extern "C" fn()>("main") {
-                        Ok(f) => {
-                            f.call();
-                            0
-                        }
-                        Err(_) => {
-                            return Err(OnuError::GrammarViolation {
-                                message: "JIT: 'main' function not found or has an unsupported \
-                                         signature (expected `fn() -> i64` or `fn()`)"
-                                    .to_string(),
-                                span: Default::default(),
-                            });
-                        }
-                    }
+    /// Run `ir` in a child `onu run` process under `ENTRY_TIMEOUT`.
+    fn run_isolated(&self, ir: &str) -> Result<std::process::Output, OnuError> {
+        let name = format!("repl_{}", ENTRIES.fetch_add(1, Ordering::Relaxed));
+        run_in_child(&self.onu, ir, &name, &[], Some(ENTRY_TIMEOUT))?.ok_or_else(|| OnuError::ResourceViolation {
+            message: format!("REPL: entry still running after {:?}; stopped", ENTRY_TIMEOUT),
+            span: Default::default(),
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Meta-commands
+    // -----------------------------------------------------------------------
+
+    /// Run a `:command` line and return the text to display.
+    ///
+    /// The inspection commands (`:tokens`, `:ast`, `:hir`, `:mir`, `:llvm`)
+    /// take an entry after the command name, or inspect the last submitted
+    /// entry when given none.  None of them changes the session.
+    pub fn meta_command(&mut self, line: &str) -> Result<String, OnuError> {
+        let line = line.trim();
+        let (command, argument) = match line.split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, argument.trim()),
+            None => (line, ""),
+        };
+
+        match command {
+            ":help" => Ok(HELP.trim_end().to_string()),
+            ":reset" => {
+                *self = Self::new(std::mem::take(&mut self.onu));
+                Ok("session cleared".to_string())
+            }
+            ":load" => {
+                if argument.is_empty() {
+                    return Err(usage(":load <file.onu>"));
                 }
+                let source = self.pipeline.env.read_file(argument)?;
+                self.compile_and_jit(source.trim())
             }
-        };
+            ":type" => match self.prepare(self.inspected(argument)?, ValueUse::Deliver)?.kind {
+                EntryKind::Expression(typ) => Ok(type_name(&typ)),
+                EntryKind::Definitions(_) => Err(usage(":type <expression>")),
+            },
+            ":tokens" => {
+                let tokens = self.pipeline.lex(self.inspected(argument)?)?;
+                Ok(format_tokens(&tokens))
+            }
+            ":ast" => {
+                let prepared = self.prepare(self.inspected(argument)?, ValueUse::Deliver)?;
+                Ok(format!("{:#?}", prepared.entered))
+            }
+            ":hir" => {
+                let prepared = self.prepare(self.inspected(argument)?, ValueUse::Deliver)?;
+                let hir_discourses = self.with_registry(&prepared.registry, |pipeline| {
+                    pipeline.lower_hir(prepared.entered.clone())
+                })?;
+                Ok(format!("{:#?}", hir_discourses))
+            }
+            ":mir" => {
+                let prepared = self.prepare(self.inspected(argument)?, ValueUse::Deliver)?;
+                let names = match &prepared.kind {
+                    EntryKind::Definitions(names) => names.clone(),
+                    EntryKind::Expression(_) => vec!["run".to_string()],
+                };
+                let mut out = String::new();
+                self.compile(&prepared, &mut |stage, mir| {
+                    let functions: Vec<_> = mir
+                        .functions
+                        .iter()
+                        .filter(|f| names.iter().any(|n| f.name == *n || f.name.starts_with(&format!("{}.", n))))
+                        .collect();
+                    out.push_str(&format!("-- after {} --\n{:#?}\n", stage, functions));
+                })?;
+                Ok(out.trim_end().to_string())
+            }
+            ":llvm" => {
+                let prepared = self.prepare(self.inspected(argument)?, ValueUse::Deliver)?;
+                let ir = self.compile(&prepared, &mut |_, _| {})?;
+                Ok(ir.trim_end().to_string())
+            }
+            ":bench" => {
+                let (expr, runs) = argument
+                    .rsplit_once(char::is_whitespace)
+                    .and_then(|(expr, runs)| Some((expr.trim(), runs.parse::<u32>().ok()?)))
+                    .filter(|(expr, runs)| !expr.is_empty() && *runs > 0)
+                    .ok_or_else(|| usage(":bench <expression> <runs>"))?;
+                self.bench(expr, runs)
+            }
+            _ => Err(OnuError::GrammarViolation {
+                message: format!("REPL: unknown command '{}' (try :help)", command),
+                span: Default::default(),
+            }),
+        }
+    }
 
-        Ok(format!("=> {}", result))
+    /// The entry an inspection command looks at.
+    fn inspected<'a>(&'a self, argument: &'a str) -> Result<&'a str, OnuError> {
+        if !argument.is_empty() {
+            return Ok(argument);
+        }
+        self.last_entry.as_deref().ok_or_else(|| OnuError::GrammarViolation {
+            message: "REPL: nothing entered yet to inspect".to_string(),
+            span: Default::default(),
+        })
     }
+
+    /// Compile `expr` once and time `runs` calls of the compiled program.
+    fn bench(&mut self, expr: &str, runs: u32) -> Result<String, OnuError> {
+        let prepared = self.prepare(expr, ValueUse::Deliver)?;
+        if matches!(prepared.kind, EntryKind::Definitions(_)) {
+            return Err(usage(":bench <expression> <runs>"));
+        }
+        let ir = self.compile(&prepared, &mut |_, _| {})?;
+        // Time the calls in this process only once a run in a child has
+        // shown that the entry neither traps nor spins.  The displaying form
+        // exits with 0 unless it traps, whatever the value.
+        let display = self.prepare(expr, ValueUse::Display)?;
+        let display = self.compile(&display, &mut |_, _| {})?;
+        let probe = self.run_isolated(&display)?;
+        if !probe.status.success() {
+            return Err(OnuError::ResourceViolation {
+                message: format!("REPL: entry failed ({}): {}", probe.status, String::from_utf8_lossy(&probe.stderr).trim()),
+                span: Default::default(),
+            });
+        }
+        let arguments = ProgramArguments::new("onu-repl", &[]);
+        let timings = with_entry_point(&ir, |main| {
+            (0..runs)
+                .map(|_| {
+                    let start = Instant::now();
+                    unsafe { main.call(arguments.argc(), arguments.argv()) };
+                    start.elapsed()
+                })
+                .collect::<Vec<_>>()
+        })?;
+
+        let total: Duration = timings.iter().sum();
+        let min = timings.iter().min().copied().unwrap_or_default();
+        let max = timings.iter().max().copied().unwrap_or_default();
+        Ok(format!(
+            "{} runs: mean {:.1}µs, min {:.1}µs, max {:.1}µs",
+            runs,
+            total.as_secs_f64() * 1e6 / runs as f64,
+            min.as_secs_f64() * 1e6,
+            max.as_secs_f64() * 1e6,
+        ))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Session helpers
+// ---------------------------------------------------------------------------
+
+const HELP: &str = "\
+Definitions (module, shape, behavior) persist; any other entry is evaluated.
+Submit an entry with a blank line or ':run'.  Commands:
+  :type <expr>          show the type of an expression
+  :tokens [entry]       show the lexer's tokens
+  :ast [entry]          show the parsed discourses
+  :hir [entry]          show the analyzed HIR
+  :mir [entry]          show the entry's MIR after each pass
+  :llvm [entry]         show the LLVM IR for the session
+  :load <file.onu>      enter every definition in a file
+  :reset                forget every definition
+  :bench <expr> <n>     time n runs of an expression
+  :help                 show this message
+  quit | exit           leave the REPL
+Inspection commands without an entry look at the last one submitted.
+";
+
+/// An entry parsed against the session, ready to compile.
+struct PreparedEntry {
+    /// The entry's own discourses; a bare expression appears as its `run` wrapper.
+    entered: Vec<Discourse>,
+    /// The session's definitions with the entry merged in.
+    program: Vec<Discourse>,
+    registry: RegistryService,
+    kind: EntryKind,
+}
+
+enum EntryKind {
+    Definitions(Vec<String>),
+    Expression(OnuType),
+}
+
+/// What the `run` wrapper of a bare expression does with its value.
+#[derive(Clone, Copy)]
+enum ValueUse {
+    /// Broadcast `=> <value> : <type>`.
+    Display,
+    /// Deliver the value from `main` without printing it.
+    Deliver,
+}
+
+fn usage(form: &str) -> OnuError {
+    OnuError::GrammarViolation { message: format!("usage: {}", form), span: Default::default() }
+}
+
+/// Tokens laid out one source line per output line.
+fn format_tokens(tokens: &[Token]) -> String {
+    let mut lines = vec![Vec::new()];
+    for token in tokens {
+        if *token == Token::NewLine {
+            lines.push(Vec::new());
+        } else if let Some(line) = lines.last_mut() {
+            line.push(format!("{:?}", token));
+        }
+    }
+    lines.iter().filter(|l| !l.is_empty()).map(|l| l.join(" ")).collect::<Vec<_>>().join("\n")
 }
 
-impl Default for Repl {
-    fn default() -> Self {
-        Self::new()
+/// The name a definition is kept under in the session; modules are not kept.
+fn definition_name(discourse: &Discourse) -> Option<&str> {
+    match discourse {
+        Discourse::Module { .. } => None,
+        Discourse::Shape { name, .. } => Some(name),
+        Discourse::Behavior { header, .. } => Some(&header.name),
+        Discourse::Test { .. } => None,
+    }
+}
+
+fn is_entry_point(name: &str) -> bool {
+    name == "run" || name == "main"
+}
+
+/// Rewrite `value` into a body that broadcasts `=> <value> : <type>`.
+fn display_value(value: Expression, typ: &OnuType) -> Result<Expression, OnuError> {
+    if *typ == OnuType::Nothing {
+        return Ok(value);
+    }
+    let rendered = rendered_as_text(Expression::Identifier("repl-value".to_string()), typ)?;
+    let joined = |lhs, rhs| Expression::BehaviorCall { name: "joined-with".to_string(), args: vec![lhs, rhs] };
+    let line = joined(
+        joined(Expression::Text("=> ".to_string()), rendered),
+        Expression::Text(format!(" : {}", type_name(typ))),
+    );
+    Ok(Expression::Derivation {
+        name: "repl-value".to_string(),
+        type_info: None,
+        value: Box::new(value),
+        body: Box::new(Expression::Emit(Box::new(line))),
+    })
+}
+
+/// An expression giving the text of `shown`, a value of type `typ`.
+pub(crate) fn rendered_as_text(shown: Expression, typ: &OnuType) -> Result<Expression, OnuError> {
+    match typ {
+        OnuType::I64 | OnuType::BigInt => {
+            Ok(Expression::BehaviorCall { name: "as-text".to_string(), args: vec![shown] })
+        }
+        OnuType::Strings => Ok(shown),
+        OnuType::Boolean => Ok(Expression::If {
+            condition: Box::new(shown),
+            then_branch: Box::new(Expression::Text("true".to_string())),
+            else_branch: Box::new(Expression::Text("false".to_string())),
+        }),
+        other => Err(OnuError::GrammarViolation {
+            message: format!("cannot display a value of type {}", type_name(other)),
+            span: Default::default(),
+        }),
+    }
+}
+
+/// The surface name of `typ`, as shown after a REPL value.
+pub fn type_name(typ: &OnuType) -> String {
+    match typ {
+        OnuType::I64 => "integer".to_string(),
+        OnuType::BigInt => "big-integer".to_string(),
+        OnuType::F64 => "float".to_string(),
+        OnuType::Boolean => "boolean".to_string(),
+        OnuType::Strings => "text".to_string(),
+        OnuType::Nothing => "nothing".to_string(),
+        OnuType::Shape(name) => name.clone(),
+        other => format!("{:?}", other).to_lowercase(),
     }
 }
 
@@ -314,7 +688,7 @@ mod tests {
 
     #[test]
     fn test_repl_starts_idle() {
-        let repl = Repl::new();
+        let repl = Repl::new("onu");
         assert_eq!(*repl.state(), ReplState::Idle);
     }
 
@@ -328,7 +702,7 @@ mod tests {
 
     #[test]
     fn test_compile_to_ir_simple_program() {
-        let repl = Repl::new();
+        let mut repl = Repl::new("onu");
         let source = r#"
 the-module-called Repl with-concern: testing
 
@@ -339,10 +713,36 @@ the-behavior-called main
     as:
         42
 "#;
-        let result = repl.compile_to_ir(source);
-        assert!(result.is_ok(), "Compile failed: {:?}", result.err());
-        let ir = result.unwrap();
-        assert!(ir.contains("define"), "IR should contain function definitions");
+        match repl.compile_entry(source) {
+            Ok(ReplEntry::Program(ir)) => {
+                assert!(ir.contains("define"), "IR should contain function definitions")
+            }
+            other => panic!("expected a program, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_definitions_persist_across_entries() {
+        let mut repl = Repl::new("onu");
+        let double = "the behavior called double\nwith intent: twice its input\ntakes:\n\
+                      an integer called n\ndelivers: an integer\nas:\nn added-to n";
+        match repl.compile_entry(double) {
+            Ok(ReplEntry::Defined(names)) => assert_eq!(names, vec!["double".to_string()]),
+            other => panic!("expected a definition, got {:?}", other),
+        }
+        match repl.compile_entry("21 utilizes double") {
+            Ok(ReplEntry::Expression { typ, .. }) => assert_eq!(typ, OnuType::I64),
+            other => panic!("expected an expression, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rejected_definition_leaves_session_unchanged() {
+        let mut repl = Repl::new("onu");
+        let broken = "the behavior called broken\nwith intent: refer to nothing\ntakes: nothing\n\
+                      delivers: an integer\nas:\nmissing-name";
+        assert!(repl.compile_entry(broken).is_err());
+        assert!(repl.definitions.is_empty());
     }
 
     // -----------------------------------------------------------------------
@@ -367,6 +767,10 @@ the-behavior-called main
                 return Some(accumulated);
             }
 
+            if accumulated.is_empty() && trimmed.starts_with(':') {
+                return Some(trimmed.to_string());
+            }
+
             if trimmed.is_empty() {
                 if !accumulated.is_empty() {
                     return Some(accumulated);
@@ -431,4 +835,61 @@ the-behavior-called main
         assert!(result.is_some());
         assert_eq!(result.unwrap().trim(), "first-line");
     }
+
+    #[test]
+    fn test_meta_command_submits_on_its_own_line() {
+        let lines = &[":type 1 added-to 2", "more"];
+        assert_eq!(simulate_accumulate(lines).as_deref(), Some(":type 1 added-to 2"));
+    }
+
+    // -----------------------------------------------------------------------
+    // Meta-commands
+    // -----------------------------------------------------------------------
+
+    const DOUBLE: &str = "the behavior called double\nwith intent: twice its input\ntakes:\n\
+                          an integer called n\ndelivers: an integer\nas:\nn added-to n";
+
+    #[test]
+    fn test_type_command_reports_expression_type() {
+        let mut repl = Repl::new("onu");
+        repl.compile_entry(DOUBLE).unwrap();
+        assert_eq!(repl.meta_command(":type 4 utilizes double").unwrap(), "integer");
+        assert_eq!(repl.meta_command(":type \"a\" joined-with \"b\"").unwrap(), "text");
+    }
+
+    #[test]
+    fn test_mir_command_shows_every_stage_without_changing_the_session() {
+        let mut repl = Repl::new("onu");
+        let mir = repl.meta_command(&format!(":mir {}", DOUBLE)).unwrap();
+        for stage in ["lowering", "memo", "inline", "idiom-recognition", "lifetime"] {
+            assert!(mir.contains(&format!("-- after {} --", stage)), "missing stage {}", stage);
+        }
+        assert!(mir.contains("\"double\""));
+        assert!(repl.definitions.is_empty());
+    }
+
+    #[test]
+    fn test_inspection_defaults_to_last_entry() {
+        let mut repl = Repl::new("onu");
+        assert!(repl.meta_command(":tokens").is_err());
+        repl.compile_and_jit(DOUBLE).unwrap();
+        assert!(repl.meta_command(":tokens").unwrap().starts_with("TheBehaviorCalled"));
+        assert!(repl.meta_command(":ast").unwrap().contains("double"));
+    }
+
+    #[test]
+    fn test_reset_forgets_definitions() {
+        let mut repl = Repl::new("onu");
+        repl.compile_entry(DOUBLE).unwrap();
+        assert_eq!(repl.meta_command(":reset").unwrap(), "session cleared");
+        assert!(repl.definitions.is_empty());
+        assert!(repl.pipeline.registry.get_signature("double").is_none());
+    }
+
+    #[test]
+    fn test_unknown_command_is_an_error() {
+        let mut repl = Repl::new("onu");
+        assert!(repl.meta_command(":frobnicate").is_err());
+        assert!(repl.meta_command(":help").unwrap().contains(":bench"));
+    }
 }
@@ -0,0 +1,226 @@
+//! Ọ̀nụ Test Runner: Infrastructure Implementation
+//!
+//! `onu test <file>` collects every `the test called … expects: … as: …`
+//! declaration in a file and checks that each body delivers its expected
+//! value.  Each test is rewritten into a `run` behavior of its own, compiled
+//! together with the file's other definitions, and executed by `onu run` in
+//! a child process under a timeout, so a test that traps or loops cannot
+//! take the others down with it.
+//!
+//! The generated `run` delivers `0` when the two values match.  Otherwise it
+//! broadcasts the actual and then the expected value, one per line, and
+//! delivers [`FAILURE_CODE`].
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::CompilationPipeline;
+use crate::adapters::codegen::OnuCodegen;
+use crate::adapters::lexer::OnuLexer;
+use crate::adapters::parser::OnuParser;
+use crate::application::options::{CompilationOptions, LogLevel};
+use crate::application::ports::environment::EnvironmentPort;
+use crate::application::use_cases::lowering_service::LoweringService;
+use crate::application::use_cases::mir_lowering_service::MirLoweringService;
+use crate::domain::entities::ast::{Discourse, Expression, ReturnType, TypeInfo};
+use crate::domain::entities::error::OnuError;
+use crate::domain::entities::types::OnuType;
+use crate::infrastructure::cli::jit::run_in_child;
+use crate::infrastructure::cli::repl::{rendered_as_text, type_name};
+use crate::infrastructure::os::NativeOsEnvironment;
+
+/// The exit code of a test program whose values did not match.
+pub const FAILURE_CODE: i32 = 2;
+
+/// How long a test may run before it is stopped and reported as errored.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How a single test ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestOutcome {
+    Passed,
+    Failed { expected: String, actual: String },
+    /// The test could not be compiled, or its program did not finish normally.
+    Errored(String),
+}
+
+/// One test's result, with where it was declared.
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub name: String,
+    pub line: usize,
+    pub outcome: TestOutcome,
+}
+
+/// Run every test in `source_file`, printing a line per test and a summary.
+/// `onu` is the compiler binary used to execute each test program, and a
+/// test still running after `timeout` is stopped.
+pub fn run_file(source_file: &str, onu: &Path, timeout: Duration) -> Result<Vec<TestResult>, OnuError> {
+    let env = NativeOsEnvironment::new(LogLevel::Error);
+    let source = env.read_file(source_file)?;
+    let mut pipeline = test_pipeline();
+    let tokens = pipeline.lex(&source)?;
+    pipeline.scan_headers(&tokens)?;
+    let discourses = pipeline.parse(tokens)?;
+
+    let (tests, definitions): (Vec<Discourse>, Vec<Discourse>) =
+        discourses.into_iter().partition(|d| matches!(d, Discourse::Test { .. }));
+    let definitions: Vec<Discourse> = definitions
+        .into_iter()
+        .filter(|d| !matches!(d, Discourse::Behavior { header, .. } if header.name == "run" || header.name == "main"))
+        .collect();
+
+    println!("\nrunning {} test{}", tests.len(), if tests.len() == 1 { "" } else { "s" });
+    let mut results = Vec::new();
+    for (index, test) in tests.into_iter().enumerate() {
+        let Discourse::Test { name, expects, body, line } = test else { continue };
+        let outcome = match test_program(&definitions, &pipeline, expects, body) {
+            Ok(program) => run_test_program(&program, onu, index, timeout),
+            Err(e) => TestOutcome::Errored(format!("{:?}", e)),
+        };
+        println!(
+            "test {} ({}:{}) ... {}",
+            name,
+            source_file,
+            line,
+            if outcome == TestOutcome::Passed { "ok" } else { "FAILED" }
+        );
+        results.push(TestResult { name, line, outcome });
+    }
+
+    let failures: Vec<&TestResult> = results.iter().filter(|r| r.outcome != TestOutcome::Passed).collect();
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for failure in &failures {
+            println!("\n---- {} ({}:{}) ----", failure.name, source_file, failure.line);
+            match &failure.outcome {
+                TestOutcome::Failed { expected, actual } => {
+                    println!("expected: {}", expected);
+                    println!("  actual: {}", actual);
+                }
+                TestOutcome::Errored(message) => println!("{}", message.trim_end()),
+                TestOutcome::Passed => {}
+            }
+        }
+    }
+    println!(
+        "\ntest result: {}. {} passed; {} failed\n",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        results.len() - failures.len(),
+        failures.len()
+    );
+    Ok(results)
+}
+
+fn test_pipeline() -> CompilationPipeline<NativeOsEnvironment, OnuCodegen> {
+    let options = CompilationOptions { log_level: LogLevel::Error, ..CompilationOptions::default() };
+    CompilationPipeline::new(
+        NativeOsEnvironment::new(options.log_level),
+        OnuCodegen::new(),
+        Box::new(OnuLexer::new(options.log_level)),
+        Box::new(OnuParser::new(options.log_level)),
+        options,
+    )
+}
+
+/// The LLVM IR of a program whose `run` checks `body` against `expects`.
+fn test_program(
+    definitions: &[Discourse],
+    parsed: &CompilationPipeline<NativeOsEnvironment, OnuCodegen>,
+    expects: Expression,
+    body: Expression,
+) -> Result<String, OnuError> {
+    let mut pipeline = test_pipeline();
+    pipeline.registry = parsed.registry.clone();
+
+    let wrapper = "the effect behavior called run\nwith intent: run a test\n\
+                   takes: nothing\ndelivers: nothing\nas:\n0";
+    let tokens = pipeline.lex(wrapper)?;
+    pipeline.scan_headers(&tokens)?;
+    let Some(Discourse::Behavior { mut header, .. }) = pipeline.parse(tokens)?.pop() else {
+        return Err(OnuError::GrammarViolation {
+            message: "test: could not build the run behavior".to_string(),
+            span: Default::default(),
+        });
+    };
+    header.delivers = ReturnType(OnuType::I64);
+
+    let typer = MirLoweringService::new(&pipeline.env, &pipeline.registry);
+    let actual_type = typer.expression_type(&LoweringService::lower_expression(&body, &pipeline.registry))?;
+    let expected_type = typer.expression_type(&LoweringService::lower_expression(&expects, &pipeline.registry))?;
+    if actual_type != expected_type {
+        return Err(OnuError::GrammarViolation {
+            message: format!(
+                "test delivers {} but expects {}",
+                type_name(&actual_type),
+                type_name(&expected_type)
+            ),
+            span: Default::default(),
+        });
+    }
+
+    let actual = Expression::Identifier("test-actual".to_string());
+    let expected = Expression::Identifier("test-expected".to_string());
+    let call = |name: &str, args| Expression::BehaviorCall { name: name.to_string(), args };
+    let equal = match actual_type {
+        OnuType::Strings => call(
+            "matches",
+            vec![call("compared-with", vec![actual.clone(), expected.clone()]), Expression::I64(0)],
+        ),
+        _ => call("matches", vec![actual.clone(), expected.clone()]),
+    };
+    let report = Expression::Block(vec![
+        Expression::Emit(Box::new(rendered_as_text(actual, &actual_type)?)),
+        Expression::Emit(Box::new(rendered_as_text(expected, &expected_type)?)),
+        Expression::I64(FAILURE_CODE as i64),
+    ]);
+    // Labelled, so that text values are not taken for integers (rule S-3).
+    let labelled = |typ: &OnuType| {
+        Some(TypeInfo { onu_type: typ.clone(), display_name: format!("{:?}", typ), via_role: None, is_observation: false })
+    };
+    let check = Expression::Derivation {
+        name: "test-actual".to_string(),
+        type_info: labelled(&actual_type),
+        value: Box::new(body),
+        body: Box::new(Expression::Derivation {
+            name: "test-expected".to_string(),
+            type_info: labelled(&expected_type),
+            value: Box::new(expects),
+            body: Box::new(Expression::If {
+                condition: Box::new(equal),
+                then_branch: Box::new(Expression::I64(0)),
+                else_branch: Box::new(report),
+            }),
+        }),
+    };
+
+    let mut program = definitions.to_vec();
+    program.push(Discourse::Behavior { header, body: check });
+    let compiled = pipeline.compile_discourses(program)?;
+    Ok(compiled.ir.unwrap_or_default())
+}
+
+/// Execute a test program with `onu run` and read its verdict.
+fn run_test_program(ir: &str, onu: &Path, index: usize, timeout: Duration) -> TestOutcome {
+    let output = match run_in_child(onu, ir, &format!("test_{}", index), &[], Some(timeout)) {
+        Ok(Some(output)) => output,
+        Ok(None) => return TestOutcome::Errored(format!("timed out after {:?}", timeout)),
+        Err(e) => return TestOutcome::Errored(format!("{:?}", e)),
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match output.status.code() {
+        Some(0) => TestOutcome::Passed,
+        Some(FAILURE_CODE) => {
+            let mut lines = stdout.lines().rev();
+            let expected = lines.next().unwrap_or_default().to_string();
+            let actual = lines.next().unwrap_or_default().to_string();
+            TestOutcome::Failed { expected, actual }
+        }
+        code => TestOutcome::Errored(format!(
+            "test program exited with {}\n{}{}",
+            code.map_or("a signal".to_string(), |c| format!("code {}", c)),
+            stdout,
+            String::from_utf8_lossy(&output.stderr)
+        )),
+    }
+}
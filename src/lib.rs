@@ -14,10 +14,21 @@ use crate::application::use_cases::registry_service::RegistryService;
 use crate::application::use_cases::safety_pass;
 use crate::domain::entities::ast::Discourse;
 use crate::domain::entities::core_module::{CoreModule, StandardMathModule};
-use crate::domain::entities::error::OnuError;
+use crate::domain::entities::error::{Diagnostic, OnuError};
 use crate::domain::entities::hir::HirDiscourse;
+use crate::domain::entities::mir::MirProgram;
 use crate::infrastructure::extensions::io::OnuIoModule;
 
+/// What an in-memory compilation produced.  Stages past
+/// `options.stop_after` are left empty.
+#[derive(Debug, Default)]
+pub struct CompiledSource {
+    /// Safety-pass warnings.
+    pub diagnostics: Vec<Diagnostic>,
+    pub mir: Option<MirProgram>,
+    pub ir: Option<String>,
+}
+
 pub struct CompilationPipeline<E: EnvironmentPort, C: CodegenPort> {
     pub env: E,
     pub codegen: C,
@@ -65,50 +76,14 @@ impl<E: EnvironmentPort, C: CodegenPort> CompilationPipeline<E, C> {
         );
 
         let source = self.env.read_file(path)?;
-
-        let tokens = self.lex(&source)?;
-        if self.options.stop_after == Some(CompilerStage::Lexing) {
-            return Ok(());
+        let compiled = self.compile_source(&source)?;
+        for d in &compiled.diagnostics {
+            eprintln!("[onu warning] {}", d.message);
         }
 
-        self.scan_headers(&tokens)?;
-
-        let discourses = self.parse(tokens)?;
-        if self.options.stop_after == Some(CompilerStage::Parsing) {
+        let Some(ir) = compiled.ir else {
             return Ok(());
-        }
-
-        let hir_discourses = self.lower_hir(discourses)?;
-        if self.options.stop_after == Some(CompilerStage::Analysis) {
-            return Ok(());
-        }
-
-        // Safety pass: enforce S-1/S-2/S-3 grammar rules.
-        // Warnings are printed; hard errors abort compilation with a clear message.
-        match safety_pass::run(&hir_discourses) {
-            Ok(diagnostics) => {
-                for d in &diagnostics {
-                    eprintln!("[onu warning] {}", d.message);
-                }
-            }
-            Err(e) => {
-                // Format and print the full error before returning so the
-                // user sees the complete bilingual message in the terminal.
-                let msg = match &e {
-                    OnuError::GrammarViolation { message, .. } => message.clone(),
-                    other => format!("{:?}", other),
-                };
-                eprintln!("\n{}\n", msg);
-                return Err(e);
-            }
-        }
-
-        let mir = self.lower_mir(hir_discourses)?;
-        if self.options.stop_after == Some(CompilerStage::Mir) {
-            return Ok(());
-        }
-
-        let ir = self.emit_ir(mir)?;
+        };
         if self.options.stop_after == Some(CompilerStage::Codegen) {
             return Ok(());
         }
@@ -127,6 +102,67 @@ impl<E: EnvironmentPort, C: CodegenPort> CompilationPipeline<E, C> {
         Ok(())
     }
 
+    /// Compile `source` in memory: lex → parse → HIR → safety → MIR → LLVM
+    /// IR, honouring `options.stop_after`.  This is the pipeline the CLI,
+    /// the REPL and the test helpers share.
+    pub fn compile_source(&mut self, source: &str) -> Result<CompiledSource, OnuError> {
+        let tokens = self.lex(source)?;
+        if self.options.stop_after == Some(CompilerStage::Lexing) {
+            return Ok(CompiledSource::default());
+        }
+
+        self.scan_headers(&tokens)?;
+
+        let discourses = self.parse(tokens)?;
+        if self.options.stop_after == Some(CompilerStage::Parsing) {
+            return Ok(CompiledSource::default());
+        }
+
+        self.compile_discourses(discourses)
+    }
+
+    /// Compile already-parsed discourses whose headers are in `registry`.
+    pub fn compile_discourses(&mut self, discourses: Vec<Discourse>) -> Result<CompiledSource, OnuError> {
+        self.compile_discourses_observed(discourses, &mut |_, _| {})
+    }
+
+    /// As [`compile_discourses`](Self::compile_discourses), handing the MIR
+    /// to `observe` after each MIR stage.
+    pub fn compile_discourses_observed(
+        &mut self,
+        discourses: Vec<Discourse>,
+        observe: &mut dyn FnMut(&str, &MirProgram),
+    ) -> Result<CompiledSource, OnuError> {
+        let hir_discourses = self.lower_hir(discourses)?;
+        let mut diagnostics = self.check_safety(&hir_discourses)?;
+        if self.options.stop_after == Some(CompilerStage::Analysis) {
+            return Ok(CompiledSource { diagnostics, ..Default::default() });
+        }
+
+        let (mir, warnings) = self.lower_mir_observed(hir_discourses, observe)?;
+        diagnostics.extend(warnings);
+        if self.options.stop_after == Some(CompilerStage::Mir) {
+            return Ok(CompiledSource { diagnostics, mir: Some(mir), ir: None });
+        }
+
+        let ir = self.emit_ir(mir.clone())?;
+        Ok(CompiledSource { diagnostics, mir: Some(mir), ir: Some(ir) })
+    }
+
+    /// Safety pass: enforce S-1/S-2/S-3 grammar rules.
+    /// Warnings are returned; hard errors abort compilation with a clear message.
+    pub fn check_safety(&self, hir_discourses: &[HirDiscourse]) -> Result<Vec<Diagnostic>, OnuError> {
+        safety_pass::run(hir_discourses).inspect_err(|e| {
+            // Format and print the full error before returning so the
+            // user sees the complete bilingual message in the terminal.
+            let msg = match e {
+                OnuError::GrammarViolation { message, .. } => message.clone(),
+                other => format!("{:?}", other),
+            };
+            eprintln!("\n{}\n", msg);
+        })
+    }
+
     pub fn lex(
         &self,
         source: &str,
@@ -151,7 +187,8 @@ impl<E: EnvironmentPort, C: CodegenPort> CompilationPipeline<E, C> {
     pub fn lower_hir(&self, discourses: Vec<Discourse>) -> Result<Vec<HirDiscourse>, OnuError> {
         let analysis_service = AnalysisService::new(&self.env, &self.registry);
         let mut hir_discourses = Vec::new();
-        for discourse in discourses {
+        // Tests are compiled only by `onu test`, never as part of the program.
+        for discourse in discourses.into_iter().filter(|d| !matches!(d, Discourse::Test { .. })) {
             let mut hir = LoweringService::lower_discourse(&discourse, &self.registry);
             analysis_service.analyze_discourse(&mut hir)?;
             if self.options.emit_hir {
@@ -163,10 +200,18 @@ impl<E: EnvironmentPort, C: CodegenPort> CompilationPipeline<E, C> {
         Ok(hir_discourses)
     }
 
-    pub fn lower_mir(
+    pub fn lower_mir(&self, hir_discourses: Vec<HirDiscourse>) -> Result<MirProgram, OnuError> {
+        self.lower_mir_observed(hir_discourses, &mut |_, _| {}).map(|(mir, _)| mir)
+    }
+
+    /// Run every MIR stage, handing the program to `observe` after each one.
+    /// Returns the program together with the warnings the passes raised.
+    pub fn lower_mir_observed(
         &self,
         hir_discourses: Vec<HirDiscourse>,
-    ) -> Result<crate::domain::entities::mir::MirProgram, OnuError> {
+        observe: &mut dyn FnMut(&str, &MirProgram),
+    ) -> Result<(MirProgram, Vec<Diagnostic>), OnuError> {
+        use crate::application::use_cases::accumulator_pass::AccumulatorPass;
         use crate::application::use_cases::idiom_recognizer_pass::IdiomRecognizerPass;
         use crate::application::use_cases::inline_pass::InlinePass;
         use crate::application::use_cases::integer_upgrade_pass::IntegerUpgradePass;
@@ -175,61 +220,95 @@ impl<E: EnvironmentPort, C: CodegenPort> CompilationPipeline<E, C> {
         use crate::application::use_cases::tco_pass::TcoPass;
 
         // Stage 1: Lower HIR → raw MIR (SSA, recursive call structure).
-        let mir_lowering_service = MirLoweringService::new(&self.env, &self.registry);
-        let mir = mir_lowering_service.lower_program(&hir_discourses)?;
-
-        // Stage 2: Automatically promote doubly-recursive pure functions from
-        // I64 to WideInt(bits) when call-site literals imply overflow.
-        // Must run before MemoPass so that the wrapper caches WideInt values,
-        // and before TcoPass so the doubly-recursive call structure is still
-        // visible for candidate detection.
-        let mir = IntegerUpgradePass::run(mir);
-
-        // Stage 3: Memoize recursive pure functions annotated with
+        let mir_lowering_service = MirLoweringService::new(&self.env, &self.registry)
+            .with_checked_arithmetic(self.options.checked_arithmetic)
+            .with_runtime_checks(self.options.runtime_checks);
+        let mut mir = mir_lowering_service.lower_program(&hir_discourses)?;
+
+        // With `--no-hardware-intrinsics`, drop `with known behavior:` tags
+        // so every tagged behavior compiles to its software body alone.
+        if !self.options.hardware_intrinsics {
+            for func in &mut mir.functions {
+                func.known_behavior = None;
+            }
+        }
+        observe("lowering", &mir);
+
+        // Stage 2: Automatically promote recursive pure functions from I64 to
+        // WideInt(bits) when range analysis of their call-site literals proves
+        // overflow.  Must run before MemoPass so that the wrapper caches
+        // WideInt values, and before AccumulatorPass and TcoPass so the
+        // recursive call structure is still visible for candidate detection.
+        let (mir, mut diagnostics) = IntegerUpgradePass::run_with_diagnostics(mir);
+        observe("integer-upgrade", &mir);
+
+        // Stage 3: Carry pending `added-to` / `scales-by` / `joined-with`
+        // work in accumulators so linear recursion like factorial becomes a
+        // loop. Must run before MemoPass, which would otherwise route the
+        // recursive call through the cache wrapper and hide it.
+        let mir = AccumulatorPass::run(mir);
+        observe("accumulator", &mir);
+
+        // Stage 4: Memoize recursive pure functions annotated with
         // `with diminishing:`. Must run BEFORE TcoPass: TcoPass erases
         // tail-recursive Call instructions into Branch loops, so any
         // memoizable call that is also tail-recursive would be missed.
-        let mir = MemoPass::run(mir, &self.registry);
+        // With `--memo-report`, explain each decision now and keep the
+        // wrappers' hit/miss probes so `run` prints the counters at exit.
+        let mir = if self.options.memo_report {
+            for decision in MemoPass::report(&mir, &self.registry) {
+                eprintln!("[onu memo] {}", decision);
+            }
+            MemoPass::run_instrumented(mir, &self.registry)
+        } else {
+            MemoPass::run(mir, &self.registry)
+        };
+        observe("memo", &mir);
 
-        // Stage 4: Loop-lower self-tail-calls.
+        // Stage 5: Loop-lower self-tail-calls.
         // Recursion → loop so the body becomes finite and inlineable.
         // Acts on .inner functions (produced by MemoPass) as well as
         // non-memoized tail-recursive helpers (e.g. collatz-steps).
         let mir = TcoPass::run(mir);
+        observe("tco", &mir);
 
-        // Stage 5: Inline pure loop-shaped callees into their callers.
+        // Stage 6: Inline pure loop-shaped callees into their callers.
         // Now that single-recursive functions are loops, InlinePass can fuse them.
-        let mir = InlinePass::run(mir);
+        let (mir, inline_warnings) = InlinePass::run_with_diagnostics(mir);
+        diagnostics.extend(inline_warnings);
+        observe("inline", &mir);
 
-        // Stage 6: Second TcoPass — catches tail calls exposed by inlining.
+        // Stage 7: Second TcoPass — catches tail calls exposed by inlining.
         let mir = TcoPass::run(mir);
+        observe("tco (after inline)", &mir);
 
-        // Stage 7: Operation Legalization — replace any WideInt (> 128-bit)
+        // Stage 8: Operation Legalization — replace any WideInt (> 128-bit)
         // division or modulo with a call to a compiler-internal helper
         // (__onu_wide_div_N / __onu_wide_mod_N) so the LLVM backend never sees
         // an sdiv/srem on a type wider than i128 (for which no runtime library
         // helper exists).
         use crate::application::use_cases::wide_div_legalization_pass::WideDivLegalizationPass;
         let mir = WideDivLegalizationPass::run(mir);
+        observe("wide-div-legalization", &mir);
 
-        // Stage 8: Idiom Recognition — detect well-known computational patterns
-        // (e.g. rotate-right via shift+or) and replace with LLVM target-independent
-        // intrinsics (llvm.fshr) for single-instruction hardware rotation.
+        // Stage 9: Idiom Recognition — detect well-known computational patterns
+        // (rotations, byte swaps, bit-counting loops, min/max/abs selects) and
+        // replace them with LLVM target-independent intrinsics (llvm.fshr,
+        // llvm.bswap, llvm.ctpop, llvm.smin, ...) that lower to one instruction.
         let mir = IdiomRecognizerPass::run(mir);
+        observe("idiom-recognition", &mir);
 
-        // Stage 9: Lifetime Pass — region-based memory management.
+        // Stage 10: Lifetime Pass — region-based memory management.
         // Inserts SaveArena/RestoreArena scopes for O(1) bulk deallocation
         // and promotes fixed-size arena allocations to stack (alloca) when
         // the buffer doesn't escape the function.
         let mir = LifetimePass::run(mir);
+        observe("lifetime", &mir);
 
-        Ok(mir)
+        Ok((mir, diagnostics))
     }
 
-    pub fn emit_ir(
-        &mut self,
-        mir: crate::domain::entities::mir::MirProgram,
-    ) -> Result<String, OnuError> {
+    pub fn emit_ir(&mut self, mir: MirProgram) -> Result<String, OnuError> {
         self.env.log(LogLevel::Info, "Starting Codegen stage.");
         self.codegen.set_registry(self.registry.clone());
         let ir = self.codegen.generate(&mir)?;
@@ -1,7 +1,11 @@
 use onu_refactor::application::options::{CompilationOptions, CompilerStage, LogLevel};
 use onu_refactor::infrastructure::os::NativeOsEnvironment;
-use onu_refactor::infrastructure::cli::Repl;
+use onu_refactor::application::ports::environment::EnvironmentPort;
+use onu_refactor::infrastructure::cli::{Repl, bench, golden, jit, test_runner};
 use onu_refactor::adapters::codegen::OnuCodegen;
+use onu_refactor::adapters::docgen::{DocFormat, OnuDocGenerator, Reference};
+use onu_refactor::adapters::formatter::OnuFormatter;
+use onu_refactor::application::use_cases::analyzer::lints::{self, Lint, LintConfig};
 use onu_refactor::CompilationPipeline;
 use std::env as std_env;
 
@@ -10,13 +14,66 @@ fn main() {
 
     // REPL mode: `onu --repl`
     if args.get(1).map(|s| s.as_str()) == Some("--repl") {
-        let mut repl = Repl::new();
+        let onu = match std_env::current_exe() {
+            Ok(onu) => onu,
+            Err(e) => {
+                eprintln!("PIPELINE ERROR: cannot locate the onu binary: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let mut repl = Repl::new(onu);
         repl.run();
         return;
     }
 
+    // JIT mode: `onu run <source_file> [args...]`
+    if args.get(1).map(|s| s.as_str()) == Some("run") {
+        let Some(source_file) = args.get(2) else {
+            eprintln!("Usage: {} run <source_file> [args...]", args[0]);
+            std::process::exit(1);
+        };
+        std::process::exit(run_jit(source_file, &args[3..]));
+    }
+
+    // Test mode: `onu test <source_file> [--timeout <secs>]`
+    if args.get(1).map(|s| s.as_str()) == Some("test") {
+        std::process::exit(run_tests(&args[0], &args[2..]));
+    }
+
+    // Golden-output mode: `onu golden <path>... [--update] [--timeout <secs>]`
+    if args.get(1).map(|s| s.as_str()) == Some("golden") {
+        std::process::exit(run_golden(&args[0], &args[2..]));
+    }
+
+    // Benchmark mode: `onu bench [<file.onu>[=<file.c>]...] [options]`
+    if args.get(1).map(|s| s.as_str()) == Some("bench") {
+        std::process::exit(run_bench(&args[0], &args[2..]));
+    }
+
+    // Lint mode: `onu lint <source_file> [--allow <lint>]... [--warn <lint>]...`
+    if args.get(1).map(|s| s.as_str()) == Some("lint") {
+        std::process::exit(run_lint(&args[0], &args[2..]));
+    }
+
+    // Documentation mode: `onu doc <path>... [--format markdown|html] [--out <dir>]`
+    if args.get(1).map(|s| s.as_str()) == Some("doc") {
+        std::process::exit(run_doc(&args[0], &args[2..]));
+    }
+
+    // Formatter mode: `onu fmt <path>... [--check]`
+    if args.get(1).map(|s| s.as_str()) == Some("fmt") {
+        std::process::exit(run_fmt(&args[0], &args[2..]));
+    }
+
     if args.len() < 2 {
-        eprintln!("Usage: {} <source_file> [--stop-after <stage>] [--verbose]", args[0]);
+        eprintln!("Usage: {} <source_file> [--stop-after <stage>] [--verbose] [--checked-arithmetic] [--no-runtime-checks] [--memo-report] [--no-hardware-intrinsics]", args[0]);
+        eprintln!("       {} run <source_file> [args...]", args[0]);
+        eprintln!("       {} test <source_file> [--timeout <secs>]", args[0]);
+        eprintln!("       {} golden <dir_or_file>... [--update] [--timeout <secs>]", args[0]);
+        eprintln!("       {} bench [<file.onu>[=<file.c>]...] [--runs <n>] [--cc <compiler>] [--json <out>] [--baseline <json> [--tolerance <pct>]]", args[0]);
+        eprintln!("       {} fmt <dir_or_file>... [--check]", args[0]);
+        eprintln!("       {} lint <source_file> [--allow <lint>]... [--warn <lint>]...", args[0]);
+        eprintln!("       {} doc <dir_or_file>... [--format markdown|html] [--out <dir>]", args[0]);
         eprintln!("       {} --repl", args[0]);
         std::process::exit(1);
     }
@@ -31,6 +88,14 @@ fn main() {
             i += 1;
         } else if args[i] == "--verbose" {
             options.log_level = LogLevel::Debug;
+        } else if args[i] == "--checked-arithmetic" {
+            options.checked_arithmetic = true;
+        } else if args[i] == "--no-runtime-checks" {
+            options.runtime_checks = false;
+        } else if args[i] == "--memo-report" {
+            options.memo_report = true;
+        } else if args[i] == "--no-hardware-intrinsics" {
+            options.hardware_intrinsics = false;
         }
         i += 1;
     }
@@ -49,3 +114,442 @@ fn main() {
         }
     }
 }
+
+/// Run the tests declared in the file named in `args`; the exit code is 1
+/// if any failed.
+fn run_tests(program: &str, args: &[String]) -> i32 {
+    let usage = || {
+        eprintln!("Usage: {} test <source_file> [--timeout <secs>]", program);
+        1
+    };
+    let mut timeout = test_runner::DEFAULT_TIMEOUT;
+    let mut source_file = None;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--timeout" && i + 1 < args.len() {
+            let Ok(secs) = args[i + 1].parse::<f64>() else { return usage() };
+            timeout = std::time::Duration::from_secs_f64(secs);
+            i += 1;
+        } else if source_file.is_none() && !args[i].starts_with("--") {
+            source_file = Some(args[i].as_str());
+        } else {
+            return usage();
+        }
+        i += 1;
+    }
+    let Some(source_file) = source_file else { return usage() };
+
+    let onu = match std_env::current_exe() {
+        Ok(onu) => onu,
+        Err(e) => {
+            eprintln!("PIPELINE ERROR: cannot locate the onu binary: {}", e);
+            return 1;
+        }
+    };
+    match test_runner::run_file(source_file, &onu, timeout) {
+        Ok(results) if results.iter().all(|r| r.outcome == test_runner::TestOutcome::Passed) => 0,
+        Ok(_) => 1,
+        Err(e) => {
+            eprintln!("PIPELINE ERROR: {:?}", e);
+            1
+        }
+    }
+}
+
+/// Run the lints over one program; `--allow` turns a lint off and `--warn`
+/// back on, with `all` standing for every lint.  Exits with 1 if any lint
+/// fired.
+fn run_lint(program: &str, args: &[String]) -> i32 {
+    let usage = || {
+        eprintln!("Usage: {} lint <source_file> [--allow <lint>]... [--warn <lint>]...", program);
+        let names: Vec<&str> = Lint::ALL.iter().map(Lint::name).collect();
+        eprintln!("Lints: all, {}", names.join(", "));
+        1
+    };
+    let mut config = LintConfig::default();
+    let mut source_file = None;
+    let mut i = 0;
+    while i < args.len() {
+        if (args[i] == "--allow" || args[i] == "--warn") && i + 1 < args.len() {
+            let lints: Vec<Lint> = match args[i + 1].as_str() {
+                "all" => Lint::ALL.to_vec(),
+                name => match Lint::from_name(name) {
+                    Some(lint) => vec![lint],
+                    None => return usage(),
+                },
+            };
+            for lint in lints {
+                if args[i] == "--allow" { config.allow(lint) } else { config.warn(lint) }
+            }
+            i += 1;
+        } else if source_file.is_none() && !args[i].starts_with("--") {
+            source_file = Some(&args[i]);
+        } else {
+            return usage();
+        }
+        i += 1;
+    }
+    let Some(source_file) = source_file else { return usage() };
+
+    let options = CompilationOptions { log_level: LogLevel::Error, ..CompilationOptions::default() };
+    let env = NativeOsEnvironment::new(options.log_level);
+    let source = match env.read_file(source_file) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("PIPELINE ERROR: {:?}", e);
+            return 1;
+        }
+    };
+    let lexer = Box::new(onu_refactor::adapters::lexer::OnuLexer::new(options.log_level));
+    let parser = Box::new(onu_refactor::adapters::parser::OnuParser::new(options.log_level));
+    let mut pipeline = CompilationPipeline::new(env, OnuCodegen::new(), lexer, parser, options);
+    let hir = pipeline.lex(&source).and_then(|tokens| {
+        pipeline.scan_headers(&tokens)?;
+        let discourses = pipeline.parse(tokens)?;
+        pipeline.lower_hir(discourses)
+    });
+    let hir = match hir {
+        Ok(hir) => hir,
+        Err(e) => {
+            eprintln!("PIPELINE ERROR: {:?}", e);
+            return 1;
+        }
+    };
+
+    let findings = lints::run(&hir, &config);
+    for finding in &findings {
+        println!("warning[{}]: {}", finding.lint.name(), finding.diagnostic.message);
+        println!("  --> {}", source_file);
+        if let Some(hint) = &finding.diagnostic.actionable_hint {
+            println!("  = hint: {}", hint);
+        }
+    }
+    if findings.is_empty() {
+        0
+    } else {
+        println!("\n{} warning{} in {}", findings.len(), if findings.len() == 1 { "" } else { "s" }, source_file);
+        1
+    }
+}
+
+/// Rewrite every program named in `args` in the canonical layout, or with
+/// `--check` only report the ones that are not in it.
+fn run_fmt(program: &str, args: &[String]) -> i32 {
+    let check = args.iter().any(|a| a == "--check");
+    let paths: Vec<std::path::PathBuf> =
+        args.iter().filter(|a| *a != "--check").map(std::path::PathBuf::from).collect();
+    if paths.is_empty() {
+        eprintln!("Usage: {} fmt <dir_or_file>... [--check]", program);
+        return 1;
+    }
+
+    let Some(files) = onu_files(paths) else { return 1 };
+
+    let env = NativeOsEnvironment::new(LogLevel::Error);
+    let formatter = OnuFormatter::new(LogLevel::Error);
+    let mut status = 0;
+    for file in files {
+        let name = file.display().to_string();
+        let formatted = env.read_file(&name).and_then(|source| {
+            formatter.format(&source).map(|formatted| (formatted != source, formatted))
+        });
+        match formatted {
+            Ok((false, _)) => {}
+            Ok((true, _)) if check => {
+                println!("would reformat {}", name);
+                status = 1;
+            }
+            Ok((true, formatted)) => {
+                if let Err(e) = env.write_file(&name, &formatted) {
+                    eprintln!("PIPELINE ERROR: {:?}", e);
+                    status = 1;
+                } else {
+                    println!("formatted {}", name);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}: PIPELINE ERROR: {:?}", name, e);
+                status = 1;
+            }
+        }
+    }
+    status
+}
+
+/// `paths`, with each directory replaced by the `.onu` files in it.
+fn onu_files(paths: Vec<std::path::PathBuf>) -> Option<Vec<std::path::PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let Ok(entries) = std::fs::read_dir(&path) else {
+                eprintln!("PIPELINE ERROR: cannot read directory {}", path.display());
+                return None;
+            };
+            let mut found: Vec<_> = entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "onu"))
+                .collect();
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(path);
+        }
+    }
+    Some(files)
+}
+
+/// Write a reference page per module of the programs in `args`, or print
+/// them when no `--out` directory is given.
+fn run_doc(program: &str, args: &[String]) -> i32 {
+    let usage = || {
+        eprintln!("Usage: {} doc <dir_or_file>... [--format markdown|html] [--out <dir>]", program);
+        1
+    };
+    let mut format = DocFormat::Markdown;
+    let mut out_dir = None;
+    let mut paths = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--format" && i + 1 < args.len() {
+            let Some(f) = DocFormat::from_name(&args[i + 1]) else { return usage() };
+            format = f;
+            i += 1;
+        } else if args[i] == "--out" && i + 1 < args.len() {
+            out_dir = Some(std::path::PathBuf::from(&args[i + 1]));
+            i += 1;
+        } else if args[i].starts_with("--") {
+            return usage();
+        } else {
+            paths.push(std::path::PathBuf::from(&args[i]));
+        }
+        i += 1;
+    }
+    if paths.is_empty() {
+        return usage();
+    }
+    let Some(files) = onu_files(paths) else { return 1 };
+
+    let env = NativeOsEnvironment::new(LogLevel::Error);
+    let generator = OnuDocGenerator::new(LogLevel::Error);
+    // Each file is a program of its own, so links stay within its modules.
+    let mut programs = Vec::new();
+    let mut seen = std::collections::BTreeSet::new();
+    for file in files {
+        let name = file.display().to_string();
+        let stem = file.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        match env.read_file(&name).and_then(|source| generator.extract(&source, &stem)) {
+            Ok(modules) => {
+                if let Some(module) = modules.iter().find(|m| !seen.insert(m.name.clone())) {
+                    eprintln!("{}: PIPELINE ERROR: module {} is documented twice", name, module.name);
+                    return 1;
+                }
+                programs.push(modules);
+            }
+            Err(e) => {
+                eprintln!("{}: PIPELINE ERROR: {:?}", name, e);
+                return 1;
+            }
+        }
+    }
+
+    let mut pages = Vec::new();
+    for modules in &programs {
+        let reference = Reference::new(modules, format);
+        pages.extend(modules.iter().map(|m| (reference.file_name(m), reference.page(m))));
+    }
+    let Some(out_dir) = out_dir else {
+        for (_, page) in pages {
+            print!("{}", page);
+        }
+        return 0;
+    };
+    if let Err(e) = std::fs::create_dir_all(&out_dir) {
+        eprintln!("PIPELINE ERROR: cannot create {}: {}", out_dir.display(), e);
+        return 1;
+    }
+    let all: Vec<_> = programs.into_iter().flatten().collect();
+    pages.push((format!("index.{}", format.extension()), Reference::new(&all, format).index()));
+    for (file_name, page) in pages {
+        let path = out_dir.join(file_name).display().to_string();
+        if let Err(e) = env.write_file(&path, &page) {
+            eprintln!("PIPELINE ERROR: {:?}", e);
+            return 1;
+        }
+        println!("wrote {}", path);
+    }
+    0
+}
+
+/// Check (or with `--update`, bless) the golden output of every program
+/// named in `args`; a directory stands for the `.onu` files in it.
+fn run_golden(program: &str, args: &[String]) -> i32 {
+    let usage = || {
+        eprintln!("Usage: {} golden <dir_or_file>... [--update] [--timeout <secs>]", program);
+        1
+    };
+    let mut options = golden::GoldenOptions::default();
+    let mut paths = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--update" {
+            options.update = true;
+        } else if args[i] == "--timeout" && i + 1 < args.len() {
+            let Ok(secs) = args[i + 1].parse::<f64>() else { return usage() };
+            options.timeout = std::time::Duration::from_secs_f64(secs);
+            i += 1;
+        } else {
+            paths.push(std::path::PathBuf::from(&args[i]));
+        }
+        i += 1;
+    }
+    if paths.is_empty() {
+        return usage();
+    }
+
+    let mut cases = Vec::new();
+    for path in &paths {
+        let loaded = if path.is_dir() {
+            golden::discover(path)
+        } else {
+            golden::GoldenCase::load(path).map(|case| vec![case])
+        };
+        match loaded {
+            Ok(loaded) => cases.extend(loaded),
+            Err(e) => {
+                eprintln!("PIPELINE ERROR: {:?}", e);
+                return 1;
+            }
+        }
+    }
+
+    let onu = match std_env::current_exe() {
+        Ok(onu) => onu,
+        Err(e) => {
+            eprintln!("PIPELINE ERROR: cannot locate the onu binary: {}", e);
+            return 1;
+        }
+    };
+    let outcomes = golden::run_all(&cases, &onu, &options);
+    if outcomes.iter().any(golden::GoldenOutcome::is_failure) { 1 } else { 0 }
+}
+
+/// Time Ọ̀nụ programs against their C twins (the bundled pairs if none are
+/// named); the exit code is 1 if a build failed or a benchmark regressed.
+fn run_bench(program: &str, args: &[String]) -> i32 {
+    let usage = || {
+        eprintln!(
+            "Usage: {} bench [<file.onu>[=<file.c>]...] [--runs <n>] [--cc <compiler>] [--json <out>] [--baseline <json> [--tolerance <pct>]]",
+            program
+        );
+        1
+    };
+    let mut options = bench::BenchOptions::default();
+    let mut cases = Vec::new();
+    let mut json_path = None;
+    let mut baseline_path = None;
+    let mut tolerance = 10.0;
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        match (args[i].as_str(), value) {
+            ("--runs", Some(v)) => match v.parse() {
+                Ok(runs) if runs > 0 => options.runs = runs,
+                _ => return usage(),
+            },
+            ("--cc", Some(v)) => options.cc = v.clone(),
+            ("--json", Some(v)) => json_path = Some(v.clone()),
+            ("--baseline", Some(v)) => baseline_path = Some(v.clone()),
+            ("--tolerance", Some(v)) => match v.parse() {
+                Ok(pct) => tolerance = pct,
+                Err(_) => return usage(),
+            },
+            (flag, _) if flag.starts_with("--") => return usage(),
+            (spec, _) => {
+                cases.push(bench::BenchCase::parse(spec));
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+    if cases.is_empty() {
+        cases = bench::default_cases();
+    }
+
+    let results = bench::run_all(&cases, &options);
+    let _ = std::fs::remove_dir_all(&options.build_dir);
+    let mut failed = results.iter().any(bench::BenchResult::is_failure);
+
+    if let Some(path) = json_path {
+        if let Err(e) = std::fs::write(&path, bench::to_json(&results)) {
+            eprintln!("PIPELINE ERROR: could not write {}: {}", path, e);
+            return 1;
+        }
+        println!("results written to {}", path);
+    }
+    if let Some(path) = baseline_path {
+        let baseline = match std::fs::read_to_string(&path) {
+            Ok(json) => bench::parse_baseline(&json),
+            Err(e) => {
+                eprintln!("PIPELINE ERROR: could not read {}: {}", path, e);
+                return 1;
+            }
+        };
+        let regressions = bench::regressions(&results, &baseline, tolerance / 100.0);
+        if regressions.is_empty() {
+            println!("no regressions against {} (tolerance {}%)", path, tolerance);
+        } else {
+            println!("regressions against {} (tolerance {}%):", path, tolerance);
+            for regression in &regressions {
+                println!("  {}", regression);
+            }
+            failed = true;
+        }
+    }
+    if failed { 1 } else { 0 }
+}
+
+/// Compile `source_file` in memory and JIT-execute its `run` with
+/// `program_args`, returning the exit code to propagate.  A `.ll` file is
+/// taken to be already-compiled LLVM IR and executed as it is.
+fn run_jit(source_file: &str, program_args: &[String]) -> i32 {
+    let options = CompilationOptions { log_level: LogLevel::Error, ..CompilationOptions::default() };
+    let env = NativeOsEnvironment::new(options.log_level);
+    let source = match env.read_file(source_file) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("PIPELINE ERROR: {:?}", e);
+            return 1;
+        }
+    };
+    if source_file.ends_with(".ll") {
+        return execute(&source, source_file, program_args);
+    }
+
+    let lexer = Box::new(onu_refactor::adapters::lexer::OnuLexer::new(options.log_level));
+    let parser = Box::new(onu_refactor::adapters::parser::OnuParser::new(options.log_level));
+    let mut pipeline = CompilationPipeline::new(env, OnuCodegen::new(), lexer, parser, options);
+
+    let compiled = match pipeline.compile_source(&source) {
+        Ok(compiled) => compiled,
+        Err(e) => {
+            eprintln!("PIPELINE ERROR: {:?}", e);
+            return 1;
+        }
+    };
+    for d in &compiled.diagnostics {
+        eprintln!("[onu warning] {}", d.message);
+    }
+
+    execute(&compiled.ir.unwrap_or_default(), source_file, program_args)
+}
+
+fn execute(ir: &str, source_file: &str, program_args: &[String]) -> i32 {
+    match jit::run_program(ir, source_file, program_args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("JIT ERROR: {:?}", e);
+            1
+        }
+    }
+}
@@ -0,0 +1,88 @@
+/// Accumulator Introduction Tests: Application Use Case Layer
+///
+/// `AccumulatorPass` turns recursion whose pending work is `added-to`,
+/// `scales-by` or `joined-with` into a loop with accumulator slots, so the
+/// textbook recursive forms run in constant stack.
+mod common;
+
+use common::{compile_ir, compile_mir, program_with_run, run_ir};
+use onu_refactor::application::options::CompilationOptions;
+use onu_refactor::domain::entities::mir::{MirInstruction, MirProgram};
+
+fn run(source: &str) -> i32 {
+    run_ir(&compile_ir(source, CompilationOptions::default()))
+}
+
+fn self_calls(program: &MirProgram, name: &str) -> usize {
+    program
+        .functions
+        .iter()
+        .filter(|f| f.name == name)
+        .flat_map(|f| f.blocks.iter())
+        .flat_map(|b| b.instructions.iter())
+        .filter(|inst| matches!(inst, MirInstruction::Call { name: callee, .. } if callee == name))
+        .count()
+}
+
+fn program(behavior: &str, delivers: &str, check: &str) -> String {
+    let step = format!(
+        "the behavior called step\n    with intent: recurse once per count\n    takes:\n        an integer called n\n    \
+delivers: {}\n    as:\n        {}\n",
+        delivers, behavior
+    );
+    program_with_run(&step, &[check])
+}
+
+const FACTORIAL_PROGRAM: &str = "the module called Factorial with concern: memoized product\n\n\
+the behavior called factorial\n    with intent: multiply a descending sequence\n    takes:\n        \
+an integer called n\n    delivers: an integer\n    with diminishing: n\n    as:\n        \
+if n matches 0\n            then 1\n            else\n                \
+derivation: previous derives-from an integer n decreased-by 1\n                \
+derivation: rest derives-from previous utilizes factorial\n                \
+n scales-by rest\n\n\
+the effect behavior called run\n    with intent: multiply up to twenty\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        (20 utilizes factorial) matches 2432902008176640000\n";
+
+#[test]
+fn test_factorial_becomes_a_loop() {
+    let mir = compile_mir(FACTORIAL_PROGRAM, CompilationOptions::default());
+    assert_eq!(self_calls(&mir, "factorial.inner"), 0);
+    assert_eq!(run(FACTORIAL_PROGRAM), 1);
+}
+
+#[test]
+fn test_deep_sum_runs_in_constant_stack() {
+    let source = program(
+        "if n matches 0 then 0 else n added-to ((n decreased-by 1) utilizes step)",
+        "an integer",
+        "(1000000 utilizes step) matches 500000500000",
+    );
+    assert_eq!(self_calls(&compile_mir(&source, CompilationOptions::default()), "step"), 0);
+    assert_eq!(run(&source), 1);
+}
+
+#[test]
+fn test_joined_with_keeps_operand_order() {
+    // Left-growing while n < 3, right-growing above: 5 gives "<<|>>>".
+    let source = program(
+        "if n matches 0\n            then \"|\"\n            \
+else if n falls-short-of 3\n                then \"<\" joined-with ((n decreased-by 1) utilizes step)\n                \
+else ((n decreased-by 1) utilizes step) joined-with \">\"",
+        "a string",
+        "(((5 utilizes step) utilizes len) scales-by 1000) added-to \
+((((5 utilizes step) char-at 1) scales-by 10) added-to ((5 utilizes step) char-at 2))",
+    );
+    assert_eq!(self_calls(&compile_mir(&source, CompilationOptions::default()), "step"), 0);
+    assert_eq!(run(&source), 6724);
+}
+
+#[test]
+fn test_nested_recursion_is_left_alone() {
+    let source = program(
+        "if n falls-short-of 2 then n else ((n decreased-by 1) utilizes step) added-to ((n decreased-by 2) utilizes step)",
+        "an integer",
+        "10 utilizes step",
+    );
+    assert_eq!(self_calls(&compile_mir(&source, CompilationOptions::default()), "step"), 2);
+    assert_eq!(run(&source), 55);
+}
@@ -0,0 +1,65 @@
+/// Benchmark Command Tests: Infrastructure CLI Layer
+///
+/// `onu bench` builds an Ọ̀nụ program and its C twin, times both, and can
+/// write the results as JSON and check them against an earlier run.  These
+/// tests drive the built binary on a tiny pair so they stay fast.
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn scratch_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("onu_bench_cmd_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn onu_bench(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_onu_refactor"))
+        .arg("bench")
+        .args(args)
+        .output()
+        .expect("Failed to run onu")
+}
+
+#[test]
+fn test_bench_times_onu_and_c_and_checks_a_baseline() {
+    let dir = scratch_dir();
+    let onu = dir.join("tiny.onu");
+    let c = dir.join("tiny.c");
+    std::fs::write(
+        &onu,
+        "the module called Tiny with concern: benchmarking
+
+the effect behavior called run
+    with intent: do almost nothing
+    takes: nothing
+    delivers: nothing
+    as:
+        broadcasts \"done\"
+",
+    )
+    .unwrap();
+    std::fs::write(&c, "#include <stdio.h>\nint main(void) { puts(\"done\"); return 0; }\n").unwrap();
+    let spec = format!("{}={}", onu.display(), c.display());
+    let json = dir.join("results.json");
+
+    let output = onu_bench(&[&spec, "--runs", "2", "--json", json.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}{}", stdout, String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("benchmarking 1 program (2 runs each)"), "{}", stdout);
+    assert!(stdout.lines().any(|l| l.starts_with("tiny ") && l.ends_with('x')), "{}", stdout);
+
+    let results = std::fs::read_to_string(&json).unwrap();
+    assert!(results.contains("\"name\": \"tiny\""), "{}", results);
+    assert!(results.contains("\"onu_median_ms\": ") && !results.contains("null"), "{}", results);
+
+    // A baseline with an impossibly fast median is a regression.
+    let baseline = dir.join("baseline.json");
+    std::fs::write(&baseline, "[\n  {\"name\": \"tiny\", \"onu_median_ms\": 0.0001}\n]\n").unwrap();
+    let output = onu_bench(&[&spec, "--runs", "2", "--baseline", baseline.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("regressions against"), "{}", stdout);
+    assert!(stdout.contains("tiny: 0.00ms -> "), "{}", stdout);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,106 @@
+/// Big-Integer Tests: Runtime-Sized Results
+///
+/// `big-integer` values carry their own limb count, so a result sized by a
+/// value read at run time — `fib n` for an `n` from the command line — prints
+/// exactly instead of wrapping.
+mod common;
+
+use common::{compile_ir, printed, program_with_run};
+use onu_refactor::application::options::CompilationOptions;
+use std::process::Command;
+
+const FIB: &str = "the behavior called fib\n    with intent: add the two before\n    \
+with diminishing: n\n    takes:\n        an integer called n\n    delivers: a big-integer\n    as:\n        \
+if n matches 0\n            then 0\n            else if n matches 1\n                then 1\n                \
+else ((n decreased-by 1) utilizes fib) added-to ((n decreased-by 2) utilizes fib)\n";
+
+const TWICE: &str = "the behavior called twice\n    with intent: double a big value\n    takes:\n        \
+a big-integer called x\n    delivers: a big-integer\n    as:\n        x added-to x\n";
+
+fn fib(n: usize) -> String {
+    // Decimal addition on digit strings keeps the expectation independent
+    // of the code under test.
+    let (mut a, mut b) = (vec![0u8], vec![1u8]);
+    for _ in 0..n {
+        let mut sum = Vec::new();
+        let mut carry = 0;
+        for i in 0..a.len().max(b.len()) {
+            let d = a.get(i).unwrap_or(&0) + b.get(i).unwrap_or(&0) + carry;
+            sum.push(d % 10);
+            carry = d / 10;
+        }
+        if carry > 0 {
+            sum.push(carry);
+        }
+        a = std::mem::replace(&mut b, sum);
+    }
+    a.iter().rev().map(|d| char::from(b'0' + d)).collect()
+}
+
+#[test]
+fn test_fibonacci_with_literal_argument() {
+    let source = program_with_run(FIB, &[
+        "derivation: result derives-from a big-integer 200 utilizes fib",
+        "derivation: shown derives-from nothing broadcasts (result utilizes as-text)",
+        "0",
+    ]);
+    assert_eq!(printed(&source, "big_fib_200"), fib(200));
+}
+
+#[test]
+fn test_fibonacci_of_runtime_argument() {
+    let source = program_with_run(FIB, &[
+        "derivation: raw derives-from 1 utilizes receives-argument",
+        "derivation: n derives-from an integer raw utilizes as-integer",
+        "derivation: result derives-from a big-integer n utilizes fib",
+        "derivation: shown derives-from nothing broadcasts (result utilizes as-text)",
+        "0",
+    ]);
+    let path = std::env::temp_dir().join(format!("onu_big_fib_arg_{}.ll", std::process::id()));
+    std::fs::write(&path, compile_ir(&source, CompilationOptions::default())).unwrap();
+    for n in [5, 93, 1000] {
+        let output = Command::new("lli").arg(&path).arg(n.to_string()).output().expect("Failed to run lli");
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), fib(n), "fib {}", n);
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_signed_arithmetic_and_comparison() {
+    let source = program_with_run(TWICE, &[
+        "derivation: a derives-from a big-integer 0 decreased-by 7",
+        "derivation: b derives-from a big-integer 7",
+        "derivation: s1 derives-from nothing broadcasts ((a partitions-by 2) utilizes as-text)",
+        "derivation: s2 derives-from nothing broadcasts ((b partitions-by (0 decreased-by 2)) utilizes as-text)",
+        "derivation: s3 derives-from nothing broadcasts ((b matches 7) utilizes as-text)",
+        "derivation: s4 derives-from nothing broadcasts ((a falls-short-of b) utilizes as-text)",
+        "derivation: s5 derives-from nothing broadcasts ((a added-to b) utilizes as-text)",
+        "derivation: s6 derives-from nothing broadcasts ((21 utilizes twice) utilizes as-text)",
+        "0",
+    ]);
+    assert_eq!(printed(&source, "big_signed").lines().collect::<Vec<_>>(), vec!["-3", "-3", "1", "1", "0", "42"]);
+}
+
+#[test]
+fn test_products_and_quotients_past_128_bits() {
+    let source = program_with_run(TWICE, &[
+        "derivation: m derives-from a big-integer (0 decreased-by 9223372036854775807) decreased-by 1",
+        "derivation: cube derives-from a big-integer (m scales-by m) scales-by m",
+        "derivation: s1 derives-from nothing broadcasts (m utilizes as-text)",
+        "derivation: s2 derives-from nothing broadcasts ((m scales-by m) utilizes as-text)",
+        "derivation: s3 derives-from nothing broadcasts ((cube partitions-by m) utilizes as-text)",
+        "derivation: s4 derives-from nothing broadcasts ((cube partitions-by 1000000007) utilizes as-text)",
+        "derivation: s5 derives-from nothing broadcasts ((cube exceeds m) utilizes as-text)",
+        "0",
+    ]);
+    assert_eq!(
+        printed(&source, "big_products").lines().collect::<Vec<_>>(),
+        vec![
+            "-9223372036854775808",
+            "85070591730234615865843651857942052864",
+            "85070591730234615865843651857942052864",
+            "-784637711430871115463375869657327214411503929677",
+            "0",
+        ]
+    );
+}
@@ -0,0 +1,92 @@
+/// Checked Arithmetic Tests: Codegen Adapter Layer
+///
+/// Covers the `--checked-arithmetic` compile mode (overflow intrinsics plus a
+/// located trap) and the explicit `wrapping-added-to` / `saturating-added-to`
+/// operators.
+mod common;
+
+use common::{compile_ir, compile_mir, probe_source, run_expression, run_expression_with, run_ir_isolated};
+use onu_refactor::application::options::CompilationOptions;
+use onu_refactor::domain::entities::mir::{MirBinOp, MirInstruction, MirProgram};
+
+const I64_MAX: &str = "9223372036854775807";
+
+fn checked() -> CompilationOptions {
+    CompilationOptions { checked_arithmetic: true, ..CompilationOptions::default() }
+}
+
+const OVERFLOWING_PROGRAM: &str = "the module called Overflow with concern: checked arithmetic\n\n\
+the behavior called grow\n    with intent: double a value\n    takes:\n        an integer called n\n    \
+delivers: an integer\n    as:\n        n scales-by 2\n\n\
+the effect behavior called run\n    with intent: overflow on purpose\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        9223372036854775807 utilizes grow\n";
+
+#[test]
+fn test_wrapping_added_to_wraps_around() {
+    let expr = format!("({} wrapping-added-to 1) falls-short-of 0", I64_MAX);
+    assert_eq!(run_expression(&expr), 1);
+    assert_eq!(run_expression("40 wrapping-added-to 2"), 42);
+}
+
+#[test]
+fn test_saturating_added_to_clamps() {
+    let expr = format!("({} saturating-added-to 5) matches {}", I64_MAX, I64_MAX);
+    assert_eq!(run_expression(&expr), 1);
+    assert_eq!(run_expression("40 saturating-added-to 2"), 42);
+}
+
+fn checked_sites(program: &MirProgram) -> Vec<(MirBinOp, String)> {
+    program
+        .functions
+        .iter()
+        .flat_map(|f| f.blocks.iter())
+        .flat_map(|b| b.instructions.iter())
+        .filter_map(|inst| match inst {
+            MirInstruction::CheckedArithmetic { op, site, .. } => Some((op.clone(), site.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_checked_mode_lowers_to_checked_arithmetic() {
+    let mir = compile_mir(OVERFLOWING_PROGRAM, checked());
+    let sites = checked_sites(&mir);
+    assert!(!sites.is_empty());
+    // Copies inlined into `run` still report the behavior the code was written in.
+    assert!(sites.iter().all(|site| *site == (MirBinOp::Mul, "`grow` (line 3)".to_string())));
+
+    let unchecked = compile_mir(OVERFLOWING_PROGRAM, CompilationOptions::default());
+    assert!(checked_sites(&unchecked).is_empty());
+}
+
+#[test]
+fn test_checked_mode_emits_trap_helper() {
+    let ir = compile_ir(OVERFLOWING_PROGRAM, checked());
+    assert!(ir.contains("@__onu_trap"), "IR:\n{}", ir);
+    let unchecked = compile_ir(OVERFLOWING_PROGRAM, CompilationOptions::default());
+    assert!(!unchecked.contains("@__onu_trap"));
+}
+
+#[test]
+fn test_checked_mode_preserves_results_without_overflow() {
+    assert_eq!(run_expression_with("(6 scales-by 7) decreased-by (1 added-to 1)", checked()), 40);
+}
+
+#[test]
+fn test_checked_overflow_traps_with_location() {
+    let output = run_ir_isolated(&compile_ir(OVERFLOWING_PROGRAM, checked()), "checked_overflow");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("integer overflow in `scales-by` within `grow` (line 3)"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_wrapping_add_is_never_checked() {
+    let expr = format!("({} wrapping-added-to 1) falls-short-of 0", I64_MAX);
+    assert!(checked_sites(&compile_mir(&probe_source(&expr), checked())).is_empty());
+}
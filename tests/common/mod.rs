@@ -0,0 +1,135 @@
+//! Shared helpers for the stdlib and runtime-check integration tests.
+//!
+//! Snippets are compiled through the full pipeline and the resulting `main`
+//! is run in-process via Inkwell's JIT, so no external toolchain is required.
+//! Programs that are expected to terminate the process (runtime traps) are
+//! run out-of-process through `lli` instead.
+#![allow(dead_code)]
+
+use inkwell::OptimizationLevel;
+use inkwell::context::Context;
+use inkwell::memory_buffer::MemoryBuffer;
+use onu_refactor::CompilationPipeline;
+use onu_refactor::adapters::codegen::OnuCodegen;
+use onu_refactor::application::options::{CompilationOptions, CompilerStage};
+use onu_refactor::domain::entities::error::OnuError;
+use onu_refactor::domain::entities::mir::{MirInstruction, MirOperand, MirProgram, MirTerminator};
+use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::infrastructure::os::NativeOsEnvironment;
+use std::process::{Command, Output};
+
+/// Wrap `expr` as the body of an effect behavior `run` delivering an integer.
+pub fn probe_source(expr: &str) -> String {
+    format!(
+        "the module called StdlibProbe with concern: stdlib behaviour\n\n\
+         the effect behavior called run\n    with intent: evaluate one expression\n    \
+         takes: nothing\n    delivers: an integer\n    as:\n        {}\n",
+        expr
+    )
+}
+
+/// Wrap `behaviors` in a module whose effect `run` delivers an integer,
+/// with `run_lines` as its body, one per line.
+pub fn program_with_run(behaviors: &str, run_lines: &[&str]) -> String {
+    let body: String = run_lines.iter().map(|l| format!("        {}\n", l)).collect();
+    format!(
+        "the module called Probe with concern: compiled behaviour\n\n{}\n\
+         the effect behavior called run\n    with intent: exercise the behaviors\n    \
+         takes: nothing\n    delivers: an integer\n    as:\n{}",
+        behaviors, body
+    )
+}
+
+fn pipeline(options: CompilationOptions) -> CompilationPipeline<NativeOsEnvironment, OnuCodegen> {
+    let env = NativeOsEnvironment::new(options.log_level);
+    let lexer = Box::new(onu_refactor::adapters::lexer::OnuLexer::new(options.log_level));
+    let parser = Box::new(onu_refactor::adapters::parser::OnuParser::new(options.log_level));
+    CompilationPipeline::new(env, OnuCodegen::new(), lexer, parser, options)
+}
+
+/// Run the safety rules on `source`, returning the first hard violation.
+pub fn safety_check(source: &str) -> Result<(), OnuError> {
+    let options = CompilationOptions { stop_after: Some(CompilerStage::Analysis), ..CompilationOptions::default() };
+    pipeline(options).compile_source(source).map(|_| ())
+}
+
+/// Compile a whole program to optimized MIR.
+pub fn compile_mir(source: &str, options: CompilationOptions) -> MirProgram {
+    let options = CompilationOptions { stop_after: Some(CompilerStage::Mir), ..options };
+    let compiled = pipeline(options).compile_source(source).expect("Compilation failed");
+    compiled.mir.expect("MIR lowering skipped")
+}
+
+/// Compile a whole program to MIR and return the messages of the warnings
+/// the pipeline raised.
+pub fn warnings(source: &str) -> Vec<String> {
+    let options = CompilationOptions { stop_after: Some(CompilerStage::Mir), ..CompilationOptions::default() };
+    let compiled = pipeline(options).compile_source(source).expect("Compilation failed");
+    compiled.diagnostics.into_iter().map(|d| d.message).collect()
+}
+
+/// The type of the value `run` delivers, from the instruction that defines
+/// it in the optimized MIR of `probe_source(expr)`.
+pub fn delivered_type(expr: &str) -> Option<OnuType> {
+    let mir = compile_mir(&probe_source(expr), CompilationOptions::default());
+    let run = mir.functions.into_iter().find(|f| f.name == "run")?;
+    let returned = run.blocks.iter().find_map(|b| match &b.terminator {
+        MirTerminator::Return(MirOperand::Variable(ssa, _)) => Some(*ssa),
+        _ => None,
+    })?;
+    run.blocks.iter().flat_map(|b| &b.instructions).find_map(|inst| match inst {
+        MirInstruction::BinaryOperation { dest, dest_type, .. } if *dest == returned => Some(dest_type.clone()),
+        _ => None,
+    })
+}
+
+/// Compile a whole program to LLVM IR text.
+pub fn compile_ir(source: &str, options: CompilationOptions) -> String {
+    let compiled = pipeline(options).compile_source(source).expect("Compilation failed");
+    compiled.ir.expect("IR emission skipped")
+}
+
+/// JIT `ir` in-process and return the value delivered by `main`.
+pub fn run_ir(ir: &str) -> i32 {
+    let context = Context::create();
+    let buf = MemoryBuffer::create_from_memory_range_copy(ir.as_bytes(), "stdlib_probe");
+    let module = context.create_module_from_ir(buf).expect("IR parse failed");
+    let ee = module
+        .create_jit_execution_engine(OptimizationLevel::None)
+        .expect("JIT creation failed");
+    unsafe {
+        ee.get_function::<unsafe extern "C" fn() -> i32>("main")
+            .expect("main not found")
+            .call()
+    }
+}
+
+/// Compile `expr` as the body of `run` and return the process exit code.
+pub fn run_expression(expr: &str) -> i32 {
+    run_expression_with(expr, CompilationOptions::default())
+}
+
+pub fn run_expression_with(expr: &str, options: CompilationOptions) -> i32 {
+    run_ir(&compile_ir(&probe_source(expr), options))
+}
+
+/// Run `ir` in a separate `lli` process so a trap's `exit` cannot take the
+/// test harness down with it.
+pub fn run_ir_isolated(ir: &str, name: &str) -> Output {
+    let path = std::env::temp_dir().join(format!("onu_{}_{}.ll", name, std::process::id()));
+    std::fs::write(&path, ir).expect("Failed to write IR");
+    let output = Command::new("lli")
+        .arg(&path)
+        .output()
+        .expect("Failed to run lli");
+    let _ = std::fs::remove_file(&path);
+    output
+}
+
+/// Run `source` through `lli`, require a clean exit, and return what it
+/// printed without the trailing newline.
+pub fn printed(source: &str, name: &str) -> String {
+    let output = run_ir_isolated(&compile_ir(source, CompilationOptions::default()), name);
+    assert_eq!(output.status.code(), Some(0), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).trim_end().to_string()
+}
@@ -0,0 +1,104 @@
+/// Doc Tests: Infrastructure CLI Layer
+///
+/// `onu doc` writes one reference page per module, plus an index, from the
+/// intent and concern prose of the programs it is given.  These tests drive
+/// the built binary over a directory of programs and check the pages it
+/// writes, their cross-links, and the HTML output.
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const COUNTING: &str = "the module called Counting with concern: numbers, one after another
+
+the behavior called fib
+    with intent: the n-th Fibonacci number
+    takes:
+        an integer called n
+    delivers: an integer
+    with diminishing: n
+    as:
+        if n exceeds 1
+        then ((n decreased-by 1) utilizes fib) added-to ((n decreased-by 2) utilizes fib)
+        else n
+
+the effect behavior called run
+    with intent: print fib of 10
+    takes: nothing
+    delivers: nothing
+    as:
+        derivation: x derives-from an integer 10 utilizes fib
+        broadcasts (x utilizes as-text)
+";
+
+const GREETING: &str = "the module called Greeting with concern: saying hello
+
+the shape called Name
+    with intent: who to greet
+    takes:
+        a string called given
+
+the effect behavior called run
+    with intent: greet the world
+    takes: nothing
+    delivers: nothing
+    as:
+        broadcasts \"hello\"
+";
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("onu_doc_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("src")).expect("Failed to create scratch dir");
+    std::fs::write(dir.join("src/counting.onu"), COUNTING).expect("Failed to write source");
+    std::fs::write(dir.join("src/greeting.onu"), GREETING).expect("Failed to write source");
+    dir
+}
+
+fn onu_doc(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_onu_refactor")).arg("doc").args(args).output().expect("Failed to run onu")
+}
+
+#[test]
+fn test_markdown_pages_and_index_are_written() {
+    let dir = scratch("markdown");
+    let (src, out) = (dir.join("src"), dir.join("out"));
+    let output = onu_doc(&[src.to_str().unwrap(), "--out", out.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let counting = std::fs::read_to_string(out.join("Counting.md")).expect("Counting.md");
+    assert!(counting.contains("*numbers, one after another*"));
+    assert!(counting.contains("### fib\n\n*pure behavior, recursive*\n\n> the n-th Fibonacci number\n"));
+    assert!(counting.contains("- **Memoization:** keyed on `n`"));
+    assert!(counting.contains("- **Called by:** [run](#run)"));
+    assert!(counting.contains("- **Calls:** [fib](#fib)"));
+
+    let greeting = std::fs::read_to_string(out.join("Greeting.md")).expect("Greeting.md");
+    assert!(greeting.contains("### Name\n\n> who to greet\n\n- a string called `given`\n"));
+    // Each file is its own program: the two `run`s never link to each other.
+    assert!(!greeting.contains("Counting.md"));
+
+    let index = std::fs::read_to_string(out.join("index.md")).expect("index.md");
+    assert!(index.contains("- [Counting](Counting.md): numbers, one after another"));
+    assert!(index.contains("- [Greeting](Greeting.md): saying hello"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_html_pages_print_without_out_dir() {
+    let dir = scratch("html");
+    let file = dir.join("src/counting.onu");
+    let output = onu_doc(&[file.to_str().unwrap(), "--format", "html"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.starts_with("<!DOCTYPE html>"));
+    assert!(stdout.contains("<h3 id=\"fib\">fib</h3>"));
+    assert!(stdout.contains("<strong>Called by:</strong> <a href=\"#run\">run</a>"));
+    assert!(!dir.join("out").exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_unknown_format_is_a_usage_error() {
+    let output = onu_doc(&["samples", "--format", "pdf"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--format markdown|html"));
+}
@@ -0,0 +1,121 @@
+/// Formatter Tests: Canonical Layout
+///
+/// `onu fmt` must reprint every sample so that it parses to the same
+/// program, keeps every comment, and is stable under a second pass.  The
+/// command-line tests check `--check` and in-place rewriting.
+use onu_refactor::adapters::formatter::OnuFormatter;
+use onu_refactor::adapters::lexer::OnuLexer;
+use onu_refactor::adapters::parser::OnuParser;
+use onu_refactor::application::options::LogLevel;
+use onu_refactor::application::ports::compiler_ports::LexerPort;
+use onu_refactor::application::use_cases::registry_service::RegistryService;
+use onu_refactor::domain::entities::ast::Discourse;
+use std::process::{Command, Output};
+
+fn parse(source: &str) -> Vec<Discourse> {
+    let tokens = OnuLexer::new(LogLevel::Error).lex(source).expect("lexes");
+    let parser = OnuParser::new(LogLevel::Error);
+    let mut registry = RegistryService::new();
+    parser.scan_headers(&tokens, &mut registry).expect("scans");
+    parser
+        .parse_with_registry(tokens, &mut registry)
+        .expect("parses")
+        .into_iter()
+        .map(|d| match d {
+            Discourse::Test { name, expects, body, .. } => Discourse::Test { name, expects, body, line: 0 },
+            other => other,
+        })
+        .collect()
+}
+
+#[test]
+fn test_every_sample_round_trips_to_a_parse_equivalent_program() {
+    let formatter = OnuFormatter::new(LogLevel::Error);
+    let mut samples: Vec<_> = std::fs::read_dir("samples")
+        .expect("samples directory")
+        .map(|e| e.expect("entry").path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "onu"))
+        .collect();
+    samples.sort();
+    assert!(!samples.is_empty());
+
+    for sample in samples {
+        let source = std::fs::read_to_string(&sample).expect("readable sample");
+        let formatted = formatter
+            .format(&source)
+            .unwrap_or_else(|e| panic!("{} does not format: {:?}", sample.display(), e));
+        assert_eq!(parse(&formatted), parse(&source), "{} changed meaning", sample.display());
+        assert_eq!(formatter.format(&formatted).expect("formats again"), formatted, "{} is not stable", sample.display());
+
+        let comments = OnuLexer::new(LogLevel::Error).lex_with_layout(&source).expect("lexes").comments;
+        for comment in comments {
+            assert!(
+                formatted.contains(comment.text.trim()),
+                "{} lost the comment {:?}",
+                sample.display(),
+                comment.text
+            );
+        }
+    }
+}
+
+const UNFORMATTED: &str = "the-module-called Tidy with-concern: layout
+the-behavior-called run with-intent: add up as: {
+    -- keep this
+    derivation: x derives-from 1
+    x added-to 2
+}
+";
+
+const FORMATTED: &str = "the module called Tidy
+    with concern: layout
+
+the behavior called run
+    with intent: add up
+    takes: nothing
+    delivers: nothing
+    as:
+        -- keep this
+        derivation: x derives-from 1
+        x added-to 2
+";
+
+fn onu_fmt(name: &str, source: &str, args: &[&str]) -> (Output, String) {
+    let path = std::env::temp_dir().join(format!("onu_fmt_{}_{}.onu", name, std::process::id()));
+    std::fs::write(&path, source).expect("Failed to write source");
+    let output = Command::new(env!("CARGO_BIN_EXE_onu_refactor"))
+        .arg("fmt")
+        .arg(&path)
+        .args(args)
+        .output()
+        .expect("Failed to run onu");
+    let after = std::fs::read_to_string(&path).expect("Failed to read back source");
+    let _ = std::fs::remove_file(&path);
+    (output, after)
+}
+
+#[test]
+fn test_check_reports_unformatted_files_and_leaves_them_alone() {
+    let (output, after) = onu_fmt("check", UNFORMATTED, &["--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("would reformat"));
+    assert_eq!(after, UNFORMATTED);
+
+    let (output, _) = onu_fmt("check_clean", FORMATTED, &["--check"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_fmt_rewrites_files_in_place() {
+    let (output, after) = onu_fmt("write", UNFORMATTED, &[]);
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(after, FORMATTED);
+}
+
+#[test]
+fn test_fmt_refuses_a_file_that_does_not_parse() {
+    let broken = "the behavior called run with intent: x takes: nothing delivers: nothing as: derivation: 1\n";
+    let (output, after) = onu_fmt("broken", broken, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(after, broken);
+}
@@ -0,0 +1,54 @@
+/// Hardware Intrinsic Tests: Codegen Adapter Layer
+///
+/// A behavior tagged `with known behavior: sha256-compress` is dispatched to
+/// the host's SHA kernel when the CPU has one and to its software body
+/// otherwise.  Both paths must print the same digests for the SHA-256
+/// sample.
+mod common;
+
+use common::{compile_ir, compile_mir, run_ir_isolated};
+use onu_refactor::application::options::CompilationOptions;
+use onu_refactor::domain::entities::types::KnownBehavior;
+
+const SHA256_SAMPLE: &str = include_str!("../samples/sha256.onu");
+
+fn software_only() -> CompilationOptions {
+    CompilationOptions { hardware_intrinsics: false, ..CompilationOptions::default() }
+}
+
+#[test]
+fn test_known_behavior_tag_reaches_mir() {
+    let mir = compile_mir(SHA256_SAMPLE, CompilationOptions::default());
+    let compress = mir.functions.iter().find(|f| f.name == "sha256-compress").expect("sha256-compress survives inlining");
+    assert_eq!(compress.known_behavior, Some(KnownBehavior::Sha256Compress));
+
+    let mir = compile_mir(SHA256_SAMPLE, software_only());
+    assert!(mir.functions.iter().all(|f| f.known_behavior.is_none()));
+}
+
+#[test]
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn test_sha256_dispatches_to_hardware_kernel() {
+    let ir = compile_ir(SHA256_SAMPLE, CompilationOptions::default());
+    assert!(ir.contains("@sha256-compress.software"), "software body kept as fallback");
+    #[cfg(target_arch = "x86_64")]
+    assert!(ir.contains("@llvm.x86.sha256rnds2") && ir.contains("asm \"cpuid\""));
+    #[cfg(target_arch = "aarch64")]
+    assert!(ir.contains("@llvm.aarch64.crypto.sha256h") && ir.contains("ID_AA64ISAR0_EL1"));
+
+    let ir = compile_ir(SHA256_SAMPLE, software_only());
+    assert!(!ir.contains(".software") && !ir.contains("sha256rnds2") && !ir.contains("crypto.sha256h"));
+}
+
+#[test]
+fn test_sha256_sample_output_matches_under_both_paths() {
+    let hardware = run_ir_isolated(&compile_ir(SHA256_SAMPLE, CompilationOptions::default()), "sha256_hw");
+    let software = run_ir_isolated(&compile_ir(SHA256_SAMPLE, software_only()), "sha256_sw");
+    assert!(hardware.status.success() && software.status.success());
+
+    let digests = String::from_utf8_lossy(&software.stdout);
+    // SHA-256 of the first LCG value (1083814273) as a 4-byte big-endian message.
+    assert_eq!(digests.lines().nth(1), Some("69e1fb8f5321b1cefe30f6ad5d2e48fa9fb7535f1c14e7f6a0801173c7283741"));
+    assert_eq!(digests.lines().count(), 1001);
+    assert_eq!(hardware.stdout, software.stdout);
+}
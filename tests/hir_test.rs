@@ -88,10 +88,13 @@ fn test_mir_lowering_binop_mapping() {
         let hir_header = HirBehaviorHeader {
             name: "test".to_string(),
             is_effect: false,
+            intent: String::new(),
             args: vec![],
             return_type: onu_refactor::domain::entities::types::OnuType::I64,
             diminishing: vec![],
             memo_cache_size: None,
+            inlining: onu_refactor::domain::entities::types::InlineHint::Auto,
+            known_behavior: None,
         };
         let hir_body = HirExpression::BinaryOp {
             op: hir_op,
@@ -0,0 +1,290 @@
+/// Idiom Recognizer Tests: Application Use Case Layer
+///
+/// `IdiomRecognizerPass` replaces hand-written bit tricks with the LLVM
+/// intrinsic that computes them in one instruction: rotations, byte swaps,
+/// the bit-counting loops left behind by TCO, and min/max/abs selects.
+/// Each idiom is checked on the MIR the pipeline produces and by running
+/// the compiled program.
+mod common;
+
+use common::{compile_ir, compile_mir, program_with_run, run_ir};
+use onu_refactor::application::options::CompilationOptions;
+use onu_refactor::application::use_cases::idiom_recognizer_pass::IdiomRecognizerPass;
+use onu_refactor::domain::entities::mir::{
+    BasicBlock, MirArgument, MirBinOp, MirFunction, MirInstruction, MirIntrinsic, MirLiteral,
+    MirOperand, MirProgram, MirTerminator,
+};
+use onu_refactor::domain::entities::types::{InlineHint, OnuType};
+
+fn var(id: usize) -> MirOperand {
+    MirOperand::Variable(id, false)
+}
+
+fn int(value: i64) -> MirOperand {
+    MirOperand::Constant(MirLiteral::I64(value))
+}
+
+fn binop(dest: usize, op: MirBinOp, lhs: MirOperand, rhs: MirOperand) -> MirInstruction {
+    MirInstruction::BinaryOperation { dest, op, lhs, rhs, dest_type: OnuType::I64 }
+}
+
+fn function(args: &[(usize, OnuType)], blocks: Vec<BasicBlock>) -> MirFunction {
+    MirFunction {
+        name: "idiom".to_string(),
+        args: args
+            .iter()
+            .map(|(ssa_var, typ)| MirArgument { name: format!("a{}", ssa_var), typ: typ.clone(), ssa_var: *ssa_var })
+            .collect(),
+        return_type: OnuType::I64,
+        blocks,
+        is_pure_data_leaf: true,
+        diminishing: vec![],
+        memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
+    }
+}
+
+fn recognize(func: MirFunction) -> MirFunction {
+    IdiomRecognizerPass::run(MirProgram { functions: vec![func] }).functions.remove(0)
+}
+
+fn intrinsics(program: &MirProgram) -> Vec<MirIntrinsic> {
+    program
+        .functions
+        .iter()
+        .flat_map(|f| f.blocks.iter())
+        .flat_map(|b| b.instructions.iter())
+        .filter_map(|inst| match inst {
+            MirInstruction::Intrinsic { intrinsic, .. } => Some(*intrinsic),
+            _ => None,
+        })
+        .collect()
+}
+
+fn run(source: &str) -> i32 {
+    run_ir(&compile_ir(source, CompilationOptions::default()))
+}
+
+/// sha256's rotr32: `(x >> n) | ((x << (32 - n)) & 0xffffffff)`.
+#[test]
+fn test_masked_rotr32_with_variable_amount_becomes_fshr() {
+    // SSA vars: 0 = x, 1 = n, 2 = hi, 3 = 32 - n, 4 = x << (32 - n), 5 = lo, 6 = result
+    let func = function(
+        &[(0, OnuType::I64), (1, OnuType::I64)],
+        vec![BasicBlock {
+            id: 0,
+            instructions: vec![
+                binop(2, MirBinOp::Shr, var(0), var(1)),
+                binop(3, MirBinOp::Sub, int(32), var(1)),
+                binop(4, MirBinOp::Shl, var(0), var(3)),
+                binop(5, MirBinOp::And, var(4), int(0xffff_ffff)),
+                binop(6, MirBinOp::Or, var(2), var(5)),
+            ],
+            terminator: MirTerminator::Return(var(6)),
+        }],
+    );
+
+    let func = recognize(func);
+    assert_eq!(
+        func.blocks[0].instructions[4],
+        MirInstruction::FunnelShiftRight { dest: 6, hi: var(0), lo: var(0), amount: var(1), width: 32 }
+    );
+}
+
+#[test]
+fn test_rotation_is_kept_when_the_source_changes_before_the_or() {
+    let func = function(
+        &[(0, OnuType::I64)],
+        vec![BasicBlock {
+            id: 0,
+            instructions: vec![
+                binop(1, MirBinOp::Shr, var(0), int(8)),
+                binop(0, MirBinOp::Add, var(0), int(1)),
+                binop(2, MirBinOp::Shl, var(0), int(56)),
+                binop(3, MirBinOp::Or, var(1), var(2)),
+            ],
+            terminator: MirTerminator::Return(var(3)),
+        }],
+    );
+
+    let before = func.clone();
+    assert_eq!(recognize(func), before);
+}
+
+#[test]
+fn test_min_over_floats_is_not_a_signed_min() {
+    let func = function(
+        &[(0, OnuType::F64), (1, OnuType::F64)],
+        vec![
+            BasicBlock {
+                id: 0,
+                instructions: vec![MirInstruction::BinaryOperation {
+                    dest: 2,
+                    op: MirBinOp::Lt,
+                    lhs: var(0),
+                    rhs: var(1),
+                    dest_type: OnuType::Boolean,
+                }],
+                terminator: MirTerminator::CondBranch { condition: var(2), then_block: 1, else_block: 2 },
+            },
+            BasicBlock { id: 1, instructions: vec![], terminator: MirTerminator::Return(var(0)) },
+            BasicBlock { id: 2, instructions: vec![], terminator: MirTerminator::Return(var(1)) },
+        ],
+    );
+
+    let before = func.clone();
+    assert_eq!(recognize(func), before);
+}
+
+const POPCOUNT: &str = "the behavior called popcount
+    with intent: count the set bits
+    takes:
+        an integer called x
+    delivers: an integer
+    with diminishing: x
+    as:
+        if x matches 0
+            then 0
+            else (x bit-and-with 1) added-to ((x shifted-right-by 1) utilizes popcount)
+";
+
+#[test]
+fn test_popcount_loop_becomes_ctpop() {
+    let source = program_with_run(POPCOUNT, &["61680 utilizes popcount"]);
+    let mir = compile_mir(&source, CompilationOptions::default());
+    assert!(intrinsics(&mir).contains(&MirIntrinsic::PopCount));
+    assert_eq!(run(&source), 8);
+}
+
+#[test]
+fn test_clear_lowest_bit_loop_becomes_ctpop() {
+    let behavior = "the behavior called popcount
+    with intent: count the set bits by clearing the lowest one
+    takes:
+        an integer called x
+    delivers: an integer
+    with diminishing: x
+    as:
+        if x matches 0
+            then 0
+            else 1 added-to ((x bit-and-with (x decreased-by 1)) utilizes popcount)
+";
+    let source = program_with_run(behavior, &["(0 decreased-by 1) utilizes popcount"]);
+    assert!(intrinsics(&compile_mir(&source, CompilationOptions::default())).contains(&MirIntrinsic::PopCount));
+    assert_eq!(run(&source), 64);
+}
+
+#[test]
+fn test_trailing_zero_loop_becomes_cttz() {
+    let behavior = "the behavior called trailing-zeros
+    with intent: count the zero bits below the lowest set bit
+    takes:
+        an integer called x
+    delivers: an integer
+    with diminishing: x
+    as:
+        if (x bit-and-with 1) matches 1
+            then 0
+            else 1 added-to ((x shifted-right-by 1) utilizes trailing-zeros)
+";
+    let source = program_with_run(behavior, &["40 utilizes trailing-zeros"]);
+    assert!(intrinsics(&compile_mir(&source, CompilationOptions::default())).contains(&MirIntrinsic::TrailingZeros));
+    assert_eq!(run(&source), 3);
+}
+
+#[test]
+fn test_bit_length_loop_becomes_ctlz() {
+    let behavior = "the behavior called bit-length
+    with intent: count the significant bits
+    takes:
+        an integer called x
+    delivers: an integer
+    with diminishing: x
+    as:
+        if x matches 0
+            then 0
+            else 1 added-to ((x shifted-right-by 1) utilizes bit-length)
+";
+    let source = program_with_run(behavior, &["(1000 utilizes bit-length) added-to (0 utilizes bit-length)"]);
+    assert!(intrinsics(&compile_mir(&source, CompilationOptions::default())).contains(&MirIntrinsic::LeadingZeros));
+    assert_eq!(run(&source), 10);
+}
+
+#[test]
+fn test_byte_swap_becomes_bswap() {
+    let behavior = "the behavior called swap-bytes
+    with intent: reverse the bytes of a 32-bit word
+    takes:
+        an integer called x
+    delivers: an integer
+    as:
+        derivation: b0 derives-from (x bit-and-with 255) shifted-left-by 24
+        derivation: b1 derives-from (x bit-and-with 65280) shifted-left-by 8
+        derivation: b2 derives-from (x shifted-right-by 8) bit-and-with 65280
+        derivation: b3 derives-from (x shifted-right-by 24) bit-and-with 255
+        ((b0 bit-or-with b1) bit-or-with b2) bit-or-with b3
+";
+    let source = program_with_run(behavior, &["305419896 utilizes swap-bytes"]);
+    assert!(intrinsics(&compile_mir(&source, CompilationOptions::default())).contains(&MirIntrinsic::ByteSwap));
+    assert_eq!(run(&source), 0x7856_3412);
+}
+
+#[test]
+fn test_rotate_left_becomes_fshl() {
+    let behavior = "the behavior called rotl64
+    with intent: rotate a 64-bit word left
+    takes:
+        an integer called x
+        an integer called n
+    delivers: an integer
+    as:
+        (x shifted-left-by n) bit-or-with (x shifted-right-by (64 decreased-by n))
+";
+    let source = program_with_run(behavior, &["(5 utilizes rotl64 2) added-to (3 utilizes rotl64 63)"]);
+    assert!(intrinsics(&compile_mir(&source, CompilationOptions::default())).contains(&MirIntrinsic::FunnelShiftLeft));
+    // rotl(3, 63) = 0x8000000000000001, whose low 32 bits are 1.
+    assert_eq!(run(&source), 21);
+}
+
+#[test]
+fn test_min_max_and_abs_selects_become_intrinsics() {
+    let behaviors = "the behavior called smaller
+    with intent: the lesser of two integers
+    takes:
+        an integer called a
+        an integer called b
+    delivers: an integer
+    as:
+        if a falls-short-of b then a else b
+
+the behavior called larger-plus-one
+    with intent: one more than the greater of two integers
+    takes:
+        an integer called a
+        an integer called b
+    delivers: an integer
+    as:
+        (if a exceeds b then a else b) added-to 1
+
+the behavior called magnitude
+    with intent: the absolute value of an integer
+    takes:
+        an integer called x
+    delivers: an integer
+    as:
+        if x falls-short-of 0
+            then 0 decreased-by x
+            else x
+";
+    let source = program_with_run(
+        behaviors,
+        &["(((7 utilizes smaller (0 decreased-by 3)) added-to (4 utilizes larger-plus-one 9)) \
+           scales-by 100) added-to ((0 decreased-by 42) utilizes magnitude)"],
+    );
+    let found = intrinsics(&compile_mir(&source, CompilationOptions::default()));
+    for expected in [MirIntrinsic::SignedMin, MirIntrinsic::SignedMax, MirIntrinsic::Abs] {
+        assert!(found.contains(&expected), "{:?} not recognized in {:?}", expected, found);
+    }
+    assert_eq!(run(&source), 742);
+}
@@ -1,17 +1,17 @@
 /// Inline Pass Unit Tests: Application Use Case Layer
 ///
-/// The `InlinePass` expands `is_pure_data_leaf` function bodies directly at
-/// their call sites in MIR, before codegen. This fuses inter-function loops
+/// The `InlinePass` expands pure leaves and small non-recursive effectful
+/// function bodies directly at their call sites in MIR, before codegen. This fuses inter-function loops
 /// into a single LLVM basic block, enabling LLVM to apply full loop optimizations
 /// that cannot cross function call boundaries.
 ///
 /// TDD: These tests are written before implementation to define the contract.
-use onu_refactor::application::use_cases::inline_pass::InlinePass;
+use onu_refactor::application::use_cases::inline_pass::{InlinePass, INLINE_BUDGET};
 use onu_refactor::domain::entities::mir::{
     BasicBlock, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand,
     MirProgram, MirTerminator,
 };
-use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::domain::entities::types::{InlineHint, OnuType};
 
 /// Build a minimal pure callee: `double(n) -> n * 2`
 /// This is marked `is_pure_data_leaf = true`.
@@ -27,6 +27,8 @@ fn make_pure_callee() -> MirFunction {
         is_pure_data_leaf: true,
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         blocks: vec![BasicBlock {
             id: 0,
             instructions: vec![
@@ -59,6 +61,8 @@ fn make_caller() -> MirFunction {
         is_pure_data_leaf: false,
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         blocks: vec![BasicBlock {
             id: 0,
             instructions: vec![
@@ -208,6 +212,8 @@ fn inline_pass_is_identity_for_external_calls() {
         is_pure_data_leaf: false,
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         blocks: vec![BasicBlock {
             id: 0,
             instructions: vec![MirInstruction::Call {
@@ -240,10 +246,10 @@ fn inline_pass_is_identity_for_external_calls() {
     );
 }
 
-/// GREEN: Functions NOT marked `is_pure_data_leaf` must not be inlined.
-#[test]
-fn inline_pass_is_identity_for_non_pure_callees() {
-    let impure_callee = MirFunction {
+/// Build an effectful callee `side-effect-fn(n)` that emits `emits` values
+/// (7, 8, ...) and delivers `n`.
+fn make_effectful_callee(inlining: InlineHint, emits: usize) -> MirFunction {
+    MirFunction {
         name: "side-effect-fn".to_string(),
         args: vec![MirArgument {
             name: "n".to_string(),
@@ -252,18 +258,23 @@ fn inline_pass_is_identity_for_non_pure_callees() {
         }],
         return_type: OnuType::I64,
         is_pure_data_leaf: false,
-        diminishing: vec![], // NOT pure
+        diminishing: vec![],
         memo_cache_size: None,
+        inlining,
+        known_behavior: None,
         blocks: vec![BasicBlock {
             id: 0,
-            instructions: vec![MirInstruction::Emit(MirOperand::Constant(MirLiteral::I64(
-                0,
-            )))],
+            instructions: (0..emits)
+                .map(|i| MirInstruction::Emit(MirOperand::Constant(MirLiteral::I64(7 + i as i64))))
+                .collect(),
             terminator: MirTerminator::Return(MirOperand::Variable(0, false)),
         }],
-    };
+    }
+}
 
-    let caller = MirFunction {
+/// Build `caller(x)`: emits 1, calls `side-effect-fn(x)`, emits 2.
+fn make_effectful_caller() -> MirFunction {
+    MirFunction {
         name: "caller".to_string(),
         args: vec![MirArgument {
             name: "x".to_string(),
@@ -274,38 +285,128 @@ fn inline_pass_is_identity_for_non_pure_callees() {
         is_pure_data_leaf: false,
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         blocks: vec![BasicBlock {
             id: 0,
-            instructions: vec![MirInstruction::Call {
-                dest: 10,
-                name: "side-effect-fn".to_string(),
-                args: vec![MirOperand::Variable(1, false)],
-                return_type: OnuType::I64,
-                arg_types: vec![OnuType::I64],
-                is_tail_call: false,
-            }],
+            instructions: vec![
+                MirInstruction::Emit(MirOperand::Constant(MirLiteral::I64(1))),
+                MirInstruction::Call {
+                    dest: 10,
+                    name: "side-effect-fn".to_string(),
+                    args: vec![MirOperand::Variable(1, false)],
+                    return_type: OnuType::I64,
+                    arg_types: vec![OnuType::I64],
+                    is_tail_call: false,
+                },
+                MirInstruction::Emit(MirOperand::Constant(MirLiteral::I64(2))),
+            ],
             terminator: MirTerminator::Return(MirOperand::Variable(10, false)),
         }],
-    };
+    }
+}
 
+fn caller_after_pass(callee: MirFunction) -> MirFunction {
     let program = MirProgram {
-        functions: vec![impure_callee, caller],
+        functions: vec![callee, make_effectful_caller()],
     };
-    let result = InlinePass::run(program);
-    let transformed_caller = result
+    InlinePass::run(program)
         .functions
-        .iter()
+        .into_iter()
         .find(|f| f.name == "caller")
-        .unwrap();
+        .unwrap()
+}
 
-    let call_intact = transformed_caller.blocks.iter().any(|b| {
-        b.instructions.iter().any(
-            |inst| matches!(inst, MirInstruction::Call { name, .. } if name == "side-effect-fn"),
-        )
-    });
+fn calls(func: &MirFunction, callee: &str) -> bool {
+    func.blocks.iter().any(|b| {
+        b.instructions
+            .iter()
+            .any(|inst| matches!(inst, MirInstruction::Call { name, .. } if name == callee))
+    })
+}
 
+/// GREEN: `with inlining: never` keeps the call even to a tiny callee.
+#[test]
+fn inline_pass_keeps_calls_to_never_inlined_callees() {
+    let caller = caller_after_pass(make_effectful_callee(InlineHint::Never, 1));
     assert!(
-        call_intact,
-        "InlinePass must NOT inline non-pure callee 'side-effect-fn'"
+        calls(&caller, "side-effect-fn"),
+        "InlinePass must NOT inline callee marked `with inlining: never`"
     );
 }
+
+/// GREEN: Small non-recursive effectful callees are inlined, and their
+/// output stays between the caller's surrounding output.
+#[test]
+fn inline_pass_inlines_small_effectful_callees_in_order() {
+    let caller = caller_after_pass(make_effectful_callee(InlineHint::Auto, 1));
+    assert!(!calls(&caller, "side-effect-fn"));
+
+    let emitted: Vec<i64> = caller
+        .blocks
+        .iter()
+        .flat_map(|b| b.instructions.iter())
+        .filter_map(|inst| match inst {
+            MirInstruction::Emit(MirOperand::Constant(MirLiteral::I64(v))) => Some(*v),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(emitted, vec![1, 7, 2]);
+}
+
+/// GREEN: Effectful callees over the budget stay calls unless marked `always`.
+#[test]
+fn inline_pass_budget_applies_unless_always() {
+    let big = INLINE_BUDGET + 1;
+    assert!(calls(&caller_after_pass(make_effectful_callee(InlineHint::Auto, big)), "side-effect-fn"));
+    assert!(!calls(&caller_after_pass(make_effectful_callee(InlineHint::Always, big)), "side-effect-fn"));
+}
+
+/// GREEN: An allocating callee keeps its own arena scope once inlined.
+#[test]
+fn inline_pass_scopes_arena_of_allocating_callees() {
+    let mut callee = make_effectful_callee(InlineHint::Auto, 0);
+    callee.blocks[0].instructions.push(MirInstruction::Alloc {
+        dest: 1,
+        size_bytes: MirOperand::Variable(0, false),
+    });
+    let caller = caller_after_pass(callee);
+    assert!(!calls(&caller, "side-effect-fn"));
+
+    let instructions: Vec<&MirInstruction> =
+        caller.blocks.iter().flat_map(|b| b.instructions.iter()).collect();
+    let save = instructions
+        .iter()
+        .position(|inst| matches!(inst, MirInstruction::SaveArena { .. }))
+        .expect("inlined allocation must open an arena scope");
+    let alloc = instructions
+        .iter()
+        .position(|inst| matches!(inst, MirInstruction::Alloc { .. }))
+        .unwrap();
+    let restore = instructions
+        .iter()
+        .position(|inst| matches!(inst, MirInstruction::RestoreArena { .. }))
+        .expect("inlined allocation must close its arena scope");
+    assert!(save < alloc && alloc < restore);
+}
+
+/// GREEN: Recursive effectful callees are never expanded, even when small,
+/// and an ignored `with inlining: always` is reported as a warning.
+#[test]
+fn inline_pass_keeps_calls_to_recursive_effectful_callees() {
+    let mut callee = make_effectful_callee(InlineHint::Always, 1);
+    callee.blocks[0].instructions.push(MirInstruction::Call {
+        dest: 3,
+        name: "side-effect-fn".to_string(),
+        args: vec![MirOperand::Variable(0, false)],
+        return_type: OnuType::I64,
+        arg_types: vec![OnuType::I64],
+        is_tail_call: false,
+    });
+    assert!(calls(&caller_after_pass(callee.clone()), "side-effect-fn"));
+
+    let program = MirProgram { functions: vec![callee, make_effectful_caller()] };
+    let (_, diagnostics) = InlinePass::run_with_diagnostics(program);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "'side-effect-fn' is recursive; ignoring `with inlining: always`");
+}
@@ -0,0 +1,89 @@
+/// Integer Upgrade Tests: Application Layer
+///
+/// `IntegerUpgradePass` bounds recursive pure functions from their literal
+/// call sites and widens any whose results outgrow 64 bits, so factorial,
+/// power and binomial results print exactly instead of wrapping.
+mod common;
+
+use common::{compile_mir, printed, program_with_run, warnings};
+use onu_refactor::application::options::CompilationOptions;
+use onu_refactor::domain::entities::types::OnuType;
+
+const FACTORIAL: &str = "the behavior called factorial\n    with intent: multiply down to one\n    \
+with diminishing: n\n    takes:\n        an integer called n\n    delivers: an integer\n    as:\n        \
+if n matches 0\n            then 1\n            else n scales-by ((n decreased-by 1) utilizes factorial)\n";
+
+const POWER: &str = "the behavior called power\n    with intent: multiply a base by itself\n    \
+with diminishing: exponent\n    takes:\n        an integer called base\n        an integer called exponent\n    \
+delivers: an integer\n    as:\n        \
+if exponent matches 0\n            then 1\n            else base scales-by (base utilizes power (exponent decreased-by 1))\n";
+
+const BINOMIAL: &str = "the behavior called choose\n    with intent: walk Pascal's triangle\n    \
+with diminishing: n, k\n    takes:\n        an integer called n\n        an integer called k\n    \
+delivers: an integer\n    as:\n        \
+if k matches 0\n            then 1\n            else if k matches n\n                then 1\n                \
+else ((n decreased-by 1) utilizes choose (k decreased-by 1)) added-to ((n decreased-by 1) utilizes choose k)\n";
+
+const ACKERMANN: &str = "the behavior called ackermann\n    with intent: grow faster than any tower\n    \
+with no guaranteed termination\n    takes:\n        an integer called m\n        an integer called n\n    \
+delivers: an integer\n    as:\n        \
+if m matches 0\n            then n added-to 1\n            else if n matches 0\n                \
+then (m decreased-by 1) utilizes ackermann 1\n                \
+else (m decreased-by 1) utilizes ackermann (m utilizes ackermann (n decreased-by 1))\n";
+
+fn program(behavior: &str, call: &str) -> String {
+    let result = format!("derivation: result derives-from an integer {}", call);
+    program_with_run(behavior, &[&result, "derivation: shown derives-from nothing broadcasts (result utilizes as-text)", "0"])
+}
+
+fn return_type(source: &str, name: &str) -> OnuType {
+    compile_mir(source, CompilationOptions::default())
+        .functions
+        .into_iter()
+        .find(|f| f.name == name)
+        .unwrap_or_else(|| panic!("no function {}", name))
+        .return_type
+}
+
+#[test]
+fn test_factorial_results_widen() {
+    let source = program(FACTORIAL, "30 utilizes factorial");
+    assert_eq!(return_type(&source, "factorial"), OnuType::WideInt(128));
+    assert_eq!(printed(&source, "factorial_30"), "265252859812191058636308480000000");
+}
+
+#[test]
+fn test_factorial_within_range_stays_i64() {
+    let source = program(FACTORIAL, "20 utilizes factorial");
+    assert_eq!(return_type(&source, "factorial"), OnuType::I64);
+    assert_eq!(printed(&source, "factorial_20"), "2432902008176640000");
+}
+
+#[test]
+fn test_power_results_widen() {
+    let source = program(POWER, "3 utilizes power 100");
+    assert_eq!(return_type(&source, "power"), OnuType::WideInt(192));
+    assert_eq!(printed(&source, "power_3_100"), "515377520732011331036461129765621272702107522001");
+}
+
+#[test]
+fn test_binomial_results_widen() {
+    let source = program(BINOMIAL, "100 utilizes choose 50");
+    assert!(matches!(return_type(&source, "choose"), OnuType::WideInt(_)));
+    assert_eq!(printed(&source, "choose_100_50"), "100891344545564193334812497256");
+}
+
+#[test]
+fn test_unbounded_growth_stays_i64() {
+    // Ackermann(4, 2) has 19,729 digits; the analysis runs out of depth
+    // before it can prove a bound, so the function is left alone.
+    let source = program(ACKERMANN, "4 utilizes ackermann 2");
+    assert_eq!(return_type(&source, "ackermann"), OnuType::I64);
+    let reported = warnings(&source);
+    assert!(
+        reported.iter().any(|w| w.starts_with("Cannot bound 'ackermann'") && w.ends_with("results stay 64-bit")),
+        "{:?}",
+        reported
+    );
+    assert!(warnings(&program(FACTORIAL, "20 utilizes factorial")).is_empty());
+}
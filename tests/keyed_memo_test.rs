@@ -0,0 +1,103 @@
+/// Keyed Memoization Tests: Application Layer
+///
+/// Behaviors with observed `text`, `boolean` or shape arguments are memoized
+/// through `KeyedMemoStrategy`, which hashes argument contents and compares
+/// full keys before reusing a cached result.
+mod common;
+
+use common::{compile_ir, compile_mir, run_ir};
+use onu_refactor::application::options::CompilationOptions;
+
+fn run(source: &str) -> i32 {
+    run_ir(&compile_ir(source, CompilationOptions::default()))
+}
+
+fn is_memoized(source: &str, name: &str) -> bool {
+    let inner = format!("{}.inner", name);
+    compile_mir(source, CompilationOptions::default())
+        .functions
+        .iter()
+        .any(|f| f.name == inner)
+}
+
+fn edit_distance_program(a: &str, b: &str) -> String {
+    format!(
+        "the module called EditDistance with concern: memoized text recursion\n\n\
+the behavior called distance\n    with intent: count the edits turning one suffix into another\n    \
+with diminishing: i, j\n    takes:\n        a text called a via observation\n        \
+a text called b via observation\n        an integer called i\n        an integer called j\n    \
+delivers: an integer\n    as:\n        \
+if i matches (a utilizes len)\n            then (b utilizes len) decreased-by j\n            \
+else if j matches (b utilizes len)\n                then (a utilizes len) decreased-by i\n                \
+else if (a char-at i) matches (b char-at j)\n                    \
+then a utilizes distance b (i added-to 1) (j added-to 1)\n                    else\n                        \
+derivation: dropped derives-from an integer a utilizes distance b (i added-to 1) j\n                        \
+derivation: inserted derives-from an integer a utilizes distance b i (j added-to 1)\n                        \
+derivation: swapped derives-from an integer a utilizes distance b (i added-to 1) (j added-to 1)\n                        \
+derivation: best derives-from an integer if dropped falls-short-of inserted then dropped else inserted\n                        \
+1 added-to (if best falls-short-of swapped then best else swapped)\n\n\
+the effect behavior called run\n    with intent: compare two texts\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        \"{}\" utilizes distance \"{}\" 0 0\n",
+        a, b
+    )
+}
+
+const GRID_PROGRAM: &str = "the module called Grid with concern: memoized shape recursion\n\n\
+the shape called Cell\n    with intent: address one cell of a grid\n    takes:\n        \
+an integer called row\n        an integer called col\n\n\
+the behavior called paths\n    with intent: count monotone paths from a cell back to the origin\n    \
+with diminishing: at\n    takes:\n        a Cell called at via observation\n    \
+delivers: an integer\n    as:\n        \
+derivation: r derives-from an integer at utilizes row\n        \
+derivation: c derives-from an integer at utilizes col\n        \
+if r matches 0\n            then 1\n            else if c matches 0\n                then 1\n                else\n                    \
+derivation: up derives-from a Cell (r decreased-by 1) utilizes Cell c\n                    \
+derivation: left derives-from a Cell r utilizes Cell (c decreased-by 1)\n                    \
+(up utilizes paths) added-to (left utilizes paths)\n\n\
+the effect behavior called run\n    with intent: count paths across a grid\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        derivation: corner derives-from a Cell 16 utilizes Cell 16\n        \
+corner utilizes paths\n";
+
+const ALTERNATING_PROGRAM: &str = "the module called Alternating with concern: memoized boolean recursion\n\n\
+the behavior called tally\n    with intent: a Fibonacci-style count that flips a flag on odd steps\n    \
+with diminishing: n\n    takes:\n        an integer called n\n        a boolean called doubled\n    \
+delivers: an integer\n    as:\n        \
+if n falls-short-of 2\n            then (if doubled then 2 else 1)\n            else\n                \
+derivation: flipped derives-from a boolean if doubled then false else true\n                \
+derivation: odd derives-from an integer (n decreased-by 1) utilizes tally flipped\n                \
+derivation: even derives-from an integer (n decreased-by 2) utilizes tally doubled\n                \
+odd added-to even\n\n\
+the effect behavior called run\n    with intent: tally a long sequence\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        42 utilizes tally true\n";
+
+#[test]
+fn test_text_arguments_are_memoized() {
+    assert!(is_memoized(&edit_distance_program("kitten", "sitting"), "distance"));
+}
+
+#[test]
+fn test_memoized_edit_distance() {
+    assert_eq!(run(&edit_distance_program("kitten", "sitting")), 3);
+    assert_eq!(run(&edit_distance_program("", "abc")), 3);
+    // Exponential without the cache; needs distinct text keys to stay apart.
+    assert_eq!(run(&edit_distance_program("the quick brown fox jumps", "a lazy dog sleeps quietly")), 24);
+}
+
+#[test]
+fn test_shape_arguments_are_memoized() {
+    assert!(is_memoized(GRID_PROGRAM, "paths"));
+    assert_eq!(run(GRID_PROGRAM), 601_080_390);
+}
+
+#[test]
+fn test_boolean_arguments_are_memoized() {
+    assert!(is_memoized(ALTERNATING_PROGRAM, "tally"));
+    assert_eq!(run(ALTERNATING_PROGRAM), 599_074_578);
+}
+
+#[test]
+fn test_integer_arguments_must_still_be_diminishing() {
+    let source = edit_distance_program("ab", "ba").replace("with diminishing: i, j", "with diminishing: i");
+    assert!(!is_memoized(&source, "distance"));
+    assert_eq!(run(&source), 2);
+}
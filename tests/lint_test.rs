@@ -0,0 +1,110 @@
+/// Lint Tests: Infrastructure CLI Layer
+///
+/// `onu lint <file>` runs the HIR lint rules and prints one warning per
+/// finding, named after its rule.  These tests drive the built binary with a
+/// program that trips every rule, and check that `--allow` / `--warn`
+/// switch rules off and on.
+use std::process::{Command, Output};
+
+const UNTIDY: &str = "the module called Untidy with concern: linting
+
+the shape called Point
+    with intent: an unused shape
+    takes:
+        an integer called x
+
+the effect behavior called shout
+    with intent: print a number
+    takes:
+        an integer called n
+    delivers: an integer
+    as:
+        derivation: shown derives-from nothing broadcasts (n utilizes as-text)
+        n
+
+the behavior called double
+    with intent: twice a number
+    takes:
+        an integer called n
+    delivers: an integer
+    with diminishing: n
+    as:
+        n scales-by 2
+
+the behavior called orphan
+    with intent: nothing calls this
+    takes:
+        an integer called n
+    delivers: an integer
+    as:
+        n
+
+the behavior called run
+    with intent: nothing
+    takes: nothing
+    delivers: an integer
+    as:
+        derivation: n derives-from an integer 4 utilizes shout
+        derivation: n derives-from an integer n utilizes double
+        if 1 matches 1 then n else 0
+";
+
+fn onu_lint(name: &str, args: &[&str]) -> Output {
+    let path = std::env::temp_dir().join(format!("onu_lint_{}_{}.onu", name, std::process::id()));
+    std::fs::write(&path, UNTIDY).expect("Failed to write source");
+    let output = Command::new(env!("CARGO_BIN_EXE_onu_refactor"))
+        .arg("lint")
+        .arg(&path)
+        .args(args)
+        .output()
+        .expect("Failed to run onu");
+    let _ = std::fs::remove_file(&path);
+    output
+}
+
+#[test]
+fn test_every_rule_reports_with_a_hint() {
+    let output = onu_lint("all", &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    for rule in [
+        "unused-variable",
+        "effect-in-pure",
+        "needless-diminishing",
+        "missing-intent",
+        "constant-condition",
+        "shadowed-derivation",
+        "unused-definition",
+    ] {
+        assert!(stdout.contains(&format!("warning[{}]", rule)), "no {} warning in:\n{}", rule, stdout);
+    }
+    assert!(stdout.contains("Pure behavior 'run' calls effect behavior 'shout'"));
+    assert!(stdout.contains("Behavior 'orphan' is never called"));
+    assert!(stdout.contains("Shape 'Point' is never used"));
+    assert_eq!(stdout.matches("warning[").count(), stdout.matches("  = hint: ").count());
+}
+
+#[test]
+fn test_allow_and_warn_toggle_rules() {
+    let output = onu_lint("allow", &["--allow", "missing-intent", "--allow", "unused-definition"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("warning[missing-intent]"));
+    assert!(!stdout.contains("warning[unused-definition]"));
+    assert!(stdout.contains("warning[shadowed-derivation]"));
+
+    let output = onu_lint("only", &["--allow", "all", "--warn", "constant-condition"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("warning[").count(), 1, "{}", stdout);
+    assert!(stdout.contains("is always true; its 'else' branch never runs"));
+
+    let output = onu_lint("none", &["--allow", "all"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_unknown_lint_is_a_usage_error() {
+    let output = onu_lint("unknown", &["--allow", "no-such-lint"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("shadowed-derivation"));
+}
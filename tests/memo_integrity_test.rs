@@ -4,7 +4,7 @@ use onu_refactor::domain::entities::mir::{
     BasicBlock, MirArgument, MirFunction, MirInstruction, MirLiteral, MirOperand, MirProgram,
     MirTerminator,
 };
-use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::domain::entities::types::{InlineHint, OnuType};
 
 fn make_memoizable_fn(name: &str) -> MirFunction {
     MirFunction {
@@ -23,6 +23,8 @@ fn make_memoizable_fn(name: &str) -> MirFunction {
         is_pure_data_leaf: true,
         diminishing: vec!["n".to_string()],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
     }
 }
 
@@ -24,7 +24,7 @@ use onu_refactor::domain::entities::mir::{
     BasicBlock, MirArgument, MirFunction, MirInstruction, MirLiteral, MirOperand, MirProgram,
     MirTerminator,
 };
-use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::domain::entities::types::{InlineHint, OnuType};
 use onu_refactor::domain::entities::ARENA_SIZE_BYTES;
 
 // ---------------------------------------------------------------------------
@@ -58,6 +58,8 @@ fn make_recursive_pure_fn(name: &str) -> MirFunction {
         is_pure_data_leaf: true,
         diminishing: vec!["n".to_string()],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
     }
 }
 
@@ -239,6 +241,8 @@ fn make_two_dim_fn(name: &str) -> MirFunction {
         is_pure_data_leaf: true,
         diminishing: vec!["m".to_string(), "n".to_string()],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
     }
 }
 
@@ -270,6 +274,8 @@ fn multi_dim_non_diminishing_arg_blocks_memoization() {
         // Only one of the two args is diminishing → should not memoize.
         diminishing: vec!["n".to_string()],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
     };
     let program = MirProgram {
         functions: vec![func],
@@ -344,7 +350,7 @@ fn multi_dim_inner_has_extra_ptr_args() {
 ///
 /// The previous implementation only guarded the result-cache allocation, so the
 /// occupancy array would push the total over the arena boundary.  This test
-/// verifies that the sum of all `Alloc` sizes in the wrapper is within the arena.
+/// verifies that the sum of all `GlobalAlloc` table sizes in the wrapper is within the arena.
 #[test]
 fn multi_dim_memory_guard_caps_allocation() {
     // Build a 3-arg function — if dim_size were 10_000^3 * stride, that would be
@@ -365,6 +371,8 @@ fn multi_dim_memory_guard_caps_allocation() {
         is_pure_data_leaf: true,
         diminishing: vec!["a".to_string(), "b".to_string(), "c".to_string()],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
     };
     let program = MirProgram { functions: vec![func] };
     let registry = RegistryService::new();
@@ -373,16 +381,14 @@ fn multi_dim_memory_guard_caps_allocation() {
     let limit = ARENA_SIZE_BYTES as i64;
     let wrapper = &result.functions[0];
 
-    // Collect all Alloc sizes in the wrapper (result cache + occupancy array).
+    // Collect all table sizes in the wrapper (result cache + occupancy array).
     let total_alloc: i64 = wrapper
         .blocks
         .iter()
         .flat_map(|b| b.instructions.iter())
         .filter_map(|inst| {
-            if let MirInstruction::Alloc { size_bytes, .. } = inst {
-                if let MirOperand::Constant(MirLiteral::I64(bytes)) = size_bytes {
-                    return Some(*bytes);
-                }
+            if let MirInstruction::GlobalAlloc { size_bytes, .. } = inst {
+                return Some(*size_bytes as i64);
             }
             None
         })
@@ -448,10 +454,8 @@ fn two_dim_i64_combined_allocation_within_arena() {
         .iter()
         .flat_map(|b| b.instructions.iter())
         .filter_map(|inst| {
-            if let MirInstruction::Alloc { size_bytes, .. } = inst {
-                if let MirOperand::Constant(MirLiteral::I64(bytes)) = size_bytes {
-                    return Some(*bytes);
-                }
+            if let MirInstruction::GlobalAlloc { size_bytes, .. } = inst {
+                return Some(*size_bytes as i64);
             }
             None
         })
@@ -0,0 +1,63 @@
+/// Memo Report Tests: Application Layer
+///
+/// `--memo-report` explains each `MemoPass` decision at compile time and
+/// instruments memo wrappers so the program prints hit/miss/collision
+/// counters for every memoized behavior when `run` returns.
+mod common;
+
+use common::{compile_ir, compile_mir, run_ir_isolated};
+use onu_refactor::application::options::CompilationOptions;
+use onu_refactor::domain::entities::mir::{MemoCounter, MirInstruction, MirProgram};
+
+fn reporting() -> CompilationOptions {
+    CompilationOptions { memo_report: true, ..CompilationOptions::default() }
+}
+
+fn probes(program: &MirProgram) -> Vec<(String, MemoCounter)> {
+    program
+        .functions
+        .iter()
+        .flat_map(|f| f.blocks.iter())
+        .flat_map(|b| b.instructions.iter())
+        .filter_map(|inst| match inst {
+            MirInstruction::MemoCount { function, counter } => Some((function.clone(), *counter)),
+            _ => None,
+        })
+        .collect()
+}
+
+const FIBONACCI_PROGRAM: &str = "the module called Fibonacci with concern: memo statistics\n\n\
+the behavior called fib\n    with intent: compute a Fibonacci number\n    with diminishing: n\n    \
+takes:\n        an integer called n\n    delivers: an integer\n    as:\n        \
+if n falls-short-of 2\n            then n\n            \
+else (n decreased-by 1) utilizes fib added-to (n decreased-by 2) utilizes fib\n\n\
+the effect behavior called run\n    with intent: compute fib of thirty\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        30 utilizes fib\n";
+
+#[test]
+fn test_probes_only_survive_in_report_mode() {
+    assert!(probes(&compile_mir(FIBONACCI_PROGRAM, CompilationOptions::default())).is_empty());
+    let instrumented = probes(&compile_mir(FIBONACCI_PROGRAM, reporting()));
+    assert!(instrumented.contains(&("fib".to_string(), MemoCounter::Hit)));
+    assert!(instrumented.contains(&("fib".to_string(), MemoCounter::Miss)));
+}
+
+#[test]
+fn test_default_build_has_no_report() {
+    let output = run_ir_isolated(&compile_ir(FIBONACCI_PROGRAM, CompilationOptions::default()), "memo_plain");
+    assert_eq!(output.status.code(), Some(832_040 % 256));
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("onu memo"));
+}
+
+#[test]
+fn test_counters_are_printed_at_exit() {
+    let output = run_ir_isolated(&compile_ir(FIBONACCI_PROGRAM, reporting()), "memo_report");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(832_040 % 256), "stderr: {}", stderr);
+    // fib(0..=30) each miss once; every other recursive call is a hit.
+    assert!(
+        stderr.contains("onu memo: `fib`: 28 hits, 30 misses, 0 collisions\n"),
+        "stderr: {}",
+        stderr
+    );
+}
@@ -0,0 +1,97 @@
+/// Memo Reuse Tests: Application Layer
+///
+/// Memo tables are module-level globals shared by every call to a memoized
+/// behavior, so it may be called any number of times, and
+/// `"name" utilizes clears` empties its cache.
+mod common;
+
+use common::{compile_ir, compile_mir, program_with_run, run_ir, safety_check};
+use onu_refactor::application::options::CompilationOptions;
+use onu_refactor::domain::entities::error::OnuError;
+use onu_refactor::domain::entities::mir::MirInstruction;
+
+fn run(source: &str) -> i32 {
+    run_ir(&compile_ir(source, CompilationOptions::default()))
+}
+
+const FIB: &str = "the behavior called fib\n    with intent: compute a Fibonacci number\n    \
+with diminishing: n\n    takes:\n        an integer called n\n    delivers: an integer\n    as:\n        \
+if n falls-short-of 2\n            then n\n            \
+else (n decreased-by 1) utilizes fib added-to (n decreased-by 2) utilizes fib\n\n";
+
+const ACK: &str = "the behavior called ack\n    with intent: compute the Ackermann function\n    \
+with diminishing: m, n\n    takes:\n        an integer called m\n        an integer called n\n    \
+delivers: an integer\n    as:\n        \
+if m matches 0\n            then n added-to 1\n            \
+else if n matches 0\n                then (m decreased-by 1) utilizes ack 1\n                \
+else (m decreased-by 1) utilizes ack (m utilizes ack (n decreased-by 1))\n\n";
+
+const DISTANCE: &str = "the behavior called distance\n    with intent: count the edits turning one suffix into another\n    \
+with diminishing: i, j\n    takes:\n        a text called a via observation\n        \
+a text called b via observation\n        an integer called i\n        an integer called j\n    \
+delivers: an integer\n    as:\n        \
+if i matches (a utilizes len)\n            then (b utilizes len) decreased-by j\n            \
+else if j matches (b utilizes len)\n                then (a utilizes len) decreased-by i\n                \
+else if (a char-at i) matches (b char-at j)\n                    \
+then a utilizes distance b (i added-to 1) (j added-to 1)\n                    else\n                        \
+derivation: dropped derives-from an integer a utilizes distance b (i added-to 1) j\n                        \
+derivation: inserted derives-from an integer a utilizes distance b i (j added-to 1)\n                        \
+derivation: swapped derives-from an integer a utilizes distance b (i added-to 1) (j added-to 1)\n                        \
+derivation: best derives-from an integer if dropped falls-short-of inserted then dropped else inserted\n                        \
+1 added-to (if best falls-short-of swapped then best else swapped)\n\n";
+
+#[test]
+fn test_memoized_behavior_can_be_called_repeatedly() {
+    let source = program_with_run(FIB, &[
+        "derivation: a derives-from an integer 40 utilizes fib",
+        "derivation: b derives-from an integer 10 utilizes fib",
+        "derivation: c derives-from an integer 12 utilizes fib",
+        "(a matches 102334155) added-to (b added-to c)",
+    ]);
+    assert!(safety_check(&source).is_ok());
+    assert_eq!(run(&source), 1 + 55 + 144);
+}
+
+#[test]
+fn test_clears_empties_the_cache() {
+    let source = program_with_run(FIB, &[
+        "derivation: a derives-from an integer 20 utilizes fib",
+        "derivation: cleared derives-from nothing \"fib\" utilizes clears",
+        "derivation: b derives-from an integer 11 utilizes fib",
+        "(a matches 6765) added-to b",
+    ]);
+    let mir = compile_mir(&source, CompilationOptions::default());
+    let run_fn = mir.functions.iter().find(|f| f.name == "run").expect("run");
+    let insts: Vec<&MirInstruction> = run_fn.blocks.iter().flat_map(|b| &b.instructions).collect();
+    assert!(insts.iter().any(|i| matches!(i, MirInstruction::MemSet { .. })));
+    assert!(!insts.iter().any(|i| matches!(i, MirInstruction::ClearMemo { .. })));
+    assert_eq!(run(&source), 1 + 89);
+}
+
+#[test]
+fn test_hash_and_keyed_caches_survive_repeated_calls() {
+    let source = program_with_run(&format!("{}{}", ACK, DISTANCE), &[
+        "derivation: a derives-from an integer 2 utilizes ack 3",
+        "derivation: b derives-from an integer 2 utilizes ack 3",
+        "derivation: c derives-from an integer \"kitten\" utilizes distance \"sitting\" 0 0",
+        "derivation: d derives-from an integer \"flaw\" utilizes distance \"lawn\" 0 0",
+        "derivation: e derives-from an integer \"kitten\" utilizes distance \"sitting\" 0 0",
+        "a added-to b added-to c added-to d added-to e",
+    ]);
+    assert_eq!(run(&source), 9 + 9 + 3 + 2 + 3);
+}
+
+#[test]
+fn test_clears_must_name_a_memoized_behavior() {
+    let source = program_with_run(FIB, &[
+        "derivation: cleared derives-from nothing \"run\" utilizes clears",
+        "1 utilizes fib",
+    ]);
+    match safety_check(&source) {
+        Err(OnuError::GrammarViolation { message, .. }) => {
+            assert!(message.contains("S-2"), "{}", message);
+            assert!(message.contains("'run', which is not declared 'with diminishing'"), "{}", message);
+        }
+        other => panic!("expected S-2 violation, got {:?}", other),
+    }
+}
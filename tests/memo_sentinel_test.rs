@@ -4,7 +4,7 @@ use onu_refactor::domain::entities::mir::{
     BasicBlock, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand,
     MirProgram, MirTerminator,
 };
-use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::domain::entities::types::{InlineHint, OnuType};
 
 #[test]
 fn memo_occupancy_buffer_test() {
@@ -33,6 +33,8 @@ fn memo_occupancy_buffer_test() {
         is_pure_data_leaf: true,
         diminishing: vec!["n".to_string()],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
     };
 
     let program = MirProgram {
@@ -156,6 +158,8 @@ fn memo_multi_arg_guard_test() {
         is_pure_data_leaf: true,
         diminishing: vec!["a".to_string()],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
     };
 
     let program = MirProgram {
@@ -234,6 +238,8 @@ fn memo_compound_occupancy_test() {
         is_pure_data_leaf: true,
         diminishing: vec!["id".to_string()],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
     };
 
     let registry = RegistryService::new();
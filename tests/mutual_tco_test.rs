@@ -0,0 +1,75 @@
+/// Mutual TCO Tests: Application Use Case Layer
+///
+/// Behaviors that tail-call each other in a cycle are merged by `TcoPass`
+/// into looping functions, so mutually recursive state machines run in
+/// constant stack.
+mod common;
+
+use common::{compile_ir, compile_mir, run_ir};
+use onu_refactor::application::options::CompilationOptions;
+use onu_refactor::domain::entities::mir::{MirInstruction, MirProgram};
+
+fn parity_program(start: u64) -> String {
+    format!(
+        "the module called Parity with concern: mutual tail calls\n\n\
+the behavior called even\n    with intent: decide whether a count is even\n    takes:\n        an integer called n\n    \
+delivers: an integer\n    as:\n        \
+if n matches 0\n            then 1\n            else (n decreased-by 1) utilizes odd\n\n\
+the behavior called odd\n    with intent: decide whether a count is odd\n    takes:\n        an integer called n\n    \
+delivers: an integer\n    as:\n        \
+if n matches 0\n            then 0\n            else (n decreased-by 1) utilizes even\n\n\
+the effect behavior called run\n    with intent: check a long count\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        {} utilizes even\n",
+        start
+    )
+}
+
+// A three-state cycle whose last edge swaps its arguments, so the merged
+// loop must assign them in parallel.
+const ROTATION_PROGRAM: &str = "the module called Rotation with concern: mutual tail calls\n\n\
+the behavior called first\n    with intent: step into the third state\n    takes:\n        \
+an integer called n\n        an integer called a\n        an integer called b\n    \
+delivers: an integer\n    as:\n        \
+if n matches 0\n            then (a scales-by 10) added-to b\n            else (n decreased-by 1) utilizes third (a added-to 1) b\n\n\
+the behavior called second\n    with intent: swap the registers and restart\n    takes:\n        \
+an integer called n\n        an integer called a\n        an integer called b\n    \
+delivers: an integer\n    as:\n        \
+if n matches 0\n            then (a scales-by 10) added-to b\n            else (n decreased-by 1) utilizes first b a\n\n\
+the behavior called third\n    with intent: step into the second state\n    takes:\n        \
+an integer called n\n        an integer called a\n        an integer called b\n    \
+delivers: an integer\n    as:\n        \
+if n matches 0\n            then (a scales-by 10) added-to b\n            else (n decreased-by 1) utilizes second a (b added-to 2)\n\n\
+the effect behavior called run\n    with intent: rotate a few times\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        4 utilizes first 0 0\n";
+
+fn calls_between(program: &MirProgram, names: &[&str]) -> usize {
+    program
+        .functions
+        .iter()
+        .filter(|f| names.contains(&f.name.as_str()))
+        .flat_map(|f| f.blocks.iter())
+        .flat_map(|b| b.instructions.iter())
+        .filter(|inst| matches!(inst, MirInstruction::Call { name, .. } if names.contains(&name.as_str())))
+        .count()
+}
+
+#[test]
+fn test_mutual_tail_calls_become_branches() {
+    let mir = compile_mir(&parity_program(10), CompilationOptions::default());
+    assert_eq!(calls_between(&mir, &["even", "odd"]), 0);
+}
+
+#[test]
+fn test_mutual_recursion_runs_in_constant_stack() {
+    let run = |start| run_ir(&compile_ir(&parity_program(start), CompilationOptions::default()));
+    assert_eq!(run(10_000_000), 1);
+    assert_eq!(run(10_000_001), 0);
+}
+
+#[test]
+fn test_three_member_cycle_keeps_argument_order() {
+    let mir = compile_mir(ROTATION_PROGRAM, CompilationOptions::default());
+    assert_eq!(calls_between(&mir, &["first", "second", "third"]), 0);
+    // first -> third -> second -> first -> third; a sequential swap would give 32.
+    assert_eq!(run_ir(&compile_ir(ROTATION_PROGRAM, CompilationOptions::default())), 31);
+}
@@ -4,7 +4,7 @@ use onu_refactor::application::options::CompilationOptions;
 use onu_refactor::infrastructure::os::NativeOsEnvironment;
 use onu_refactor::adapters::codegen::OnuCodegen;
 use onu_refactor::domain::entities::ast::{Discourse, BehaviorHeader, Expression, ReturnType, Argument, TypeInfo};
-use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::domain::entities::types::{InlineHint, OnuType};
 use onu_refactor::domain::entities::registry::BehaviorSignature;
 
 #[test]
@@ -31,6 +31,8 @@ fn test_multiple_returns_phi_detection() {
         delivers: ReturnType(OnuType::I64),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     
@@ -86,6 +88,8 @@ fn test_multiple_returns_direct_ret() {
         delivers: ReturnType(OnuType::I64),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     
@@ -133,6 +137,8 @@ fn test_ackermann_specialization() {
         delivers: ReturnType(OnuType::I64),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     
@@ -182,6 +188,8 @@ fn test_comparison_specialization() {
         delivers: ReturnType(OnuType::Boolean),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     
@@ -229,6 +237,8 @@ fn test_pure_data_leaf_attributes() {
         delivers: ReturnType(OnuType::I64),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     
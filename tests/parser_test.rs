@@ -443,3 +443,85 @@ the-behavior-called used-var-demo
         warnings
     );
 }
+
+#[test]
+fn test_parser_reads_with_inlining_clause() {
+    use onu_refactor::domain::entities::ast::Discourse;
+    use onu_refactor::domain::entities::types::InlineHint;
+
+    let source = "the behavior called helper with intent: nothing takes: an integer called n delivers: an integer with inlining: never as: n";
+    let lexer = onu_refactor::adapters::lexer::OnuLexer::new(LogLevel::Error);
+    let tokens = lexer.lex(source).unwrap();
+    let parser = OnuParser::new(LogLevel::Error);
+    let mut registry = RegistryService::new();
+
+    let discourses = parser.parse_with_registry(tokens, &mut registry).expect("Parsing failed");
+    let inlining = discourses.iter().find_map(|d| match d {
+        Discourse::Behavior { header, .. } => Some(header.inlining),
+        _ => None,
+    });
+    assert_eq!(inlining, Some(InlineHint::Never));
+}
+
+#[test]
+fn test_parser_rejects_unknown_inlining_value() {
+    let source = "the behavior called helper with intent: nothing takes: nothing delivers: nothing with inlining: sometimes as: nothing";
+    let lexer = onu_refactor::adapters::lexer::OnuLexer::new(LogLevel::Error);
+    let tokens = lexer.lex(source).unwrap();
+    let parser = OnuParser::new(LogLevel::Error);
+    let mut registry = RegistryService::new();
+
+    assert!(parser.parse_with_registry(tokens, &mut registry).is_err());
+}
+
+#[test]
+fn test_parser_reads_with_known_behavior_clause() {
+    use onu_refactor::domain::entities::ast::Discourse;
+    use onu_refactor::domain::entities::types::KnownBehavior;
+
+    let source = "the behavior called compress with intent: nothing takes: an integer called n delivers: an integer with known behavior: sha256-compress as: n";
+    let lexer = onu_refactor::adapters::lexer::OnuLexer::new(LogLevel::Error);
+    let tokens = lexer.lex(source).unwrap();
+    let parser = OnuParser::new(LogLevel::Error);
+    let mut registry = RegistryService::new();
+
+    let discourses = parser.parse_with_registry(tokens, &mut registry).expect("Parsing failed");
+    let known_behavior = discourses.iter().find_map(|d| match d {
+        Discourse::Behavior { header, .. } => Some(header.known_behavior),
+        _ => None,
+    });
+    assert_eq!(known_behavior, Some(Some(KnownBehavior::Sha256Compress)));
+
+    let source = "the behavior called helper with intent: nothing takes: nothing delivers: nothing with known behavior: quicksort as: nothing";
+    let tokens = lexer.lex(source).unwrap();
+    assert!(parser.parse_with_registry(tokens, &mut registry).is_err());
+}
+
+#[test]
+fn test_parser_collects_test_declarations() {
+    use onu_refactor::domain::entities::ast::{Discourse, Expression};
+
+    let source = "the module called Arith with concern: testing
+
+the test called four-is-four
+    with intent: check a literal
+    expects: 4
+    as:
+        4
+
+the behavior called helper with intent: nothing takes: nothing delivers: nothing as: nothing
+";
+    let lexer = onu_refactor::adapters::lexer::OnuLexer::new(LogLevel::Error);
+    let tokens = lexer.lex(source).unwrap();
+    let parser = OnuParser::new(LogLevel::Error);
+    let mut registry = RegistryService::new();
+    parser.scan_headers(&tokens, &mut registry).unwrap();
+
+    let discourses = parser.parse_with_registry(tokens, &mut registry).expect("Parsing failed");
+    let test = discourses.iter().find_map(|d| match d {
+        Discourse::Test { name, expects, line, .. } => Some((name.clone(), expects.clone(), *line)),
+        _ => None,
+    });
+    assert_eq!(test, Some(("four-is-four".to_string(), Expression::I64(4), 3)));
+    assert!(discourses.iter().any(|d| matches!(d, Discourse::Behavior { header, .. } if header.name == "helper")));
+}
@@ -1,9 +1,9 @@
 use onu_refactor::application::use_cases::lowering_service::LoweringService;
 use onu_refactor::domain::entities::ast::{Discourse, BehaviorHeader, Expression, ReturnType};
 use onu_refactor::domain::entities::hir::{HirDiscourse, HirExpression, HirLiteral};
-use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::domain::entities::types::{InlineHint, OnuType};
 use onu_refactor::CompilationPipeline;
-use onu_refactor::application::options::{CompilationOptions, LogLevel};
+use onu_refactor::application::options::{CompilationOptions, CompilerStage, LogLevel};
 use onu_refactor::infrastructure::os::NativeOsEnvironment;
 use onu_refactor::application::ports::compiler_ports::CodegenPort;
 use onu_refactor::application::use_cases::registry_service::RegistryService;
@@ -47,6 +47,39 @@ the behavior called run with intent: nothing as: nothing";
     assert!(ir.is_empty()); // Mock returns empty
 }
 
+fn source_pipeline(options: CompilationOptions) -> CompilationPipeline<NativeOsEnvironment, MockCodegen> {
+    let env = NativeOsEnvironment::new(options.log_level);
+    let lexer = Box::new(onu_refactor::adapters::lexer::OnuLexer::new(options.log_level));
+    let parser = Box::new(onu_refactor::adapters::parser::OnuParser::new(options.log_level));
+    CompilationPipeline::new(env, MockCodegen, lexer, parser, options)
+}
+
+#[test]
+fn test_compile_source_runs_every_stage() {
+    let source = "the module called Test with concern: nothing
+the behavior called run with intent: nothing as: nothing";
+    let compiled = source_pipeline(CompilationOptions::default())
+        .compile_source(source)
+        .expect("Compilation failed");
+    assert!(compiled.diagnostics.is_empty());
+    assert!(!compiled.mir.expect("MIR missing").functions.is_empty());
+    assert_eq!(compiled.ir.as_deref(), Some("")); // Mock returns empty
+}
+
+#[test]
+fn test_compile_source_honours_stop_after() {
+    let source = "the module called Test with concern: nothing
+the behavior called run with intent: nothing as: nothing";
+    let options = CompilationOptions { stop_after: Some(CompilerStage::Mir), ..CompilationOptions::default() };
+    let compiled = source_pipeline(options).compile_source(source).expect("Compilation failed");
+    assert!(compiled.mir.is_some());
+    assert!(compiled.ir.is_none());
+
+    let options = CompilationOptions { stop_after: Some(CompilerStage::Parsing), ..CompilationOptions::default() };
+    let compiled = source_pipeline(options).compile_source(source).expect("Compilation failed");
+    assert!(compiled.mir.is_none());
+}
+
 #[test]
 fn test_service_injection() {
     let _registry = RegistryService::new();
@@ -78,6 +111,8 @@ fn test_synthetic_argument_injection() {
         delivers: ReturnType(OnuType::Nothing),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     let body = Expression::Nothing;
@@ -105,6 +140,8 @@ fn test_broadcasts_lowering() {
         delivers: ReturnType(OnuType::Nothing),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     let body = Expression::Emit(Box::new(Expression::Text("Hello".to_string())));
@@ -139,6 +176,8 @@ fn test_drop_lowering() {
         delivers: ReturnType(OnuType::Nothing),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     let body = Expression::Drop(Box::new(Expression::Identifier("x".to_string())));
@@ -171,3 +210,26 @@ fn test_stdlib_op_registry_dispatches() {
     assert!(registry.get("as-text").is_some());
     assert!(registry.get("len").is_some());
 }
+
+#[test]
+fn test_lower_hir_leaves_test_declarations_out() {
+    let source = "the module called Arith with concern: testing
+
+the test called four-is-four
+    with intent: check a literal
+    expects: 4
+    as:
+        4
+
+the behavior called run with intent: nothing takes: nothing delivers: nothing as: nothing
+";
+    let mut pipeline = source_pipeline(CompilationOptions::default());
+    let tokens = pipeline.lex(source).expect("Lexing failed");
+    pipeline.scan_headers(&tokens).expect("Header scan failed");
+    let discourses = pipeline.parse(tokens).expect("Parsing failed");
+    assert!(discourses.iter().any(|d| matches!(d, Discourse::Test { .. })));
+
+    let hir = pipeline.lower_hir(discourses).expect("HIR lowering failed");
+    assert_eq!(hir.len(), 2);
+    assert!(!hir.iter().any(|d| matches!(d, HirDiscourse::Module { name, .. } if name == "four-is-four")));
+}
@@ -0,0 +1,105 @@
+/// REPL Session Tests: Infrastructure CLI Layer
+///
+/// A `Repl` session keeps every shape and behavior entered so far and
+/// evaluates bare expressions against them.  Each expression compiles to a
+/// program that broadcasts `=> <value> : <type>`; these tests run that program
+/// and check what it prints.
+mod common;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use common::run_ir_isolated;
+use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::infrastructure::cli::repl::{Repl, ReplEntry};
+
+const FIB: &str = "the behavior called fib
+with intent: the nth Fibonacci number
+takes:
+an integer called n
+delivers: an integer
+with diminishing: n
+as:
+if n falls-short-of 2
+then n
+else ((n decreased-by 1) utilizes fib) added-to ((n decreased-by 2) utilizes fib)";
+
+fn evaluate(repl: &mut Repl, source: &str, name: &str) -> (String, OnuType) {
+    match repl.compile_entry(source) {
+        Ok(ReplEntry::Expression { ir, typ }) => {
+            let output = run_ir_isolated(&ir, name);
+            (String::from_utf8_lossy(&output.stdout).trim().to_string(), typ)
+        }
+        other => panic!("expected an expression entry, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_bare_expression_uses_earlier_definition() {
+    let mut repl = Repl::new(env!("CARGO_BIN_EXE_onu_refactor"));
+    assert!(matches!(repl.compile_entry(FIB), Ok(ReplEntry::Defined(_))));
+
+    let (printed, typ) = evaluate(&mut repl, "20 utilizes fib", "repl_fib");
+    assert_eq!(typ, OnuType::I64);
+    assert_eq!(printed, "=> 6765 : integer");
+}
+
+#[test]
+fn test_bare_expression_prints_text_and_boolean() {
+    let mut repl = Repl::new(env!("CARGO_BIN_EXE_onu_refactor"));
+    let (printed, _) = evaluate(&mut repl, "\"on\" joined-with \"u\"", "repl_text");
+    assert_eq!(printed, "=> onu : text");
+
+    let (printed, typ) = evaluate(&mut repl, "3 exceeds 2", "repl_bool");
+    assert_eq!(typ, OnuType::Boolean);
+    assert_eq!(printed, "=> true : boolean");
+}
+
+#[test]
+fn test_redefinition_replaces_the_earlier_behavior() {
+    let mut repl = Repl::new(env!("CARGO_BIN_EXE_onu_refactor"));
+    let answer = |value: i64| {
+        format!(
+            "the behavior called answer\nwith intent: a constant\ntakes: nothing\n\
+             delivers: an integer\nas:\n{}",
+            value
+        )
+    };
+    repl.compile_entry(&answer(1)).expect("first definition");
+    repl.compile_entry(&answer(42)).expect("redefinition");
+
+    let (printed, _) = evaluate(&mut repl, "answer", "repl_redefine");
+    assert_eq!(printed, "=> 42 : integer");
+}
+
+#[test]
+fn test_bench_runs_an_expression() {
+    let mut repl = Repl::new(env!("CARGO_BIN_EXE_onu_refactor"));
+    repl.compile_entry(FIB).unwrap();
+    let report = repl.meta_command(":bench 15 utilizes fib 5").unwrap();
+    assert!(report.starts_with("5 runs: mean "), "{}", report);
+    assert!(repl.meta_command(":bench 15 utilizes fib").is_err());
+    // A trapping entry is refused before it is timed in this process.
+    assert!(repl.meta_command(":bench 10 partitions-by 0 3").is_err());
+}
+
+#[test]
+fn test_session_survives_a_trapping_entry() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_onu_refactor"))
+        .arg("--repl")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start the REPL");
+    let input = format!("{}\n\n10 partitions-by 0\n\n12 utilizes fib\n\nquit\n", FIB);
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().expect("Failed to wait for the REPL");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("division by zero in `partitions-by`"), "{}", stderr);
+    assert!(stdout.contains("=> 144 : integer"), "{}", stdout);
+    assert!(stdout.contains("Farewell."), "{}", stdout);
+    assert_eq!(output.status.code(), Some(0));
+}
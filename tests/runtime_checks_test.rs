@@ -0,0 +1,125 @@
+/// Runtime Check Tests: Codegen Adapter Layer
+///
+/// Division by zero, out-of-range `char-at` / `set-char` indices and input
+/// text that is not UTF-8 must trap with a located message and a non-zero
+/// exit instead of faulting, unless runtime checks are switched off.
+mod common;
+
+use common::{compile_ir, compile_mir, probe_source, run_expression, run_ir_isolated};
+use onu_refactor::application::options::CompilationOptions;
+use onu_refactor::domain::entities::mir::{MirInstruction, MirProgram};
+use std::process::Command;
+
+fn unchecked() -> CompilationOptions {
+    CompilationOptions { runtime_checks: false, ..CompilationOptions::default() }
+}
+
+fn check_messages(program: &MirProgram) -> Vec<String> {
+    program
+        .functions
+        .iter()
+        .flat_map(|f| f.blocks.iter())
+        .flat_map(|b| b.instructions.iter())
+        .filter_map(|inst| match inst {
+            MirInstruction::RuntimeCheck { message, .. } => Some(message.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+const DIVIDING_PROGRAM: &str = "the module called Ratio with concern: runtime checks\n\n\
+the behavior called halve-by\n    with intent: divide ten by a value\n    takes:\n        an integer called d\n    \
+delivers: an integer\n    as:\n        10 partitions-by d\n\n\
+the effect behavior called run\n    with intent: divide by zero on purpose\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        0 utilizes halve-by\n";
+
+#[test]
+fn test_division_is_guarded_by_default() {
+    let messages = check_messages(&compile_mir(DIVIDING_PROGRAM, CompilationOptions::default()));
+    assert!(!messages.is_empty());
+    assert!(messages.iter().all(|m| m == "division by zero in `partitions-by` within `halve-by` (line 3)"
+        || m == "integer overflow in `partitions-by` within `halve-by` (line 3)"));
+    assert!(messages.iter().any(|m| m.starts_with("integer overflow")));
+}
+
+#[test]
+fn test_checks_can_be_disabled() {
+    assert!(check_messages(&compile_mir(DIVIDING_PROGRAM, unchecked())).is_empty());
+    let expr = "(\"abc\" char-at 1) added-to 0";
+    assert!(!check_messages(&compile_mir(&probe_source(expr), CompilationOptions::default())).is_empty());
+    assert!(check_messages(&compile_mir(&probe_source(expr), unchecked())).is_empty());
+}
+
+#[test]
+fn test_guarded_operations_still_compute() {
+    assert_eq!(run_expression("84 partitions-by 2"), 42);
+    assert_eq!(run_expression("\"abc\" char-at 2"), 99);
+    assert_eq!(run_expression("((\"ab\" joined-with \"c\") utilizes set-char 0 120) char-at 0"), 120);
+}
+
+#[test]
+fn test_division_by_zero_traps() {
+    let output = run_ir_isolated(&compile_ir(DIVIDING_PROGRAM, CompilationOptions::default()), "div_zero");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(
+        stderr.contains("division by zero in `partitions-by` within `halve-by` (line 3)"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_most_negative_divided_by_minus_one_traps() {
+    let source = "the module called Ratio with concern: runtime checks\n\n\
+the behavior called divide\n    with intent: divide one value by another\n    takes:\n        an integer called n\n        \
+an integer called d\n    delivers: an integer\n    as:\n        n partitions-by d\n\n\
+the effect behavior called run\n    with intent: overflow a quotient on purpose\n    takes: nothing\n    \
+delivers: an integer\n    as:\n        \
+derivation: low derives-from (0 decreased-by 9223372036854775807) decreased-by 1\n        \
+low utilizes divide (0 decreased-by 1)\n";
+    let output = run_ir_isolated(&compile_ir(source, CompilationOptions::default()), "div_overflow");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(stderr.contains("integer overflow in `partitions-by` within `divide` (line 3)"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_char_at_out_of_bounds_traps() {
+    for index in ["3", "(0 decreased-by 1)"] {
+        let source = probe_source(&format!("\"abc\" char-at {}", index));
+        let output = run_ir_isolated(&compile_ir(&source, CompilationOptions::default()), "char_at_oob");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(1), "index {}: stderr: {}", index, stderr);
+        assert!(stderr.contains("index out of bounds in `char-at` within `run`"), "stderr: {}", stderr);
+    }
+}
+
+#[test]
+fn test_set_char_out_of_bounds_traps() {
+    let source = probe_source("(\"abc\" utilizes set-char 7 120) char-at 0");
+    let output = run_ir_isolated(&compile_ir(&source, CompilationOptions::default()), "set_char_oob");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(stderr.contains("index out of bounds in `set-char` within `run`"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_invalid_utf8_argument_traps() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let source = probe_source("(1 utilizes receives-argument) utilizes character-count");
+    let path = std::env::temp_dir().join(format!("onu_utf8_arg_{}.ll", std::process::id()));
+    std::fs::write(&path, compile_ir(&source, CompilationOptions::default())).unwrap();
+    let run = |arg: &[u8]| Command::new("lli").arg(&path).arg(OsStr::from_bytes(arg)).output().expect("Failed to run lli");
+
+    let output = run("Ọ̀nụ".as_bytes());
+    assert_eq!(output.status.code(), Some(4), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let output = run(b"caf\xe9");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(output.status.code(), Some(1), "stderr: {}", stderr);
+    assert!(stderr.contains("invalid UTF-8 from `receives-argument` within `run`"), "stderr: {}", stderr);
+}
@@ -1,50 +1,122 @@
-use onu_refactor::CompilationPipeline;
-use onu_refactor::infrastructure::os::NativeOsEnvironment;
-use onu_refactor::adapters::codegen::OnuCodegen;
-use onu_refactor::application::options::{CompilationOptions, LogLevel};
-use std::process::Command;
+/// Golden-Output Tests: Samples
+///
+/// Every sample with a recorded `.stdout` sidecar is compiled and run through
+/// the golden harness and must reproduce its output and exit code.  Refresh
+/// the sidecars with `cargo run -- golden samples --update`.
+use onu_refactor::infrastructure::cli::golden::{self, GoldenCase, GoldenOptions, GoldenOutcome};
 use std::path::Path;
+use std::time::Duration;
 
-fn run_sample_test(sample_name: &str) {
-    let mut options = CompilationOptions::default();
-    options.log_level = LogLevel::Trace; // Enable granular logging for tests
-    options.emit_hir = true;
-    options.emit_tokens = true;
-    
-    let env = NativeOsEnvironment::new(options.log_level);
-    let codegen = OnuCodegen::new();
-    let lexer = Box::new(onu_refactor::adapters::lexer::OnuLexer::new(options.log_level));
-    let parser = Box::new(onu_refactor::adapters::parser::OnuParser::new(options.log_level));
-    let mut pipeline = CompilationPipeline::new(env, codegen, lexer, parser, options);
-    
-    let sample_path = format!("samples/{}.onu", sample_name);
-    assert!(Path::new(&sample_path).exists(), "Sample file not found: {}", sample_path);
-    
-    // Compile
-    pipeline.compile(&sample_path).expect(&format!("Failed to compile {}", sample_name));
-    
-    // Execute the generated binary
-    let prog_path = format!("./{}_bin", sample_name);
-    let output = Command::new(&prog_path)
-        .output()
-        .expect(&format!("Failed to execute {}", sample_name));
-    
-    assert!(output.status.success(), "Execution of {} failed. Output: {}", sample_name, String::from_utf8_lossy(&output.stderr));
+const ONU: &str = env!("CARGO_BIN_EXE_onu_refactor");
+
+fn assert_golden(sample: &str) {
+    let case = GoldenCase::load(Path::new(&format!("samples/{}.onu", sample))).expect("Failed to load sample");
+    assert!(case.expected_stdout.is_some(), "No recorded output for {}", sample);
+    let outcome = golden::check(&case, Path::new(ONU), &GoldenOptions::default());
+    assert_eq!(outcome, GoldenOutcome::Passed, "{}", sample);
 }
 
-macro_rules! sample_test {
-    ($name:ident) => {
+macro_rules! golden_test {
+    ($name:ident, $sample:literal) => {
         #[test]
         fn $name() {
-            run_sample_test(stringify!($name));
+            assert_golden($sample);
         }
     };
 }
 
-// Ported Samples from original 'onu'
-sample_test!(hello_world);
-sample_test!(factorial);
-sample_test!(fibonacci);
-sample_test!(parity);
-sample_test!(sample);
-sample_test!(collatz);
+golden_test!(ackermann_output, "ackermann");
+golden_test!(collatz_output, "collatz");
+golden_test!(collatz_bench_output, "collatz_bench");
+golden_test!(factorial_output, "factorial");
+golden_test!(fibonacci_output, "fibonacci");
+golden_test!(hello_world_output, "hello_world");
+golden_test!(hello_world_int_output, "hello_world_int");
+golden_test!(parity_output, "parity");
+golden_test!(sample_output, "sample");
+golden_test!(test_logic_output, "test_logic");
+golden_test!(test_ownership_output, "test_ownership");
+golden_test!(test_recursion_output, "test_recursion");
+
+// ============================================================================
+// Harness behaviour
+// ============================================================================
+
+fn scratch_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("onu_golden_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+const GREETER: &str = "the module called Greeter with concern: golden output
+
+the effect behavior called run
+    with intent: greet and report a status
+    takes: nothing
+    delivers: an integer
+    as:
+        derivation: shown derives-from nothing broadcasts \"hello\"
+        3
+";
+
+#[test]
+fn test_update_blesses_then_checks_output_and_exit_code() {
+    let dir = scratch_dir("update");
+    std::fs::write(dir.join("greeter.onu"), GREETER).unwrap();
+
+    let cases = golden::discover(&dir).unwrap();
+    assert_eq!(cases.len(), 1);
+    let options = GoldenOptions::default();
+    assert_eq!(golden::check(&cases[0], Path::new(ONU), &options), GoldenOutcome::Unblessed);
+
+    let update = GoldenOptions { update: true, ..GoldenOptions::default() };
+    assert_eq!(golden::check(&cases[0], Path::new(ONU), &update), GoldenOutcome::Blessed);
+    assert_eq!(std::fs::read_to_string(dir.join("greeter.stdout")).unwrap(), "hello\n");
+    assert_eq!(std::fs::read_to_string(dir.join("greeter.exit")).unwrap(), "3\n");
+
+    let cases = golden::discover(&dir).unwrap();
+    assert_eq!(golden::check(&cases[0], Path::new(ONU), &options), GoldenOutcome::Passed);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_mismatch_reports_a_diff() {
+    let dir = scratch_dir("mismatch");
+    std::fs::write(dir.join("greeter.onu"), format!("-- expect-stdout: goodbye\n{}", GREETER)).unwrap();
+
+    let case = GoldenCase::load(&dir.join("greeter.onu")).unwrap();
+    let outcome = golden::check(&case, Path::new(ONU), &GoldenOptions::default());
+    let GoldenOutcome::Mismatch { diff } = outcome else { panic!("expected a mismatch, got {:?}", outcome) };
+    assert_eq!(diff, "line 1:\n-goodbye\n+hello\nexit code: expected 0, got 3\n");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_runaway_program_times_out() {
+    let dir = scratch_dir("timeout");
+    let source = "-- expect-stdout: never
+the module called Spin with concern: golden output
+
+the behavior called spin
+    with intent: never finish
+    takes:
+        an integer called n
+    delivers: an integer
+    as:
+        if n matches 0 then 0 else (n added-to 1) utilizes spin
+
+the effect behavior called run
+    with intent: spin forever
+    takes: nothing
+    delivers: an integer
+    as:
+        1 utilizes spin
+";
+    std::fs::write(dir.join("spin.onu"), source).unwrap();
+
+    let case = GoldenCase::load(&dir.join("spin.onu")).unwrap();
+    let options = GoldenOptions { timeout: Duration::from_millis(500), ..GoldenOptions::default() };
+    assert_eq!(golden::check(&case, Path::new(ONU), &options), GoldenOutcome::TimedOut);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -10,9 +10,9 @@ use onu_refactor::application::use_cases::tco_pass::TcoPass;
 /// They define the contract that `TcoPass` must fulfill.
 use onu_refactor::domain::entities::mir::{
     BasicBlock, MirArgument, MirBinOp, MirFunction, MirInstruction, MirLiteral, MirOperand,
-    MirTerminator,
+    MirProgram, MirTerminator,
 };
-use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::domain::entities::types::{InlineHint, OnuType};
 
 /// Helper: build the MIR for a function that models:
 ///   collatz-steps(n, count):
@@ -42,6 +42,8 @@ fn make_self_tail_call_function() -> MirFunction {
         is_pure_data_leaf: true,
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         blocks: vec![
             // Block 0: condition check
             BasicBlock {
@@ -243,6 +245,8 @@ fn tco_pass_is_identity_for_non_recursive_functions() {
         is_pure_data_leaf: true,
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         blocks: vec![BasicBlock {
             id: 0,
             instructions: vec![MirInstruction::BinaryOperation {
@@ -265,3 +269,109 @@ fn tco_pass_is_identity_for_non_recursive_functions() {
         "TcoPass must not modify functions without self-tail-calls"
     );
 }
+
+/// Helper: `name(n)` returns 0 when `n == 0`, else tail-calls `other(n - 1)`
+/// and copies the call's result before returning it, as lowering does when
+/// the result is bound to a derivation.
+fn make_ping_pong_member(name: &str, other: &str) -> MirFunction {
+    // SSA vars: 0 = n (arg), 1 = cond, 2 = n_minus_one, 3 = result, 4 = copy
+    MirFunction {
+        name: name.to_string(),
+        args: vec![MirArgument { name: "n".to_string(), typ: OnuType::I64, ssa_var: 0 }],
+        return_type: OnuType::I64,
+        is_pure_data_leaf: true,
+        diminishing: vec![],
+        memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
+        blocks: vec![
+            BasicBlock {
+                id: 0,
+                instructions: vec![MirInstruction::BinaryOperation {
+                    dest: 1,
+                    op: MirBinOp::Eq,
+                    lhs: MirOperand::Variable(0, false),
+                    rhs: MirOperand::Constant(MirLiteral::I64(0)),
+                    dest_type: OnuType::Boolean,
+                }],
+                terminator: MirTerminator::CondBranch {
+                    condition: MirOperand::Variable(1, false),
+                    then_block: 1,
+                    else_block: 2,
+                },
+            },
+            BasicBlock {
+                id: 1,
+                instructions: vec![],
+                terminator: MirTerminator::Return(MirOperand::Constant(MirLiteral::I64(0))),
+            },
+            BasicBlock {
+                id: 2,
+                instructions: vec![
+                    MirInstruction::BinaryOperation {
+                        dest: 2,
+                        op: MirBinOp::Sub,
+                        lhs: MirOperand::Variable(0, false),
+                        rhs: MirOperand::Constant(MirLiteral::I64(1)),
+                        dest_type: OnuType::I64,
+                    },
+                    MirInstruction::Call {
+                        dest: 3,
+                        name: other.to_string(),
+                        args: vec![MirOperand::Variable(2, false)],
+                        return_type: OnuType::I64,
+                        arg_types: vec![OnuType::I64],
+                        is_tail_call: true,
+                    },
+                    MirInstruction::Assign { dest: 4, src: MirOperand::Variable(3, true) },
+                ],
+                terminator: MirTerminator::Return(MirOperand::Variable(4, false)),
+            },
+        ],
+    }
+}
+
+/// GREEN CONTRACT: Once a mutual tail call becomes argument assigns and a
+/// back-edge, nothing after it in the block may survive: it could only read
+/// the removed call's result, which is never defined.
+#[test]
+fn merged_tail_call_drops_instructions_after_the_call() {
+    let program = MirProgram {
+        functions: vec![make_ping_pong_member("ping", "pong"), make_ping_pong_member("pong", "ping")],
+    };
+    let merged = TcoPass::merge_mutual_tail_calls(program);
+
+    for func in &merged.functions {
+        let mut defined: Vec<usize> = func.args.iter().map(|a| a.ssa_var).collect();
+        for inst in func.blocks.iter().flat_map(|b| &b.instructions) {
+            match inst {
+                MirInstruction::BinaryOperation { dest, .. }
+                | MirInstruction::Call { dest, .. }
+                | MirInstruction::Assign { dest, .. } => defined.push(*dest),
+                _ => {}
+            }
+        }
+        for block in &func.blocks {
+            assert!(
+                !block.instructions.iter().any(|i| matches!(i, MirInstruction::Call { .. })),
+                "'{}' still calls a group member",
+                func.name
+            );
+            for inst in &block.instructions {
+                if let MirInstruction::Assign { src: MirOperand::Variable(src, _), .. } = inst {
+                    assert!(defined.contains(src), "'{}' block {} reads undefined SSA {}", func.name, block.id, src);
+                }
+            }
+            if let MirTerminator::Branch(_) = block.terminator {
+                let last = block.instructions.last();
+                assert!(
+                    matches!(last, None | Some(MirInstruction::Assign { src: MirOperand::Variable(_, false), .. })),
+                    "'{}' block {} does work after its argument assigns: {:?}",
+                    func.name,
+                    block.id,
+                    last
+                );
+            }
+        }
+    }
+}
@@ -4,7 +4,7 @@ use onu_refactor::infrastructure::os::NativeOsEnvironment;
 use onu_refactor::application::ports::compiler_ports::CodegenPort;
 use onu_refactor::domain::entities::mir::{MirProgram, MirInstruction};
 use onu_refactor::domain::entities::ast::{Discourse, BehaviorHeader, Expression, ReturnType};
-use onu_refactor::domain::entities::types::OnuType;
+use onu_refactor::domain::entities::types::{InlineHint, OnuType};
 use onu_refactor::domain::entities::registry::BehaviorSignature;
 use onu_refactor::domain::entities::error::OnuError;
 use onu_refactor::application::use_cases::registry_service::RegistryService;
@@ -42,6 +42,8 @@ fn test_mir_call_has_tco_metadata() {
         delivers: ReturnType(OnuType::Nothing),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     
@@ -101,6 +103,8 @@ fn test_if_tail_call_propagation() {
         delivers: ReturnType(OnuType::Nothing),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     
@@ -160,6 +164,8 @@ fn test_nested_if_tail_call_propagation() {
         delivers: ReturnType(OnuType::Nothing),
         diminishing: vec![],
         memo_cache_size: None,
+        inlining: InlineHint::Auto,
+        known_behavior: None,
         skip_termination_check: false,
     };
     
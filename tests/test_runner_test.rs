@@ -0,0 +1,128 @@
+/// Test Runner Tests: Infrastructure CLI Layer
+///
+/// `onu test <file>` runs each `the test called … expects: …` declaration
+/// as a program of its own.  These tests drive the built binary and check
+/// the per-test report, the failure details and the exit code.
+use std::process::{Command, Output};
+
+const ARITH: &str = "the module called Arith with concern: testing
+
+the behavior called double
+    with intent: twice a number
+    takes:
+        an integer called n
+    delivers: an integer
+    as:
+        n scales-by 2
+
+the test called doubling-works
+    with intent: check double on a small number
+    expects: 8
+    as:
+        4 utilizes double
+
+the test called greeting
+    expects: \"hi there\"
+    as:
+        \"hi\" joined-with \" there\"
+";
+
+fn onu_test(name: &str, source: &str) -> Output {
+    let path = std::env::temp_dir().join(format!("onu_tests_{}_{}.onu", name, std::process::id()));
+    std::fs::write(&path, source).expect("Failed to write source");
+    let output = Command::new(env!("CARGO_BIN_EXE_onu_refactor"))
+        .arg("test")
+        .arg(&path)
+        .output()
+        .expect("Failed to run onu");
+    let _ = std::fs::remove_file(&path);
+    output
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_passing_tests_are_reported_ok() {
+    let output = onu_test("pass", ARITH);
+    let out = stdout(&output);
+    assert!(out.contains("running 2 tests"), "{}", out);
+    assert!(out.contains("test doubling-works ("), "{}", out);
+    assert!(out.contains(":11) ... ok"), "{}", out);
+    assert!(out.contains("test greeting ("), "{}", out);
+    assert!(out.contains("test result: ok. 2 passed; 0 failed"), "{}", out);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_failing_test_shows_expected_and_actual() {
+    let source = format!(
+        "{}
+the test called doubling-is-wrong
+    expects: 9
+    as:
+        4 utilizes double
+",
+        ARITH
+    );
+    let output = onu_test("fail", &source);
+    let out = stdout(&output);
+    assert!(out.contains(":22) ... FAILED"), "{}", out);
+    assert!(out.contains("expected: 9\n  actual: 8"), "{}", out);
+    assert!(out.contains("test result: FAILED. 2 passed; 1 failed"), "{}", out);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_mismatched_types_fail_the_test() {
+    let source = "the module called Mismatch with concern: testing
+
+the test called wrong-type
+    expects: \"four\"
+    as:
+        4
+";
+    let output = onu_test("types", source);
+    let out = stdout(&output);
+    assert!(out.contains("test wrong-type ("), "{}", out);
+    assert!(out.contains("test delivers integer but expects text"), "{}", out);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_spinning_test_times_out_and_the_rest_still_run() {
+    let source = format!(
+        "{}
+the behavior called spin
+    with intent: never finish
+    takes:
+        an integer called n
+    delivers: an integer
+    with no guaranteed termination
+    with inlining: never
+    as:
+        (n added-to 1) utilizes spin
+
+the test called spins-forever
+    expects: 0
+    as:
+        0 utilizes spin
+",
+        ARITH
+    );
+    let path = std::env::temp_dir().join(format!("onu_tests_spin_{}.onu", std::process::id()));
+    std::fs::write(&path, source).expect("Failed to write source");
+    let output = Command::new(env!("CARGO_BIN_EXE_onu_refactor"))
+        .arg("test")
+        .arg(&path)
+        .args(["--timeout", "1"])
+        .output()
+        .expect("Failed to run onu");
+    let _ = std::fs::remove_file(&path);
+
+    let out = stdout(&output);
+    assert!(out.contains("timed out after 1s"), "{}", out);
+    assert!(out.contains("test result: FAILED. 2 passed; 1 failed"), "{}", out);
+    assert_eq!(output.status.code(), Some(1));
+}
@@ -0,0 +1,74 @@
+/// Text Comparison / Search / Slicing Stdlib Tests: Application Use Case Layer
+///
+/// Results are reduced to integers (`len`, `index-of`, `code-point-at`, ...)
+/// because the JIT probe observes `main`'s exit code.
+mod common;
+
+use common::run_expression;
+
+#[test]
+fn test_lexicographic_ordering() {
+    assert_eq!(run_expression("\"apple\" utilizes compared-with \"banana\""), -1);
+    assert_eq!(run_expression("\"pear\" utilizes compared-with \"pea\""), 1);
+    assert_eq!(run_expression("\"same\" utilizes compared-with \"same\""), 0);
+    assert_eq!(run_expression("\"\" utilizes compared-with \"a\""), -1);
+    assert_eq!(run_expression("\"abc\" utilizes precedes \"abd\""), 1);
+    assert_eq!(run_expression("\"abc\" utilizes follows \"abd\""), 0);
+    // Bytes compare unsigned, so non-ASCII sorts after ASCII.
+    assert_eq!(run_expression("\"é\" utilizes follows \"z\""), 1);
+}
+
+#[test]
+fn test_contains_starts_and_ends_with() {
+    assert_eq!(run_expression("\"hello world\" utilizes contains \"o w\""), 1);
+    assert_eq!(run_expression("\"hello world\" utilizes contains \"xyz\""), 0);
+    assert_eq!(run_expression("\"hi\" utilizes contains \"hiya\""), 0);
+    assert_eq!(run_expression("\"hello\" utilizes starts-with \"he\""), 1);
+    assert_eq!(run_expression("\"hello\" utilizes starts-with \"lo\""), 0);
+    assert_eq!(run_expression("\"he\" utilizes starts-with \"hello\""), 0);
+    assert_eq!(run_expression("\"hello\" utilizes ends-with \"llo\""), 1);
+    assert_eq!(run_expression("\"hello\" utilizes ends-with \"hel\""), 0);
+    assert_eq!(run_expression("\"lo\" utilizes ends-with \"hello\""), 0);
+    assert_eq!(run_expression("\"hello\" utilizes ends-with \"\""), 1);
+}
+
+#[test]
+fn test_index_of() {
+    assert_eq!(run_expression("\"banana\" utilizes index-of \"nan\""), 2);
+    assert_eq!(run_expression("\"banana\" utilizes index-of \"a\""), 1);
+    assert_eq!(run_expression("\"banana\" utilizes index-of \"x\""), -1);
+    assert_eq!(run_expression("\"banana\" utilizes index-of \"\""), 0);
+}
+
+#[test]
+fn test_substring_from_to() {
+    assert_eq!(run_expression("(\"hello world\" utilizes substring from 6 to 11) utilizes index-of \"world\""), 0);
+    assert_eq!(run_expression("(\"hello world\" utilizes substring from 6 to 11) utilizes len"), 5);
+    assert_eq!(run_expression("(\"hello\" utilizes substring 1 3) utilizes char-at 0"), 'e' as i32);
+    assert_eq!(run_expression("(\"hello\" utilizes substring from 3 to 99) utilizes len"), 2);
+    assert_eq!(run_expression("(\"hello\" utilizes substring from 4 to 2) utilizes len"), 0);
+}
+
+#[test]
+fn test_replaced_with() {
+    assert_eq!(run_expression("(\"a-b-c\" utilizes replaced-with \"-\" \"::\") utilizes len"), 7);
+    assert_eq!(run_expression("((\"a-b-c\" utilizes replaced-with \"-\" \"::\") utilizes index-of \"b::c\")"), 3);
+    assert_eq!(run_expression("(\"aaaa\" utilizes replaced-with \"aa\" \"b\") utilizes compared-with \"bb\""), 0);
+    assert_eq!(run_expression("(\"abc\" utilizes replaced-with \"\" \"x\") utilizes compared-with \"abc\""), 0);
+    assert_eq!(run_expression("(\"abc\" utilizes replaced-with \"b\" \"\") utilizes compared-with \"ac\""), 0);
+}
+
+#[test]
+fn test_case_mapping() {
+    // ASCII only: the non-ASCII letters pass through unchanged.
+    assert_eq!(run_expression("(\"Hello, Ọ̀nụ!\" utilizes uppercased) utilizes compared-with \"HELLO, Ọ̀Nụ!\""), 0);
+    assert_eq!(run_expression("(\"Hello, World\" utilizes uppercased) utilizes compared-with \"HELLO, WORLD\""), 0);
+    assert_eq!(run_expression("(\"Hello, World\" utilizes lowercased) utilizes compared-with \"hello, world\""), 0);
+}
+
+#[test]
+fn test_repeated_times() {
+    assert_eq!(run_expression("(\"ab\" utilizes repeated-times 3) utilizes compared-with \"ababab\""), 0);
+    assert_eq!(run_expression("(\"ab\" utilizes repeated-times 0) utilizes len"), 0);
+    assert_eq!(run_expression("(\"ab\" utilizes repeated-times (0 decreased-by 2)) utilizes len"), 0);
+}
@@ -0,0 +1,80 @@
+/// Text Parsing Stdlib Tests: Application Use Case Layer
+///
+/// Exercises the `as-integer` family and the `trimmed` / `before-first` /
+/// `after-first` helpers end to end.
+mod common;
+
+use common::{delivered_type, run_expression};
+use onu_refactor::domain::entities::types::OnuType;
+
+#[test]
+fn test_as_integer_parses_signed_decimal() {
+    assert_eq!(run_expression("\"123\" utilizes as-integer"), 123);
+    assert_eq!(run_expression("\"-42\" utilizes as-integer"), -42);
+    assert_eq!(run_expression("\"+7\" utilizes as-integer"), 7);
+}
+
+#[test]
+fn test_as_integer_malformed_input_yields_zero() {
+    assert_eq!(run_expression("\"12a\" utilizes as-integer"), 0);
+    assert_eq!(run_expression("\"\" utilizes as-integer"), 0);
+    assert_eq!(run_expression("\"-\" utilizes as-integer"), 0);
+    // 20 digits: does not fit in 64 bits.
+    assert_eq!(run_expression("\"99999999999999999999\" utilizes as-integer"), 0);
+}
+
+#[test]
+fn test_as_integer_accepts_the_64_bit_boundaries() {
+    // `run` delivers an i32 exit code, so compare in Onu and deliver 1/0.
+    let max = "if (\"9223372036854775807\" utilizes as-integer) matches 9223372036854775807 then 1 else 0";
+    assert_eq!(run_expression(max), 1);
+    let min = "if (\"-9223372036854775808\" utilizes as-integer) matches \
+               ((0 decreased-by 9223372036854775807) decreased-by 1) then 1 else 0";
+    assert_eq!(run_expression(min), 1);
+    let hex_min = "if (\"-8000000000000000\" utilizes hex-as-integer) matches \
+                   ((0 decreased-by 9223372036854775807) decreased-by 1) then 1 else 0";
+    assert_eq!(run_expression(hex_min), 1);
+    assert_eq!(run_expression("\"-9223372036854775808\" utilizes is-integer"), 1);
+    // One past either end does not fit.
+    assert_eq!(run_expression("\"9223372036854775808\" utilizes is-integer"), 0);
+    assert_eq!(run_expression("\"-9223372036854775809\" utilizes is-integer"), 0);
+    assert_eq!(run_expression("\"9223372036854775808\" utilizes as-integer"), 0);
+}
+
+#[test]
+fn test_is_integer_reports_failure() {
+    assert_eq!(run_expression("\"-12\" utilizes is-integer"), 1);
+    assert_eq!(run_expression("\"12a\" utilizes is-integer"), 0);
+    assert_eq!(run_expression("\" 12\" utilizes is-integer"), 0);
+    assert_eq!(run_expression("if (\"12\" utilizes is-integer) matches true then 7 else 3"), 7);
+}
+
+#[test]
+fn test_hex_and_binary_parsing() {
+    assert_eq!(run_expression("\"ff\" utilizes hex-as-integer"), 255);
+    assert_eq!(run_expression("\"7FfF\" utilizes hex-as-integer"), 32767);
+    assert_eq!(run_expression("\"fg\" utilizes hex-as-integer"), 0);
+    assert_eq!(run_expression("\"1011\" utilizes binary-as-integer"), 11);
+    assert_eq!(run_expression("\"102\" utilizes binary-as-integer"), 0);
+}
+
+#[test]
+fn test_trimmed_strips_surrounding_whitespace() {
+    assert_eq!(run_expression("\"  hi there  \" utilizes trimmed utilizes len"), 8);
+    assert_eq!(run_expression("\"    \" utilizes trimmed utilizes len"), 0);
+    assert_eq!(run_expression("\" 17 \" utilizes trimmed utilizes as-integer"), 17);
+}
+
+#[test]
+fn test_before_and_after_first_split_on_delimiter() {
+    assert_eq!(run_expression("(\"key=value\" utilizes before-first 61) utilizes len"), 3);
+    assert_eq!(run_expression("(\"key=value\" utilizes after-first 61) utilizes len"), 5);
+    assert_eq!(run_expression("(\"novalue\" utilizes before-first 61) utilizes len"), 7);
+    assert_eq!(run_expression("(\"novalue\" utilizes after-first 61) utilizes len"), 0);
+    assert_eq!(run_expression("(\"a,42\" utilizes after-first 44) utilizes as-integer"), 42);
+}
+
+#[test]
+fn test_is_integer_delivers_a_boolean() {
+    assert_eq!(delivered_type("\"12\" utilizes is-integer"), Some(OnuType::Boolean));
+}
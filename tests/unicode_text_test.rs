@@ -0,0 +1,53 @@
+/// Unicode Text Stdlib Tests: Application Use Case Layer
+///
+/// The code-point operations must agree with Rust's own UTF-8 handling on
+/// Yorùbá / Igbo text, while the byte operations keep counting bytes.
+mod common;
+
+use common::{delivered_type, run_expression};
+use onu_refactor::domain::entities::types::OnuType;
+
+#[test]
+fn test_character_count_counts_code_points() {
+    // "Ọ̀nụ" is O-dot-below + combining grave + n + u-dot-below: 4 code points, 9 bytes.
+    assert_eq!("Ọ̀nụ".chars().count(), 4);
+    assert_eq!(run_expression("\"Ọ̀nụ\" utilizes character-count"), 4);
+    assert_eq!(run_expression("\"Ọ̀nụ\" utilizes len"), 9);
+    assert_eq!(run_expression("\"\" utilizes character-count"), 0);
+    assert_eq!(run_expression("\"a😀b\" utilizes character-count"), 3);
+}
+
+#[test]
+fn test_code_point_at_decodes_all_widths() {
+    assert_eq!(run_expression("\"Ọ̀nụ\" utilizes code-point-at 0"), 0x1ECC);
+    assert_eq!(run_expression("\"Ọ̀nụ\" utilizes code-point-at 1"), 0x0300);
+    assert_eq!(run_expression("\"Ọ̀nụ\" utilizes code-point-at 2"), 'n' as i32);
+    assert_eq!(run_expression("\"aé😀\" utilizes code-point-at 1"), 0xE9);
+    assert_eq!(run_expression("\"aé😀\" utilizes code-point-at 2"), 0x1F600);
+    assert_eq!(run_expression("\"aé😀\" utilizes code-point-at 3"), -1);
+}
+
+#[test]
+fn test_characters_between_slices_on_code_points() {
+    assert_eq!(run_expression("(\"Ọ̀nụ\" utilizes characters-between 0 2) utilizes len"), 5);
+    assert_eq!(run_expression("(\"Ọ̀nụ\" utilizes characters-between 2 4) utilizes character-count"), 2);
+    assert_eq!(run_expression("(\"Ọ̀nụ\" utilizes characters-between 3 99) utilizes code-point-at 0"), 0x1EE5);
+    assert_eq!(run_expression("(\"Ọ̀nụ\" utilizes characters-between 3 1) utilizes len"), 0);
+}
+
+#[test]
+fn test_is_valid_utf8() {
+    assert_eq!(run_expression("\"Ọ̀nụ 😀\" utilizes is-valid-utf8"), 1);
+    assert_eq!(run_expression("\"\" utilizes is-valid-utf8"), 1);
+    // A lone 0xFF byte is never valid.
+    assert_eq!(run_expression("(255 utilizes char-from-code) utilizes is-valid-utf8"), 0);
+    // Dropping the last byte of "é" truncates a two-byte sequence.
+    assert_eq!(run_expression("(\"é\" utilizes init-of) utilizes is-valid-utf8"), 0);
+    // A stray continuation byte (the second byte of "é").
+    assert_eq!(run_expression("(169 utilizes char-from-code) utilizes is-valid-utf8"), 0);
+    // Overlong encoding of '/' (C0 AF) and a UTF-16 surrogate (ED A0 80).
+    assert_eq!(run_expression("((192 utilizes char-from-code) joined-with (175 utilizes char-from-code)) utilizes is-valid-utf8"), 0);
+    assert_eq!(run_expression("((237 utilizes char-from-code) joined-with ((160 utilizes char-from-code) joined-with (128 utilizes char-from-code))) utilizes is-valid-utf8"), 0);
+    assert_eq!(run_expression("((195 utilizes char-from-code) joined-with (169 utilizes char-from-code)) utilizes is-valid-utf8"), 1);
+    assert_eq!(delivered_type("\"Ọ̀nụ\" utilizes is-valid-utf8"), Some(OnuType::Boolean));
+}